@@ -0,0 +1,28 @@
+// Copyright (C) 2026 Ethan Uppal.
+//
+// This program is free software: you can redistribute it and/or modify it under
+// the terms of the GNU General Public License as published by the Free Software
+// Foundation, version 3 of the License only.
+//
+// This program is distributed in the hope that it will be useful, but WITHOUT
+// ANY WARRANTY; without even the implied warranty of MERCHANTABILITY or FITNESS
+// FOR A PARTICULAR PURPOSE. See the GNU General Public License for more
+// details.
+//
+// You should have received a copy of the GNU General Public License along with
+// this program.  If not, see <https://www.gnu.org/licenses/>.
+
+//! What menu items run outside the TUI, with the terminal to themselves.
+
+pub mod archive;
+pub mod bundle;
+pub mod config;
+pub mod create;
+pub mod engines;
+pub mod export;
+pub mod install;
+pub mod launch;
+pub mod manifests;
+pub mod prompts;
+pub mod setup;
+pub mod winetricks;
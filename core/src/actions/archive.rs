@@ -0,0 +1,355 @@
+// Copyright (C) 2026 Ethan Uppal.
+//
+// This program is free software: you can redistribute it and/or modify it under
+// the terms of the GNU General Public License as published by the Free Software
+// Foundation, version 3 of the License only.
+//
+// This program is distributed in the hope that it will be useful, but WITHOUT
+// ANY WARRANTY; without even the implied warranty of MERCHANTABILITY or FITNESS
+// FOR A PARTICULAR PURPOSE. See the GNU General Public License for more
+// details.
+//
+// You should have received a copy of the GNU General Public License along with
+// this program.  If not, see <https://www.gnu.org/licenses/>.
+
+//! Archiving kegs, importing archived ones, and adopting Wine apps made
+//! elsewhere.
+
+use std::{fs, path::Path, process::Command};
+
+use color_eyre::{
+    Result,
+    eyre::{Context, bail},
+};
+
+use crate::{
+    actions::{
+        create::choose_keg_path_in,
+        prompts::{
+            choose_numbered, prompt, spawn_thread_with_spinner, wait_for_enter,
+        },
+    },
+    adopt,
+    app::{App, AsyncState},
+    app_config,
+    app_config::expand_path,
+    disk_usage, engine_install, journal,
+    keg::WrapperFlavor,
+    keg_creation, picker,
+    picker::PickKind,
+    snapshots,
+    tasks::KegLock,
+};
+
+/// Archives the whole keg into a `.tar.xz` in a folder the user picks, e.g.,
+/// to keep a working keg before experimenting with its engine.
+pub fn archive_keg(app: &mut App, _state: &AsyncState) -> Result<()> {
+    let Some(current_keg) = &app.current_keg else {
+        return Ok(());
+    };
+    eprintln!("┌──────────────────────────────────┐");
+    eprintln!("│ Archive keg                      │");
+    eprintln!("└──────────────────────────────────┘");
+    if app.is_current_keg_running() {
+        println!("  The keg is running; kill its processes first");
+        println!("Press enter to return to the TUI");
+        return wait_for_enter();
+    }
+    println!("  Choose the folder to save the archive in");
+    let Some(destination) =
+        picker::pick_path(Path::new(&expand_path("~")), PickKind::Directory)?
+    else {
+        return Ok(());
+    };
+    let bundle = current_keg.bundle_path();
+    if destination.starts_with(&bundle) {
+        println!("  The archive can't be saved inside the keg itself");
+        println!("Press enter to return to the TUI");
+        return wait_for_enter();
+    }
+    // Holding the lock keeps queued winetricks from changing the prefix
+    // halfway through.
+    let Some(_keg_lock) = KegLock::try_acquire(&bundle) else {
+        println!(
+            "  Something else is changing the keg; try again once it's done"
+        );
+        println!("Press enter to return to the TUI");
+        return wait_for_enter();
+    };
+    let archive = {
+        let bundle = bundle.clone();
+        let destination = destination.clone();
+        spawn_thread_with_spinner(
+            &format!("Compressing {}...", bundle.display()),
+            move || {
+                snapshots::archive_bundle(&bundle, &destination)
+                    .context("Failed to archive the keg")
+            },
+        )?
+    };
+    let size = fs::metadata(&archive).map(|metadata| metadata.len())?;
+    let _ = journal::append(
+        &bundle,
+        "archive",
+        &format!("archived to {}", archive.display()),
+    );
+    eprintln!("┌──────────────────────────────────┐");
+    eprintln!("│ Archived keg                     │");
+    eprintln!("│ Press enter to return to the TUI │");
+    eprintln!("└──────────────────────────────────┘");
+    println!(
+        "  {} ({})",
+        archive.display(),
+        disk_usage::format_size(size)
+    );
+    println!("  Restore it with Import Keg in the main menu");
+    app.runner
+        .status(Command::new(&app.config.explorer).arg(&destination))?;
+    wait_for_enter()
+}
+
+/// Unpacks a keg archived with Archive Keg into a keg search path, so it shows
+/// up in the kegs list.
+pub fn import_keg(app: &mut App, _state: &AsyncState) -> Result<()> {
+    eprintln!("┌──────────────────────────────────┐");
+    eprintln!("│ Import an archived keg           │");
+    eprintln!("└──────────────────────────────────┘");
+    println!("  Choose a {} archive", snapshots::KEG_ARCHIVE_SUFFIX);
+    let Some(archive) =
+        picker::pick_path(Path::new(&expand_path("~")), PickKind::File)?
+    else {
+        return Ok(());
+    };
+    if !archive.to_string_lossy().ends_with(".tar.xz") {
+        println!(
+            "  {} isn't a {} archive",
+            archive.display(),
+            snapshots::KEG_ARCHIVE_SUFFIX
+        );
+        println!("Press enter to return to the TUI");
+        return wait_for_enter();
+    }
+
+    let search_paths = &app.config.keg_search_paths;
+    let directory = match search_paths.as_slice() {
+        [] => bail!("No keg search path is configured to import into"),
+        [directory] => directory.clone(),
+        _ => {
+            println!("  Which keg search path should it go in?");
+            let choices = search_paths
+                .iter()
+                .map(|path| path.display().to_string())
+                .collect::<Vec<_>>();
+            let Some(index) = choose_numbered("search path", &choices)? else {
+                return Ok(());
+            };
+            search_paths[index].clone()
+        }
+    };
+    fs::create_dir_all(&directory)
+        .with_context(|| format!("Failed to create {}", directory.display()))?;
+
+    let unpacked = {
+        let archive = archive.clone();
+        let directory = directory.clone();
+        spawn_thread_with_spinner(
+            &format!("Unpacking {}...", archive.display()),
+            move || {
+                snapshots::unpack_bundle(&archive, &directory)
+                    .context("Failed to unpack the archive")
+            },
+        )?
+    };
+    if WrapperFlavor::detect(&unpacked).is_none() {
+        snapshots::discard_unpacked(&directory)?;
+        println!("  {} doesn't hold a keg", archive.display());
+        println!("Press enter to return to the TUI");
+        return wait_for_enter();
+    }
+    let name = unpacked
+        .file_stem()
+        .unwrap_or_default()
+        .to_string_lossy()
+        .into_owned();
+    let Some((name, keg_path)) = choose_keg_path_in(
+        app,
+        &directory,
+        "Name for the imported keg: ",
+        Some(name),
+        None,
+    )?
+    else {
+        snapshots::discard_unpacked(&directory)?;
+        return Ok(());
+    };
+    fs::rename(&unpacked, &keg_path).with_context(|| {
+        format!("Failed to move the keg to {}", keg_path.display())
+    })?;
+    snapshots::discard_unpacked(&directory)?;
+    keg_creation::open_up_permissions(
+        &keg_path,
+        &keg_path.join("Contents/SharedSupport/wine"),
+    )?;
+    let _ = journal::append(
+        &keg_path,
+        "import",
+        &format!("imported as {name} from {}", archive.display()),
+    );
+    app.refresh();
+    eprintln!("┌──────────────────────────────────┐");
+    eprintln!("│ Imported keg                     │");
+    eprintln!("│ Press enter to return to the TUI │");
+    eprintln!("└──────────────────────────────────┘");
+    println!("  {}", keg_path.display());
+    wait_for_enter()
+}
+
+/// Turns a hand-rolled Wine `.app` into a keg by filling in what it lacks
+/// from a wrapper template, unpacking an engine into it if it ships no Wine.
+pub fn adopt_app(app: &mut App, state: &AsyncState) -> Result<()> {
+    eprintln!("┌──────────────────────────────────┐");
+    eprintln!("│ Adopt an app                     │");
+    eprintln!("└──────────────────────────────────┘");
+    println!("  Choose an .app with a Wine prefix that isn't a keg yet");
+    let Some(bundle) =
+        picker::pick_path(Path::new(&expand_path("~")), PickKind::Directory)?
+    else {
+        return Ok(());
+    };
+    let adoption = match adopt::Adoption::inspect(&bundle) {
+        Ok(adoption) => adoption,
+        Err(error) => {
+            println!("  {error}");
+            wait_for_enter()?;
+            return Ok(());
+        }
+    };
+    println!("  Found its prefix at {}", adoption.prefix.display());
+
+    let templates = state
+        .wrappers
+        .iter()
+        .filter(|wrapper| wrapper.flavor.is_some())
+        .collect::<Vec<_>>();
+    let template = match templates.as_slice() {
+        [] => {
+            println!(
+                "  No wrapper templates found; install one from the Engines menu"
+            );
+            wait_for_enter()?;
+            return Ok(());
+        }
+        [template] => template,
+        templates => {
+            println!(
+                "  Choose the wrapper to fill in the missing pieces from:"
+            );
+            let summaries = templates
+                .iter()
+                .map(|wrapper| {
+                    format!(
+                        "{} ({})",
+                        wrapper.path.display(),
+                        wrapper.summary()
+                    )
+                })
+                .collect::<Vec<_>>();
+            let Some(index) = choose_numbered("wrapper", &summaries)? else {
+                return Ok(());
+            };
+            templates[index]
+        }
+    };
+
+    let engine = match &adoption.wine {
+        Some(wine) => {
+            println!("  Found its Wine at {}", wine.display());
+            None
+        }
+        None if state.engines.is_empty() => {
+            println!(
+                "  It ships no Wine and no engines were found; install one from the Engines menu"
+            );
+            wait_for_enter()?;
+            return Ok(());
+        }
+        None => {
+            println!(
+                "  It ships no Wine, so choose an engine to unpack into it:"
+            );
+            let names = state
+                .engines
+                .iter()
+                .map(|engine| engine.path.display().to_string())
+                .collect::<Vec<_>>();
+            let Some(index) = choose_numbered("engine", &names)? else {
+                return Ok(());
+            };
+            Some(state.engines[index].path.clone())
+        }
+    };
+
+    println!(
+        "  Adopting copies in what {} has that the app lacks and keeps the app's own files",
+        template.path.display()
+    );
+    println!(
+        "  Its Info.plist is backed up to {}",
+        bundle.join(adopt::ORIGINAL_INFO_PLIST).display()
+    );
+    let answer =
+        prompt(&format!("Adopt {}? [yY/nN] ", bundle.display()), |answer| {
+            ["y", "Y", "n", "N"].contains(&answer.trim())
+        })?;
+    if !["y", "Y"].contains(&answer.trim()) {
+        return Ok(());
+    }
+
+    let template_path = template.path.clone();
+    let adopted = {
+        let adoption = adoption.clone();
+        spawn_thread_with_spinner("Adopting the app...", move || {
+            adoption.adopt(&template_path)?;
+            if let Some(engine) = engine {
+                engine_install::install_engine(&engine, &adoption.bundle)?;
+            }
+            Ok(())
+        })
+    };
+    if let Err(error) = adopted {
+        println!("  Failed to adopt {}: {error:#}", bundle.display());
+        wait_for_enter()?;
+        return Ok(());
+    }
+    let _ = journal::append(
+        &bundle,
+        "adopt",
+        &format!("adopted with {}", template.path.display()),
+    );
+    println!("  Adopted {}", bundle.display());
+
+    if let Some(parent) = bundle.parent()
+        && !app
+            .config
+            .keg_search_paths
+            .iter()
+            .any(|path| path == parent)
+    {
+        let answer = prompt(
+            &format!(
+                "{} isn't a keg search path, so the keg won't be listed. Add it? [yY/nN] ",
+                parent.display()
+            ),
+            |answer| ["y", "Y", "n", "N"].contains(&answer.trim()),
+        )?;
+        if ["y", "Y"].contains(&answer.trim()) {
+            app_config::add_keg_search_path(parent)?;
+            println!("  Restart kegtui to search it");
+        }
+    }
+    eprintln!("┌──────────────────────────────────┐");
+    eprintln!("│ Press enter to return to the TUI │");
+    eprintln!("└──────────────────────────────────┘");
+    wait_for_enter()?;
+    Ok(())
+}
@@ -0,0 +1,312 @@
+// Copyright (C) 2026 Ethan Uppal.
+//
+// This program is free software: you can redistribute it and/or modify it under
+// the terms of the GNU General Public License as published by the Free Software
+// Foundation, version 3 of the License only.
+//
+// This program is distributed in the hope that it will be useful, but WITHOUT
+// ANY WARRANTY; without even the implied warranty of MERCHANTABILITY or FITNESS
+// FOR A PARTICULAR PURPOSE. See the GNU General Public License for more
+// details.
+//
+// You should have received a copy of the GNU General Public License along with
+// this program.  If not, see <https://www.gnu.org/licenses/>.
+
+//! Actions on a keg's `.app` bundle itself, like renaming it or replacing
+//! its wrapper.
+
+use std::{fs, path::Path, process::Command};
+
+use color_eyre::{
+    Result,
+    eyre::{Context, bail},
+};
+
+use crate::{
+    actions::{
+        create::choose_keg_path_in,
+        prompts::{
+            choose_numbered, is_current_keg_busy, prompt,
+            read_multiline_input_with_draft, report_unreadable_draft,
+            spawn_thread_with_spinner, wait_for_enter,
+        },
+    },
+    app::{App, AsyncState},
+    disk_usage,
+    drafts::Draft,
+    journal,
+    keg::{CurrentKeg, Keg, Wrapper, WrapperFlavor},
+    keg_name, snapshots,
+    tasks::{KegLock, PrefixTask, TaskKind},
+    wrapper_update,
+};
+
+pub fn edit_app_info(app: &mut App, _state: &AsyncState) -> Result<()> {
+    if is_current_keg_busy(app)? {
+        return Ok(());
+    }
+    let Some(current_keg) = &app.current_keg else {
+        return Ok(());
+    };
+    let toml_info = toml::to_string_pretty(&current_keg.plist.bundle_info())?;
+    let draft = Draft::new(&current_keg.keg.name, "app-info");
+    let contents = read_multiline_input_with_draft(
+        app,
+        &toml_info,
+        "/tmp/kegtui_app_info.toml",
+        &draft,
+        |_| None,
+    )?;
+    let new_info = match toml::from_str(&contents) {
+        Ok(new_info) => new_info,
+        Err(error) => return report_unreadable_draft(error),
+    };
+    if let Some(current_keg) = &mut app.current_keg {
+        current_keg.plist.set_bundle_info(new_info);
+        current_keg.save_plist("App Info")?;
+    }
+    draft.discard();
+    Ok(())
+}
+
+/// Renames the keg's bundle and updates its `CFBundleName` and
+/// `CFBundleIdentifier` to match.
+pub fn rename_keg(app: &mut App, _state: &AsyncState) -> Result<()> {
+    if is_current_keg_busy(app)? {
+        return Ok(());
+    }
+    let Some(current_keg) = &app.current_keg else {
+        return Ok(());
+    };
+    eprintln!("┌──────────────────────────────────┐");
+    eprintln!("│ Rename keg                       │");
+    eprintln!("└──────────────────────────────────┘");
+    if app.tasks.pending_count(&current_keg.keg.name) > 0 {
+        println!("  Wait for this keg's queued tasks to finish first");
+        println!("Press enter to return to the TUI");
+        return wait_for_enter();
+    }
+    if app.is_current_keg_running() {
+        println!("  The keg is running; kill its processes first");
+        println!("Press enter to return to the TUI");
+        return wait_for_enter();
+    }
+    let bundle = current_keg.bundle_path();
+    let old_name = current_keg.keg.name.trim_end_matches(".app").to_owned();
+    println!("  Currently {}", bundle.display());
+    let Some(keg_directory) = bundle.parent() else {
+        bail!("{} has no enclosing folder", bundle.display());
+    };
+    // Locked before choosing the name, since overwriting another keg backs it
+    // up straight away.
+    let Some(_keg_lock) = KegLock::try_acquire(&bundle) else {
+        println!("  Something else is changing the keg; try again");
+        println!("Press enter to return to the TUI");
+        return wait_for_enter();
+    };
+    let Some((name, new_bundle)) = choose_keg_path_in(
+        app,
+        keg_directory,
+        "New name: ",
+        None,
+        Some(&bundle),
+    )?
+    else {
+        return Ok(());
+    };
+    if name == old_name {
+        return Ok(());
+    }
+
+    fs::rename(&bundle, &new_bundle).with_context(|| {
+        format!(
+            "Failed to rename {} to {}",
+            bundle.display(),
+            new_bundle.display()
+        )
+    })?;
+    // Snapshots are kept by keg name, so they follow the keg unless the new
+    // name already has some.
+    let old_snapshots = snapshots::snapshot_directory(&current_keg.keg.name);
+    let new_snapshots = snapshots::snapshot_directory(&format!("{name}.app"));
+    let are_snapshots_moved = old_snapshots.is_dir()
+        && !new_snapshots.exists()
+        && fs::rename(&old_snapshots, &new_snapshots).is_ok();
+
+    let renamed = match rename_bundle_info(&new_bundle, &name) {
+        Ok(renamed) => renamed,
+        Err(error) => {
+            // Otherwise the keg would keep its old `CFBundleName` and
+            // `CFBundleIdentifier` under the new name.
+            if are_snapshots_moved {
+                let _ = fs::rename(&new_snapshots, &old_snapshots);
+            }
+            if let Err(rename_error) = fs::rename(&new_bundle, &bundle) {
+                return Err(error.wrap_err(format!(
+                    "Failed to move the keg back to {}: {rename_error}",
+                    bundle.display()
+                )));
+            }
+            return Err(error);
+        }
+    };
+    let _ = journal::append(
+        &new_bundle,
+        "rename",
+        &format!("renamed from {old_name} to {name}"),
+    );
+    app.current_keg = Some(renamed);
+    app.refresh();
+    println!("  Renamed to {}", new_bundle.display());
+    println!(
+        "  Settings kept under the old name, like [keg-tags], need to be renamed by hand"
+    );
+    println!("Press enter to return to the TUI");
+    wait_for_enter()
+}
+
+/// Reads the keg just renamed to `name` at `new_bundle` and saves its
+/// `CFBundleName` and `CFBundleIdentifier` to match.
+fn rename_bundle_info(new_bundle: &Path, name: &str) -> Result<CurrentKeg> {
+    let mut renamed = CurrentKeg::try_from(&Keg::from_path(new_bundle))
+        .context("Failed to read the renamed keg")?;
+    renamed.plist.cf_bundle_name = name.to_owned();
+    if let Some(bundle_identifier) = keg_name::bundle_identifier(name) {
+        renamed.plist.cf_bundle_identifier = bundle_identifier;
+    }
+    renamed.save_plist("Rename Keg")?;
+    Ok(renamed)
+}
+
+/// Replaces the keg's wrapper files, like its launcher and frameworks, with a
+/// newer template's, keeping its prefix, engine, and config.
+pub fn update_keg_wrapper(app: &mut App, state: &AsyncState) -> Result<()> {
+    if is_current_keg_busy(app)? {
+        return Ok(());
+    }
+    let Some(current_keg) = &app.current_keg else {
+        return Ok(());
+    };
+    eprintln!("┌──────────────────────────────────┐");
+    eprintln!("│ Update wrapper                   │");
+    eprintln!("└──────────────────────────────────┘");
+    if app.is_current_keg_running() {
+        println!("  The keg is running; kill its processes first");
+        println!("Press enter to return to the TUI");
+        return wait_for_enter();
+    }
+    let bundle = current_keg.bundle_path();
+    let current = Wrapper::from_path(&bundle);
+    println!("  Currently {}", current.summary());
+    let templates = state
+        .wrappers
+        .iter()
+        .filter(|wrapper| wrapper.flavor.is_some())
+        .collect::<Vec<_>>();
+    if templates.is_empty() {
+        println!(
+            "  No wrapper templates found; install one from the Engines menu"
+        );
+        println!("Press enter to return to the TUI");
+        return wait_for_enter();
+    }
+    println!("  Choose the wrapper to update to:");
+    let summaries = templates
+        .iter()
+        .map(|wrapper| {
+            format!("{} ({})", wrapper.path.display(), wrapper.summary())
+        })
+        .collect::<Vec<_>>();
+    let Some(index) = choose_numbered("wrapper", &summaries)? else {
+        return Ok(());
+    };
+    let template = templates[index];
+    if template.flavor != current.flavor {
+        println!(
+            "  Warning: this changes the keg from {} to {}",
+            current
+                .flavor
+                .map_or("an unknown wrapper", WrapperFlavor::name),
+            template
+                .flavor
+                .map_or("an unknown wrapper", WrapperFlavor::name)
+        );
+    }
+    let entries = wrapper_update::replaced_entries(&template.path)?;
+    println!("  Replaces, after snapshotting them:");
+    for entry in &entries {
+        println!("    Contents/{}", entry.display());
+    }
+    println!("  The prefix, engine, Info.plist, and Resources are kept");
+    let answer = prompt("Continue? [yY/nN] ", |answer| {
+        ["y", "Y", "n", "N"].contains(&answer.trim())
+    })?;
+    if !["y", "Y"].contains(&answer.trim()) {
+        return Ok(());
+    }
+
+    let snapshot = {
+        let keg_name = current_keg.keg.name.clone();
+        let bundle = bundle.clone();
+        let template = template.path.clone();
+        spawn_thread_with_spinner(
+            &format!("Updating {}...", current_keg.keg.name),
+            move || {
+                wrapper_update::update_wrapper(&keg_name, &bundle, &template)
+            },
+        )
+    };
+    match snapshot {
+        Ok(snapshot) => {
+            let _ = journal::append(
+                &bundle,
+                "wrapper",
+                &format!(
+                    "{} → {} (snapshot: {})",
+                    current.summary(),
+                    template.summary(),
+                    snapshot.display()
+                ),
+            );
+            app.current_keg = Some(
+                CurrentKeg::try_from(&Keg::from_path(&bundle))
+                    .context("Failed to read the updated keg")?,
+            );
+            app.refresh();
+            println!("  Updated to {}", template.summary());
+            println!("  The previous wrapper is in {}", snapshot.display());
+        }
+        Err(error) => println!("  Failed to update the wrapper: {error:#}"),
+    }
+    println!("Press enter to return to the TUI");
+    wait_for_enter()
+}
+
+/// Queues `du` over the keg's bundle. It only reads the keg, so it runs right
+/// away, even while a winetricks install holds the keg.
+pub fn measure_keg_size(app: &mut App, _state: &AsyncState) -> Result<()> {
+    let Some(current_keg) = &app.current_keg else {
+        return Ok(());
+    };
+    let bundle = current_keg.bundle_path();
+    let mut command = Command::new("du");
+    command.arg("-sk").arg(&bundle);
+    app.tasks.enqueue(PrefixTask {
+        keg_name: current_keg.keg.name.clone(),
+        bundle,
+        kind: TaskKind::ReadOnly,
+        description: "measure size".to_owned(),
+        run_label: "measure",
+        command,
+        wineskin_launcher: current_keg.keg.wineskin_launcher.clone(),
+        log_directory: current_keg.keg.log_directory.clone(),
+        config_file: current_keg.keg.config_file.clone(),
+        winetricks_run_flags: None,
+        timeout: None,
+        output_log: None,
+        explain_failure: None,
+        summarize: Some(disk_usage::summarize_du),
+        snapshot_prefix: false,
+    });
+    Ok(())
+}
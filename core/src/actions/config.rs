@@ -0,0 +1,614 @@
+// Copyright (C) 2026 Ethan Uppal.
+//
+// This program is free software: you can redistribute it and/or modify it under
+// the terms of the GNU General Public License as published by the Free Software
+// Foundation, version 3 of the License only.
+//
+// This program is distributed in the hope that it will be useful, but WITHOUT
+// ANY WARRANTY; without even the implied warranty of MERCHANTABILITY or FITNESS
+// FOR A PARTICULAR PURPOSE. See the GNU General Public License for more
+// details.
+//
+// You should have received a copy of the GNU General Public License along with
+// this program.  If not, see <https://www.gnu.org/licenses/>.
+
+//! Actions that change a keg's settings, from its config and presets to
+//! its display mode and registry.
+
+use std::{fs, io, path::Path, process::Command};
+
+use color_eyre::{Result, eyre::Context};
+
+use crate::{
+    actions::prompts::{
+        EnterListener, choose_numbered, is_current_keg_busy, prompt,
+        read_multiline_input_with_draft, report_unreadable_draft,
+        wait_for_enter,
+    },
+    app::{App, AsyncState},
+    app_config::expand_path,
+    config_presets, display_mode,
+    display_mode::DisplayMode,
+    drafts::Draft,
+    hardware, keg_config, keyboard, pe, picker,
+    picker::PickKind,
+    probe, registry,
+    tasks::{PrefixTask, TaskKind},
+    wine_path::WinePath,
+};
+
+pub fn edit_config(app: &mut App, _state: &AsyncState) -> Result<()> {
+    if is_current_keg_busy(app)? {
+        return Ok(());
+    }
+    let Some(current_keg) = &app.current_keg else {
+        return Ok(());
+    };
+    let toml_config = keg_config::annotate_config(&toml::to_string_pretty(
+        &current_keg.plist.extract_config(),
+    )?);
+    eprintln!("┌──────────────────┐");
+    eprintln!("│ Edit config      │");
+    eprintln!("└──────────────────┘");
+    let query = prompt(
+        "Jump to a setting by name or description (e.g., msync; blank for the top): ",
+        |answer| {
+            let answer = answer.trim();
+            let found = answer.is_empty()
+                || keg_config::find_config_field(&toml_config, answer)
+                    .is_some();
+            if !found {
+                println!("  No setting matches \"{answer}\".");
+            }
+            found
+        },
+    )?;
+    let query = query.trim();
+    if let Some((field, _)) = keg_config::find_config_field(&toml_config, query)
+    {
+        println!("  Jumping to {}: {}", field.path, field.description);
+    }
+    let draft = Draft::new(&current_keg.keg.name, "config");
+    let contents = read_multiline_input_with_draft(
+        app,
+        &toml_config,
+        "/tmp/kegtui.toml",
+        &draft,
+        |text| keg_config::find_config_field(text, query).map(|(_, line)| line),
+    )?;
+    let new_toml_config = match toml::from_str(&contents) {
+        Ok(new_toml_config) => new_toml_config,
+        Err(error) => return report_unreadable_draft(error),
+    };
+    if let Some(current_keg) = &mut app.current_keg {
+        let keyboard = current_keg.plist.extract_config().keyboard;
+        current_keg.plist.update_from_config(&new_toml_config);
+        current_keg.save_plist("Edit Config")?;
+        keyboard::apply_if_changed(&app.tasks, current_keg, &keyboard)?;
+    }
+    draft.discard();
+    Ok(())
+}
+
+pub fn apply_preset(app: &mut App, _state: &AsyncState) -> Result<()> {
+    if is_current_keg_busy(app)? {
+        return Ok(());
+    }
+    let presets = config_presets::all(&app.config.presets);
+    let Some(current_keg) = &mut app.current_keg else {
+        return Ok(());
+    };
+    eprintln!("┌──────────────────────────────────┐");
+    eprintln!("│ Apply a preset                   │");
+    eprintln!("└──────────────────────────────────┘");
+    let choices = presets
+        .iter()
+        .map(|preset| {
+            if preset.description.is_empty() {
+                preset.name.clone()
+            } else {
+                format!("{}: {}", preset.name, preset.description)
+            }
+        })
+        .collect::<Vec<_>>();
+    let Some(index) = choose_numbered("preset", &choices)? else {
+        return Ok(());
+    };
+    let preset = &presets[index];
+    match preset.apply(&current_keg.plist.extract_config()) {
+        Err(error) => {
+            println!("  Couldn't apply {}: {error:#}", preset.name);
+        }
+        Ok((_, changes)) if changes.is_empty() => {
+            println!("  The keg already has every setting in {}", preset.name);
+        }
+        Ok((config, changes)) => {
+            for change in &changes {
+                println!("  {change}");
+            }
+            let answer = prompt("Apply these changes? [yY/nN] ", |answer| {
+                ["y", "Y", "n", "N"].contains(&answer.trim())
+            })?;
+            if ["y", "Y"].contains(&answer.trim()) {
+                let keyboard = current_keg.plist.extract_config().keyboard;
+                current_keg.plist.update_from_config(&config);
+                current_keg.save_plist(&format!("Preset {}", preset.name))?;
+                keyboard::apply_if_changed(&app.tasks, current_keg, &keyboard)?;
+                println!("  Applied {}", preset.name);
+            }
+        }
+    }
+    println!("Press enter to return to the TUI");
+    wait_for_enter()
+}
+
+pub fn probe_keg(app: &mut App, _state: &AsyncState) -> Result<()> {
+    let Some(current_keg) = &app.current_keg else {
+        return Ok(());
+    };
+    eprintln!("┌──────────────────────────────────┐");
+    eprintln!("│ Probing what this keg can do     │");
+    eprintln!("│ Press enter to cancel            │");
+    eprintln!("└──────────────────────────────────┘");
+    println!("  Runs a few small programs in the keg; this can take a minute");
+    let listener = EnterListener::spawn();
+    let report =
+        probe::probe(current_keg, &*app.runner, &|| listener.was_pressed());
+    let print_finding = |finding: &probe::Finding| {
+        println!(
+            "  {} {:<9} {}",
+            finding.verdict.symbol(),
+            finding.name,
+            finding.detail
+        );
+    };
+    println!();
+    for finding in &report.checks {
+        print_finding(finding);
+    }
+    println!();
+    println!("  Graphics translation:");
+    for finding in &report.backends {
+        print_finding(finding);
+    }
+    println!();
+
+    let translation = current_keg.plist.extract_config().translation;
+    let enabled = [
+        ("translation.d3d_metal", translation.d3d_metal),
+        ("translation.dxmt", translation.dxmt),
+        ("translation.dxvk", translation.dxvk),
+    ]
+    .into_iter()
+    .filter_map(|(key, is_enabled)| is_enabled.then_some(key))
+    .collect::<Vec<_>>();
+    for backend in &report.backends {
+        if backend.verdict == probe::Verdict::Fails
+            && backend
+                .config_key
+                .is_some_and(|config_key| enabled.contains(&config_key))
+        {
+            println!(
+                "  Warning: {} is on, but {} likely won't work",
+                backend.config_key.unwrap_or_default(),
+                backend.name
+            );
+        }
+    }
+    match report.recommendation() {
+        Some(backend) => println!(
+            "  Try {}: turn on {} (and only it) in Edit Config",
+            backend.name,
+            backend.config_key.unwrap_or_default()
+        ),
+        None => {
+            println!("  Leave the graphics translations off so WineD3D is used")
+        }
+    }
+    println!();
+    println!("Press enter to return to the TUI");
+    listener.wait_for_enter()
+}
+
+pub fn configure_hardware(app: &mut App, _state: &AsyncState) -> Result<()> {
+    if is_current_keg_busy(app)? {
+        return Ok(());
+    }
+    let Some(current_keg) = &mut app.current_keg else {
+        return Ok(());
+    };
+
+    eprintln!("┌──────────────────┐");
+    eprintln!("│ GPU and CPU use  │");
+    eprintln!("└──────────────────┘");
+    match hardware::detect_gpus(&*app.runner) {
+        Ok(gpus) if !gpus.is_empty() => {
+            for gpu in gpus {
+                let mut details = vec![];
+                if let Some(cores) = gpu.cores {
+                    details.push(format!("{cores} cores"));
+                }
+                if let Some(vram) = gpu.vram {
+                    details.push(format!("{vram} VRAM"));
+                }
+                if let Some(metal) = gpu.metal {
+                    details.push(metal);
+                }
+                if details.is_empty() {
+                    println!("  GPU: {}", gpu.model);
+                } else {
+                    println!("  GPU: {} ({})", gpu.model, details.join(", "));
+                }
+            }
+        }
+        Ok(_) => println!("  GPU: none detected"),
+        Err(error) => println!("  GPU: couldn't detect ({error})"),
+    }
+    println!(
+        "  Try To Use GPU Info is {}: Wine {} this GPU's name and memory.",
+        if current_keg.plist.try_to_use_gpu_info {
+            "on"
+        } else {
+            "off"
+        },
+        if current_keg.plist.try_to_use_gpu_info {
+            "reports"
+        } else {
+            "doesn't report"
+        }
+    );
+    let answer = prompt("Use GPU info? [yY/nN, blank to keep] ", |answer| {
+        ["y", "Y", "n", "N", ""].contains(&answer.trim())
+    })?;
+    match answer.trim() {
+        "y" | "Y" => current_keg.plist.try_to_use_gpu_info = true,
+        "n" | "N" => current_keg.plist.try_to_use_gpu_info = false,
+        _ => {}
+    }
+
+    let cpu_count = hardware::cpu_count();
+    let cpu_limit = current_keg
+        .plist
+        .kegtui_environment
+        .get(hardware::CPU_TOPOLOGY_VARIABLE)
+        .and_then(|topology| hardware::cpu_count_from_topology(topology));
+    println!();
+    println!(
+        "  This Mac has {cpu_count} CPUs; the keg currently uses {}.",
+        match cpu_limit {
+            Some(limit) => limit.to_string(),
+            None if current_keg.plist.disable_cpus => {
+                "fewer (Disable CPUs is on)".to_owned()
+            }
+            None => "all of them".to_owned(),
+        }
+    );
+    let answer = prompt(
+        &format!("CPUs to use [1-{cpu_count}, 0 for all, blank to keep] "),
+        |answer| {
+            answer.trim().is_empty()
+                || answer
+                    .trim()
+                    .parse::<usize>()
+                    .is_ok_and(|count| count <= cpu_count)
+        },
+    )?;
+    if let Ok(count) = answer.trim().parse::<usize>() {
+        // The explicit limit replaces Kegworks' own all-or-nothing switch.
+        current_keg.plist.disable_cpus = false;
+        if count == 0 || count == cpu_count {
+            current_keg
+                .plist
+                .kegtui_environment
+                .remove(hardware::CPU_TOPOLOGY_VARIABLE);
+        } else {
+            current_keg.plist.kegtui_environment.insert(
+                hardware::CPU_TOPOLOGY_VARIABLE.to_owned(),
+                hardware::cpu_topology(count),
+            );
+        }
+    }
+
+    current_keg.save_plist("GPU and CPU")?;
+    Ok(())
+}
+
+pub fn configure_start_exe(app: &mut App, _state: &AsyncState) -> Result<()> {
+    if is_current_keg_busy(app)? {
+        return Ok(());
+    }
+    let Some(current_keg) = &mut app.current_keg else {
+        return Ok(());
+    };
+
+    eprintln!("┌──────────────────────────────────────────────┐");
+    eprintln!("│ start.exe paths                              │");
+    eprintln!("│ Paths can be Windows paths (C:\\...), macOS   │");
+    eprintln!("│ paths inside drive_c, or relative to drive_c │");
+    eprintln!("└──────────────────────────────────────────────┘");
+    if !current_keg.plist.use_start_exe {
+        let answer =
+            prompt("start.exe is off. Turn it on? [yY/nN] ", |answer| {
+                ["y", "Y", "n", "N"].contains(&answer.trim())
+            })?;
+        if !["y", "Y"].contains(&answer.trim()) {
+            return Ok(());
+        }
+        current_keg.plist.use_start_exe = true;
+    }
+
+    println!("  Program: {}", current_keg.plist.program_name_and_path);
+    if let Ok(info) = pe::inspect(&current_keg.program_path()) {
+        println!("           {}", info.summary());
+    }
+    let answer =
+        prompt("Program path (blank to keep, ? to browse): ", |answer| {
+            answer.trim().is_empty()
+                || answer.trim() == "?"
+                || WinePath::parse(answer, &current_keg.keg.c_drive)
+                    .is_some_and(|path| {
+                        path.to_host(&current_keg.keg.c_drive).is_file()
+                    })
+        })?;
+    // A blank answer still normalizes the current path in case it was typed
+    // as a Windows path.
+    let current_program_path = || {
+        WinePath::parse(
+            &current_keg.plist.program_name_and_path,
+            &current_keg.keg.c_drive,
+        )
+    };
+    let program_path = match answer.trim() {
+        "" => current_program_path(),
+        "?" => picker::pick_path(&current_keg.keg.c_drive, PickKind::File)?
+            .and_then(|path| {
+                WinePath::from_host(&path, &current_keg.keg.c_drive)
+            })
+            .or_else(current_program_path),
+        _ => WinePath::parse(&answer, &current_keg.keg.c_drive),
+    };
+    if let Some(path) = program_path {
+        current_keg.plist.program_name_and_path = path.to_kegworks();
+    }
+
+    println!(
+        "  Working directory: {}",
+        if current_keg.plist.kegtui_working_directory.is_empty() {
+            "the program's folder"
+        } else {
+            current_keg.plist.kegtui_working_directory.as_str()
+        }
+    );
+    let answer = prompt(
+        "Working directory (blank for the program's folder): ",
+        |answer| {
+            answer.trim().is_empty()
+                || WinePath::parse(answer, &current_keg.keg.c_drive)
+                    .is_some_and(|path| {
+                        path.to_host(&current_keg.keg.c_drive).is_dir()
+                    })
+        },
+    )?;
+    current_keg.plist.kegtui_working_directory = if answer.trim().is_empty() {
+        String::new()
+    } else {
+        WinePath::parse(&answer, &current_keg.keg.c_drive)
+            .map(|path| path.to_kegworks())
+            .unwrap_or_default()
+    };
+
+    current_keg.save_plist("start.exe Paths")?;
+    Ok(())
+}
+
+pub fn configure_display_mode(
+    app: &mut App,
+    _state: &AsyncState,
+) -> Result<()> {
+    if is_current_keg_busy(app)? {
+        return Ok(());
+    }
+    let Some(current_keg) = &mut app.current_keg else {
+        return Ok(());
+    };
+
+    eprintln!("┌──────────────────────────────────┐");
+    eprintln!("│ Display mode                     │");
+    eprintln!("└──────────────────────────────────┘");
+    let program_path = current_keg.program_path();
+    let program = (!current_keg.plist.program_name_and_path.is_empty())
+        .then(|| program_path.file_name())
+        .flatten()
+        .map(|name| name.to_string_lossy().into_owned());
+    match &program {
+        Some(program) => println!("  Applies to {program} only"),
+        None => println!(
+            "  No program is configured, so this applies to every program in the keg"
+        ),
+    }
+    let engine = display_mode::GameEngine::detect(&program_path);
+    if let Some(engine) = engine {
+        println!("  Looks like {}, so its flags are set too", engine.name());
+    }
+    let choices = DisplayMode::ALL
+        .iter()
+        .map(|mode| mode.description().to_owned())
+        .collect::<Vec<_>>();
+    let Some(index) = choose_numbered("display mode", &choices)? else {
+        return Ok(());
+    };
+    let mode = DisplayMode::ALL[index];
+    let window_size = if mode == DisplayMode::Windowed {
+        let answer = prompt(
+            &format!(
+                "Window size (blank for {}): ",
+                display_mode::DEFAULT_WINDOW_SIZE
+            ),
+            |answer| {
+                answer.trim().is_empty()
+                    || display_mode::is_window_size(answer.trim())
+            },
+        )?;
+        match answer.trim() {
+            "" => display_mode::DEFAULT_WINDOW_SIZE.to_owned(),
+            size => size.to_owned(),
+        }
+    } else {
+        display_mode::DEFAULT_WINDOW_SIZE.to_owned()
+    };
+
+    current_keg.plist.program_flags = display_mode::apply_program_flags(
+        &current_keg.plist.program_flags,
+        engine,
+        mode,
+    );
+    current_keg.save_plist("Display Mode")?;
+
+    let registry_file = WinePath::from_kegworks(display_mode::REGISTRY_FILE);
+    let registry_file_on_host = registry_file.to_host(&current_keg.keg.c_drive);
+    if let Some(parent) = registry_file_on_host.parent() {
+        fs::create_dir_all(parent)?;
+    }
+    fs::write(
+        &registry_file_on_host,
+        display_mode::registry_file(program.as_deref(), mode, &window_size),
+    )
+    .context("Failed to write the registry changes")?;
+    let mut command = Command::new(current_keg.wine_executable());
+    command
+        .env_clear()
+        .envs(current_keg.winetricks_environment())
+        .args(["regedit", "/S"])
+        .arg(registry_file.to_windows());
+    let current_keg = &*current_keg;
+    let ahead = app.tasks.enqueue(PrefixTask {
+        keg_name: current_keg.keg.name.clone(),
+        bundle: current_keg.bundle_path(),
+        kind: TaskKind::Mutation,
+        description: format!("display mode: {}", mode.name()),
+        run_label: "display-mode",
+        command,
+        wineskin_launcher: current_keg.keg.wineskin_launcher.clone(),
+        log_directory: current_keg.keg.log_directory.clone(),
+        config_file: current_keg.keg.config_file.clone(),
+        winetricks_run_flags: None,
+        timeout: None,
+        output_log: None,
+        explain_failure: None,
+        summarize: None,
+        snapshot_prefix: false,
+    });
+    println!(
+        "  Program flags: {}",
+        if current_keg.plist.program_flags.is_empty() {
+            "(none)"
+        } else {
+            &current_keg.plist.program_flags
+        }
+    );
+    if ahead > 0 {
+        println!(
+            "  The registry changes run after {ahead} other task(s) in the Task Queue"
+        );
+    } else {
+        println!("  The registry changes are running in the Task Queue");
+    }
+    println!("Press enter to return to the TUI");
+    wait_for_enter()
+}
+
+/// Reads a `.reg` snippet pasted into the terminal, up to a line with only a
+/// `.` or the end of input.
+fn read_pasted_registry() -> Result<String> {
+    println!(
+        "  Paste the .reg snippet, then enter a line with only a . to finish:"
+    );
+    let mut snippet = String::new();
+    for line in io::stdin().lines() {
+        let line = line?;
+        if line.trim() == "." {
+            break;
+        }
+        snippet.push_str(&line);
+        snippet.push('\n');
+    }
+    Ok(snippet)
+}
+
+pub fn edit_registry(app: &mut App, _state: &AsyncState) -> Result<()> {
+    let Some(current_keg) = &app.current_keg else {
+        return Ok(());
+    };
+    eprintln!("┌──────────────────────────────────┐");
+    eprintln!("│ Edit the keg's registry          │");
+    eprintln!("└──────────────────────────────────┘");
+    let choices = [
+        "Paste a .reg snippet".to_owned(),
+        "Apply a .reg file".to_owned(),
+        "Open regedit in the keg".to_owned(),
+    ];
+    let snippet = match choose_numbered("option", &choices)? {
+        Some(0) => read_pasted_registry()?,
+        Some(1) => {
+            let Some(path) = picker::pick_path(
+                Path::new(&expand_path("~")),
+                PickKind::File,
+            )?
+            else {
+                return Ok(());
+            };
+            let bytes = fs::read(&path).with_context(|| {
+                format!("Failed to read {}", path.display())
+            })?;
+            registry::decode(&bytes).with_context(|| {
+                format!("{} isn't a text .reg file", path.display())
+            })?
+        }
+        Some(_) => {
+            app.runner.spawn(
+                Command::new(&current_keg.keg.wineskin_launcher)
+                    .arg("WSS-regedit"),
+            )?;
+            app.open_kegs_wineskin_launchers
+                .insert(current_keg.keg.wineskin_launcher.clone());
+            return Ok(());
+        }
+        None => return Ok(()),
+    };
+    let registry_file = match registry::registry_file(&snippet) {
+        Ok(registry_file) => registry_file,
+        Err(error) => {
+            println!("  {error}");
+            println!("Press enter to return to the TUI");
+            return wait_for_enter();
+        }
+    };
+    let keys = registry::keys(&registry_file);
+    for key in &keys {
+        println!("  [{key}]");
+    }
+    let answer = prompt(
+        &format!("Apply changes to {} key(s)? [yY/nN] ", keys.len()),
+        |answer| ["y", "Y", "n", "N"].contains(&answer.trim()),
+    )?;
+    if !["y", "Y"].contains(&answer.trim()) {
+        return Ok(());
+    }
+
+    let ahead = registry::queue(
+        &app.tasks,
+        current_keg,
+        &registry_file,
+        format!("registry: {}", keys.join(", ")),
+        "registry",
+    )?;
+    if ahead > 0 {
+        println!(
+            "  The registry changes run after {ahead} other task(s) in the Task Queue"
+        );
+    } else {
+        println!("  The registry changes are running in the Task Queue");
+    }
+    println!("Press enter to return to the TUI");
+    wait_for_enter()
+}
@@ -0,0 +1,353 @@
+// Copyright (C) 2026 Ethan Uppal.
+//
+// This program is free software: you can redistribute it and/or modify it under
+// the terms of the GNU General Public License as published by the Free Software
+// Foundation, version 3 of the License only.
+//
+// This program is distributed in the hope that it will be useful, but WITHOUT
+// ANY WARRANTY; without even the implied warranty of MERCHANTABILITY or FITNESS
+// FOR A PARTICULAR PURPOSE. See the GNU General Public License for more
+// details.
+//
+// You should have received a copy of the GNU General Public License along with
+// this program.  If not, see <https://www.gnu.org/licenses/>.
+
+//! The parts of creating a keg that borrow the terminal, like choosing its
+//! name and downloading its engine.
+
+use std::{
+    fs,
+    path::{Path, PathBuf},
+    time::{Instant, SystemTime},
+};
+
+use color_eyre::{Result, eyre::Context};
+
+use crate::{
+    actions::{
+        launch::kill_wineserver_via_wineskin_launcher,
+        prompts::{
+            EnterListener, prompt, spawn_thread_with_spinner, wait_for_enter,
+        },
+    },
+    app::{App, AsyncState},
+    app_config,
+    app_config::{TimeoutsConfig, default_keg_location, expand_path},
+    engine_install, keg_creation, keg_name, name_conflict,
+    name_conflict::NameConflict,
+    picker,
+    picker::PickKind,
+    remote, run_logs,
+    runner::Completion,
+};
+
+#[derive(Clone, Copy)]
+enum Download {
+    Engine,
+    Wrapper,
+}
+
+/// Resolves a keg creator choice to a local path. If `choice` is a URL, the
+/// archive is downloaded into the first engine or wrapper search path after
+/// the user checks its SHA-256. Returns `None` if the download failed or was
+/// rejected, in which case the user should choose again.
+fn fetch_creator_choice(
+    app: &App,
+    choice: &str,
+    kind: Download,
+) -> Option<String> {
+    if !remote::is_url(choice) {
+        return Some(choice.to_owned());
+    }
+    match try_fetch_creator_choice(app, choice, kind) {
+        Ok(path) => path,
+        Err(error) => {
+            println!("  {error:#}");
+            None
+        }
+    }
+}
+
+fn try_fetch_creator_choice(
+    app: &App,
+    url: &str,
+    kind: Download,
+) -> Result<Option<String>> {
+    let search_paths = match kind {
+        Download::Engine => &app.config.engine_search_paths,
+        Download::Wrapper => &app.config.wrapper_search_paths,
+    };
+    let Some(directory) = search_paths.first() else {
+        println!("  No search path is configured to download into");
+        return Ok(None);
+    };
+    let Some(file_name) = remote::file_name_from_url(url) else {
+        println!("  Could not determine a file name from {url}");
+        return Ok(None);
+    };
+    fs::create_dir_all(directory)
+        .context(format!("Failed to create {}", directory.display()))?;
+    let destination = directory.join(file_name);
+    let partial = directory.join(format!("{file_name}.part"));
+
+    println!("Downloading {url}");
+    let started = Instant::now();
+    let download = remote::download(&*app.runner, url, &partial);
+    app.notify_finished(
+        &format!(
+            "Download of {file_name} {}",
+            if download.is_ok() { "done" } else { "failed" }
+        ),
+        started.elapsed(),
+    );
+    download?;
+    let checksum = remote::sha256(&*app.runner, &partial)?;
+    println!("  SHA-256: {checksum}");
+    let expected =
+        prompt("Expected SHA-256 (leave empty to accept): ", |_| true)?;
+    let expected = expected.trim().to_lowercase();
+    if !expected.is_empty() && expected != checksum {
+        fs::remove_file(&partial)?;
+        println!("  Checksum mismatch, so the download was discarded");
+        return Ok(None);
+    }
+    fs::rename(&partial, &destination)?;
+
+    let local_path = match kind {
+        Download::Engine => destination,
+        Download::Wrapper => {
+            let wrapper =
+                remote::unpack_wrapper(&*app.runner, &destination, directory)?;
+            fs::remove_file(&destination)?;
+            wrapper
+        }
+    };
+    println!("  Saved to {}", local_path.display());
+    Ok(Some(local_path.to_string_lossy().to_string()))
+}
+
+pub fn add_keg_search_path(app: &mut App, _state: &AsyncState) -> Result<()> {
+    eprintln!("┌───────────────────────┐");
+    eprintln!("│ Add a keg search path │");
+    eprintln!("└───────────────────────┘");
+    let Some(directory) =
+        picker::pick_path(Path::new(&expand_path("~")), PickKind::Directory)?
+    else {
+        return Ok(());
+    };
+    eprintln!("  Adding {}", directory.display());
+    app_config::add_keg_search_path(&directory)?;
+    app.add_keg_search_path(directory);
+    Ok(())
+}
+
+/// Downloads an engine or wrapper for the keg creator and chooses it there.
+pub fn download_for_keg_creator(
+    app: &mut App,
+    _state: &AsyncState,
+) -> Result<()> {
+    eprintln!("┌──────────────────────────────────┐");
+    eprintln!("│ Download an engine or wrapper    │");
+    eprintln!("└──────────────────────────────────┘");
+    let url = prompt(
+        "URL of an engine (.tar.xz) or wrapper (.tar.xz or .zip): ",
+        |answer| answer.trim().is_empty() || remote::is_url(answer.trim()),
+    )?;
+    let url = url.trim();
+    if url.is_empty() {
+        return Ok(());
+    }
+    let answer = prompt("Is it an engine or a wrapper? [eE/wW] ", |answer| {
+        ["e", "E", "w", "W"].contains(&answer.trim())
+    })?;
+    let kind = if ["e", "E"].contains(&answer.trim()) {
+        Download::Engine
+    } else {
+        Download::Wrapper
+    };
+    let Some(path) = fetch_creator_choice(app, url, kind) else {
+        wait_for_enter()?;
+        return Ok(());
+    };
+    let path = PathBuf::from(path);
+    match kind {
+        Download::Engine => app.keg_creator.select_engine(path),
+        Download::Wrapper => app.keg_creator.select_wrapper(path),
+    }
+    app.refresh();
+    eprintln!("┌──────────────────────────────────┐");
+    eprintln!("│ Press enter to return to the TUI │");
+    eprintln!("└──────────────────────────────────┘");
+    wait_for_enter()?;
+    Ok(())
+}
+
+/// Asks for the name of a new keg until it is valid and free, trying
+/// `initial` first if given, and returns the name and where the keg goes, or
+/// `None` if the user cancels.
+pub fn choose_keg_path(
+    app: &App,
+    initial: Option<String>,
+) -> Result<Option<(String, PathBuf)>> {
+    let keg_directory = PathBuf::from(expand_path(default_keg_location()));
+    fs::create_dir_all(&keg_directory)
+        .context("Failed to create keg directory")?;
+    choose_keg_path_in(
+        app,
+        &keg_directory,
+        "Name (can be changed later with Rename Keg): ",
+        initial,
+        None,
+    )
+}
+
+/// Whether `a` and `b` are the same file, e.g., because they differ only in
+/// case on a case-insensitive volume.
+fn is_same_file(a: &Path, b: &Path) -> bool {
+    use std::os::unix::fs::MetadataExt;
+
+    match (fs::metadata(a), fs::metadata(b)) {
+        (Ok(a), Ok(b)) => a.dev() == b.dev() && a.ino() == b.ino(),
+        _ => false,
+    }
+}
+
+/// Like [`choose_keg_path`], but for a keg in `keg_directory`, asking with
+/// `question`. The keg at `renaming`, if given, doesn't count as taking its
+/// own name. A keg that is overwritten is backed up first, which is refused
+/// while it is running or has queued tasks.
+pub fn choose_keg_path_in(
+    app: &App,
+    keg_directory: &Path,
+    question: &str,
+    mut initial: Option<String>,
+    renaming: Option<&Path>,
+) -> Result<Option<(String, PathBuf)>> {
+    loop {
+        let mut name = match initial.take() {
+            Some(name) => name,
+            None => prompt(question, |_| true)?,
+        }
+        .trim()
+        .trim_end_matches(".app")
+        .to_owned();
+        if let Some(problem) = keg_name::problem(&name) {
+            let suggestion = keg_name::sanitize(&name);
+            println!("  {problem}");
+            let answer = prompt(
+                &format!("Use \"{suggestion}\" instead? [yY/nN] "),
+                |answer| ["y", "Y", "n", "N"].contains(&answer.trim()),
+            )?;
+            if !["y", "Y"].contains(&answer.trim()) {
+                continue;
+            }
+            name = suggestion;
+        }
+        let keg_path = keg_directory.join(format!("{name}.app"));
+        if !keg_path
+            .try_exists()
+            .context("Failed to check if new keg location exists already")?
+            || renaming.is_some_and(|bundle| is_same_file(bundle, &keg_path))
+        {
+            return Ok(Some((name, keg_path)));
+        }
+        match name_conflict::choose(&keg_path)? {
+            NameConflict::PickNewName => {}
+            NameConflict::Overwrite => {
+                let pending_tasks =
+                    app.tasks.pending_count(&format!("{name}.app"));
+                let backup = match keg_creation::back_up_replaced_keg(
+                    &*app.runner,
+                    pending_tasks,
+                    &keg_path,
+                ) {
+                    Ok(backup) => backup,
+                    Err(error) => {
+                        println!("  {error:#}");
+                        continue;
+                    }
+                };
+                println!(
+                    "  Backed up {} to {}",
+                    keg_path.display(),
+                    backup.display()
+                );
+                return Ok(Some((name, keg_path)));
+            }
+            NameConflict::Cancel => return Ok(None),
+        }
+    }
+}
+
+/// Copies `wrapper` to `keg_path`, unpacks `engine` into it, and creates its
+/// prefix, returning whether the keg is ready. Enter cancels creating the
+/// prefix, so the returned listener must be waited on before reading stdin
+/// again or returning to the TUI.
+pub fn assemble_keg(
+    app: &App,
+    engine: &str,
+    wrapper: &str,
+    name: &str,
+    keg_path: &Path,
+) -> Result<(bool, EnterListener)> {
+    keg_creation::copy_template(Path::new(wrapper), keg_path)?;
+    println!("  Copied template {wrapper} to {}", keg_path.display());
+
+    let engine_pathbuf = PathBuf::from(engine);
+    let keg_path_copy = keg_path.to_path_buf();
+    let wine_folder = spawn_thread_with_spinner(
+        &format!("Unpacking {engine} into {}...", keg_path.display()),
+        move || engine_install::install_engine(&engine_pathbuf, &keg_path_copy),
+    )?;
+    keg_creation::open_up_permissions(keg_path, &wine_folder)?;
+
+    eprintln!("┌──────────────────────────────────┐");
+    eprintln!("│ Creating the Wine prefix         │");
+    eprintln!("│ Press enter to cancel            │");
+    eprintln!("└──────────────────────────────────┘");
+    let wineskin_launcher = keg_creation::wineskin_launcher(keg_path);
+    let listener = EnterListener::spawn();
+    let mut command = keg_creation::prefix_create_command(keg_path);
+    let started = SystemTime::now();
+    let run = app.runner.run_cancellable(
+        &mut command,
+        TimeoutsConfig::limit(app.config.timeouts.prefix_create),
+        &|| listener.was_pressed(),
+    )?;
+    let run_log = run_logs::record(
+        &keg_path.join("Contents/Logs"),
+        "prefix-create",
+        started,
+        &command,
+        run.completion,
+        &run.stdout,
+        &run.stderr,
+    )?;
+
+    if let Some(reason) = run.completion.interruption() {
+        kill_wineserver_via_wineskin_launcher(
+            &*app.runner,
+            wineskin_launcher.as_os_str(),
+        )?;
+        eprintln!("FAILED: prefix creation {reason}");
+        eprintln!("The keg at {} is incomplete", keg_path.display());
+        eprintln!("Output saved to {}", run_log.display());
+        eprintln!("\nPlease try again");
+        return Ok((false, listener));
+    } else if let Completion::Exited(status) = run.completion
+        && !status.success()
+    {
+        eprintln!("FAILED: {status}");
+        eprintln!("Output saved to {}", run_log.display());
+        eprintln!("\nPlease try again");
+        return Ok((false, listener));
+    }
+
+    // Checked when the name was chosen, so it's always valid.
+    keg_creation::apply_new_keg_defaults(
+        keg_path,
+        name,
+        &app.config.new_keg_defaults,
+    )?;
+    Ok((true, listener))
+}
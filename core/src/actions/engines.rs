@@ -0,0 +1,272 @@
+// Copyright (C) 2026 Ethan Uppal.
+//
+// This program is free software: you can redistribute it and/or modify it under
+// the terms of the GNU General Public License as published by the Free Software
+// Foundation, version 3 of the License only.
+//
+// This program is distributed in the hope that it will be useful, but WITHOUT
+// ANY WARRANTY; without even the implied warranty of MERCHANTABILITY or FITNESS
+// FOR A PARTICULAR PURPOSE. See the GNU General Public License for more
+// details.
+//
+// You should have received a copy of the GNU General Public License along with
+// this program.  If not, see <https://www.gnu.org/licenses/>.
+
+//! Upgrading, pruning, and installing engines.
+
+use std::{fmt::Write, fs, path::Path};
+
+use color_eyre::Result;
+
+use crate::{
+    actions::prompts::{
+        prompt, read_multiline_input, spawn_thread_with_spinner, wait_for_enter,
+    },
+    app::{App, AsyncState},
+    app_config::app_config_file_path,
+    brew, disk_usage, engine_install,
+    engine_install::UpgradeOutcome,
+    engine_pruning,
+    engine_pruning::KeepReason,
+};
+
+pub fn upgrade_engines(app: &mut App, state: &AsyncState) -> Result<()> {
+    eprintln!("┌────────────────┐");
+    eprintln!("│ Engine upgrade │");
+    eprintln!("└────────────────┘");
+
+    let mut upgrade_txt = String::from(
+        "# Uncomment one engine and every keg to upgrade to it\n# Each keg's current engine is snapshotted first\n# Save and quit your editor to select\n# Select nothing to quit\n\n",
+    );
+    for engine in &state.engines {
+        writeln!(&mut upgrade_txt, "# {}", engine.path.display())?;
+        if let Some(engine_arch) = engine.arch() {
+            writeln!(&mut upgrade_txt, "#   ^ {}", engine_arch.badge())?;
+        }
+    }
+    writeln!(&mut upgrade_txt)?;
+    for keg in &state.kegs {
+        writeln!(&mut upgrade_txt, "# {}", keg.bundle_path().display())?;
+        writeln!(
+            &mut upgrade_txt,
+            "#   ^ currently {}",
+            keg.engine_version.as_deref().unwrap_or("an unknown engine")
+        )?;
+    }
+
+    let choices =
+        read_multiline_input(app, &upgrade_txt, "/tmp/kegupgrade.txt")?;
+    let choices = choices
+        .lines()
+        .map(|line| line.trim())
+        .filter(|line| !line.is_empty() && !line.starts_with("#"))
+        .collect::<Vec<_>>();
+    if choices.is_empty() {
+        return Ok(());
+    }
+
+    let engines = choices
+        .iter()
+        .filter_map(|choice| {
+            state
+                .engines
+                .iter()
+                .find(|engine| engine.path == Path::new(choice))
+        })
+        .collect::<Vec<_>>();
+    let kegs = choices
+        .iter()
+        .filter_map(|choice| {
+            state
+                .kegs
+                .iter()
+                .find(|keg| keg.bundle_path() == Path::new(choice))
+        })
+        .collect::<Vec<_>>();
+    let [engine] = engines[..] else {
+        println!("  Select exactly one engine");
+        println!("Press enter to return to the TUI");
+        wait_for_enter()?;
+        return Ok(());
+    };
+    if kegs.is_empty() {
+        println!("  Select at least one keg");
+        println!("Press enter to return to the TUI");
+        wait_for_enter()?;
+        return Ok(());
+    }
+
+    println!("Upgrading {} kegs to {}", kegs.len(), engine.path.display());
+    let answer = prompt("Continue? [yY/nN] ", |answer| {
+        ["y", "Y", "n", "N"].contains(&answer.trim())
+    })?;
+    if !["y", "Y"].contains(&answer.trim()) {
+        return Ok(());
+    }
+
+    let mut outcomes = vec![];
+    for keg in kegs {
+        let outcome = if app
+            .open_kegs_wineskin_launchers
+            .contains(&keg.wineskin_launcher)
+        {
+            UpgradeOutcome::Skipped("it is running")
+        } else {
+            let keg = keg.clone();
+            let engine_path = engine.path.clone();
+            spawn_thread_with_spinner(
+                &format!("Upgrading {}...", keg.name),
+                move || Ok(engine_install::upgrade_keg(&keg, &engine_path)),
+            )?
+        };
+        outcomes.push((keg, outcome));
+    }
+
+    eprintln!("┌──────────────────────────────────┐");
+    eprintln!("│ Summary                          │");
+    eprintln!("│ Press enter to return to the TUI │");
+    eprintln!("└──────────────────────────────────┘");
+    for (keg, outcome) in outcomes {
+        match outcome {
+            UpgradeOutcome::Upgraded {
+                previous_version,
+                snapshot,
+            } => println!(
+                "  {}: upgraded from {} (snapshot: {})",
+                keg.name,
+                previous_version.as_deref().unwrap_or("an unknown engine"),
+                snapshot.display()
+            ),
+            UpgradeOutcome::Skipped(reason) => {
+                println!("  {}: skipped because {reason}", keg.name)
+            }
+            UpgradeOutcome::Failed(error) => {
+                println!("  {}: failed: {error}", keg.name)
+            }
+        }
+    }
+    wait_for_enter()?;
+    Ok(())
+}
+
+pub fn prune_engines(app: &mut App, state: &AsyncState) -> Result<()> {
+    eprintln!("┌───────────────┐");
+    eprintln!("│ Prune engines │");
+    eprintln!("└───────────────┘");
+    let plan = engine_pruning::plan(
+        &state.engines,
+        &state.kegs,
+        app.config.keep_newest_engines,
+    );
+    for (engine, reason) in &plan.kept {
+        let reason = match reason {
+            KeepReason::UsedBy(kegs) => format!("used by {}", kegs.join(", ")),
+            KeepReason::Newest => "one of the newest".to_owned(),
+            KeepReason::MaybeUsedBy(kegs) => {
+                format!("the engine of {} is unknown", kegs.join(", "))
+            }
+        };
+        println!("  Keep   {} ({reason})", engine.path.display());
+    }
+    for (engine, size) in &plan.removed {
+        println!(
+            "  Delete {} ({})",
+            engine.path.display(),
+            disk_usage::format_size(*size)
+        );
+    }
+    if plan.removed.is_empty() {
+        println!(
+            "Nothing to delete: every engine is among the {} newest or might be used by a keg",
+            app.config.keep_newest_engines
+        );
+        println!("Press enter to return to the TUI");
+        return wait_for_enter();
+    }
+    let answer = prompt(
+        &format!(
+            "Delete {} engine(s) to free {}? [yY/nN] ",
+            plan.removed.len(),
+            disk_usage::format_size(plan.reclaimable())
+        ),
+        |answer| ["y", "Y", "n", "N"].contains(&answer.trim()),
+    )?;
+    if !["y", "Y"].contains(&answer.trim()) {
+        return Ok(());
+    }
+    for (engine, _) in &plan.removed {
+        match fs::remove_file(&engine.path) {
+            Ok(()) => println!("  Deleted {}", engine.path.display()),
+            Err(error) => {
+                println!(
+                    "  Failed to delete {}: {error}",
+                    engine.path.display()
+                )
+            }
+        }
+    }
+    println!("Press enter to return to the TUI");
+    wait_for_enter()
+}
+
+pub fn install_from_brew_taps(app: &mut App, state: &AsyncState) -> Result<()> {
+    eprintln!("┌───────────────────────────────┐");
+    eprintln!("│ Homebrew engines and wrappers │");
+    eprintln!("└───────────────────────────────┘");
+    if !state.is_brew_installed {
+        println!(
+            "Homebrew is not installed. You can install it from the Setup Wizard."
+        );
+    } else if app.config.brew_taps.is_empty() {
+        println!(
+            "No taps are configured. Add, e.g., brew-taps = [\"user/repo\"] to {}",
+            app_config_file_path().display()
+        );
+    } else {
+        let mut packages = vec![];
+        for tap in &app.config.brew_taps {
+            println!("=> Listing {tap}");
+            match brew::list_tap_packages(&*app.runner, tap) {
+                Ok(tap_packages) => packages.extend(tap_packages),
+                Err(error) => println!("  {error:#}"),
+            }
+        }
+
+        if packages.is_empty() {
+            println!("The configured taps have no formulae or casks.");
+        } else {
+            for (index, package) in packages.iter().enumerate() {
+                println!(
+                    "  [{}] {}{}",
+                    index + 1,
+                    package.name,
+                    if package.is_cask { " (cask)" } else { "" }
+                );
+            }
+            let answer = prompt(
+                "Numbers to install, separated by spaces (leave empty to cancel): ",
+                |answer| {
+                    answer.split_whitespace().all(|number| {
+                        number.parse::<usize>().is_ok_and(|number| {
+                            (1..=packages.len()).contains(&number)
+                        })
+                    })
+                },
+            )?;
+            for number in answer.split_whitespace() {
+                let package = &packages[number.parse::<usize>()? - 1];
+                println!("=> Installing {}", package.name);
+                if let Err(error) = brew::install(&*app.runner, package) {
+                    println!("  {error:#}");
+                }
+            }
+        }
+    }
+
+    eprintln!("┌──────────────────────────────────┐");
+    eprintln!("│ Press enter to return to the TUI │");
+    eprintln!("└──────────────────────────────────┘");
+    wait_for_enter()?;
+
+    Ok(())
+}
@@ -0,0 +1,261 @@
+// Copyright (C) 2026 Ethan Uppal.
+//
+// This program is free software: you can redistribute it and/or modify it under
+// the terms of the GNU General Public License as published by the Free Software
+// Foundation, version 3 of the License only.
+//
+// This program is distributed in the hope that it will be useful, but WITHOUT
+// ANY WARRANTY; without even the implied warranty of MERCHANTABILITY or FITNESS
+// FOR A PARTICULAR PURPOSE. See the GNU General Public License for more
+// details.
+//
+// You should have received a copy of the GNU General Public License along with
+// this program.  If not, see <https://www.gnu.org/licenses/>.
+
+//! Actions that write out what kegtui knows about a keg, e.g., for a bug
+//! report or another Mac.
+
+use std::{fs, path::PathBuf, process::Command};
+
+use color_eyre::{Result, eyre::Context};
+
+use crate::{
+    actions::prompts::{prompt, wait_for_enter},
+    app::{App, AsyncState},
+    app_config::{app_state_directory, expand_path},
+    desktop_launcher, diagnostics,
+    diagnostics::{KegReport, ReportFormat},
+    journal, keg_diff,
+    keg_manifest::KegManifest,
+};
+
+/// Where exports are written, which, unlike `/tmp`, other users can't read
+/// and a restart doesn't clear.
+fn exports_directory() -> Result<PathBuf> {
+    let exports = app_state_directory().join("exports");
+    fs::create_dir_all(&exports)
+        .with_context(|| format!("Failed to create {}", exports.display()))?;
+    Ok(exports)
+}
+
+pub fn compare_kegs(app: &mut App, state: &AsyncState) -> Result<()> {
+    let Some(current_keg) = &app.current_keg else {
+        return Ok(());
+    };
+    eprintln!("┌──────────────────────────────────┐");
+    eprintln!("│ Compare with another keg         │");
+    eprintln!("└──────────────────────────────────┘");
+    let bundle = current_keg.bundle_path();
+    let Some(this_keg) =
+        state.kegs.iter().find(|keg| keg.bundle_path() == bundle)
+    else {
+        return Ok(());
+    };
+    let others = state
+        .kegs
+        .iter()
+        .filter(|keg| keg.bundle_path() != bundle)
+        .collect::<Vec<_>>();
+    if others.is_empty() {
+        println!("There are no other kegs to compare with.");
+        println!("Press enter to return to the TUI");
+        wait_for_enter()?;
+        return Ok(());
+    }
+    for (index, keg) in others.iter().enumerate() {
+        println!(
+            "  [{}] {} ({})",
+            index + 1,
+            keg.name,
+            keg.enclosing_location.display()
+        );
+    }
+    let answer = prompt(
+        "Number of the keg to compare with (leave empty to cancel): ",
+        |answer| {
+            answer.trim().is_empty()
+                || answer
+                    .trim()
+                    .parse::<usize>()
+                    .is_ok_and(|number| (1..=others.len()).contains(&number))
+        },
+    )?;
+    if answer.trim().is_empty() {
+        return Ok(());
+    }
+    let other_keg = others[answer.trim().parse::<usize>()? - 1];
+
+    let comparison = keg_diff::compare(this_keg, other_keg)?;
+    let width = crossterm::terminal::size()
+        .map(|(columns, _)| columns as usize)
+        .unwrap_or(80);
+    let mut rows = vec![[
+        "Setting".to_owned(),
+        this_keg.name.clone(),
+        other_keg.name.clone(),
+    ]];
+    rows.extend(comparison.config.iter().map(|difference| {
+        [
+            difference.key.clone(),
+            difference
+                .left
+                .clone()
+                .unwrap_or_else(|| "(unset)".to_owned()),
+            difference
+                .right
+                .clone()
+                .unwrap_or_else(|| "(unset)".to_owned()),
+        ]
+    }));
+    println!();
+    if comparison.config.is_empty() {
+        println!("The settings are identical.");
+    } else {
+        for line in keg_diff::format_columns(&rows, width) {
+            println!("{line}");
+        }
+    }
+
+    println!();
+    println!(
+        "Winetricks: {} verb(s) installed in both",
+        comparison.shared_winetricks
+    );
+    for (keg, verbs) in [
+        (this_keg, &comparison.only_left),
+        (other_keg, &comparison.only_right),
+    ] {
+        if !verbs.is_empty() {
+            println!("  Only in {}: {}", keg.name, verbs.join(" "));
+        }
+    }
+
+    println!();
+    println!("Press enter to return to the TUI");
+    wait_for_enter()?;
+    Ok(())
+}
+
+pub fn export_keg_diagnostics(
+    app: &mut App,
+    _state: &AsyncState,
+) -> Result<()> {
+    let Some(current_keg) = &mut app.current_keg else {
+        return Ok(());
+    };
+    let crash_dumps = current_keg.keg.find_crash_dumps();
+    let exports = exports_directory()?;
+    let archive_path =
+        diagnostics::export_diagnostics(current_keg, &crash_dumps, &exports)?;
+    eprintln!("┌──────────────────────────────────┐");
+    eprintln!("│ Exported diagnostics             │");
+    eprintln!("│ Press enter to return to the TUI │");
+    eprintln!("└──────────────────────────────────┘");
+    println!("  {}", archive_path.display());
+    println!(
+        "  Includes {} crash dump(s), the keg's logs, and its Info.plist",
+        crash_dumps.len()
+    );
+    app.runner
+        .status(Command::new(&app.config.explorer).arg(&exports))?;
+    wait_for_enter()?;
+    Ok(())
+}
+
+pub fn create_desktop_launcher(
+    app: &mut App,
+    _state: &AsyncState,
+) -> Result<()> {
+    let Some(current_keg) = &app.current_keg else {
+        return Ok(());
+    };
+    eprintln!("┌──────────────────────────────────┐");
+    eprintln!("│ Create a Desktop launcher        │");
+    eprintln!("└──────────────────────────────────┘");
+    println!(
+        "  The launcher opens this keg by its path, so moving or renaming"
+    );
+    println!("  the keg breaks it; create it again afterwards.");
+    let default_name = current_keg.keg.name.trim_end_matches(".app");
+    let answer = prompt(
+        &format!("Launcher name (leave empty for \"{default_name}\"): "),
+        |answer| !answer.contains('/'),
+    )?;
+    let name = match answer.trim() {
+        "" => default_name,
+        name => name,
+    };
+    let desktop = PathBuf::from(expand_path("~/Desktop"));
+    let launcher = desktop_launcher::launcher_path(&desktop, name);
+    if launcher.exists() {
+        let answer = prompt(
+            &format!("{} exists; replace it? [yY/nN] ", launcher.display()),
+            |answer| ["y", "Y", "n", "N"].contains(&answer.trim()),
+        )?;
+        if !["y", "Y"].contains(&answer.trim()) {
+            return Ok(());
+        }
+    }
+    let launcher = desktop_launcher::create(current_keg, name, &desktop)?;
+    let _ = journal::append(
+        &current_keg.bundle_path(),
+        "launcher",
+        &launcher.display().to_string(),
+    );
+    println!("  Created {}", launcher.display());
+    println!("Press enter to return to the TUI");
+    wait_for_enter()
+}
+
+pub fn export_keg_info(app: &mut App, state: &AsyncState) -> Result<()> {
+    let Some(current_keg) = &mut app.current_keg else {
+        return Ok(());
+    };
+    let answer =
+        prompt("Export as [t]ext or [m]arkdown? [t/m/q] ", |answer| {
+            ["t", "m", "q"].contains(&answer.trim())
+        })?;
+    let format = match answer.trim() {
+        "t" => ReportFormat::PlainText,
+        "m" => ReportFormat::Markdown,
+        _ => return Ok(()),
+    };
+    let crash_dumps = current_keg.keg.find_crash_dumps();
+    let exports = exports_directory()?;
+    let report_path = diagnostics::export_report(
+        &KegReport::new(current_keg, state, &crash_dumps),
+        format,
+        &exports,
+    )?;
+    eprintln!("┌──────────────────────────────────┐");
+    eprintln!("│ Exported keg info                │");
+    eprintln!("│ Press enter to return to the TUI │");
+    eprintln!("└──────────────────────────────────┘");
+    println!("  {}", report_path.display());
+    app.runner
+        .status(Command::new(&app.config.explorer).arg(&exports))?;
+    wait_for_enter()?;
+    Ok(())
+}
+
+pub fn export_keg_manifest(app: &mut App, _state: &AsyncState) -> Result<()> {
+    let Some(current_keg) = &app.current_keg else {
+        return Ok(());
+    };
+    let manifest = KegManifest::from_keg(current_keg);
+    let exports = exports_directory()?;
+    let manifest_path = manifest.export(&exports)?;
+    eprintln!("┌──────────────────────────────────┐");
+    eprintln!("│ Exported keg manifest            │");
+    eprintln!("│ Press enter to return to the TUI │");
+    eprintln!("└──────────────────────────────────┘");
+    println!("  {}", manifest_path.display());
+    println!(
+        "  Includes the config and {} winetricks verb(s); add any installers as [[step]] tables",
+        manifest.winetricks.len()
+    );
+    app.runner
+        .status(Command::new(&app.config.explorer).arg(&exports))?;
+    wait_for_enter()?;
+    Ok(())
+}
@@ -0,0 +1,221 @@
+// Copyright (C) 2026 Ethan Uppal.
+//
+// This program is free software: you can redistribute it and/or modify it under
+// the terms of the GNU General Public License as published by the Free Software
+// Foundation, version 3 of the License only.
+//
+// This program is distributed in the hope that it will be useful, but WITHOUT
+// ANY WARRANTY; without even the implied warranty of MERCHANTABILITY or FITNESS
+// FOR A PARTICULAR PURPOSE. See the GNU General Public License for more
+// details.
+//
+// You should have received a copy of the GNU General Public License along with
+// this program.  If not, see <https://www.gnu.org/licenses/>.
+
+//! Running a Windows installer or disc image in a keg.
+
+use std::{
+    ffi::OsString,
+    path::{Path, PathBuf},
+    process::Command,
+    sync::Arc,
+    time::SystemTime,
+};
+
+use color_eyre::Result;
+
+use crate::{
+    actions::{
+        launch::kill_wineserver_via_wineskin_launcher,
+        prompts::{
+            EnterListener, choose_numbered, is_current_keg_busy, wait_for_enter,
+        },
+    },
+    app::{App, AsyncState},
+    app_config::expand_path,
+    disc_image::MountedImage,
+    journal,
+    keg::CurrentKeg,
+    picker,
+    picker::PickKind,
+    run_logs,
+    runner::CommandRunner,
+    tasks::KegLock,
+    views,
+    wine_path::WinePath,
+};
+
+/// Mounts the disc image `image` into `current_keg` and asks which of its
+/// installers to run, returning the mounted disc and the installer's path on
+/// the Mac and in Windows.
+fn choose_disc_installer(
+    runner: Arc<dyn CommandRunner>,
+    current_keg: &CurrentKeg,
+    image: &Path,
+) -> Result<Option<(MountedImage, PathBuf, String)>> {
+    let Some(prefix) = current_keg.keg.c_drive.parent() else {
+        return Ok(None);
+    };
+    let disc = match MountedImage::mount(runner, image, prefix) {
+        Ok(disc) => disc,
+        Err(error) => {
+            println!("  {error:#}");
+            println!("Press enter to return to the TUI");
+            wait_for_enter()?;
+            return Ok(None);
+        }
+    };
+    println!("  Mounted the disc at {}", disc.mount_point().display());
+    let installers = disc.installers();
+    if installers.is_empty() {
+        println!("  The disc has no installer at its top level");
+        println!("Press enter to return to the TUI");
+        wait_for_enter()?;
+        return Ok(None);
+    }
+    let choices = installers
+        .iter()
+        .map(|installer| disc.windows_path(installer))
+        .collect::<Vec<_>>();
+    let Some(index) = choose_numbered("installer", &choices)? else {
+        return Ok(None);
+    };
+    let installer = installers[index].clone();
+    let windows_path = choices[index].clone();
+    Ok(Some((disc, installer, windows_path)))
+}
+
+pub fn install_software(app: &mut App, _state: &AsyncState) -> Result<()> {
+    if is_current_keg_busy(app)? {
+        return Ok(());
+    }
+    let Some(current_keg) = &mut app.current_keg else {
+        return Ok(());
+    };
+    eprintln!("┌──────────────────────────────────┐");
+    eprintln!("│ Install software into this keg   │");
+    eprintln!("└──────────────────────────────────┘");
+    println!(
+        "  Choose the Windows installer (.exe or .msi) or disc image (.iso)"
+    );
+    let Some(chosen) =
+        picker::pick_path(Path::new(&expand_path("~")), PickKind::File)?
+    else {
+        return Ok(());
+    };
+    let extension = |path: &Path| {
+        path.extension()
+            .map(|extension| extension.to_string_lossy().to_lowercase())
+    };
+    // The disc stays mounted until the installer is done.
+    let (installer, installer_argument, disc) = if extension(&chosen).as_deref()
+        == Some("iso")
+    {
+        let Some((disc, installer, windows_path)) =
+            choose_disc_installer(app.runner.clone(), current_keg, &chosen)?
+        else {
+            return Ok(());
+        };
+        (installer, OsString::from(windows_path), Some(disc))
+    } else {
+        (chosen.clone(), chosen.clone().into_os_string(), None)
+    };
+    let mut command = Command::new(current_keg.wine_executable());
+    command
+        .env_clear()
+        .envs(current_keg.winetricks_environment());
+    match extension(&installer).as_deref() {
+        Some("exe") => command.arg(&installer_argument),
+        Some("msi") => command.args(["msiexec", "/i"]).arg(&installer_argument),
+        _ => {
+            println!("  {} isn't an .exe, .msi, or .iso", chosen.display());
+            println!("Press enter to return to the TUI");
+            return wait_for_enter();
+        }
+    };
+    if let Some(folder) = installer.parent() {
+        command.current_dir(folder);
+    }
+
+    let bundle = current_keg.bundle_path();
+    let Some(keg_lock) = KegLock::try_acquire(&bundle) else {
+        println!(
+            "  Something else is changing the keg; try again once it's done"
+        );
+        println!("Press enter to return to the TUI");
+        return wait_for_enter();
+    };
+    let executables_before =
+        views::executables::find_executables(&current_keg.keg.c_drive);
+    eprintln!("┌──────────────────────────────────┐");
+    eprintln!("│ Running the installer            │");
+    eprintln!("│ Press enter to cancel            │");
+    eprintln!("└──────────────────────────────────┘");
+    let listener = EnterListener::spawn();
+    let started = SystemTime::now();
+    let run = app
+        .runner
+        .run_cancellable(&mut command, None, &|| listener.was_pressed())?;
+    let run_log = run_logs::record(
+        &current_keg.keg.log_directory,
+        "install",
+        started,
+        &command,
+        run.completion,
+        &run.stdout,
+        &run.stderr,
+    )?;
+    drop(keg_lock);
+    drop(disc);
+    let installer_name = chosen
+        .file_name()
+        .unwrap_or_default()
+        .to_string_lossy()
+        .to_string();
+    let _ = journal::append(
+        &bundle,
+        "install",
+        &format!("{installer_name} ({})", run.completion),
+    );
+    if let Some(reason) = run.completion.interruption() {
+        kill_wineserver_via_wineskin_launcher(
+            &*app.runner,
+            &current_keg.keg.wineskin_launcher,
+        )?;
+        println!("  The installer {reason}");
+        println!("  Output saved to {}", run_log.display());
+        println!("Press enter to return to the TUI");
+        return listener.wait_for_enter();
+    }
+    println!("  The installer finished ({})", run.completion);
+    println!("  Output saved to {}", run_log.display());
+    println!("Press enter to continue");
+    listener.wait_for_enter()?;
+
+    let installed =
+        views::executables::find_executables(&current_keg.keg.c_drive)
+            .into_iter()
+            .filter(|path| !executables_before.contains(path))
+            .filter_map(|path| {
+                WinePath::from_host(&path, &current_keg.keg.c_drive)
+            })
+            .collect::<Vec<_>>();
+    if installed.is_empty() {
+        println!("  The installer added no executables to the C drive");
+        println!("Press enter to return to the TUI");
+        return wait_for_enter();
+    }
+    println!("  Use a newly installed executable as the keg's program?");
+    let choices = installed
+        .iter()
+        .map(WinePath::to_windows)
+        .collect::<Vec<_>>();
+    let Some(index) = choose_numbered("program", &choices)? else {
+        return Ok(());
+    };
+    current_keg.plist.program_name_and_path = installed[index].to_kegworks();
+    current_keg.save_plist("Install")?;
+    println!("  Program Name and Path is now {}", choices[index]);
+    println!("Press enter to return to the TUI");
+    wait_for_enter()
+}
@@ -0,0 +1,437 @@
+// Copyright (C) 2026 Ethan Uppal.
+//
+// This program is free software: you can redistribute it and/or modify it under
+// the terms of the GNU General Public License as published by the Free Software
+// Foundation, version 3 of the License only.
+//
+// This program is distributed in the hope that it will be useful, but WITHOUT
+// ANY WARRANTY; without even the implied warranty of MERCHANTABILITY or FITNESS
+// FOR A PARTICULAR PURPOSE. See the GNU General Public License for more
+// details.
+//
+// You should have received a copy of the GNU General Public License along with
+// this program.  If not, see <https://www.gnu.org/licenses/>.
+
+//! Launching a keg and the programs in it, and stopping them.
+
+use std::{
+    ffi::OsStr,
+    process::Command,
+    sync::{
+        Arc,
+        atomic::{AtomicBool, Ordering},
+    },
+    thread,
+    time::SystemTime,
+};
+
+use color_eyre::{Result, eyre::Context};
+
+use crate::{
+    actions::prompts::{prompt, wait_for_enter},
+    app::{App, AsyncState},
+    journal,
+    keg::{CurrentKeg, LAST_LAUNCH_KIND},
+    keg_plist::LaunchMode,
+    launch_arguments,
+    launch_arguments::TemporaryFlags,
+    run_logs,
+    runner::CommandRunner,
+    view::ViewID,
+    views,
+};
+
+pub fn open_c_drive(app: &mut App, _state: &AsyncState) -> Result<()> {
+    let Some(current_keg) = &app.current_keg else {
+        return Ok(());
+    };
+    app.runner.status(
+        Command::new(&app.config.explorer)
+            .arg(current_keg.keg.c_drive.to_string_lossy().to_string()),
+    )?;
+    Ok(())
+}
+
+/// Why some of the keg's launch options won't apply, if they won't.
+fn launch_warnings(current_keg: &CurrentKeg) -> Vec<String> {
+    let mut warnings = Vec::new();
+    if let Some(warning) = current_keg.arch_warning() {
+        warnings.push(warning);
+    }
+    // `open` starts the app through Launch Services, so there's no process to
+    // wrap in `taskpolicy`.
+    if current_keg.plist.kegtui_high_priority
+        && !current_keg.plist.kegtui_launch_mode.is_direct()
+    {
+        warnings.push(
+            "High Priority only applies to direct launches, but this keg launches through open, so it will run at the normal priority.".to_owned(),
+        );
+    }
+    warnings
+}
+
+/// Warns if the configured program or the prefix is 32-bit but the keg's
+/// engine only ships 64-bit Wine, or if a launch option will be ignored,
+/// returning whether the user wants to launch anyway.
+pub fn confirm_launch(current_keg: &CurrentKeg) -> Result<bool> {
+    let warnings = launch_warnings(current_keg);
+    if warnings.is_empty() {
+        return Ok(true);
+    }
+    eprintln!("┌──────────────────────────────────────────────┐");
+    eprintln!("│ Warning: launch options                      │");
+    eprintln!("└──────────────────────────────────────────────┘");
+    for warning in &warnings {
+        for line in textwrap::wrap(warning, 76) {
+            println!("  {line}");
+        }
+    }
+    let answer = prompt("Launch anyway? [yY/nN] ", |answer| {
+        ["y", "Y", "n", "N"].contains(&answer.trim())
+    })?;
+    Ok(["y", "Y"].contains(&answer.trim()))
+}
+
+/// Set next to `NSAppSleepDisabled` to remember that kegtui wrote it, so a
+/// value set some other way is never removed.
+const APP_NAP_MARKER_KEY: &str = "kegtuiWroteAppSleepDisabled";
+
+/// Whether the defaults of the app with `bundle_id` have `key`.
+fn has_default(runner: &dyn CommandRunner, bundle_id: &str, key: &str) -> bool {
+    runner
+        .output(Command::new("defaults").args(["read", bundle_id, key]))
+        .is_ok_and(|output| output.status.success())
+}
+
+/// Sets `NSAppSleepDisabled` in the defaults of the app with `bundle_id`,
+/// which macOS reads when the app starts. It's removed when the option is
+/// off, but only if kegtui was the one that set it.
+fn set_app_nap_disabled(
+    runner: &dyn CommandRunner,
+    bundle_id: &str,
+    is_disabled: bool,
+) {
+    let defaults = |arguments: &[&str]| {
+        runner
+            .output(Command::new("defaults").args(arguments))
+            .is_ok_and(|output| output.status.success())
+    };
+    let wrote_it = has_default(runner, bundle_id, APP_NAP_MARKER_KEY);
+    if is_disabled {
+        if !wrote_it && has_default(runner, bundle_id, "NSAppSleepDisabled") {
+            return;
+        }
+        let is_success = defaults(&[
+            "write",
+            bundle_id,
+            "NSAppSleepDisabled",
+            "-bool",
+            "YES",
+        ]) && defaults(&[
+            "write",
+            bundle_id,
+            APP_NAP_MARKER_KEY,
+            "-bool",
+            "YES",
+        ]);
+        if !is_success {
+            println!("  Couldn't disable App Nap for {bundle_id}");
+        }
+    } else if wrote_it {
+        defaults(&["delete", bundle_id, "NSAppSleepDisabled"]);
+        defaults(&["delete", bundle_id, APP_NAP_MARKER_KEY]);
+    }
+}
+
+/// Updates the defaults macOS reads when `current_keg` starts to match its
+/// Disable App Nap option, so it should run right before a launch.
+pub fn apply_app_nap_setting(
+    runner: &dyn CommandRunner,
+    current_keg: &CurrentKeg,
+) {
+    if !current_keg.plist.cf_bundle_identifier.is_empty() {
+        set_app_nap_disabled(
+            runner,
+            &current_keg.plist.cf_bundle_identifier,
+            current_keg.plist.kegtui_disable_app_nap,
+        );
+    }
+}
+
+/// The command that launches `current_keg` with its kegtui launch options.
+pub fn launch_command(current_keg: &CurrentKeg) -> Command {
+    let wrapper = &current_keg.keg.wineskin_launcher;
+    let environment = &current_keg.plist.kegtui_environment;
+    let mut command = match current_keg.plist.kegtui_launch_mode {
+        // QoS tier 0 is the highest a normal user can ask for.
+        LaunchMode::Direct if current_keg.plist.kegtui_high_priority => {
+            let mut command = Command::new("taskpolicy");
+            command
+                .args(["-t", "0", "-l", "0"])
+                .arg(wrapper)
+                .envs(environment);
+            command
+        }
+        LaunchMode::Direct => {
+            let mut command = Command::new(wrapper);
+            command.envs(environment);
+            command
+        }
+        // `-W` keeps `open` running until the app quits, so the run is
+        // recorded like a direct launch.
+        LaunchMode::Open => {
+            let mut command = Command::new("open");
+            command.arg("-W").arg("-a").arg(current_keg.bundle_path());
+            for (variable, value) in environment {
+                command.arg("--env").arg(format!("{variable}={value}"));
+            }
+            command
+        }
+    };
+    // start.exe starts the program wherever the launcher was started.
+    if current_keg.plist.use_start_exe {
+        let working_directory = current_keg.working_directory();
+        if working_directory.is_dir() {
+            command.current_dir(working_directory);
+        }
+    }
+    command
+}
+
+/// Launches the current keg, with `temporary_flags` in place until it has
+/// started, and follows its log.
+fn start_launch(
+    app: &mut App,
+    temporary_flags: Option<TemporaryFlags>,
+) -> Result<()> {
+    let Some(current_keg) = &mut app.current_keg else {
+        return Ok(());
+    };
+    apply_app_nap_setting(&*app.runner, current_keg);
+    let mut command = launch_command(current_keg);
+    let log_directory = current_keg.keg.log_directory.clone();
+    let runner = app.runner.clone();
+    let launched = SystemTime::now();
+    let has_exited = Arc::new(AtomicBool::new(false));
+    {
+        let has_exited = has_exited.clone();
+        thread::spawn(move || {
+            let _ = run_logs::record_streaming(
+                &*runner,
+                &log_directory,
+                "launch",
+                &mut command,
+            );
+            has_exited.store(true, Ordering::Relaxed);
+        });
+    }
+    if let Some(temporary_flags) = temporary_flags {
+        let last_run_log = current_keg.last_run_log();
+        thread::spawn(move || {
+            let _ = temporary_flags.restore_once_started(
+                &last_run_log,
+                launched,
+                &has_exited,
+            );
+        });
+    }
+    current_keg.last_launch = Some(launched);
+    // Recorded so crash dumps from this run still count as new after kegtui
+    // restarts.
+    let _ = journal::append(
+        &current_keg.bundle_path(),
+        LAST_LAUNCH_KIND,
+        &current_keg.plist.program_name_and_path,
+    );
+    app.open_kegs_wineskin_launchers
+        .insert(current_keg.keg.wineskin_launcher.clone());
+    app.keg_log.follow(current_keg.last_run_log(), launched);
+    app.load_view(ViewID::Named("keg_log"));
+    Ok(())
+}
+
+pub fn launch_keg(app: &mut App, _state: &AsyncState) -> Result<()> {
+    let Some(current_keg) = &app.current_keg else {
+        return Ok(());
+    };
+    if !confirm_launch(current_keg)? {
+        return Ok(());
+    }
+    start_launch(app, None)
+}
+
+pub fn launch_keg_with_arguments(
+    app: &mut App,
+    _state: &AsyncState,
+) -> Result<()> {
+    let Some(current_keg) = &app.current_keg else {
+        return Ok(());
+    };
+    eprintln!("┌──────────────────────────────────┐");
+    eprintln!("│ Launch with arguments            │");
+    eprintln!("└──────────────────────────────────┘");
+    if app.is_current_keg_running() {
+        println!(
+            "  The keg is already running, so it wouldn't see the arguments"
+        );
+        println!("Press enter to return to the TUI");
+        return wait_for_enter();
+    }
+    let saved_flags = &current_keg.plist.program_flags;
+    println!(
+        "  Program Flags: {}",
+        if saved_flags.is_empty() {
+            "(none)"
+        } else {
+            saved_flags
+        }
+    );
+    println!("  The arguments are added to these for this launch only.");
+    let history = launch_arguments::history(&current_keg.keg.name);
+    for (index, arguments) in history.iter().enumerate() {
+        println!("  [{}] {arguments}", index + 1);
+    }
+    let answer = prompt(
+        if history.is_empty() {
+            "Arguments (leave empty to cancel): "
+        } else {
+            "Arguments, or the number of earlier ones (leave empty to cancel): "
+        },
+        |_| true,
+    )?;
+    let answer = answer.trim();
+    let arguments = match answer.parse::<usize>() {
+        Ok(number) if (1..=history.len()).contains(&number) => {
+            history[number - 1].clone()
+        }
+        _ => answer.to_owned(),
+    };
+    if arguments.is_empty() || !confirm_launch(current_keg)? {
+        return Ok(());
+    }
+    let _ = launch_arguments::remember(&current_keg.keg.name, &arguments);
+    let temporary_flags = TemporaryFlags::apply(
+        &current_keg.keg.name,
+        &current_keg.keg.config_file,
+        saved_flags,
+        &arguments,
+    )
+    .context("Failed to pass the arguments through Program Flags")?;
+    start_launch(app, Some(temporary_flags))
+}
+
+/// Runs the executable last run in the current keg again.
+pub fn resume_executable(app: &mut App, _state: &AsyncState) -> Result<()> {
+    let Some(executable) = app
+        .current_keg
+        .as_ref()
+        .and_then(|current_keg| {
+            app.last_executables.get(&current_keg.bundle_path())
+        })
+        .cloned()
+    else {
+        return Ok(());
+    };
+    views::executables::launch(app, &executable);
+    Ok(())
+}
+
+/// Runs the current keg's custom command at `index`, ordered by name, with
+/// `sh` in its C drive and the environment winetricks gets.
+pub fn run_custom_command(
+    app: &mut App,
+    _state: &AsyncState,
+    index: usize,
+) -> Result<()> {
+    let Some(current_keg) = &app.current_keg else {
+        return Ok(());
+    };
+    let Some((name, command)) =
+        current_keg.plist.kegtui_commands.iter().nth(index)
+    else {
+        return Ok(());
+    };
+    eprintln!("┌──────────────────────────────────┐");
+    eprintln!("│ Running a custom command         │");
+    eprintln!("└──────────────────────────────────┘");
+    println!("  {name}: {command}");
+    let status = app.runner.status(
+        Command::new("sh")
+            .arg("-c")
+            .arg(command)
+            .current_dir(&current_keg.keg.c_drive)
+            .env_clear()
+            .envs(current_keg.winetricks_environment())
+            .envs(&current_keg.plist.kegtui_environment),
+    )?;
+    if !status.success() {
+        println!("  {name} failed ({status})");
+    }
+    println!("Press enter to return to the TUI");
+    wait_for_enter()
+}
+
+pub fn kill_wineserver_via_wineskin_launcher(
+    runner: &dyn CommandRunner,
+    wineskin_launcher: &OsStr,
+) -> Result<()> {
+    runner.status(Command::new(wineskin_launcher).arg("WSS-wineserverkill"))?;
+    Ok(())
+}
+
+pub fn kill_wineserver(app: &mut App, _state: &AsyncState) -> Result<()> {
+    if let Some(current_keg) = &mut app.current_keg {
+        eprintln!("┌─────────────────────────────────────────┐");
+        eprintln!("│ Killing processes spawned from this keg │");
+        eprintln!("└─────────────────────────────────────────┘");
+        kill_wineserver_via_wineskin_launcher(
+            &*app.runner,
+            &current_keg.keg.wineskin_launcher,
+        )?;
+        app.open_kegs_wineskin_launchers
+            .remove(&current_keg.keg.wineskin_launcher);
+    }
+    Ok(())
+}
+
+pub fn open_crash_dumps(app: &mut App, _state: &AsyncState) -> Result<()> {
+    let Some(current_keg) = &mut app.current_keg else {
+        return Ok(());
+    };
+    if let Some(newest) = current_keg.keg.find_crash_dumps().first()
+        && let Some(parent) = newest.path.parent()
+    {
+        app.runner.status(
+            Command::new(&app.config.explorer)
+                .arg(parent.to_string_lossy().to_string()),
+        )?;
+    } else {
+        eprintln!("┌──────────────────────────────────┐");
+        eprintln!("│ No crash dumps found             │");
+        eprintln!("│ Press enter to return to the TUI │");
+        eprintln!("└──────────────────────────────────┘");
+        wait_for_enter()?;
+    }
+    Ok(())
+}
+
+pub fn kill_all_wineservers(app: &mut App, _state: &AsyncState) -> Result<()> {
+    if app.open_kegs_wineskin_launchers.is_empty() {
+        eprintln!("┌──────────────────────────────────┐");
+        eprintln!("│ No kegs opened                   │");
+        eprintln!("│ Press enter to return to the TUI │");
+        eprintln!("└──────────────────────────────────┘");
+        wait_for_enter()?;
+    } else {
+        eprintln!("┌─────────────────────────────────────────┐");
+        eprintln!("│ Killing all processes spawned from kegs │");
+        eprintln!("└─────────────────────────────────────────┘");
+        for wineskin_launcher in app.open_kegs_wineskin_launchers.drain() {
+            kill_wineserver_via_wineskin_launcher(
+                &*app.runner,
+                &wineskin_launcher,
+            )?;
+        }
+    }
+    Ok(())
+}
@@ -0,0 +1,399 @@
+// Copyright (C) 2026 Ethan Uppal.
+//
+// This program is free software: you can redistribute it and/or modify it under
+// the terms of the GNU General Public License as published by the Free Software
+// Foundation, version 3 of the License only.
+//
+// This program is distributed in the hope that it will be useful, but WITHOUT
+// ANY WARRANTY; without even the implied warranty of MERCHANTABILITY or FITNESS
+// FOR A PARTICULAR PURPOSE. See the GNU General Public License for more
+// details.
+//
+// You should have received a copy of the GNU General Public License along with
+// this program.  If not, see <https://www.gnu.org/licenses/>.
+
+//! Applying keg manifests to kegs and building new kegs from them.
+
+use std::{
+    fs,
+    path::{Path, PathBuf},
+    process::Command,
+    time::Instant,
+};
+
+use color_eyre::{Result, eyre::Context};
+
+use crate::{
+    actions::{
+        create::{assemble_keg, choose_keg_path},
+        prompts::{
+            choose_numbered, is_current_keg_busy, prompt, wait_for_enter,
+        },
+        winetricks::{prepare_winetricks, queue_winetricks},
+    },
+    app::{App, AsyncState},
+    app_config::expand_path,
+    keg::{CurrentKeg, Keg},
+    keg_manifest::{InstallerStep, KegManifest},
+    keyboard, remote,
+    tasks::{PrefixTask, TaskKind},
+    wine_path::WinePath,
+};
+
+/// Asks the question of each step that needs a file from the user, so
+/// everything after can run unattended. Returns `None` if the user cancels.
+fn ask_for_installers(
+    steps: &[InstallerStep],
+) -> Result<Option<Vec<Option<PathBuf>>>> {
+    let mut answers = vec![];
+    for step in steps {
+        let Some(question) = &step.ask else {
+            answers.push(None);
+            continue;
+        };
+        println!("  {}", step.description);
+        let answer =
+            prompt(&format!("{question} (blank to cancel): "), |answer| {
+                let answer = answer.trim();
+                let exists = answer.is_empty()
+                    || Path::new(&expand_path(answer)).is_file();
+                if !exists {
+                    println!("  No file at {answer}");
+                }
+                exists
+            })?;
+        if answer.trim().is_empty() {
+            return Ok(None);
+        }
+        answers.push(Some(PathBuf::from(expand_path(answer.trim()))));
+    }
+    Ok(Some(answers))
+}
+
+/// Queues running `step`'s program in `current_keg`, which is `local_file`
+/// if the step asked for one, downloading it into the C drive first if
+/// needed. Returns `false` if it couldn't be queued.
+fn queue_installer_step(
+    app: &App,
+    current_keg: &CurrentKeg,
+    step: &InstallerStep,
+    local_file: Option<&Path>,
+) -> Result<bool> {
+    let program = if let Some(local_file) = local_file {
+        local_file.to_path_buf()
+    } else if let Some(url) = &step.download {
+        let Some(file_name) = remote::file_name_from_url(url) else {
+            println!(
+                "  Skipping \"{}\": {url} names no file",
+                step.description
+            );
+            return Ok(false);
+        };
+        let downloads = current_keg.keg.c_drive.join("users/Public/Downloads");
+        fs::create_dir_all(&downloads)?;
+        let destination = downloads.join(file_name);
+        println!("  Downloading {url}");
+        if let Err(error) = remote::download(&*app.runner, url, &destination) {
+            println!("  Skipping \"{}\": {error}", step.description);
+            return Ok(false);
+        }
+        destination
+    } else if let Some(run) = &step.run {
+        WinePath::from_kegworks(run).to_host(&current_keg.keg.c_drive)
+    } else {
+        println!(
+            "  Skipping \"{}\": it has no download or run",
+            step.description
+        );
+        return Ok(false);
+    };
+    let mut command = Command::new(current_keg.wine_executable());
+    command
+        .env_clear()
+        .envs(current_keg.winetricks_environment())
+        .arg(program)
+        .args(&step.arguments);
+    app.tasks.enqueue(PrefixTask {
+        keg_name: current_keg.keg.name.clone(),
+        bundle: current_keg.bundle_path(),
+        kind: TaskKind::Mutation,
+        description: step.description.clone(),
+        run_label: "installer",
+        command,
+        wineskin_launcher: current_keg.keg.wineskin_launcher.clone(),
+        log_directory: current_keg.keg.log_directory.clone(),
+        config_file: current_keg.keg.config_file.clone(),
+        winetricks_run_flags: None,
+        timeout: None,
+        output_log: None,
+        explain_failure: None,
+        summarize: None,
+        snapshot_prefix: false,
+    });
+    Ok(true)
+}
+
+/// Asks for the path or URL of a manifest and reads it, explaining any
+/// problem and returning `None` if there is one or the user cancels.
+fn read_manifest_from_user(app: &App) -> Result<Option<KegManifest>> {
+    let answer = prompt(
+        "Path or URL of a keg-manifest.toml (blank to cancel): ",
+        |_| true,
+    )?;
+    let answer = answer.trim();
+    if answer.is_empty() {
+        return Ok(None);
+    }
+    let manifest_path = if remote::is_url(answer) {
+        let destination = PathBuf::from("/tmp/kegtui-keg-manifest.toml");
+        if let Err(error) = remote::download(&*app.runner, answer, &destination)
+        {
+            println!("  {error}");
+            println!("Press enter to return to the TUI");
+            wait_for_enter()?;
+            return Ok(None);
+        }
+        destination
+    } else {
+        PathBuf::from(expand_path(answer))
+    };
+    match KegManifest::read(&manifest_path) {
+        Ok(manifest) => Ok(Some(manifest)),
+        Err(error) => {
+            println!("  {error:#}");
+            println!("Press enter to return to the TUI");
+            wait_for_enter()?;
+            Ok(None)
+        }
+    }
+}
+
+pub fn apply_keg_manifest(app: &mut App, _state: &AsyncState) -> Result<()> {
+    if is_current_keg_busy(app)? {
+        return Ok(());
+    }
+    let Some(current_keg) = &app.current_keg else {
+        return Ok(());
+    };
+    eprintln!("┌──────────────────────────────────┐");
+    eprintln!("│ Apply a keg manifest             │");
+    eprintln!("└──────────────────────────────────┘");
+    let Some(manifest) = read_manifest_from_user(app)? else {
+        return Ok(());
+    };
+
+    println!("  Manifest for {}", manifest.name);
+    if let Some(engine) = &manifest.engine
+        && current_keg.keg.engine_version.as_ref() != Some(engine)
+    {
+        println!(
+            "  Warning: it was made with {engine}, but this keg has {}",
+            current_keg
+                .keg
+                .engine_version
+                .as_deref()
+                .unwrap_or("an unknown engine")
+        );
+    }
+    println!("  Replaces this keg's config");
+    let verbs = manifest.missing_winetricks(current_keg);
+    if !verbs.is_empty() {
+        println!("  Installs winetricks {}", verbs.join(" "));
+    }
+    for step in &manifest.steps {
+        println!("  Runs {}", step.summary());
+    }
+    if !manifest.steps.is_empty() {
+        println!(
+            "  Installers run as you, so only apply manifests from people you trust"
+        );
+    }
+    let answer = prompt("Apply? [yY/nN] ", |answer| {
+        ["y", "Y", "n", "N"].contains(&answer.trim())
+    })?;
+    if !["y", "Y"].contains(&answer.trim()) {
+        return Ok(());
+    }
+    let Some(local_files) = ask_for_installers(&manifest.steps)? else {
+        return Ok(());
+    };
+
+    if let Some(current_keg) = &mut app.current_keg {
+        let keyboard = current_keg.plist.extract_config().keyboard;
+        current_keg.plist.update_from_config(&manifest.config);
+        current_keg.save_plist_before_installing("Apply Manifest")?;
+        keyboard::apply_if_changed(&app.tasks, current_keg, &keyboard)?;
+    }
+    let Some(current_keg) = &app.current_keg else {
+        return Ok(());
+    };
+    let queued =
+        queue_manifest_tasks(app, current_keg, &manifest, verbs, &local_files)?;
+    eprintln!("┌──────────────────────────────────┐");
+    eprintln!("│ Applied the manifest's config    │");
+    eprintln!("│ Press enter to return to the TUI │");
+    eprintln!("└──────────────────────────────────┘");
+    if queued > 0 {
+        println!("  Queued {queued} task(s); follow them from Task Queue");
+    }
+    wait_for_enter()?;
+    Ok(())
+}
+
+/// Queues installing `verbs` and then each of `manifest`'s installer steps,
+/// given the files [`ask_for_installers`] got, returning how many tasks were
+/// queued.
+fn queue_manifest_tasks(
+    app: &App,
+    current_keg: &CurrentKeg,
+    manifest: &KegManifest,
+    verbs: Vec<String>,
+    local_files: &[Option<PathBuf>],
+) -> Result<usize> {
+    let mut queued = 0;
+    if !verbs.is_empty() {
+        prepare_winetricks(app, current_keg)?;
+        queue_winetricks(app, current_keg, verbs, None);
+        queued += 1;
+    }
+    for (step, local_file) in manifest.steps.iter().zip(local_files) {
+        if queue_installer_step(app, current_keg, step, local_file.as_deref())?
+        {
+            queued += 1;
+        }
+    }
+    Ok(queued)
+}
+
+pub fn build_keg_from_manifest(
+    app: &mut App,
+    state: &AsyncState,
+) -> Result<()> {
+    eprintln!("┌──────────────────────────────────┐");
+    eprintln!("│ Build a keg from a manifest      │");
+    eprintln!("└──────────────────────────────────┘");
+    let Some(manifest) = read_manifest_from_user(app)? else {
+        return Ok(());
+    };
+    println!("  Manifest for {}", manifest.name);
+
+    let engine = match state
+        .engines
+        .iter()
+        .find(|engine| manifest.is_for_engine(engine))
+    {
+        Some(engine) => engine,
+        None => {
+            match &manifest.engine {
+                Some(engine) => println!(
+                    "  No installed engine is {engine}; install it from Engines, or pick another"
+                ),
+                None => println!("  The manifest names no engine"),
+            }
+            let choices = state
+                .engines
+                .iter()
+                .map(|engine| engine.path.display().to_string())
+                .collect::<Vec<_>>();
+            let Some(index) = choose_numbered("engine", &choices)? else {
+                return Ok(());
+            };
+            &state.engines[index]
+        }
+    };
+    let wrapper = match state.wrappers.as_slice() {
+        [] => {
+            println!("  No wrappers found; install one from Engines first");
+            println!("Press enter to return to the TUI");
+            wait_for_enter()?;
+            return Ok(());
+        }
+        [wrapper] => wrapper,
+        wrappers => {
+            let choices = wrappers
+                .iter()
+                .map(|wrapper| {
+                    format!(
+                        "{} ({})",
+                        wrapper.path.display(),
+                        wrapper.summary()
+                    )
+                })
+                .collect::<Vec<_>>();
+            let Some(index) = choose_numbered("wrapper", &choices)? else {
+                return Ok(());
+            };
+            &wrappers[index]
+        }
+    };
+    println!("  Engine:  {}", engine.path.display());
+    println!("  Wrapper: {}", wrapper.path.display());
+    if !manifest.winetricks.is_empty() {
+        println!(
+            "  Then installs winetricks {}",
+            manifest.winetricks.join(" ")
+        );
+    }
+    for step in &manifest.steps {
+        println!("  Then runs {}", step.summary());
+    }
+    if !manifest.steps.is_empty() {
+        println!(
+            "  Installers run as you, so only build from manifests by people you trust"
+        );
+    }
+    let answer = prompt("Build? [yY/nN] ", |answer| {
+        ["y", "Y", "n", "N"].contains(&answer.trim())
+    })?;
+    if !["y", "Y"].contains(&answer.trim()) {
+        return Ok(());
+    }
+    // Everything that needs an answer is asked up front, so the rest of the
+    // build runs unattended.
+    let Some(local_files) = ask_for_installers(&manifest.steps)? else {
+        return Ok(());
+    };
+    let Some((name, keg_path)) =
+        choose_keg_path(app, Some(manifest.name.clone()))?
+    else {
+        return Ok(());
+    };
+
+    let started = Instant::now();
+    let (is_created, listener) = assemble_keg(
+        app,
+        &engine.path.to_string_lossy(),
+        &wrapper.path.to_string_lossy(),
+        &name,
+        &keg_path,
+    )?;
+    app.notify_finished(
+        &format!(
+            "{name}: keg creation {}",
+            if is_created { "done" } else { "failed" }
+        ),
+        started.elapsed(),
+    );
+    if !is_created {
+        return listener.wait_for_enter();
+    }
+    let mut new_keg = CurrentKeg::try_from(&Keg::from_path(&keg_path))
+        .context("Failed to read the new keg")?;
+    let keyboard = new_keg.plist.extract_config().keyboard;
+    new_keg.plist.update_from_config(&manifest.config);
+    new_keg.save_plist_before_installing("Build from Manifest")?;
+    keyboard::apply_if_changed(&app.tasks, &new_keg, &keyboard)?;
+    let verbs = manifest.missing_winetricks(&new_keg);
+    let queued =
+        queue_manifest_tasks(app, &new_keg, &manifest, verbs, &local_files)?;
+    eprintln!("┌──────────────────────────────────┐");
+    eprintln!("│ Built your keg from the manifest │");
+    eprintln!("│ Press enter to return to the TUI │");
+    eprintln!("└──────────────────────────────────┘");
+    if queued > 0 {
+        println!(
+            "  Queued {queued} task(s); follow them from the keg's Task Queue"
+        );
+    }
+    listener.wait_for_enter()
+}
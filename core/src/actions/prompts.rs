@@ -0,0 +1,236 @@
+// Copyright (C) 2026 Ethan Uppal.
+//
+// This program is free software: you can redistribute it and/or modify it under
+// the terms of the GNU General Public License as published by the Free Software
+// Foundation, version 3 of the License only.
+//
+// This program is distributed in the hope that it will be useful, but WITHOUT
+// ANY WARRANTY; without even the implied warranty of MERCHANTABILITY or FITNESS
+// FOR A PARTICULAR PURPOSE. See the GNU General Public License for more
+// details.
+//
+// You should have received a copy of the GNU General Public License along with
+// this program.  If not, see <https://www.gnu.org/licenses/>.
+
+//! Prompts for actions that borrow the terminal from the TUI, e.g., asking
+//! for a line of input or an edit in the user's editor.
+
+use std::{
+    cell::Cell, ffi::OsStr, fmt::Display, fs, io, path::Path, process::Command,
+    sync::mpsc, thread, time::Duration,
+};
+
+use color_eyre::Result;
+
+use crate::{app::App, drafts::Draft, run_logs::describe_age, tasks::KegLock};
+
+pub fn wait_for_enter() -> Result<()> {
+    io::stdin().read_line(&mut String::new())?;
+    Ok(())
+}
+
+pub fn prompt(prompt: &str, validate: impl Fn(&str) -> bool) -> Result<String> {
+    use std::io::Write;
+
+    let mut buffer = String::new();
+    loop {
+        buffer.clear();
+        print!("{prompt}");
+        io::stdout().flush()?;
+        io::stdin().read_line(&mut buffer)?;
+        if validate(&buffer) {
+            break;
+        }
+    }
+    Ok(buffer)
+}
+
+/// Reads a line from stdin on a background thread, so enter can cancel a
+/// running command. The read stays pending until consumed with
+/// [`EnterListener::wait_for_enter`], which must happen before returning to
+/// the TUI.
+pub struct EnterListener {
+    receiver: mpsc::Receiver<()>,
+    pressed: Cell<bool>,
+}
+
+impl EnterListener {
+    pub fn spawn() -> Self {
+        let (sender, receiver) = mpsc::channel();
+        thread::spawn(move || {
+            let _ = io::stdin().read_line(&mut String::new());
+            let _ = sender.send(());
+        });
+        Self {
+            receiver,
+            pressed: Cell::new(false),
+        }
+    }
+
+    pub fn was_pressed(&self) -> bool {
+        if !self.pressed.get() && self.receiver.try_recv().is_ok() {
+            self.pressed.set(true);
+        }
+        self.pressed.get()
+    }
+
+    /// Waits for the next press of enter that hasn't already been handled.
+    pub fn wait_for_enter(self) -> Result<()> {
+        if self.pressed.get() {
+            wait_for_enter()
+        } else {
+            let _ = self.receiver.recv();
+            Ok(())
+        }
+    }
+}
+
+pub fn spawn_thread_with_spinner<T: Send + 'static>(
+    message: &str,
+    work: impl FnOnce() -> Result<T> + Send + 'static,
+) -> Result<T> {
+    use std::io::Write;
+
+    let spinner = ["|", "/", "-", "\\"];
+    let mut i = 0;
+    let thread = thread::spawn(work);
+    print!("\x1B[?25l");
+    while !thread.is_finished() {
+        print!("{} {message}\r", spinner[i % spinner.len()]);
+        io::stdout().flush()?;
+        thread::sleep(Duration::from_millis(50));
+        i += 1;
+    }
+    print!("\x1B[?25h");
+    println!("  {message}");
+    thread.join().expect("Thread panicked")
+}
+
+pub fn read_multiline_input(
+    app: &App,
+    initial: &str,
+    editor_file: impl AsRef<OsStr>,
+) -> Result<String> {
+    let editor_file = editor_file.as_ref();
+    fs::write(editor_file, initial)?;
+    app.runner
+        .status(Command::new(&app.config.editor).arg(editor_file))?;
+    let contents = fs::read_to_string(editor_file)?;
+    Ok(contents)
+}
+
+/// Explains and returns `true` if another kegtui is changing the current keg,
+/// so an action shouldn't write to it now.
+pub fn is_current_keg_busy(app: &App) -> Result<bool> {
+    let Some(current_keg) = &app.current_keg else {
+        return Ok(false);
+    };
+    if !KegLock::is_held_elsewhere(&current_keg.bundle_path()) {
+        return Ok(false);
+    }
+    eprintln!("┌──────────────────────────────────────────┐");
+    eprintln!("│ Another kegtui is changing this keg      │");
+    eprintln!("│ Try again once it's done                 │");
+    eprintln!("│ Press enter to return to the TUI         │");
+    eprintln!("└──────────────────────────────────────────┘");
+    wait_for_enter()?;
+    Ok(true)
+}
+
+/// The arguments that open `file` in `editor` with the cursor at `line`, for
+/// the editors known to support it.
+fn editor_arguments(
+    editor: &str,
+    file: &str,
+    line: Option<usize>,
+) -> Vec<String> {
+    let Some(line) = line else {
+        return vec![file.to_owned()];
+    };
+    let name = Path::new(editor)
+        .file_name()
+        .map(|name| name.to_string_lossy().into_owned())
+        .unwrap_or_default();
+    match name.as_str() {
+        "vi" | "vim" | "nvim" | "nano" | "emacs" | "micro" | "kak" | "mg" => {
+            vec![format!("+{line}"), file.to_owned()]
+        }
+        "hx" | "helix" | "subl" | "zed" => vec![format!("{file}:{line}")],
+        "code" | "codium" | "cursor" => {
+            vec!["-g".to_owned(), format!("{file}:{line}")]
+        }
+        _ => vec![file.to_owned()],
+    }
+}
+
+/// Like [`read_multiline_input`], but autosaves the edits to `draft` and
+/// first offers to restore a draft left by an edit that was never applied.
+/// The editor opens at the line `line_of` finds in the text, if any.
+pub fn read_multiline_input_with_draft(
+    app: &App,
+    initial: &str,
+    editor_file: &str,
+    draft: &Draft,
+    line_of: impl Fn(&str) -> Option<usize>,
+) -> Result<String> {
+    let mut initial = initial.to_owned();
+    if let Some((saved, modified)) = draft.saved()
+        && saved != initial
+    {
+        println!(
+            "  Unapplied edits from {} were found.",
+            describe_age(modified)
+        );
+        let answer = prompt("Restore draft? [yY/nN] ", |answer| {
+            ["y", "Y", "n", "N"].contains(&answer.trim())
+        })?;
+        if ["y", "Y"].contains(&answer.trim()) {
+            initial = saved;
+        } else {
+            draft.discard();
+        }
+    }
+    fs::write(editor_file, &initial)?;
+    let arguments =
+        editor_arguments(&app.config.editor, editor_file, line_of(&initial));
+    draft.autosave_while(Path::new(editor_file), || {
+        app.runner
+            .status(Command::new(&app.config.editor).args(&arguments))
+    })?;
+    Ok(fs::read_to_string(editor_file)?)
+}
+
+/// Explains that an edited form couldn't be parsed and that its draft is
+/// kept for next time.
+pub fn report_unreadable_draft(error: impl Display) -> Result<()> {
+    eprintln!("┌──────────────────────────────────────────┐");
+    eprintln!("│ Nothing was changed                      │");
+    eprintln!("│ Your edits are kept as a draft and will  │");
+    eprintln!("│ be offered the next time you open this   │");
+    eprintln!("└──────────────────────────────────────────┘");
+    println!("{error}");
+    println!("Press enter to return to the TUI");
+    wait_for_enter()
+}
+
+/// Lists `choices` as `[1] choice` and asks for the number of one, returning
+/// its index, or `None` if the answer is blank.
+pub fn choose_numbered(
+    noun: &str,
+    choices: &[String],
+) -> Result<Option<usize>> {
+    for (index, choice) in choices.iter().enumerate() {
+        println!("  [{}] {choice}", index + 1);
+    }
+    let answer = prompt(
+        &format!("Number of the {noun} to use (leave empty to cancel): "),
+        |answer| {
+            answer.trim().is_empty()
+                || answer
+                    .trim()
+                    .parse::<usize>()
+                    .is_ok_and(|number| (1..=choices.len()).contains(&number))
+        },
+    )?;
+    Ok(answer.trim().parse::<usize>().ok().map(|number| number - 1))
+}
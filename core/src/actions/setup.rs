@@ -0,0 +1,101 @@
+// Copyright (C) 2026 Ethan Uppal.
+//
+// This program is free software: you can redistribute it and/or modify it under
+// the terms of the GNU General Public License as published by the Free Software
+// Foundation, version 3 of the License only.
+//
+// This program is distributed in the hope that it will be useful, but WITHOUT
+// ANY WARRANTY; without even the implied warranty of MERCHANTABILITY or FITNESS
+// FOR A PARTICULAR PURPOSE. See the GNU General Public License for more
+// details.
+//
+// You should have received a copy of the GNU General Public License along with
+// this program.  If not, see <https://www.gnu.org/licenses/>.
+
+//! Installing kegtui's dependencies and checking for kegtui updates.
+
+use color_eyre::Result;
+
+use crate::{
+    actions::prompts::{prompt, wait_for_enter},
+    app::{App, AsyncState},
+    remote,
+    setup::SetupComponent,
+    views,
+};
+
+pub fn check_for_updates(app: &mut App, _state: &AsyncState) -> Result<()> {
+    eprintln!("┌──────────────────────────────────┐");
+    eprintln!("│ Checking for updates             │");
+    eprintln!("│ Press enter to return to the TUI │");
+    eprintln!("└──────────────────────────────────┘");
+    match remote::latest_release_tag(&*app.runner, views::about::REPOSITORY) {
+        Ok(tag) if tag.trim_start_matches('v') == views::about::VERSION => {
+            println!(
+                "  kegtui {} is the latest release",
+                views::about::VERSION
+            );
+        }
+        Ok(tag) => {
+            println!(
+                "  kegtui {tag} is available (you have {})",
+                views::about::VERSION
+            );
+            println!(
+                "  https://github.com/{}/releases/latest",
+                views::about::REPOSITORY
+            );
+        }
+        Err(error) => println!("  Couldn't check for updates: {error}"),
+    }
+    wait_for_enter()?;
+    Ok(())
+}
+
+pub fn install_setup_components(
+    app: &mut App,
+    _state: &AsyncState,
+) -> Result<()> {
+    let components = SetupComponent::ALL
+        .into_iter()
+        .filter(|component| app.setup_selection.contains(component))
+        .collect::<Vec<_>>();
+
+    eprintln!("┌──────────────┐");
+    eprintln!("│ Setup wizard │");
+    eprintln!("└──────────────┘");
+    if components.is_empty() {
+        println!("No components are selected.");
+    } else {
+        println!("kegtui will now install:");
+        for component in &components {
+            println!("  {}: {}", component.name(), component.description());
+        }
+        let answer = prompt("Is this ok? [yY/nN] ", |answer| {
+            ["y", "Y", "n", "N"].contains(&answer.trim())
+        })?;
+
+        if ["y", "Y"].contains(&answer.trim()) {
+            for component in components {
+                println!("=> Installing {}", component.name());
+                match component.install(&*app.runner, &app.config) {
+                    Ok(()) => {
+                        app.setup_selection.remove(&component);
+                    }
+                    Err(error) => {
+                        println!("  {error:#}");
+                        println!("  Skipping the remaining components");
+                        break;
+                    }
+                }
+            }
+        }
+    }
+
+    eprintln!("┌──────────────────────────────────┐");
+    eprintln!("│ Press enter to return to the TUI │");
+    eprintln!("└──────────────────────────────────┘");
+    wait_for_enter()?;
+
+    Ok(())
+}
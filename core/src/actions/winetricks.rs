@@ -0,0 +1,222 @@
+// Copyright (C) 2026 Ethan Uppal.
+//
+// This program is free software: you can redistribute it and/or modify it under
+// the terms of the GNU General Public License as published by the Free Software
+// Foundation, version 3 of the License only.
+//
+// This program is distributed in the hope that it will be useful, but WITHOUT
+// ANY WARRANTY; without even the implied warranty of MERCHANTABILITY or FITNESS
+// FOR A PARTICULAR PURPOSE. See the GNU General Public License for more
+// details.
+//
+// You should have received a copy of the GNU General Public License along with
+// this program.  If not, see <https://www.gnu.org/licenses/>.
+
+//! Installing winetricks verbs into a keg and undoing them.
+
+use std::{
+    fs,
+    os::unix::fs::PermissionsExt,
+    path::{Path, PathBuf},
+    process::Command,
+};
+
+use color_eyre::Result;
+
+use crate::{
+    actions::prompts::{
+        is_current_keg_busy, prompt, spawn_thread_with_spinner, wait_for_enter,
+    },
+    app::{App, AsyncState},
+    app_config::TimeoutsConfig,
+    disk_usage, journal,
+    keg::CurrentKeg,
+    keg_config::WinetricksRunFlags,
+    run_logs::describe_age,
+    setup,
+    setup::WINETRICKS_SCRIPT,
+    snapshots,
+    tasks::{KegLock, PrefixTask, TaskKind},
+    winetricks_cache, winetricks_catalog, winetricks_errors,
+};
+
+/// Puts the latest winetricks, fetching it if needed, where WSS-winetricks
+/// looks for it in `current_keg`.
+pub fn prepare_winetricks(app: &App, current_keg: &CurrentKeg) -> Result<()> {
+    if !Path::new(WINETRICKS_SCRIPT).is_file() {
+        eprintln!("┌────────────────────────────┐");
+        eprintln!("│ Fetching latest winetricks │");
+        eprintln!("└────────────────────────────┘");
+        setup::fetch_winetricks(&*app.runner)?;
+    }
+    fs::copy(
+        WINETRICKS_SCRIPT,
+        current_keg.keg.wine_prefix.join("winetricks"),
+    )?;
+    fs::set_permissions(
+        current_keg.keg.wine_prefix.join("winetricks"),
+        fs::Permissions::from_mode(0o777),
+    )?;
+    Ok(())
+}
+
+/// Where WSS-winetricks writes its output when run with `run_flags`, or `None`
+/// if they turn logging off.
+pub fn winetricks_output_log(
+    current_keg: &CurrentKeg,
+    run_flags: WinetricksRunFlags,
+) -> Option<PathBuf> {
+    (!run_flags.winetricks_disable_logging)
+        .then(|| current_keg.keg.winetricks_logfile.clone())
+}
+
+/// Queues installing `verbs` into `current_keg`, returning how many tasks are
+/// ahead of it.
+pub fn queue_winetricks(
+    app: &App,
+    current_keg: &CurrentKeg,
+    verbs: Vec<String>,
+    run_flags: Option<WinetricksRunFlags>,
+) -> usize {
+    let description = verbs.join(" ");
+    let mut command = Command::new(&current_keg.keg.wineskin_launcher);
+    command
+        .env_clear()
+        .envs(current_keg.winetricks_environment())
+        .arg("WSS-winetricks")
+        .args(verbs);
+    app.tasks.enqueue(PrefixTask {
+        keg_name: current_keg.keg.name.clone(),
+        bundle: current_keg.bundle_path(),
+        kind: TaskKind::Mutation,
+        description: format!("winetricks {description}"),
+        run_label: "winetricks",
+        command,
+        wineskin_launcher: current_keg.keg.wineskin_launcher.clone(),
+        log_directory: current_keg.keg.log_directory.clone(),
+        config_file: current_keg.keg.config_file.clone(),
+        winetricks_run_flags: run_flags,
+        timeout: TimeoutsConfig::limit(app.config.timeouts.winetricks),
+        output_log: winetricks_output_log(
+            current_keg,
+            run_flags
+                .unwrap_or_else(|| current_keg.plist.winetricks_run_flags()),
+        ),
+        explain_failure: Some(winetricks_errors::distill),
+        summarize: None,
+        snapshot_prefix: app.config.snapshots.before_winetricks,
+    })
+}
+
+/// Restores the prefix from the snapshot taken before the last winetricks
+/// install. Each undo uses up its snapshot, so undoing again goes back one
+/// install further.
+pub fn undo_winetricks(app: &mut App, _state: &AsyncState) -> Result<()> {
+    if is_current_keg_busy(app)? {
+        return Ok(());
+    }
+    let Some(current_keg) = &app.current_keg else {
+        return Ok(());
+    };
+    eprintln!("┌──────────────────────────────────┐");
+    eprintln!("│ Undo winetricks                  │");
+    eprintln!("└──────────────────────────────────┘");
+    let bundle = current_keg.bundle_path();
+    let snapshot = snapshots::list_prefix_snapshots(&current_keg.keg.name)
+        .into_iter()
+        .next();
+    if let Some(snapshot) = &snapshot
+        && app.tasks.pending_count(&current_keg.keg.name) == 0
+        && !app.is_current_keg_running()
+    {
+        let taken = fs::metadata(snapshot)
+            .and_then(|metadata| metadata.modified())
+            .map(describe_age)
+            .unwrap_or_else(|_| "at an unknown time".to_owned());
+        println!("  Snapshot taken {taken}: {}", snapshot.display());
+        println!(
+            "  Everything changed in the prefix since then is lost, not just winetricks"
+        );
+        let answer = prompt("Restore it? [yY/nN] ", |answer| {
+            ["y", "Y", "n", "N"].contains(&answer.trim())
+        })?;
+        if !["y", "Y"].contains(&answer.trim()) {
+            return Ok(());
+        }
+        let Some(_keg_lock) = KegLock::try_acquire(&bundle) else {
+            println!("  Something else started changing the keg; try again");
+            println!("Press enter to return to the TUI");
+            return wait_for_enter();
+        };
+        let restore = {
+            let bundle = bundle.clone();
+            let snapshot = snapshot.clone();
+            spawn_thread_with_spinner("Restoring the prefix...", move || {
+                Ok(snapshots::restore_prefix(&bundle, &snapshot))
+            })?
+        };
+        match restore {
+            Ok(()) => {
+                fs::remove_file(snapshot)?;
+                let _ = journal::append(
+                    &bundle,
+                    "snapshot",
+                    &format!("restored prefix ({})", snapshot.display()),
+                );
+                println!("  Restored the prefix");
+            }
+            Err(error) => {
+                println!("  Failed to restore the prefix: {error}");
+            }
+        }
+    } else if snapshot.is_none() {
+        println!(
+            "  No snapshots to undo to; winetricks installs are snapshotted when [snapshots] before-winetricks is on"
+        );
+    } else if app.is_current_keg_running() {
+        println!("  The keg is running; kill its processes first");
+    } else {
+        println!("  Wait for this keg's queued tasks to finish first");
+    }
+    println!("Press enter to return to the TUI");
+    wait_for_enter()
+}
+
+pub fn clear_winetricks_cache(
+    app: &mut App,
+    _state: &AsyncState,
+) -> Result<()> {
+    eprintln!("┌──────────────────────────────────┐");
+    eprintln!("│ Press enter to return to the TUI │");
+    eprintln!("└──────────────────────────────────┘");
+    for file in [WINETRICKS_SCRIPT, winetricks_catalog::CACHE_FILE] {
+        if PathBuf::from(file).try_exists()? {
+            fs::remove_file(file)?;
+            eprintln!("rm {file}");
+        }
+    }
+    app.winetricks_browser.reload();
+    let downloads_size = winetricks_cache::entries()
+        .iter()
+        .map(|entry| entry.size)
+        .sum::<u64>();
+    if downloads_size > 0 {
+        let answer = prompt(
+            &format!(
+                "Also delete {} of downloads shared by every keg? [yY/nN] ",
+                disk_usage::format_size(downloads_size)
+            ),
+            |answer| ["y", "Y", "n", "N"].contains(&answer.trim()),
+        )?;
+        if ["y", "Y"].contains(&answer.trim()) {
+            winetricks_cache::clear()?;
+            eprintln!(
+                "rm -r {}",
+                winetricks_cache::cache_directory().display()
+            );
+        }
+    }
+    wait_for_enter()?;
+
+    Ok(())
+}
@@ -0,0 +1,218 @@
+// Copyright (C) 2026 Ethan Uppal.
+//
+// This program is free software: you can redistribute it and/or modify it under
+// the terms of the GNU General Public License as published by the Free Software
+// Foundation, version 3 of the License only.
+//
+// This program is distributed in the hope that it will be useful, but WITHOUT
+// ANY WARRANTY; without even the implied warranty of MERCHANTABILITY or FITNESS
+// FOR A PARTICULAR PURPOSE. See the GNU General Public License for more
+// details.
+//
+// You should have received a copy of the GNU General Public License along with
+// this program.  If not, see <https://www.gnu.org/licenses/>.
+
+//! Adopting hand-rolled `.app` wrappers, which have a Wine prefix but no
+//! `KegworksConfig.app`, as kegs by filling in what they lack from a wrapper
+//! template.
+
+use std::{
+    fs,
+    os::unix,
+    path::{Path, PathBuf},
+};
+
+use color_eyre::{
+    Result,
+    eyre::{Context, bail},
+};
+use walkdir::WalkDir;
+
+use crate::{keg::WrapperFlavor, keg_plist::KegPlist};
+
+/// Where the bundle's own `Info.plist` is kept after adopting it.
+pub const ORIGINAL_INFO_PLIST: &str = "Contents/Info.plist.kegtui-original";
+
+/// Where kegs keep their prefix and their Wine build.
+const PREFIX: &str = "Contents/SharedSupport/prefix";
+const WINE: &str = "Contents/SharedSupport/wine";
+
+/// How deep in `Contents` to look for the prefix and Wine build.
+const SEARCH_DEPTH: usize = 5;
+
+/// Parts of the template never copied into the bundle, relative to
+/// `Contents`.
+const SKIPPED_TEMPLATE_PATHS: [&str; 3] =
+    ["Info.plist", "SharedSupport/prefix", "SharedSupport/wine"];
+
+fn is_prefix(path: &Path) -> bool {
+    path.join("system.reg").is_file() && path.join("drive_c").is_dir()
+}
+
+fn is_wine_build(path: &Path) -> bool {
+    ["wine", "wine64"]
+        .into_iter()
+        .any(|wine| path.join("bin").join(wine).is_file())
+}
+
+fn find_directory(
+    bundle: &Path,
+    predicate: impl Fn(&Path) -> bool,
+) -> Option<PathBuf> {
+    WalkDir::new(bundle.join("Contents"))
+        .max_depth(SEARCH_DEPTH)
+        .into_iter()
+        .flatten()
+        .filter(|entry| entry.file_type().is_dir())
+        .map(|entry| entry.into_path())
+        .find(|path| predicate(path))
+}
+
+/// A bundle that can be adopted.
+#[derive(Debug, Clone)]
+pub struct Adoption {
+    pub bundle: PathBuf,
+    /// The bundle's Wine prefix, which has a `system.reg` and a `drive_c`.
+    pub prefix: PathBuf,
+    /// The Wine build the bundle ships, i.e., the folder with `bin/wine`, if
+    /// it ships one.
+    pub wine: Option<PathBuf>,
+}
+
+impl Adoption {
+    /// Checks that `bundle` is an `.app` with a Wine prefix that isn't already
+    /// a keg.
+    pub fn inspect(bundle: &Path) -> Result<Self> {
+        if bundle
+            .extension()
+            .is_none_or(|extension| extension != "app")
+            || !bundle.is_dir()
+        {
+            bail!("{} isn't an .app bundle", bundle.display());
+        }
+        if let Some(flavor) = WrapperFlavor::detect(bundle) {
+            bail!("{} is already a {} keg", bundle.display(), flavor.name());
+        }
+        let Some(prefix) = find_directory(bundle, is_prefix) else {
+            bail!(
+                "{} has no Wine prefix (a folder with system.reg and drive_c)",
+                bundle.display()
+            );
+        };
+        Ok(Self {
+            bundle: bundle.to_path_buf(),
+            prefix,
+            wine: find_directory(bundle, is_wine_build),
+        })
+    }
+
+    /// Copies in whatever `template` has that the bundle lacks, keeping the
+    /// bundle's own files, merges the template's `Info.plist` under the
+    /// bundle's, and links the prefix and Wine build to where kegs keep them.
+    pub fn adopt(&self, template: &Path) -> Result<()> {
+        let info_plist = self.merged_info_plist(template)?;
+        self.link_into_place(&self.prefix, PREFIX)?;
+        if let Some(wine) = &self.wine {
+            self.link_into_place(wine, WINE)?;
+        }
+        copy_missing(template, &self.bundle).with_context(|| {
+            format!("Failed to copy {} into the app", template.display())
+        })?;
+
+        let info_plist_path = self.bundle.join("Contents/Info.plist");
+        if info_plist_path.exists() {
+            fs::copy(&info_plist_path, self.bundle.join(ORIGINAL_INFO_PLIST))
+                .context("Failed to back up the app's Info.plist")?;
+        }
+        info_plist
+            .to_file_xml(&info_plist_path)
+            .context("Failed to write the merged Info.plist")
+    }
+
+    /// The template's `Info.plist` with the bundle's keys on top, so its
+    /// name, identifier, and executable stay the same.
+    fn merged_info_plist(&self, template: &Path) -> Result<plist::Value> {
+        let Some(mut info) =
+            plist::Value::from_file(template.join("Contents/Info.plist"))
+                .context("Failed to read the template's Info.plist")?
+                .into_dictionary()
+        else {
+            bail!("The template's Info.plist isn't a dictionary");
+        };
+        let own_info_path = self.bundle.join("Contents/Info.plist");
+        if own_info_path.exists() {
+            let Some(own_info) = plist::Value::from_file(&own_info_path)
+                .context("Failed to read the app's Info.plist")?
+                .into_dictionary()
+            else {
+                bail!("The app's Info.plist isn't a dictionary");
+            };
+            for (key, value) in own_info {
+                info.insert(key, value);
+            }
+        }
+        let info = plist::Value::Dictionary(info);
+        plist::from_value::<KegPlist>(&info).context(
+            "The merged Info.plist is missing settings kegtui needs; try a different wrapper",
+        )?;
+        Ok(info)
+    }
+
+    /// Links `target` to `location` in the bundle, relative to `Contents` so
+    /// the app can still be moved, unless it's already there.
+    fn link_into_place(&self, target: &Path, location: &str) -> Result<()> {
+        let link = self.bundle.join(location);
+        if link == target {
+            return Ok(());
+        }
+        if link.symlink_metadata().is_ok() {
+            bail!(
+                "{} already exists, so {} can't be linked there",
+                link.display(),
+                target.display()
+            );
+        }
+        let relative_target = Path::new("..").join(
+            target
+                .strip_prefix(self.bundle.join("Contents"))
+                .unwrap_or(target),
+        );
+        if let Some(parent) = link.parent() {
+            fs::create_dir_all(parent)?;
+        }
+        unix::fs::symlink(&relative_target, &link).with_context(|| {
+            format!("Failed to link {} to {}", link.display(), target.display())
+        })
+    }
+}
+
+/// Copies the files and folders in `template`'s `Contents` that `bundle`
+/// doesn't have, except for [`SKIPPED_TEMPLATE_PATHS`].
+fn copy_missing(template: &Path, bundle: &Path) -> Result<()> {
+    let from = template.join("Contents");
+    let to = bundle.join("Contents");
+    let skipped = SKIPPED_TEMPLATE_PATHS.map(|path| from.join(path));
+    for entry in WalkDir::new(&from)
+        .min_depth(1)
+        .into_iter()
+        .filter_entry(|entry| !skipped.iter().any(|path| entry.path() == path))
+    {
+        let entry = entry?;
+        let destination = to.join(entry.path().strip_prefix(&from)?);
+        if destination.symlink_metadata().is_ok() {
+            continue;
+        }
+        if entry.file_type().is_dir() {
+            fs::create_dir_all(&destination)?;
+        } else if entry.file_type().is_symlink() {
+            unix::fs::symlink(fs::read_link(entry.path())?, &destination)?;
+        } else {
+            fs::copy(entry.path(), &destination)?;
+            let _ = xattrs::remove_xattr(
+                destination.as_path(),
+                "com.apple.quarantine",
+            );
+        }
+    }
+    Ok(())
+}
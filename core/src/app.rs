@@ -13,34 +13,49 @@
 // this program.  If not, see <https://www.gnu.org/licenses/>.
 
 use std::{
-    collections::HashSet,
-    env,
+    borrow::Cow,
+    collections::{HashMap, HashSet},
     ffi::OsString,
-    fs, io,
+    io,
+    ops::RangeInclusive,
     path::PathBuf,
-    sync::{self, Arc, RwLock},
+    sync::{Arc, RwLock},
     thread,
     time::{Duration, Instant},
 };
 
-use crossterm::{
-    ExecutableCommand,
-    event::{self, Event, KeyCode, KeyEvent, KeyEventKind},
-    terminal::{
-        EnterAlternateScreen, LeaveAlternateScreen, disable_raw_mode,
-        enable_raw_mode,
-    },
+use crossterm::event::{
+    Event, KeyCode, KeyEvent, KeyEventKind, MouseEvent, MouseEventKind,
 };
-use ratatui::{
-    DefaultTerminal,
-    widgets::{Block, Borders, Clear, List, ListItem, ListState, Padding},
+use ratatui::widgets::{
+    Block, Borders, Clear, List, ListItem, ListState, Padding,
 };
 use symbols::line::VERTICAL;
 
 use crate::{
     app_config::AppConfig,
-    keg::{CurrentKeg, Engine, Keg, Wrapper},
+    clipboard,
+    crash_dumps::CrashDump,
+    driver::Driver,
+    instance::Instance,
+    keg::{CurrentKeg, Engine, Keg, WineVersion, Wrapper},
+    notify,
+    plist_validation::SavePlistError,
+    processes::KegProcess,
+    runner::CommandRunner,
+    setup::SetupComponent,
+    tasks::TaskQueue,
+    translation_layers::TranslationLayerVersions,
     view::prelude::*,
+    views::{
+        config_form::ConfigForm, delete_keg::KegDeletion,
+        engine_inventory::EngineInventory, executables::ExecutableBrowser,
+        keg_creator::KegCreator, keg_log::LiveLog,
+        keg_processes::ProcessKiller, kegs::KegFilter,
+        winetricks_browser::WinetricksBrowser,
+    },
+    winetricks_cache::CacheEntry,
+    worker::{WorkerHandle, WorkerTask},
 };
 
 pub const SELECTED_FOCUSED_STYLE: Style =
@@ -48,6 +63,57 @@ pub const SELECTED_FOCUSED_STYLE: Style =
 pub const SELECTED_UNFOCUSED_STYLE: Style =
     Style::new().fg(Color::White).add_modifier(Modifier::BOLD);
 
+/// How long a finished task's message stays at the bottom of the TUI.
+const TOAST_DURATION: Duration = Duration::from_secs(6);
+
+/// Draws `paragraphs` in a box titled `title` over the middle of `area`,
+/// until any key is pressed.
+fn draw_message_modal(
+    frame: &mut Frame,
+    area: Rect,
+    title: &'static str,
+    paragraphs: &[&str],
+) {
+    let modal_width = area.width.saturating_sub(4).min(50);
+    if modal_width < 10 {
+        return;
+    }
+    let mut lines = vec![];
+    for (index, paragraph) in paragraphs.iter().enumerate() {
+        if index > 0 {
+            lines.push(Line::from(""));
+        }
+        lines.extend(
+            textwrap::wrap(paragraph, modal_width.saturating_sub(4) as usize)
+                .into_iter()
+                .map(|line| Line::from(line.into_owned())),
+        );
+    }
+    lines.push(Line::from(""));
+    lines.push(Line::from("Press any key to continue".dark_gray()));
+    let modal_height = lines.len() as u16 + 4;
+    if modal_height > area.height {
+        return;
+    }
+    let modal_area = Rect {
+        x: area.x + (area.width - modal_width) / 2,
+        y: area.y + (area.height - modal_height) / 2,
+        width: modal_width,
+        height: modal_height,
+    };
+
+    frame.render_widget(Clear, modal_area);
+    frame.render_widget(
+        Paragraph::new(lines).block(
+            Block::default()
+                .title(Span::from(title).into_centered_line())
+                .borders(Borders::ALL)
+                .padding(Padding::uniform(1)),
+        ),
+        modal_area,
+    );
+}
+
 fn make_keybinds_help_table() -> (Table<'static>, u16, u16) {
     macro_rules! make {
         ($((
@@ -91,30 +157,62 @@ fn make_keybinds_help_table() -> (Table<'static>, u16, u16) {
         (["<Right>", "<L>"], "Focus content"),
         (["<Up>", "<K>"], "Navigate up"),
         (["<Down>", "<J>"], "Navigate down"),
+        (["<Wheel>"], "Scroll the menu or content under the mouse"),
         (
             ["<Enter>"],
             "Focus content (in menu), select button (in content)"
         ),
+        (["<Y>"], "Copy the content pane"),
+        (["<V>"], "Select lines to copy with <Y> (in run output)"),
+        (["<R>"], "Rescan kegs, engines, and wrappers now"),
         (["<Z>"], "Suspend app"),
         (["<Q>"], "Exit app")
     ]
 }
 
-pub fn inspect_terminal(_app: &mut App, _state: &AsyncState) -> Result<()> {
+pub fn inspect_terminal(app: &mut App, _state: &AsyncState) -> Result<()> {
     eprintln!("┌──────────────────────────────────┐");
     eprintln!("│ Press enter to return to the TUI │");
     eprintln!("└──────────────────────────────────┘");
+    if let Some(current_keg) = &app.current_keg {
+        println!("Winetricks environment for {}:", current_keg.keg.name);
+        for (variable, value) in current_keg.winetricks_environment() {
+            println!("  {variable}={}", value.to_string_lossy());
+        }
+    }
     io::stdin().read_line(&mut String::new())?;
     Ok(())
 }
 
-#[derive(Default, PartialEq, Eq)]
+/// Measurements shown in the timing overlay.
+#[derive(Default)]
+struct Timings {
+    frame: Duration,
+    event: Duration,
+}
+
+#[derive(Default, Clone, Copy, PartialEq, Eq)]
 enum Focus {
     #[default]
     Menu,
     Content,
 }
 
+/// A range of lines being selected in visual mode.
+struct VisualSelection {
+    /// Where the selection started.
+    anchor: usize,
+    cursor: usize,
+}
+
+/// Where the user was in a nav before another was pushed on top of it.
+struct NavPosition<'a> {
+    focus: Focus,
+    menu_state: usize,
+    current_view: Option<ViewID<'a>>,
+    clickables_state: usize,
+}
+
 pub struct App<'a> {
     // TODO: separate this state out of App into something like a NavController
     exit: bool,
@@ -122,38 +220,246 @@ pub struct App<'a> {
     menu_state: usize,
     current_view: Option<ViewID<'a>>,
     clickables_state: usize,
+    /// One entry for each nav below the top of the stack.
+    nav_history: Vec<NavPosition<'a>>,
     // ENDTODO
     pub current_keg: Option<CurrentKeg>,
-    pub config: &'a AppConfig,
+    /// Borrowed until changed from within the TUI, e.g., by adding a keg
+    /// search path.
+    pub config: Cow<'a, AppConfig>,
+    /// Spawns every external program the actions run.
+    pub runner: Arc<dyn CommandRunner>,
     show_keybinds_modal: bool,
+    /// Set by the copy keybind and handled once the content has been drawn.
+    is_copy_requested: bool,
+    /// Set while selecting lines of the current view with <V>.
+    visual_selection: Option<VisualSelection>,
+    /// Where the menu and content were last drawn, to tell which one the
+    /// mouse wheel is over.
+    menu_area: Rect,
+    content_area: Rect,
+    /// Set when a menu item was chosen without meeting this requirement.
+    unmet_requirement: Option<Requirement>,
+    /// Why the last attempt to save a keg's settings was refused.
+    plist_problems: Option<Vec<String>>,
+    /// Toggled by the undocumented <`> keybind.
+    show_timings_overlay: bool,
+    timings: Timings,
     /// The paths to wineskin launchers of open kegs.
     pub open_kegs_wineskin_launchers: HashSet<OsString>,
+    /// Components checked in the setup wizard.
+    pub setup_selection: HashSet<SetupComponent>,
+    /// What the keg creator has chosen so far.
+    pub keg_creator: KegCreator,
+    /// What has been typed to confirm deleting the current keg.
+    pub keg_deletion: KegDeletion,
+    /// The current keg's settings as edited in the config form.
+    pub config_form: ConfigForm,
+    /// The name filter typed over the kegs list.
+    pub keg_filter: KegFilter,
+    /// The log of the last launch, followed while it's written.
+    pub keg_log: LiveLog,
+    /// Which engine the engine inventory is about to delete.
+    pub engine_inventory: EngineInventory,
+    /// The executables found in the current keg and the search over them.
+    pub executable_browser: ExecutableBrowser,
+    /// The executable last run in each keg, by bundle path, offered as
+    /// Resume.
+    pub last_executables: HashMap<PathBuf, PathBuf>,
+    /// Which process the processes view is about to kill.
+    pub process_killer: ProcessKiller,
+    /// The winetricks browser's search and checked verbs.
+    pub winetricks_browser: WinetricksBrowser,
+    /// Runs prefix changes like winetricks installs one at a time.
+    pub tasks: TaskQueue,
+    /// Whether another kegtui is using the same config.
+    pub instance: Instance,
+    /// Rescans kegs, engines, and wrappers on demand.
+    worker: &'a WorkerHandle,
+    /// Finished tasks already announced.
+    announced_tasks: HashSet<u64>,
+    /// The last announcement and when it was made.
+    toast: Option<(String, Instant)>,
 }
 
 impl<'a> App<'a> {
-    pub fn new(config: &'a AppConfig) -> Self {
+    pub fn new(
+        config: &'a AppConfig,
+        runner: Arc<dyn CommandRunner>,
+        instance: Instance,
+        worker: &'a WorkerHandle,
+    ) -> Self {
         Self {
             exit: Default::default(),
             focus: Default::default(),
             menu_state: Default::default(),
             current_view: Default::default(),
             clickables_state: Default::default(),
+            nav_history: Default::default(),
             current_keg: Default::default(),
-            config,
+            config: Cow::Borrowed(config),
+            runner: runner.clone(),
             show_keybinds_modal: Default::default(),
+            is_copy_requested: Default::default(),
+            visual_selection: Default::default(),
+            menu_area: Default::default(),
+            content_area: Default::default(),
+            unmet_requirement: Default::default(),
+            plist_problems: Default::default(),
+            show_timings_overlay: Default::default(),
+            timings: Default::default(),
             open_kegs_wineskin_launchers: Default::default(),
+            setup_selection: Default::default(),
+            keg_creator: Default::default(),
+            keg_deletion: Default::default(),
+            config_form: Default::default(),
+            keg_filter: Default::default(),
+            keg_log: Default::default(),
+            executable_browser: Default::default(),
+            last_executables: Default::default(),
+            process_killer: Default::default(),
+            engine_inventory: Default::default(),
+            winetricks_browser: Default::default(),
+            tasks: TaskQueue::spawn(runner),
+            instance,
+            worker,
+            announced_tasks: Default::default(),
+            toast: Default::default(),
         }
     }
 
+    /// Tells the user a task that took `took` finished, as configured under
+    /// `[notifications]`.
+    pub fn notify_finished(&self, message: &str, took: Duration) {
+        notify::task_finished(
+            &self.config.notifications,
+            &self.runner,
+            message,
+            took,
+        );
+    }
+
+    /// Shows a message for each task that finished since the last frame,
+    /// and notifies the user unless they're already watching the queue.
+    fn announce_finished_tasks(&mut self) {
+        let entries = self.tasks.entries();
+        self.announced_tasks
+            .retain(|id| entries.iter().any(|entry| entry.id == *id));
+        let is_watching_tasks =
+            self.current_view == Some(ViewID::Named("tasks"));
+        for entry in entries {
+            let Some(outcome) = entry.status.outcome() else {
+                continue;
+            };
+            if !self.announced_tasks.insert(entry.id) {
+                continue;
+            }
+            let message =
+                format!("{}: {} {outcome}", entry.keg_name, entry.description);
+            if !is_watching_tasks {
+                self.notify_finished(
+                    &message,
+                    entry
+                        .started
+                        .map(|started| started.elapsed())
+                        .unwrap_or_default(),
+                );
+            }
+            self.toast = Some((message, Instant::now()));
+        }
+    }
+
+    /// Asks the worker to run every task now and rereads the current keg,
+    /// for when something changed outside kegtui.
+    pub fn refresh(&mut self) {
+        for task in WorkerTask::ALL {
+            self.worker.trigger(task);
+        }
+        if let Some(current_keg) = &mut self.current_keg {
+            // A keg that can't be read anymore keeps what was last shown.
+            let _ = current_keg.reload();
+        }
+        self.winetricks_browser.reload();
+        self.executable_browser.rescan();
+    }
+
+    /// Searches `path` for kegs from now on, as if it had been configured at
+    /// startup.
+    pub fn add_keg_search_path(&mut self, path: PathBuf) {
+        self.config.to_mut().keg_search_paths.push(path.clone());
+        self.worker.add_keg_search_path(path);
+    }
+
+    /// Whether the worker has seen a process from the current keg.
+    pub fn is_current_keg_running(&self) -> bool {
+        self.current_keg.as_ref().is_some_and(|current_keg| {
+            self.open_kegs_wineskin_launchers
+                .contains(&current_keg.keg.wineskin_launcher)
+        })
+    }
+
+    /// Keeps the worker listing each keg's processes, about once a second,
+    /// for as long as this is called, e.g., on every draw of a view that shows
+    /// them.
+    pub fn watch_processes(&self) {
+        self.worker.trigger(WorkerTask::ListProcesses);
+    }
+
+    /// Asks the worker to check whether github.com can be reached, at most
+    /// about once a minute, e.g., while an action that downloads is
+    /// highlighted.
+    pub fn watch_network(&self) {
+        self.worker.trigger(WorkerTask::CheckNetwork);
+    }
+
+    /// Keeps the worker measuring the winetricks cache, every few seconds,
+    /// for as long as this is called, like [`App::watch_processes`].
+    pub fn watch_winetricks_cache(&self) {
+        self.worker.trigger(WorkerTask::MeasureWinetricksCache);
+    }
+
+    /// Keeps the worker looking for each keg's crash dumps, every few
+    /// seconds, for as long as this is called, like
+    /// [`App::watch_processes`], since the search walks the whole prefix.
+    pub fn watch_crash_dumps(&self) {
+        self.worker.trigger(WorkerTask::FindCrashDumps);
+    }
+
+    /// Explains in a modal why a keg's settings weren't saved.
+    pub fn show_plist_problems(&mut self, problems: Vec<String>) {
+        self.plist_problems = Some(problems);
+    }
+
     pub fn interaction_state(&self) -> usize {
         self.clickables_state
     }
 
+    /// Moves the selection to the view's clickable at `index`, e.g., to the
+    /// first result after a search.
+    pub fn select_clickable(&mut self, index: usize) {
+        self.clickables_state = index;
+    }
+
+    /// The selected lines of [`View::selectable_lines`] in visual mode.
+    pub fn visual_selection(&self) -> Option<RangeInclusive<usize>> {
+        self.visual_selection.as_ref().map(|selection| {
+            selection.anchor.min(selection.cursor)
+                ..=selection.anchor.max(selection.cursor)
+        })
+    }
+
+    /// The line visual mode would extend the selection from.
+    pub fn visual_cursor(&self) -> Option<usize> {
+        self.visual_selection
+            .as_ref()
+            .map(|selection| selection.cursor)
+    }
+
     pub fn run(
         &mut self,
         context: &mut NavContext<'a>,
         initial: NavID<'a>,
-        terminal: &mut DefaultTerminal,
+        driver: &mut impl Driver,
         state: Arc<RwLock<AsyncState>>,
     ) -> Result<()> {
         context.push_nav(initial);
@@ -169,10 +475,17 @@ impl<'a> App<'a> {
                 thread::sleep(interval - now);
             }
 
+            self.announce_finished_tasks();
             if let Ok(state) = state.read() {
-                terminal
+                let frame_start = Instant::now();
+                driver
+                    .terminal()
                     .draw(|frame| self.draw(context, frame, &state).unwrap())?;
-                self.handle_events(context, &state, terminal)?;
+                self.timings.frame = frame_start.elapsed();
+                if driver.is_finished() {
+                    break;
+                }
+                self.handle_events(context, &state, driver)?;
             }
         }
         Ok(())
@@ -186,9 +499,22 @@ impl<'a> App<'a> {
     ) -> Result<()> {
         let area = frame.area();
 
-        let main_block = Block::default()
+        let title = match self.instance {
+            Instance::Only(_) | Instance::Unchecked => Line::from(" kegtui "),
+            Instance::Another(pid) => Line::from(vec![
+                " kegtui ".into(),
+                match pid {
+                    Some(pid) => {
+                        format!("(another kegtui, pid {pid}, is open) ")
+                    }
+                    None => "(another kegtui is open) ".to_owned(),
+                }
+                .yellow(),
+            ]),
+        };
+        let mut main_block = Block::default()
             .borders(Borders::ALL)
-            .title(Span::from(" kegtui ").into_centered_line())
+            .title(title.centered())
             .title_bottom(
                 Line::from(vec![
                     " View keybinds ".into(),
@@ -197,10 +523,50 @@ impl<'a> App<'a> {
                 ])
                 .centered(),
             );
-        let inner_area = main_block.inner(area);
+        if let Some((message, shown)) = &self.toast
+            && shown.elapsed() < TOAST_DURATION
+        {
+            main_block = main_block.title_bottom(
+                Line::from(format!(" {message} ").yellow()).right_aligned(),
+            );
+        }
+        let mut inner_area = main_block.inner(area);
 
         frame.render_widget(main_block, area);
 
+        let menu = context
+            .top_nav()
+            .map(|current_nav| context.get_nav(current_nav).menu(self, state))
+            .unwrap_or_default();
+        let header = context.top_nav().and_then(|current_nav| {
+            context.get_nav(current_nav).header(self, state)
+        });
+        // Dynamic menus may have shrunk since the last frame.
+        self.menu_state = self.menu_state.min(menu.len().saturating_sub(1));
+
+        // Checked ahead of time, so it's usually known by the time the
+        // highlighted item is chosen.
+        if menu.get(self.menu_state).is_some_and(|item| {
+            item.requirements().contains(&Requirement::Network)
+        }) {
+            self.watch_network();
+        }
+
+        if let Some(description) = menu
+            .get(self.menu_state)
+            .and_then(|item| item.get_description())
+        {
+            let footer_rects = Layout::default()
+                .direction(Direction::Vertical)
+                .constraints([Constraint::Fill(1), Constraint::Length(1)])
+                .split(inner_area);
+            inner_area = footer_rects[0];
+            frame.render_widget(
+                Line::from(description.to_owned()).dark_gray().italic(),
+                footer_rects[1],
+            );
+        }
+
         let section_rects = Layout::default()
             .direction(Direction::Horizontal)
             .constraints(
@@ -213,9 +579,10 @@ impl<'a> App<'a> {
             )
             .split(inner_area);
 
-        if let Some(current_nav) = context.top_nav() {
-            let menu = context.get_nav(current_nav).menu();
-            self.draw_menu(frame, section_rects[0], menu);
+        self.menu_area = section_rects[0];
+        self.content_area = section_rects[2];
+        if context.top_nav().is_some() {
+            self.draw_menu(frame, section_rects[0], header, &menu);
             self.draw_vertical_separator(frame, section_rects[1]);
             self.draw_content(context, state, frame, section_rects[2])?;
         } else {
@@ -250,15 +617,95 @@ impl<'a> App<'a> {
             }
         }
 
+        if let Some(requirement) = self.unmet_requirement {
+            draw_message_modal(
+                frame,
+                area,
+                " Can't do that yet ",
+                &[requirement.explanation()],
+            );
+        }
+
+        if let Some(problems) = &self.plist_problems {
+            draw_message_modal(
+                frame,
+                area,
+                " Settings not saved ",
+                &problems.iter().map(String::as_str).collect::<Vec<_>>(),
+            );
+        }
+
+        if self.show_timings_overlay {
+            self.draw_timings_overlay(frame, area, state);
+        }
+
         Ok(())
     }
-    fn draw_menu(&mut self, frame: &mut Frame, area: Rect, menu: &[MenuItem]) {
+
+    fn draw_timings_overlay(
+        &self,
+        frame: &mut Frame,
+        area: Rect,
+        state: &AsyncState,
+    ) {
+        let mut lines = vec![
+            Line::from(format!("frame: {:?}", self.timings.frame)),
+            Line::from(format!("event: {:?}", self.timings.event)),
+        ];
+        for task in WorkerTask::ALL {
+            lines.push(Line::from(
+                match state.last_task_durations.get(&task) {
+                    Some(duration) => format!("{task:?}: {duration:?}"),
+                    None => format!("{task:?}: pending"),
+                },
+            ));
+        }
+
+        let width = lines.iter().map(Line::width).max().unwrap_or(0) as u16 + 2;
+        let height = lines.len() as u16 + 2;
+        if width > area.width || height > area.height {
+            return;
+        }
+        let overlay_area = Rect {
+            x: area.right() - width,
+            y: area.y,
+            width,
+            height,
+        };
+
+        frame.render_widget(Clear, overlay_area);
+        frame.render_widget(
+            Paragraph::new(lines).dark_gray().block(
+                Block::default().borders(Borders::ALL).title(" Timings "),
+            ),
+            overlay_area,
+        );
+    }
+
+    fn draw_menu(
+        &mut self,
+        frame: &mut Frame,
+        area: Rect,
+        header: Option<Text<'static>>,
+        menu: &[MenuItem],
+    ) {
+        let header_height = header.as_ref().map_or(0, |header| {
+            // Leave a blank line between the header and the menu.
+            header.height() as u16 + 1
+        });
         let chunks = Layout::default()
             .direction(Direction::Vertical)
-            .constraints([Constraint::Length(1), Constraint::Min(0)])
+            .constraints([
+                Constraint::Length(header_height),
+                Constraint::Length(1),
+                Constraint::Min(0),
+            ])
             .split(area);
 
-        frame.render_widget("Menu:".bold(), chunks[0]);
+        if let Some(header) = header {
+            frame.render_widget(header, chunks[0]);
+        }
+        frame.render_widget("Menu:".bold(), chunks[1]);
 
         let menu_items: Vec<ListItem> = menu
             .iter()
@@ -273,7 +720,7 @@ impl<'a> App<'a> {
             .highlight_symbol(">> ");
         frame.render_stateful_widget(
             menu,
-            chunks[1],
+            chunks[2],
             &mut ListState::default().with_selected(Some(self.menu_state)),
         );
     }
@@ -302,6 +749,13 @@ impl<'a> App<'a> {
                 self.focus == Focus::Content,
             )?;
         }
+        if self.is_copy_requested {
+            self.is_copy_requested = false;
+            // Copying what was drawn works for every view without each one
+            // having to produce its text separately.
+            let text = clipboard::buffer_text(frame.buffer_mut(), area);
+            let _ = clipboard::copy(&*self.runner, &text);
+        }
         Ok(())
     }
 
@@ -309,14 +763,19 @@ impl<'a> App<'a> {
         &mut self,
         context: &mut NavContext<'a>,
         state: &AsyncState,
-        terminal: &mut DefaultTerminal,
+        driver: &mut impl Driver,
     ) -> Result<()> {
-        if event::poll(Duration::from_millis(5))? {
-            match event::read()? {
+        if let Some(event) = driver.next_event(Duration::from_millis(5))? {
+            match event {
                 Event::Key(key_event)
                     if key_event.kind == KeyEventKind::Press =>
                 {
-                    self.handle_key_event(context, key_event, state, terminal)?
+                    let event_start = Instant::now();
+                    self.handle_key_event(context, key_event, state, driver)?;
+                    self.timings.event = event_start.elapsed();
+                }
+                Event::Mouse(mouse_event) => {
+                    self.handle_mouse_event(context, mouse_event, state)?;
                 }
                 _ => {}
             };
@@ -329,7 +788,7 @@ impl<'a> App<'a> {
         context: &mut NavContext<'a>,
         key_event: KeyEvent,
         state: &AsyncState,
-        terminal: &mut DefaultTerminal,
+        driver: &mut impl Driver,
     ) -> Result<()> {
         if self.show_keybinds_modal {
             if matches!(key_event.code, KeyCode::Esc | KeyCode::Char('?')) {
@@ -337,9 +796,30 @@ impl<'a> App<'a> {
             }
             return Ok(());
         }
+        if self.unmet_requirement.is_some() {
+            self.unmet_requirement = None;
+            return Ok(());
+        }
+        if self.plist_problems.is_some() {
+            self.plist_problems = None;
+            return Ok(());
+        }
+        if self.visual_selection.is_some() {
+            return self.handle_visual_key_event(context, key_event, state);
+        }
+        if self.focus == Focus::Content
+            && let Some(view_id) = self.current_view
+            && context
+                .get_view(view_id)
+                .handle_key(self, state, key_event)?
+        {
+            return Ok(());
+        }
         let current_nav = context.top_nav().unwrap();
-        let menu = context.get_nav(current_nav).menu();
-        let current_menu_item = &menu[self.menu_state];
+        let menu = context.get_nav(current_nav).menu(self, state);
+        // Dynamic menus may have shrunk, or be empty, since the last frame.
+        self.menu_state = self.menu_state.min(menu.len().saturating_sub(1));
+        let current_menu_item = menu.get(self.menu_state);
 
         match key_event.code {
             KeyCode::Char('q') => self.exit(),
@@ -349,93 +829,100 @@ impl<'a> App<'a> {
             KeyCode::Char('?') => {
                 self.show_keybinds_modal = true;
             }
+            KeyCode::Char('y') => {
+                self.is_copy_requested = true;
+            }
+            KeyCode::Char('r') => self.refresh(),
+            KeyCode::Char('v') if self.focus == Focus::Content => {
+                if let Some(view_id) = self.current_view
+                    && let Some(lines) =
+                        context.get_view(view_id).selectable_lines(self, state)
+                    && !lines.is_empty()
+                {
+                    // Start at the end, which is where logs usually show
+                    // what went wrong.
+                    let last = lines.len() - 1;
+                    self.visual_selection = Some(VisualSelection {
+                        anchor: last,
+                        cursor: last,
+                    });
+                }
+            }
+            KeyCode::Char('`') => {
+                self.show_timings_overlay = !self.show_timings_overlay;
+            }
             KeyCode::Up | KeyCode::Char('k') => match self.focus {
                 Focus::Menu => {
-                    self.menu_state = self.menu_state.saturating_sub(1);
+                    self.scroll_menu(menu.len(), ScrollDirection::Backward);
                 }
                 Focus::Content => {
-                    let current_view = context
-                        .get_view(self.current_view.expect(
-                            "Focused view but app has no current view",
-                        ));
-                    match current_view.interactivity(self, state)? {
-                        ViewInteractivity::None => {}
-                        ViewInteractivity::Scrollable => {
-                            self.clickables_state =
-                                self.clickables_state.saturating_sub(3);
-                        }
-                        ViewInteractivity::Clickables(_) => {
-                            self.clickables_state =
-                                self.clickables_state.saturating_sub(1);
-                        }
-                    }
+                    self.scroll_content(
+                        context,
+                        state,
+                        ScrollDirection::Backward,
+                    )?;
                 }
             },
             KeyCode::Down | KeyCode::Char('j') => match self.focus {
                 Focus::Menu => {
-                    if self.menu_state + 1 < menu.len() {
-                        self.menu_state += 1;
-                    }
+                    self.scroll_menu(menu.len(), ScrollDirection::Forward);
                 }
                 Focus::Content => {
-                    let current_view = context
-                        .get_view(self.current_view.expect(
-                            "Focused view but app has no current view",
-                        ));
-                    match current_view.interactivity(self, state)? {
-                        ViewInteractivity::None => {}
-                        ViewInteractivity::Scrollable => {
-                            self.clickables_state += 3;
-                        }
-                        ViewInteractivity::Clickables(count) => {
-                            if self.clickables_state + 1 < count {
-                                self.clickables_state += 1;
-                            }
-                        }
-                    }
+                    self.scroll_content(
+                        context,
+                        state,
+                        ScrollDirection::Forward,
+                    )?;
                 }
             },
             KeyCode::Left | KeyCode::Char('h') => {
                 self.focus = Focus::Menu;
             }
             KeyCode::Right | KeyCode::Char('l') => {
-                if self.focus == Focus::Menu {
-                    let menu_action = current_menu_item.action().clone();
-                    self.execute_menu_action(
+                if self.focus == Focus::Menu
+                    && let Some(current_menu_item) = current_menu_item
+                {
+                    self.choose_menu_item(
                         context,
                         state,
-                        terminal,
-                        menu_action,
+                        driver,
+                        current_menu_item,
                     )?;
                 }
             }
 
             KeyCode::Enter => match self.focus {
                 Focus::Menu => {
-                    let menu_action = current_menu_item.action().clone();
-                    self.execute_menu_action(
-                        context,
-                        state,
-                        terminal,
-                        menu_action,
-                    )?;
+                    if let Some(current_menu_item) = current_menu_item {
+                        self.choose_menu_item(
+                            context,
+                            state,
+                            driver,
+                            current_menu_item,
+                        )?;
+                    }
                 }
                 Focus::Content => {
-                    if let Some(nav_action) =
+                    if let Some(menu_action) =
                         context
                             .get_view(self.current_view.expect(
                                 "Focused view but app has no current view",
                             ))
                             .click(self, state, self.clickables_state)?
                     {
-                        self.execute_nav_action(context, nav_action);
+                        self.execute_menu_action(
+                            context,
+                            state,
+                            driver,
+                            menu_action,
+                        )?;
                     }
                 }
             },
             KeyCode::Char('z') => self.execute_menu_action(
                 context,
                 state,
-                terminal,
+                driver,
                 MenuItemAction::External(inspect_terminal),
             )?,
             _ => {}
@@ -443,27 +930,241 @@ impl<'a> App<'a> {
         Ok(())
     }
 
+    /// Moves the end of the visual selection, or copies it with <Y> and leaves
+    /// visual mode.
+    fn handle_visual_key_event(
+        &mut self,
+        context: &NavContext<'a>,
+        key_event: KeyEvent,
+        state: &AsyncState,
+    ) -> Result<()> {
+        let lines = self
+            .current_view
+            .and_then(|view_id| {
+                context.get_view(view_id).selectable_lines(self, state)
+            })
+            .unwrap_or_default();
+        let Some(selection) = &mut self.visual_selection else {
+            return Ok(());
+        };
+        if lines.is_empty() {
+            self.visual_selection = None;
+            return Ok(());
+        }
+        let last = lines.len() - 1;
+        match key_event.code {
+            KeyCode::Esc | KeyCode::Char('v') | KeyCode::Char('q') => {
+                self.visual_selection = None;
+            }
+            KeyCode::Up | KeyCode::Char('k') => {
+                selection.cursor = selection.cursor.saturating_sub(1);
+            }
+            KeyCode::Down | KeyCode::Char('j') => {
+                selection.cursor = (selection.cursor + 1).min(last);
+            }
+            KeyCode::PageUp => {
+                selection.cursor = selection.cursor.saturating_sub(10);
+            }
+            KeyCode::PageDown => {
+                selection.cursor = (selection.cursor + 10).min(last);
+            }
+            KeyCode::Char('g') => selection.cursor = 0,
+            KeyCode::Char('G') => selection.cursor = last,
+            KeyCode::Char('y') | KeyCode::Enter => {
+                if let Some(range) = self.visual_selection()
+                    && let Some(selected) = lines.get(range)
+                {
+                    let _ =
+                        clipboard::copy(&*self.runner, &selected.join("\n"));
+                }
+                self.visual_selection = None;
+            }
+            _ => {}
+        }
+        Ok(())
+    }
+
+    /// Scrolls whichever of the menu or content is under the mouse, without
+    /// moving the focus.
+    fn handle_mouse_event(
+        &mut self,
+        context: &NavContext<'a>,
+        mouse_event: MouseEvent,
+        state: &AsyncState,
+    ) -> Result<()> {
+        if self.show_keybinds_modal
+            || self.unmet_requirement.is_some()
+            || self.plist_problems.is_some()
+            || self.visual_selection.is_some()
+        {
+            return Ok(());
+        }
+        let direction = match mouse_event.kind {
+            MouseEventKind::ScrollUp => ScrollDirection::Backward,
+            MouseEventKind::ScrollDown => ScrollDirection::Forward,
+            _ => return Ok(()),
+        };
+        let position = Position::new(mouse_event.column, mouse_event.row);
+        if self.menu_area.contains(position)
+            && let Some(current_nav) = context.top_nav()
+        {
+            let menu_length =
+                context.get_nav(current_nav).menu(self, state).len();
+            self.scroll_menu(menu_length, direction);
+        } else if self.content_area.contains(position)
+            && self.current_view.is_some()
+        {
+            self.scroll_content(context, state, direction)?;
+        }
+        Ok(())
+    }
+
+    fn scroll_menu(&mut self, menu_length: usize, direction: ScrollDirection) {
+        match direction {
+            ScrollDirection::Backward => {
+                self.menu_state = self.menu_state.saturating_sub(1);
+            }
+            ScrollDirection::Forward => {
+                if self.menu_state + 1 < menu_length {
+                    self.menu_state += 1;
+                }
+            }
+        }
+    }
+
+    /// Scrolls a scrollable view by a few lines, or moves the selection of a
+    /// view with clickables by one.
+    fn scroll_content(
+        &mut self,
+        context: &NavContext<'a>,
+        state: &AsyncState,
+        direction: ScrollDirection,
+    ) -> Result<()> {
+        let current_view = context.get_view(
+            self.current_view
+                .expect("Scrolled view but app has no current view"),
+        );
+        match (current_view.interactivity(self, state)?, direction) {
+            (ViewInteractivity::None, _) => {}
+            (ViewInteractivity::Scrollable, ScrollDirection::Backward) => {
+                self.clickables_state = self.clickables_state.saturating_sub(3);
+            }
+            (ViewInteractivity::Scrollable, ScrollDirection::Forward) => {
+                self.clickables_state += 3;
+            }
+            (ViewInteractivity::Clickables(_), ScrollDirection::Backward) => {
+                self.clickables_state = self.clickables_state.saturating_sub(1);
+            }
+            (
+                ViewInteractivity::Clickables(count),
+                ScrollDirection::Forward,
+            ) => {
+                if self.clickables_state + 1 < count {
+                    self.clickables_state += 1;
+                }
+            }
+        }
+        Ok(())
+    }
+
+    fn choose_menu_item(
+        &mut self,
+        context: &mut NavContext<'a>,
+        state: &AsyncState,
+        driver: &mut impl Driver,
+        menu_item: &MenuItem<'a>,
+    ) -> Result<()> {
+        if let Some(requirement) = menu_item
+            .requirements()
+            .iter()
+            .copied()
+            .find(|requirement| !self.is_requirement_met(state, *requirement))
+        {
+            self.unmet_requirement = Some(requirement);
+            return Ok(());
+        }
+        self.execute_menu_action(
+            context,
+            state,
+            driver,
+            menu_item.action().clone(),
+        )
+    }
+
+    fn is_requirement_met(
+        &self,
+        state: &AsyncState,
+        requirement: Requirement,
+    ) -> bool {
+        match requirement {
+            Requirement::CurrentKeg => self.current_keg.is_some(),
+            // Unknown until the worker's first check, in which case the
+            // action can try and explain its own failure.
+            Requirement::Network => {
+                // The network may be back by the time it's tried again.
+                self.watch_network();
+                state.is_network_reachable.unwrap_or(true)
+            }
+            Requirement::Engines => !state.engines.is_empty(),
+        }
+    }
+
     fn execute_menu_action(
         &mut self,
         context: &mut NavContext<'a>,
         state: &AsyncState,
-        terminal: &mut DefaultTerminal,
+        driver: &mut impl Driver,
         menu_action: MenuItemAction<'a>,
     ) -> Result<()> {
         match menu_action {
             MenuItemAction::NavAction(nav_action) => {
-                self.execute_nav_action(context, nav_action)
+                self.execute_nav_action(context, state, nav_action)
             }
             MenuItemAction::LoadView(view_id) => {
                 self.load_view(view_id);
             }
+            MenuItemAction::LoadViewAt(view_id, index) => {
+                self.load_view(view_id);
+                self.clickables_state = match context
+                    .get_view(view_id)
+                    .interactivity(self, state)?
+                {
+                    ViewInteractivity::Clickables(count) => {
+                        index.min(count.saturating_sub(1))
+                    }
+                    _ => index,
+                };
+            }
             MenuItemAction::External(external) => {
-                io::stdout().execute(LeaveAlternateScreen)?;
-                disable_raw_mode()?;
-                external(self, state)?;
-                io::stdout().execute(EnterAlternateScreen)?;
-                enable_raw_mode()?;
-                terminal.clear()?;
+                self.run_external(state, driver, external)?;
+            }
+            MenuItemAction::ExternalAt(external, index) => {
+                self.run_external(state, driver, |app, state| {
+                    external(app, state, index)
+                })?;
+            }
+        }
+        Ok(())
+    }
+
+    /// Runs `external` with the terminal handed back to it.
+    fn run_external(
+        &mut self,
+        state: &AsyncState,
+        driver: &mut impl Driver,
+        external: impl FnOnce(&mut Self, &AsyncState) -> Result<()>,
+    ) -> Result<()> {
+        driver.suspend()?;
+        let result = external(self, state);
+        driver.resume()?;
+        if let Err(error) = result {
+            // Settings that wouldn't work are explained, not fatal.
+            match error.downcast::<SavePlistError>() {
+                Ok(SavePlistError::Invalid(problems)) => {
+                    self.show_plist_problems(problems);
+                }
+                Ok(error) => return Err(error.into()),
+                Err(error) => return Err(error),
             }
         }
         Ok(())
@@ -472,11 +1173,24 @@ impl<'a> App<'a> {
     fn execute_nav_action(
         &mut self,
         context: &mut NavContext<'a>,
+        state: &AsyncState,
         nav_action: NavAction<'a>,
     ) {
         match nav_action {
-            NavAction::Pop => context.pop_nav(),
+            NavAction::Pop => {
+                context.pop_nav();
+                if let Some(position) = self.nav_history.pop() {
+                    self.restore_nav_position(context, state, position);
+                    return;
+                }
+            }
             NavAction::Push(nav_id) => {
+                self.nav_history.push(NavPosition {
+                    focus: self.focus,
+                    menu_state: self.menu_state,
+                    current_view: self.current_view,
+                    clickables_state: self.clickables_state,
+                });
                 context.push_nav(nav_id);
             }
         }
@@ -484,13 +1198,36 @@ impl<'a> App<'a> {
         self.current_view = None;
         self.menu_state = context
             .get_nav(context.top_nav().unwrap())
-            .default_item_index();
+            .default_item_index(self, state);
+    }
+
+    /// Returns to `position`, clamping the selection in case the view has
+    /// fewer items than it did, e.g., because a keg was deleted.
+    fn restore_nav_position(
+        &mut self,
+        context: &NavContext<'a>,
+        state: &AsyncState,
+        position: NavPosition<'a>,
+    ) {
+        self.focus = position.focus;
+        self.menu_state = position.menu_state;
+        self.current_view = position.current_view;
+        self.clickables_state = position.clickables_state;
+        if let Some(view_id) = self.current_view
+            && let Ok(ViewInteractivity::Clickables(count)) =
+                context.get_view(view_id).interactivity(self, state)
+        {
+            self.clickables_state =
+                self.clickables_state.min(count.saturating_sub(1));
+        }
     }
 
-    fn load_view(&mut self, view_id: ViewID<'a>) {
+    /// Shows the view with `view_id`, e.g., once an external action is done.
+    pub fn load_view(&mut self, view_id: ViewID<'a>) {
         self.current_view = Some(view_id);
         self.focus = Focus::Content;
         self.clickables_state = 0;
+        self.visual_selection = None;
     }
 
     fn exit(&mut self) {
@@ -503,109 +1240,29 @@ pub struct AsyncState {
     pub kegs: Vec<Keg>,
     pub engines: Vec<Engine>,
     pub wrappers: Vec<Wrapper>,
-}
-
-pub struct TerminateWorkerGuard(sync::mpsc::Sender<()>);
-
-impl Drop for TerminateWorkerGuard {
-    fn drop(&mut self) {
-        let _ = self.0.send(());
-    }
-}
-
-fn read_search_paths(
-    search_paths: &[PathBuf],
-    home_directory: &str,
-) -> impl Iterator<Item = fs::DirEntry> {
-    search_paths
-        .iter()
-        .map(move |enclosing_location| {
-            enclosing_location
-                .to_string_lossy()
-                .replace("~", home_directory)
-        })
-        .filter_map(|fixed_enclosing_location| {
-            fs::read_dir(fixed_enclosing_location).ok()
-        })
-        .flat_map(|read_dir| read_dir.flatten())
-}
-
-pub fn spawn_worker(
-    config: Arc<AppConfig>,
-) -> (Arc<RwLock<AsyncState>>, TerminateWorkerGuard) {
-    let async_state = Arc::new(RwLock::new(AsyncState::default()));
-
-    let (quit_tx, quit_rx) = sync::mpsc::channel();
-
-    {
-        let async_state = async_state.clone();
-        thread::spawn(move || {
-            loop {
-                if quit_rx.try_recv().is_ok() {
-                    break;
-                }
-
-                let mut kegs = vec![];
-                let mut engines = vec![];
-                let mut wrappers = vec![];
-
-                let home_directory = env::var("HOME")
-                    .expect("User missing home directory env variable");
-
-                for entry in
-                    read_search_paths(&config.keg_search_paths, &home_directory)
-                {
-                    if entry.path().join("Contents/KegworksConfig.app").exists()
-                        || entry
-                            .path()
-                            .join("Contents/MacOS/Sikarugir")
-                            .exists()
-                    {
-                        kegs.push(Keg::from_path(&entry.path()));
-                    }
-                }
-                for entry in read_search_paths(
-                    &config.engine_search_paths,
-                    &home_directory,
-                ) {
-                    if entry
-                        .path()
-                        .file_name()
-                        .and_then(|name| name.to_str())
-                        .map(|name| {
-                            name.ends_with(".tar.7z")
-                                || name.ends_with(".tar.xz")
-                        })
-                        .unwrap_or(false)
-                    {
-                        engines.push(Engine { path: entry.path() });
-                    }
-                }
-                for entry in read_search_paths(
-                    &config.wrapper_search_paths,
-                    &home_directory,
-                ) {
-                    if entry
-                        .path()
-                        .file_name()
-                        .and_then(|name| name.to_str())
-                        .map(|name| name.ends_with(".app"))
-                        .unwrap_or(false)
-                    {
-                        wrappers.push(Wrapper { path: entry.path() });
-                    }
-                }
-
-                if let Ok(mut lock) = async_state.try_write() {
-                    lock.kegs = kegs;
-                    lock.engines = engines;
-                    lock.wrappers = wrappers;
-                }
-
-                thread::sleep(Duration::from_secs(1));
-            }
-        });
-    }
-
-    (async_state, TerminateWorkerGuard(quit_tx))
+    pub is_brew_installed: bool,
+    pub is_kegworks_installed: bool,
+    pub kegworks_version: Option<String>,
+    /// Whether github.com answered the last check, or `None` before the
+    /// first one finishes.
+    pub is_network_reachable: Option<bool>,
+    /// How long each worker task took the last time it ran.
+    pub last_task_durations: HashMap<WorkerTask, Duration>,
+    /// Keyed by each keg's [`Keg::wine_executable`].
+    pub wine_versions: HashMap<PathBuf, WineVersion>,
+    /// Bytes on disk, keyed by each keg's [`Keg::bundle_path`].
+    pub keg_sizes: HashMap<PathBuf, u64>,
+    /// What each keg's engine bundles, keyed by each keg's
+    /// [`Keg::bundle_path`].
+    pub translation_layers: HashMap<PathBuf, TranslationLayerVersions>,
+    /// Only listed while [`App::watch_processes`] is called. Keyed by each
+    /// keg's [`Keg::bundle_path`], leaving out kegs with none.
+    pub keg_processes: HashMap<PathBuf, Vec<KegProcess>>,
+    /// The cached winetricks downloads, largest first, or `None` until
+    /// [`App::watch_winetricks_cache`] has been called and they're measured.
+    pub winetricks_cache: Option<Vec<CacheEntry>>,
+    /// Minidumps and backtraces, newest first, keyed by each keg's
+    /// [`Keg::bundle_path`], once [`App::watch_crash_dumps`] has been called
+    /// and they're found.
+    pub crash_dumps: HashMap<PathBuf, Vec<CrashDump>>,
 }
@@ -12,24 +12,237 @@
 // You should have received a copy of the GNU General Public License along with
 // this program.  If not, see <https://www.gnu.org/licenses/>.
 
-use std::{env, path::PathBuf};
+use std::{
+    collections::BTreeMap,
+    env, fs,
+    path::{Path, PathBuf},
+    time::Duration,
+};
 
+use color_eyre::{
+    Result,
+    eyre::{Context, OptionExt},
+};
 use serde::{Deserialize, Serialize};
 
 const CONFIG_FILE_NAME: &str = "kegtui.toml";
 
+/// Expands a leading `~` as well as `$VAR` and `${VAR}` environment variables
+/// in `path`. Variables that aren't set are left as written, and a `~`
+/// anywhere but the start is taken literally.
+pub fn expand_path(path: &str) -> String {
+    let mut expanded = String::new();
+    let mut rest = path;
+    if (rest == "~" || rest.starts_with("~/"))
+        && let Ok(home_directory) = env::var("HOME")
+    {
+        expanded.push_str(&home_directory);
+        rest = &rest[1..];
+    }
+
+    while let Some(dollar) = rest.find('$') {
+        expanded.push_str(&rest[..dollar]);
+        let after_dollar = &rest[dollar + 1..];
+        let (name, original_length) = if let Some(braced) =
+            after_dollar.strip_prefix('{')
+            && let Some(close) = braced.find('}')
+        {
+            (&braced[..close], close + 2)
+        } else {
+            let length = after_dollar
+                .find(|c: char| !(c == '_' || c.is_ascii_alphanumeric()))
+                .unwrap_or(after_dollar.len());
+            (&after_dollar[..length], length)
+        };
+        match env::var(name) {
+            Ok(value) if !name.is_empty() => expanded.push_str(&value),
+            _ => expanded.push_str(&rest[dollar..dollar + 1 + original_length]),
+        }
+        rest = &after_dollar[original_length..];
+    }
+    expanded.push_str(rest);
+    expanded
+}
+
+fn expand_path_buf(path: &mut PathBuf) {
+    *path = PathBuf::from(expand_path(&path.to_string_lossy()));
+}
+
 pub fn app_config_file_path() -> PathBuf {
+    app_config_file_path_in(env::var("XDG_CONFIG_HOME").ok().as_deref())
+}
+
+/// Where the config file is when `$XDG_CONFIG_HOME` is `config_home`, for
+/// callers like the GUI whose own environment differs from the shell's.
+pub fn app_config_file_path_in(config_home: Option<&str>) -> PathBuf {
     let config_home_guess = PathBuf::from(
         env::var("HOME").expect("User does not have $HOME directory set"),
     )
     .join(".config");
 
-    env::var("XDG_CONFIG_HOME")
+    config_home
         .map(PathBuf::from)
         .unwrap_or(config_home_guess)
         .join(CONFIG_FILE_NAME)
 }
 
+/// Reads the config file at `config_file` as TOML, lets `edit` change it, and
+/// writes it back. The file is rewritten, so any comments in it are lost.
+fn edit_config_file(
+    config_file: &Path,
+    edit: impl FnOnce(&mut toml::Table) -> Result<()>,
+) -> Result<()> {
+    let mut table = fs::read_to_string(config_file)
+        .unwrap_or_default()
+        .parse::<toml::Table>()
+        .context(format!("Failed to parse {}", config_file.display()))?;
+    edit(&mut table)?;
+    if let Some(parent) = config_file.parent() {
+        fs::create_dir_all(parent)?;
+    }
+    fs::write(config_file, toml::to_string_pretty(&table)?)
+        .context(format!("Failed to write {}", config_file.display()))?;
+    Ok(())
+}
+
+fn paths_to_toml(paths: &[PathBuf]) -> toml::Value {
+    toml::Value::Array(
+        paths
+            .iter()
+            .map(|path| path.to_string_lossy().into_owned().into())
+            .collect(),
+    )
+}
+
+/// Appends `path` to `keg-search-paths` in the config file, starting from the
+/// default search paths if the key isn't set yet. The file is rewritten, so
+/// any comments in it are lost.
+pub fn add_keg_search_path(path: &Path) -> Result<()> {
+    edit_config_file(&app_config_file_path(), |table| {
+        let search_paths = table
+            .entry("keg-search-paths")
+            .or_insert_with(|| paths_to_toml(&default_keg_search_paths()))
+            .as_array_mut()
+            .ok_or_eyre("keg-search-paths should be an array")?;
+        search_paths.push(path.to_string_lossy().into_owned().into());
+        Ok(())
+    })
+}
+
+/// The settings the GUI's Preferences tab edits, as written in the config
+/// file, so `~` and variables stay unexpanded.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Preferences {
+    pub keg_search_paths: Vec<PathBuf>,
+    pub engine_search_paths: Vec<PathBuf>,
+    pub wrapper_search_paths: Vec<PathBuf>,
+    /// Empty if unset, in which case `$EDITOR` or `vim` is used.
+    pub editor: String,
+    /// Empty if unset, in which case `$EXPLORER` or `open` is used.
+    pub explorer: String,
+}
+
+impl Default for Preferences {
+    fn default() -> Self {
+        Self {
+            keg_search_paths: default_keg_search_paths(),
+            engine_search_paths: default_engine_search_paths(),
+            wrapper_search_paths: default_wrapper_search_paths(),
+            editor: String::new(),
+            explorer: String::new(),
+        }
+    }
+}
+
+impl Preferences {
+    /// Reads the preferences from the config file at `config_file`, which
+    /// needn't exist yet.
+    pub fn read(config_file: &Path) -> Result<Self> {
+        let table = fs::read_to_string(config_file)
+            .unwrap_or_default()
+            .parse::<toml::Table>()
+            .context(format!("Failed to parse {}", config_file.display()))?;
+        let paths = |key: &str, default: fn() -> Vec<PathBuf>| {
+            table
+                .get(key)
+                .and_then(toml::Value::as_array)
+                .map(|paths| {
+                    paths
+                        .iter()
+                        .filter_map(toml::Value::as_str)
+                        .map(PathBuf::from)
+                        .collect()
+                })
+                .unwrap_or_else(default)
+        };
+        let string = |key: &str| {
+            table
+                .get(key)
+                .and_then(toml::Value::as_str)
+                .unwrap_or_default()
+                .to_owned()
+        };
+        Ok(Self {
+            keg_search_paths: paths(
+                "keg-search-paths",
+                default_keg_search_paths,
+            ),
+            engine_search_paths: paths(
+                "engine-search-paths",
+                default_engine_search_paths,
+            ),
+            wrapper_search_paths: paths(
+                "wrapper-search-paths",
+                default_wrapper_search_paths,
+            ),
+            editor: string("editor"),
+            explorer: string("explorer"),
+        })
+    }
+
+    /// Writes the preferences into the config file at `config_file`, leaving
+    /// every other setting as it was.
+    pub fn write(&self, config_file: &Path) -> Result<()> {
+        edit_config_file(config_file, |table| {
+            table.insert(
+                "keg-search-paths".to_owned(),
+                paths_to_toml(&self.keg_search_paths),
+            );
+            table.insert(
+                "engine-search-paths".to_owned(),
+                paths_to_toml(&self.engine_search_paths),
+            );
+            table.insert(
+                "wrapper-search-paths".to_owned(),
+                paths_to_toml(&self.wrapper_search_paths),
+            );
+            for (key, value) in
+                [("editor", &self.editor), ("explorer", &self.explorer)]
+            {
+                if value.trim().is_empty() {
+                    table.remove(key);
+                } else {
+                    table.insert(key.to_owned(), value.trim().into());
+                }
+            }
+            Ok(())
+        })
+    }
+}
+
+/// Directory for data kegtui manages itself, such as snapshots.
+pub fn app_state_directory() -> PathBuf {
+    let state_home_guess = PathBuf::from(
+        env::var("HOME").expect("User does not have $HOME directory set"),
+    )
+    .join(".local/state");
+
+    env::var("XDG_STATE_HOME")
+        .map(PathBuf::from)
+        .unwrap_or(state_home_guess)
+        .join("kegtui")
+}
+
 pub fn default_keg_location() -> &'static str {
     "~/Applications/kegtui"
 }
@@ -75,6 +288,263 @@ fn default_explorer() -> String {
     env::var("EXPLORER").unwrap_or("open".into())
 }
 
+fn default_max_log_size() -> u64 {
+    4 * 1024 * 1024
+}
+
+fn default_keep_rotated_logs() -> usize {
+    3
+}
+
+fn default_keep_snapshots() -> usize {
+    5
+}
+
+fn default_keep_runs() -> usize {
+    20
+}
+
+fn default_keep_backups() -> usize {
+    3
+}
+
+fn default_clear_temp_files() -> bool {
+    true
+}
+
+fn default_keep_newest_engines() -> usize {
+    2
+}
+
+fn default_snapshot_before_winetricks() -> bool {
+    true
+}
+
+fn default_notification_bell() -> bool {
+    true
+}
+
+fn default_notification_min_seconds() -> u64 {
+    10
+}
+
+fn default_winetricks_timeout() -> u64 {
+    60 * 60
+}
+
+fn default_prefix_create_timeout() -> u64 {
+    10 * 60
+}
+
+/// A column of the kegs list, after the keg's name.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "kebab-case")]
+pub enum KegColumn {
+    /// The folder the keg is in.
+    Location,
+    /// Kegworks or Sikarugir.
+    Wrapper,
+    /// Whether the engine ships 32-bit Wine.
+    Arch,
+    /// What the keg's `wine --version` printed.
+    Wine,
+    /// The name of the engine unpacked into the keg.
+    Engine,
+    /// The keg's size on disk.
+    Size,
+    /// When the keg was last launched from kegtui.
+    LastPlayed,
+    /// The tags given to the keg under `[keg-tags]`.
+    Tags,
+}
+
+impl KegColumn {
+    pub fn title(self) -> &'static str {
+        match self {
+            KegColumn::Location => "Location",
+            KegColumn::Wrapper => "Wrapper",
+            KegColumn::Arch => "Arch",
+            KegColumn::Wine => "Wine",
+            KegColumn::Engine => "Engine",
+            KegColumn::Size => "Size",
+            KegColumn::LastPlayed => "Last Played",
+            KegColumn::Tags => "Tags",
+        }
+    }
+}
+
+fn default_keg_list_columns() -> Vec<KegColumn> {
+    vec![
+        KegColumn::Location,
+        KegColumn::Wrapper,
+        KegColumn::Arch,
+        KegColumn::Wine,
+    ]
+}
+
+/// Time limits, in seconds, for commands that can hang on a stuck Wine
+/// process. A limit of `0` disables it.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct TimeoutsConfig {
+    #[serde(default = "default_winetricks_timeout")]
+    pub winetricks: u64,
+
+    #[serde(
+        rename = "prefix-create",
+        default = "default_prefix_create_timeout"
+    )]
+    pub prefix_create: u64,
+}
+
+impl TimeoutsConfig {
+    pub fn limit(seconds: u64) -> Option<Duration> {
+        (seconds > 0).then(|| Duration::from_secs(seconds))
+    }
+}
+
+impl Default for TimeoutsConfig {
+    fn default() -> Self {
+        Self {
+            winetricks: default_winetricks_timeout(),
+            prefix_create: default_prefix_create_timeout(),
+        }
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct MaintenanceConfig {
+    /// Run maintenance on every keg when kegtui starts.
+    #[serde(rename = "run-on-start", default)]
+    pub run_on_start: bool,
+
+    /// Logs larger than this many bytes are rotated.
+    #[serde(rename = "max-log-size", default = "default_max_log_size")]
+    pub max_log_size: u64,
+
+    /// How many rotated copies of each log to keep.
+    #[serde(
+        rename = "keep-rotated-logs",
+        default = "default_keep_rotated_logs"
+    )]
+    pub keep_rotated_logs: usize,
+
+    /// How many snapshots of each kind, e.g., prefix or engine, to keep per
+    /// keg, newest first.
+    #[serde(rename = "keep-snapshots", default = "default_keep_snapshots")]
+    pub keep_snapshots: usize,
+
+    /// How many recorded runs, e.g., of winetricks, to keep per keg, newest
+    /// first.
+    #[serde(rename = "keep-runs", default = "default_keep_runs")]
+    pub keep_runs: usize,
+
+    /// How many backups of overwritten kegs to keep, newest first.
+    #[serde(rename = "keep-backups", default = "default_keep_backups")]
+    pub keep_backups: usize,
+
+    /// Empty the Windows temp directories in each prefix.
+    #[serde(rename = "clear-temp-files", default = "default_clear_temp_files")]
+    pub clear_temp_files: bool,
+}
+
+impl Default for MaintenanceConfig {
+    fn default() -> Self {
+        Self {
+            run_on_start: false,
+            max_log_size: default_max_log_size(),
+            keep_rotated_logs: default_keep_rotated_logs(),
+            keep_snapshots: default_keep_snapshots(),
+            keep_runs: default_keep_runs(),
+            keep_backups: default_keep_backups(),
+            clear_temp_files: default_clear_temp_files(),
+        }
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SnapshotsConfig {
+    /// Snapshot a keg's prefix before each winetricks install, so Undo
+    /// Winetricks can restore it.
+    #[serde(
+        rename = "before-winetricks",
+        default = "default_snapshot_before_winetricks"
+    )]
+    pub before_winetricks: bool,
+}
+
+impl Default for SnapshotsConfig {
+    fn default() -> Self {
+        Self {
+            before_winetricks: default_snapshot_before_winetricks(),
+        }
+    }
+}
+
+/// How kegtui announces a long task finishing, like a winetricks install or
+/// creating a keg, besides the message at the bottom of the TUI.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct NotificationsConfig {
+    /// Ring the terminal bell.
+    #[serde(default = "default_notification_bell")]
+    pub bell: bool,
+
+    /// Post a macOS notification with `osascript`.
+    #[serde(default)]
+    pub macos: bool,
+
+    /// Tasks that finish sooner than this many seconds are not announced.
+    #[serde(
+        rename = "min-seconds",
+        default = "default_notification_min_seconds"
+    )]
+    pub min_seconds: u64,
+}
+
+impl Default for NotificationsConfig {
+    fn default() -> Self {
+        Self {
+            bell: default_notification_bell(),
+            macos: false,
+            min_seconds: default_notification_min_seconds(),
+        }
+    }
+}
+
+/// Settings written into the plist of every keg created by kegtui. Unset
+/// fields keep the wrapper template's value.
+#[derive(Debug, Clone, Serialize, Deserialize, Default)]
+#[serde(rename_all = "kebab-case")]
+pub struct NewKegDefaults {
+    pub d3d_metal: Option<bool>,
+    pub dxvk: Option<bool>,
+    pub dxmt: Option<bool>,
+    pub molten_vkcx: Option<bool>,
+    pub fast_math: Option<bool>,
+    pub advertise_avx: Option<bool>,
+    pub metal_hud: Option<bool>,
+    pub wine_esync: Option<bool>,
+    pub wine_msync: Option<bool>,
+    pub wine_debug: Option<String>,
+    pub symlinks_in_user_folder: Option<bool>,
+    pub symlink_desktop: Option<String>,
+    pub symlink_downloads: Option<String>,
+    pub symlink_documents: Option<String>,
+    pub symlink_music: Option<String>,
+    pub symlink_pictures: Option<String>,
+    pub symlink_videos: Option<String>,
+    pub symlink_templates: Option<String>,
+}
+
+/// A named set of keg settings under `[presets]`, in the same shape as Edit
+/// Config as TOML, e.g., `translation.dxvk = true`.
+#[derive(Debug, Clone, Serialize, Deserialize, Default)]
+pub struct PresetConfig {
+    #[serde(default)]
+    pub description: String,
+    #[serde(flatten)]
+    pub settings: toml::Table,
+}
+
 #[derive(Debug, Clone, Serialize, Deserialize, Default)]
 pub struct AppConfig {
     /// Directories with full Kegworks wrappers.
@@ -95,9 +565,65 @@ pub struct AppConfig {
     )]
     pub wrapper_search_paths: Vec<PathBuf>,
 
+    /// Homebrew taps, as `user/repo`, that publish engines or wrappers.
+    #[serde(rename = "brew-taps", default)]
+    pub brew_taps: Vec<String>,
+
+    /// How many of the newest engines Prune Engines keeps even when no keg
+    /// uses them.
+    #[serde(
+        rename = "keep-newest-engines",
+        default = "default_keep_newest_engines"
+    )]
+    pub keep_newest_engines: usize,
+
     #[serde(default = "default_editor")]
     pub editor: String,
 
     #[serde(default = "default_explorer")]
     pub explorer: String,
+
+    #[serde(default)]
+    pub maintenance: MaintenanceConfig,
+
+    #[serde(default)]
+    pub timeouts: TimeoutsConfig,
+
+    #[serde(default)]
+    pub notifications: NotificationsConfig,
+
+    #[serde(default)]
+    pub snapshots: SnapshotsConfig,
+
+    #[serde(rename = "new-keg-defaults", default)]
+    pub new_keg_defaults: NewKegDefaults,
+
+    /// Presets to apply to kegs, keyed by name, alongside the built-in ones.
+    #[serde(default)]
+    pub presets: BTreeMap<String, PresetConfig>,
+
+    /// Which columns the kegs list shows after each keg's name.
+    #[serde(rename = "keg-list-columns", default = "default_keg_list_columns")]
+    pub keg_list_columns: Vec<KegColumn>,
+
+    /// Tags for each keg, keyed by the keg's name, e.g., `"Steam.app"`.
+    #[serde(rename = "keg-tags", default)]
+    pub keg_tags: BTreeMap<String, Vec<String>>,
+}
+
+impl AppConfig {
+    /// Expands `~` and environment variables in every configured path,
+    /// including the editor and explorer commands.
+    pub fn expand_paths(&mut self) {
+        for path in self
+            .keg_search_paths
+            .iter_mut()
+            .chain(&mut self.engine_search_paths)
+            .chain(&mut self.wrapper_search_paths)
+        {
+            expand_path_buf(path);
+        }
+        self.editor = expand_path(&self.editor);
+        self.explorer = expand_path(&self.explorer);
+    }
 }
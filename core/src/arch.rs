@@ -0,0 +1,127 @@
+// Copyright (C) 2026 Ethan Uppal.
+//
+// This program is free software: you can redistribute it and/or modify it under
+// the terms of the GNU General Public License as published by the Free Software
+// Foundation, version 3 of the License only.
+//
+// This program is distributed in the hope that it will be useful, but WITHOUT
+// ANY WARRANTY; without even the implied warranty of MERCHANTABILITY or FITNESS
+// FOR A PARTICULAR PURPOSE. See the GNU General Public License for more
+// details.
+//
+// You should have received a copy of the GNU General Public License along with
+// this program.  If not, see <https://www.gnu.org/licenses/>.
+
+use std::{
+    fs::File,
+    io::{self, BufRead, BufReader},
+    path::Path,
+    process::Command,
+    sync::OnceLock,
+};
+
+use crate::{
+    pe::{self, Machine},
+    runner::CommandRunner,
+};
+
+/// Engines starting from this Wineskin version drop the 32-bit Wine loader.
+const FIRST_64_BIT_ONLY_WS_VERSION: u32 = 12;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum EngineArch {
+    /// The engine ships both 32-bit and 64-bit Wine.
+    Mixed,
+    /// The engine (WS12+) only ships 64-bit Wine, so 32-bit programs rely on
+    /// WoW64.
+    SixtyFourBitOnly,
+}
+
+impl EngineArch {
+    /// Determines the architecture from an engine name such as
+    /// `WS12WineSikarugir10.0_4.tar.xz`.
+    pub fn from_engine_name(name: &str) -> Option<Self> {
+        let version = ws_version(name)?;
+        Some(if version >= FIRST_64_BIT_ONLY_WS_VERSION {
+            EngineArch::SixtyFourBitOnly
+        } else {
+            EngineArch::Mixed
+        })
+    }
+
+    pub fn badge(&self) -> &'static str {
+        match self {
+            EngineArch::Mixed => "32/64-bit",
+            EngineArch::SixtyFourBitOnly => "64-bit only",
+        }
+    }
+}
+
+/// Whether a Wine prefix was created for 32-bit or 64-bit Windows.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum PrefixArch {
+    Win32,
+    Win64,
+}
+
+impl PrefixArch {
+    /// Reads the `#arch=` line Wine writes near the top of the `system.reg`
+    /// in `prefix`. Returns `None` if the prefix hasn't been created.
+    pub fn detect(prefix: &Path) -> Option<Self> {
+        let system_reg = File::open(prefix.join("system.reg")).ok()?;
+        BufReader::new(system_reg)
+            .lines()
+            .take(10)
+            .map_while(Result::ok)
+            .find_map(|line| match line.trim().strip_prefix("#arch=")? {
+                "win32" => Some(PrefixArch::Win32),
+                "win64" => Some(PrefixArch::Win64),
+                _ => None,
+            })
+    }
+
+    pub fn name(&self) -> &'static str {
+        match self {
+            PrefixArch::Win32 => "win32",
+            PrefixArch::Win64 => "win64",
+        }
+    }
+}
+
+/// Parses the `12` out of `WS12Wine...`.
+pub fn ws_version(name: &str) -> Option<u32> {
+    let rest = name.strip_prefix("WS")?;
+    let digits = rest
+        .find(|c: char| !c.is_ascii_digit())
+        .unwrap_or(rest.len());
+    rest[..digits].parse().ok()
+}
+
+fn sysctl_is_set(runner: &dyn CommandRunner, name: &str) -> bool {
+    runner
+        .output(Command::new("sysctl").args(["-n", name]))
+        .map(|output| String::from_utf8_lossy(&output.stdout).trim() == "1")
+        .unwrap_or(false)
+}
+
+/// Whether the host is an Apple Silicon Mac, even if kegtui itself is running
+/// under Rosetta 2. Only the first call asks `runner`.
+pub fn host_is_apple_silicon(runner: &dyn CommandRunner) -> bool {
+    static IS_APPLE_SILICON: OnceLock<bool> = OnceLock::new();
+    *IS_APPLE_SILICON.get_or_init(|| {
+        cfg!(target_arch = "aarch64")
+            || sysctl_is_set(runner, "hw.optional.arm64")
+            || sysctl_is_set(runner, "sysctl.proc_translated")
+    })
+}
+
+/// Whether Rosetta 2, which every Wine engine needs on Apple Silicon, is
+/// installed.
+pub fn is_rosetta_installed() -> bool {
+    Path::new("/Library/Apple/usr/share/rosetta/rosetta").exists()
+}
+
+/// Whether the Windows executable at `path` is a 32-bit (i386) PE image.
+pub fn is_32_bit_executable(path: &Path) -> io::Result<bool> {
+    Ok(pe::inspect(path)?.machine == Machine::X86)
+}
@@ -0,0 +1,93 @@
+// Copyright (C) 2026 Ethan Uppal.
+//
+// This program is free software: you can redistribute it and/or modify it under
+// the terms of the GNU General Public License as published by the Free Software
+// Foundation, version 3 of the License only.
+//
+// This program is distributed in the hope that it will be useful, but WITHOUT
+// ANY WARRANTY; without even the implied warranty of MERCHANTABILITY or FITNESS
+// FOR A PARTICULAR PURPOSE. See the GNU General Public License for more
+// details.
+//
+// You should have received a copy of the GNU General Public License along with
+// this program.  If not, see <https://www.gnu.org/licenses/>.
+
+use std::{path::PathBuf, process::Command};
+
+use color_eyre::{
+    Result,
+    eyre::{Context, bail},
+};
+use walkdir::WalkDir;
+
+use crate::{checks::brew_executable, runner::CommandRunner};
+
+/// A formula or cask published by a tap.
+#[derive(Debug, Clone)]
+pub struct TapPackage {
+    /// The fully qualified name, e.g., `user/repo/engine`.
+    pub name: String,
+    pub is_cask: bool,
+}
+
+fn brew_output(runner: &dyn CommandRunner, args: &[&str]) -> Result<String> {
+    let output = runner
+        .output(Command::new(brew_executable()).args(args))
+        .context("Failed to run brew")?;
+    if !output.status.success() {
+        bail!(
+            "brew {} failed: {}",
+            args.join(" "),
+            String::from_utf8_lossy(&output.stderr).trim()
+        );
+    }
+    Ok(String::from_utf8_lossy(&output.stdout).trim().to_owned())
+}
+
+/// Taps `tap` if needed and lists the packages it publishes by reading its
+/// `Formula` and `Casks` directories, which is much faster than searching.
+pub fn list_tap_packages(
+    runner: &dyn CommandRunner,
+    tap: &str,
+) -> Result<Vec<TapPackage>> {
+    brew_output(runner, &["tap", tap])?;
+    let repository =
+        PathBuf::from(brew_output(runner, &["--repository", tap])?);
+
+    let mut packages = vec![];
+    for (directory, is_cask) in [("Formula", false), ("Casks", true)] {
+        // Large taps shard definitions into one subdirectory per letter.
+        for entry in WalkDir::new(repository.join(directory))
+            .max_depth(2)
+            .into_iter()
+            .flatten()
+        {
+            let path = entry.path();
+            if path.extension().is_some_and(|extension| extension == "rb")
+                && let Some(stem) = path.file_stem()
+            {
+                packages.push(TapPackage {
+                    name: format!("{tap}/{}", stem.to_string_lossy()),
+                    is_cask,
+                });
+            }
+        }
+    }
+    packages.sort_by(|a, b| a.name.cmp(&b.name));
+    Ok(packages)
+}
+
+pub fn install(runner: &dyn CommandRunner, package: &TapPackage) -> Result<()> {
+    let mut command = Command::new(brew_executable());
+    command.arg("install");
+    if package.is_cask {
+        command.args(["--cask", "--no-quarantine"]);
+    }
+    let status = runner
+        .status(command.arg(&package.name))
+        .context("Failed to run brew")?;
+    if !status.success() {
+        bail!("Failed to install {} ({status})", package.name);
+    }
+    Ok(())
+}
@@ -14,14 +14,53 @@
 
 use std::{path::Path, process::Command};
 
-pub fn is_brew_installed() -> bool {
-    Command::new("which")
-        .arg("brew")
-        .output()
+use crate::runner::CommandRunner;
+
+pub fn is_brew_installed(runner: &dyn CommandRunner) -> bool {
+    Path::new(brew_executable()).is_absolute()
+        || runner
+            .output(Command::new("which").arg("brew"))
+            .map(|output| output.status.success())
+            .unwrap_or(false)
+}
+
+/// Homebrew isn't on `PATH` until the shell is restarted, so look in its
+/// default prefixes first in case it was just installed.
+pub fn brew_executable() -> &'static str {
+    ["/opt/homebrew/bin/brew", "/usr/local/bin/brew"]
+        .into_iter()
+        .find(|path| Path::new(path).exists())
+        .unwrap_or("brew")
+}
+
+/// Whether github.com, where kegtui downloads everything from, answers within
+/// a few seconds.
+pub fn is_network_reachable(runner: &dyn CommandRunner) -> bool {
+    runner
+        .output(Command::new("curl").args([
+            "-fsSI",
+            "--max-time",
+            "3",
+            "-o",
+            "/dev/null",
+            "https://github.com",
+        ]))
         .map(|output| output.status.success())
         .unwrap_or(false)
 }
 
+const KEGWORKS_APP: &str = "/Applications/Kegworks Winery.app";
+
 pub fn is_kegworks_installed() -> bool {
-    Path::new("/Applications/Kegworks Winery.app").exists()
+    Path::new(KEGWORKS_APP).exists()
+}
+
+/// The installed Kegworks Winery's `CFBundleShortVersionString`.
+pub fn kegworks_version() -> Option<String> {
+    plist::Value::from_file(Path::new(KEGWORKS_APP).join("Contents/Info.plist"))
+        .ok()?
+        .as_dictionary()?
+        .get("CFBundleShortVersionString")?
+        .as_string()
+        .map(str::to_owned)
 }
@@ -0,0 +1,202 @@
+// Copyright (C) 2026 Ethan Uppal.
+//
+// This program is free software: you can redistribute it and/or modify it under
+// the terms of the GNU General Public License as published by the Free Software
+// Foundation, version 3 of the License only.
+//
+// This program is distributed in the hope that it will be useful, but WITHOUT
+// ANY WARRANTY; without even the implied warranty of MERCHANTABILITY or FITNESS
+// FOR A PARTICULAR PURPOSE. See the GNU General Public License for more
+// details.
+//
+// You should have received a copy of the GNU General Public License along with
+// this program.  If not, see <https://www.gnu.org/licenses/>.
+
+//! The subcommands and flags that run instead of the TUI, e.g.,
+//! `kegtui logs`.
+
+use std::process::Stdio;
+
+use color_eyre::{
+    Result,
+    eyre::{Context, bail},
+};
+
+use crate::{
+    actions::launch::{apply_app_nap_setting, confirm_launch, launch_command},
+    app::AsyncState,
+    app_config::AppConfig,
+    keg::{CurrentKeg, Keg},
+    keg_config, log_tail,
+    maintenance::{self, MaintenanceReport},
+    processes, quick_launch, run_scripted,
+    runner::{CommandRunner, SystemRunner},
+    tasks::KegLock,
+    worker::scan_kegs,
+};
+
+/// Runs `kegtui --quick`, which launches a keg picked by fuzzy search and
+/// exits.
+pub fn run_quick_launch(config: &AppConfig) -> Result<()> {
+    let mut terminal = ratatui::init();
+    let picked =
+        quick_launch::pick_keg(&mut terminal, scan_kegs(config).collect());
+    ratatui::restore();
+    let Some(keg) = picked? else {
+        return Ok(());
+    };
+    let current_keg = CurrentKeg::try_from(&keg).with_context(|| {
+        format!("Failed to read {}", keg.config_file.display())
+    })?;
+    if !confirm_launch(&current_keg)? {
+        return Ok(());
+    }
+    let runner = SystemRunner::default();
+    apply_app_nap_setting(&runner, &current_keg);
+    let mut command = launch_command(&current_keg);
+    // kegtui exits right away, so the keg keeps running on its own and this
+    // run isn't recorded in its run logs.
+    command
+        .stdin(Stdio::null())
+        .stdout(Stdio::null())
+        .stderr(Stdio::null());
+    runner
+        .spawn(&mut command)
+        .with_context(|| format!("Failed to launch {}", keg.name))?;
+    Ok(())
+}
+
+/// Prints what each keg config key does, or only those under `path`.
+pub fn describe_config(path: Option<&str>) {
+    let fields = keg_config::CONFIG_FIELDS.iter().filter(|field| {
+        path.is_none_or(|path| {
+            field.path == path || field.path.starts_with(&format!("{path}."))
+        })
+    });
+    let mut is_empty = true;
+    for field in fields {
+        println!("{}\n    {}", field.path, field.description);
+        is_empty = false;
+    }
+    if is_empty && let Some(path) = path {
+        println!("No config key {path}");
+    }
+}
+
+/// `kegtui logs <keg> [--follow] [--channel <channel>]...`
+pub fn print_keg_logs(app_config: &AppConfig, args: &[&str]) -> Result<()> {
+    const USAGE: &str =
+        "usage: kegtui logs <keg> [--follow] [--channel <channel>]...";
+    let mut keg_name = None;
+    let mut follow = false;
+    let mut channels = vec![];
+    let mut args = args.iter();
+    while let Some(&arg) = args.next() {
+        match arg {
+            "-f" | "--follow" => follow = true,
+            "-c" | "--channel" => match args.next() {
+                Some(channel) => channels.push(channel.to_string()),
+                None => {
+                    bail!("--channel needs a channel, e.g., err:d3d\n{USAGE}")
+                }
+            },
+            flag if flag.starts_with('-') => {
+                bail!("Unknown option {flag}\n{USAGE}")
+            }
+            name if keg_name.is_none() => keg_name = Some(name),
+            _ => bail!("Only one keg can be given\n{USAGE}"),
+        }
+    }
+    let Some(keg_name) = keg_name else {
+        bail!(USAGE);
+    };
+    let Some(keg) = log_tail::find_keg(scan_kegs(app_config), keg_name) else {
+        bail!("No keg named {keg_name} in the keg search paths");
+    };
+    log_tail::tail(&keg, &log_tail::ChannelFilter::new(channels), follow)
+}
+
+/// Runs the TUI on a `size` screen, e.g., `100x30`, pressing `keys`, e.g.,
+/// `jj<Enter>`, and prints the last frame, so scripts can test the TUI.
+/// Nothing is scanned, run, or locked, so the screen only depends on the keys.
+pub fn run_headless(
+    app_config: &AppConfig,
+    size: &str,
+    keys: &str,
+) -> Result<()> {
+    let Some((width, height)) =
+        size.split_once('x').and_then(|(width, height)| {
+            Some((width.parse().ok()?, height.parse().ok()?))
+        })
+    else {
+        bail!("Expected a screen size like 100x30, not {size}");
+    };
+    let screen =
+        run_scripted(app_config, AsyncState::default(), width, height, keys)?;
+    print!("{screen}");
+    Ok(())
+}
+
+pub fn run_maintenance(app_config: &AppConfig) {
+    let kegs = scan_kegs(app_config).collect::<Vec<_>>();
+    let bundles = kegs.iter().map(Keg::bundle_path).collect::<Vec<_>>();
+    let keg_processes =
+        processes::by_keg(&processes::list(&SystemRunner::default()), &bundles);
+    for keg in kegs {
+        let bundle = keg.bundle_path();
+        let Some(_keg_lock) = KegLock::try_acquire(&bundle) else {
+            println!("{}: skipped, another kegtui is changing it", keg.name);
+            continue;
+        };
+        let is_running = keg_processes
+            .get(&bundle)
+            .is_some_and(|processes| !processes.is_empty());
+        let report = maintenance::maintain_keg(
+            &keg,
+            &app_config.maintenance,
+            is_running,
+        );
+        print_maintenance_report(&keg.name, &report);
+    }
+    let report = maintenance::prune_backups(&app_config.maintenance);
+    print_maintenance_report("backups", &report);
+}
+
+fn print_maintenance_report(name: &str, report: &MaintenanceReport) {
+    if report.is_empty() {
+        return;
+    }
+    let mut done = vec![];
+    if !report.rotated_logs.is_empty() {
+        done.push(format!("rotated {} log(s)", report.rotated_logs.len()));
+    }
+    if !report.removed_snapshots.is_empty() {
+        done.push(format!(
+            "removed {} snapshot(s)",
+            report.removed_snapshots.len()
+        ));
+    }
+    if !report.removed_runs.is_empty() {
+        done.push(format!("removed {} run(s)", report.removed_runs.len()));
+    }
+    if !report.removed_backups.is_empty() {
+        done.push(format!(
+            "removed {} backup(s)",
+            report.removed_backups.len()
+        ));
+    }
+    if report.skipped_temp_files {
+        done.push("kept temp files since it's running".to_owned());
+    } else if report.removed_temp_files > 0 {
+        done.push(format!(
+            "removed {} temp file(s)",
+            report.removed_temp_files
+        ));
+    }
+    if !done.is_empty() {
+        println!("{name}: {}", done.join(", "));
+    }
+    for failure in &report.failures {
+        eprintln!("{name}: {failure}");
+    }
+}
@@ -0,0 +1,51 @@
+// Copyright (C) 2026 Ethan Uppal.
+//
+// This program is free software: you can redistribute it and/or modify it under
+// the terms of the GNU General Public License as published by the Free Software
+// Foundation, version 3 of the License only.
+//
+// This program is distributed in the hope that it will be useful, but WITHOUT
+// ANY WARRANTY; without even the implied warranty of MERCHANTABILITY or FITNESS
+// FOR A PARTICULAR PURPOSE. See the GNU General Public License for more
+// details.
+//
+// You should have received a copy of the GNU General Public License along with
+// this program.  If not, see <https://www.gnu.org/licenses/>.
+
+use std::{
+    io::{self, Write},
+    process::{Command, Stdio},
+};
+
+use ratatui::{buffer::Buffer, layout::Rect};
+
+use crate::runner::CommandRunner;
+
+/// Replaces the clipboard's contents with `text` using `pbcopy`.
+pub fn copy(runner: &dyn CommandRunner, text: &str) -> io::Result<()> {
+    let mut child =
+        runner.spawn(Command::new("pbcopy").stdin(Stdio::piped()))?;
+    if let Some(mut stdin) = child.take_stdin() {
+        stdin.write_all(text.as_bytes())?;
+    }
+    child.wait()?;
+    Ok(())
+}
+
+/// The text drawn in `area` of `buffer`, one line per row, without trailing
+/// whitespace or blank rows.
+pub fn buffer_text(buffer: &Buffer, area: Rect) -> String {
+    let area = area.intersection(buffer.area);
+    let mut lines = (area.top()..area.bottom())
+        .map(|y| {
+            let line = (area.left()..area.right())
+                .map(|x| buffer[(x, y)].symbol())
+                .collect::<String>();
+            line.trim_end().to_owned()
+        })
+        .collect::<Vec<_>>();
+    while lines.last().is_some_and(|line| line.is_empty()) {
+        lines.pop();
+    }
+    lines.join("\n")
+}
@@ -0,0 +1,147 @@
+// Copyright (C) 2026 Ethan Uppal.
+//
+// This program is free software: you can redistribute it and/or modify it under
+// the terms of the GNU General Public License as published by the Free Software
+// Foundation, version 3 of the License only.
+//
+// This program is distributed in the hope that it will be useful, but WITHOUT
+// ANY WARRANTY; without even the implied warranty of MERCHANTABILITY or FITNESS
+// FOR A PARTICULAR PURPOSE. See the GNU General Public License for more
+// details.
+//
+// You should have received a copy of the GNU General Public License along with
+// this program.  If not, see <https://www.gnu.org/licenses/>.
+
+//! Named sets of keg settings, applied on top of a keg's config so only the
+//! settings they mention change. Some are built in, and users add their own
+//! under `[presets]` in `kegtui.toml`.
+
+use std::{collections::BTreeMap, fmt};
+
+use color_eyre::{
+    Result,
+    eyre::{Context, bail},
+};
+
+use crate::{app_config::PresetConfig, keg_config::KegworksConfig};
+
+/// The built-in presets, written like the user's `[presets]`.
+const BUILT_IN: &str = r#"
+["DXVK gaming"]
+description = "DirectX 9-11 games through DXVK and MoltenVK-CX"
+translation = { d3d_metal = false, dxmt = false, dxvk = true, molten_vkcx = true, fast_math = true }
+wine = { wine_esync = false, wine_msync = true }
+
+["DirectX 11 via D3DMetal"]
+description = "DirectX 11 and 12 games through Apple's D3DMetal"
+translation = { d3d_metal = true, dxmt = false, dxvk = false, advertise_avx = true }
+wine = { wine_esync = false, wine_msync = true }
+
+["DirectX 10/11 via DXMT"]
+description = "Older DirectX 10 and 11 games straight to Metal"
+translation = { d3d_metal = false, dxmt = true, dxvk = false }
+wine = { wine_esync = false, wine_msync = true }
+
+["Old 2D game"]
+description = "Wine's own DirectX on one CPU, for games from before multicore"
+translation = { d3d_metal = false, dxmt = false, dxvk = false, fast_math = false }
+debug = { disable_cpus = true }
+"#;
+
+pub struct Preset {
+    pub name: String,
+    pub description: String,
+    /// Settings in the shape of Edit Config as TOML, e.g., `translation`
+    /// holding `dxvk = true`.
+    settings: toml::Table,
+}
+
+/// A setting a preset changes, where `None` means unset.
+pub struct Change {
+    pub key: String,
+    pub old: Option<toml::Value>,
+    pub new: toml::Value,
+}
+
+impl fmt::Display for Change {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match &self.old {
+            Some(old) => write!(f, "{} = {} (was {old})", self.key, self.new),
+            None => write!(f, "{} = {} (was unset)", self.key, self.new),
+        }
+    }
+}
+
+/// The built-in presets followed by `user`'s, by name, where the user's
+/// replace built-in ones of the same name.
+pub fn all(user: &BTreeMap<String, PresetConfig>) -> Vec<Preset> {
+    let mut presets =
+        toml::from_str::<BTreeMap<String, PresetConfig>>(BUILT_IN)
+            .unwrap_or_default();
+    presets.extend(user.clone());
+    presets
+        .into_iter()
+        .map(|(name, preset)| Preset {
+            name,
+            description: preset.description,
+            settings: preset.settings,
+        })
+        .collect()
+}
+
+impl Preset {
+    /// `config` with the preset's settings, and the settings that changed.
+    pub fn apply(
+        &self,
+        config: &KegworksConfig,
+    ) -> Result<(KegworksConfig, Vec<Change>)> {
+        let mut root = toml::Value::try_from(config)
+            .context("Failed to read the keg's config")?;
+        let mut changes = vec![];
+        merge("", &self.settings, &mut root, &mut changes)?;
+        let config = root
+            .try_into()
+            .context("The preset's settings have the wrong types")?;
+        Ok((config, changes))
+    }
+}
+
+/// Overwrites the settings in `target` at `prefix` with `settings`,
+/// recording what changed.
+fn merge(
+    prefix: &str,
+    settings: &toml::Table,
+    target: &mut toml::Value,
+    changes: &mut Vec<Change>,
+) -> Result<()> {
+    let toml::Value::Table(table) = target else {
+        bail!("{prefix} isn't a table of settings");
+    };
+    for (key, value) in settings {
+        let path = if prefix.is_empty() {
+            key.clone()
+        } else {
+            format!("{prefix}.{key}")
+        };
+        // Environment variables are named by the user, so any can be set.
+        if prefix != "environment" && !table.contains_key(key) {
+            bail!("{path} isn't a keg setting");
+        }
+        if let toml::Value::Table(settings) = value
+            && let Some(slot @ toml::Value::Table(_)) = table.get_mut(key)
+        {
+            merge(&path, settings, slot, changes)?;
+            continue;
+        }
+        let old = table.get(key).cloned();
+        if old.as_ref() != Some(value) {
+            changes.push(Change {
+                key: path,
+                old,
+                new: value.clone(),
+            });
+            table.insert(key.clone(), value.clone());
+        }
+    }
+    Ok(())
+}
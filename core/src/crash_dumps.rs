@@ -0,0 +1,78 @@
+// Copyright (C) 2026 Ethan Uppal.
+//
+// This program is free software: you can redistribute it and/or modify it under
+// the terms of the GNU General Public License as published by the Free Software
+// Foundation, version 3 of the License only.
+//
+// This program is distributed in the hope that it will be useful, but WITHOUT
+// ANY WARRANTY; without even the implied warranty of MERCHANTABILITY or FITNESS
+// FOR A PARTICULAR PURPOSE. See the GNU General Public License for more
+// details.
+//
+// You should have received a copy of the GNU General Public License along with
+// this program.  If not, see <https://www.gnu.org/licenses/>.
+
+use std::{
+    cmp::Reverse,
+    path::{Path, PathBuf},
+    time::SystemTime,
+};
+
+use walkdir::WalkDir;
+
+/// How deep under `drive_c/users` to look. Wine and winedbg write minidumps
+/// into `AppData/Local/CrashDumps` or `Temp`, both of which are well within
+/// this depth.
+const USERS_SEARCH_DEPTH: usize = 6;
+
+#[derive(Debug, Clone)]
+pub struct CrashDump {
+    pub path: PathBuf,
+    pub modified: SystemTime,
+}
+
+impl CrashDump {
+    /// Whether this dump was written after `since`, e.g., the last launch.
+    pub fn is_newer_than(&self, since: Option<SystemTime>) -> bool {
+        since.is_some_and(|since| self.modified >= since)
+    }
+}
+
+fn is_crash_dump(path: &Path) -> bool {
+    let Some(name) = path.file_name().and_then(|name| name.to_str()) else {
+        return false;
+    };
+    let name = name.to_ascii_lowercase();
+    name.ends_with(".dmp")
+        || name.ends_with(".mdmp")
+        || name.contains("backtrace")
+}
+
+/// Finds minidumps and backtraces in the prefix containing `c_drive` as well
+/// as in `log_directories`, newest first.
+pub fn find_crash_dumps(
+    c_drive: &Path,
+    log_directories: &[&Path],
+) -> Vec<CrashDump> {
+    let users = c_drive.join("users");
+    let mut crash_dumps = WalkDir::new(&users)
+        .max_depth(USERS_SEARCH_DEPTH)
+        .into_iter()
+        .chain(
+            log_directories
+                .iter()
+                .flat_map(|directory| WalkDir::new(directory).max_depth(1)),
+        )
+        .flatten()
+        .filter(|entry| entry.file_type().is_file())
+        .filter(|entry| is_crash_dump(entry.path()))
+        .filter_map(|entry| {
+            Some(CrashDump {
+                modified: entry.metadata().ok()?.modified().ok()?,
+                path: entry.into_path(),
+            })
+        })
+        .collect::<Vec<_>>();
+    crash_dumps.sort_by_key(|crash_dump| Reverse(crash_dump.modified));
+    crash_dumps
+}
@@ -0,0 +1,116 @@
+// Copyright (C) 2026 Ethan Uppal.
+//
+// This program is free software: you can redistribute it and/or modify it under
+// the terms of the GNU General Public License as published by the Free Software
+// Foundation, version 3 of the License only.
+//
+// This program is distributed in the hope that it will be useful, but WITHOUT
+// ANY WARRANTY; without even the implied warranty of MERCHANTABILITY or FITNESS
+// FOR A PARTICULAR PURPOSE. See the GNU General Public License for more
+// details.
+//
+// You should have received a copy of the GNU General Public License along with
+// this program.  If not, see <https://www.gnu.org/licenses/>.
+
+//! Small apps that open a keg, so its program can be started from the
+//! Desktop or the Dock without kegtui. They open the keg by path, so they
+//! stop working if the keg is moved or renamed.
+
+use std::{
+    fs,
+    os::unix::fs::PermissionsExt,
+    path::{Path, PathBuf},
+};
+
+use color_eyre::{Result, eyre::Context};
+
+use crate::keg::CurrentKeg;
+
+/// The launcher's executable, relative to its `Contents/MacOS`.
+const EXECUTABLE: &str = "launch";
+
+/// Quotes `text` for a POSIX shell.
+fn shell_quote(text: &str) -> String {
+    format!("'{}'", text.replace('\'', "'\\''"))
+}
+
+/// The keg's icon, if its `Info.plist` names one that exists.
+fn keg_icon(current_keg: &CurrentKeg) -> Option<PathBuf> {
+    let icon_file = &current_keg.plist.cf_bundle_icon_file;
+    if icon_file.is_empty() {
+        return None;
+    }
+    let mut icon = current_keg
+        .bundle_path()
+        .join("Contents/Resources")
+        .join(icon_file);
+    if icon.extension().is_none() {
+        icon.set_extension("icns");
+    }
+    icon.is_file().then_some(icon)
+}
+
+/// The path of the launcher named `name` in `directory`.
+pub fn launcher_path(directory: &Path, name: &str) -> PathBuf {
+    directory.join(format!("{name}.app"))
+}
+
+/// Creates an app named `name` in `directory` that opens `current_keg`,
+/// replacing any launcher already there, and returns its path.
+pub fn create(
+    current_keg: &CurrentKeg,
+    name: &str,
+    directory: &Path,
+) -> Result<PathBuf> {
+    let launcher = launcher_path(directory, name);
+    if launcher.exists() {
+        fs::remove_dir_all(&launcher).with_context(|| {
+            format!("Failed to replace {}", launcher.display())
+        })?;
+    }
+    let contents = launcher.join("Contents");
+    let executable_directory = contents.join("MacOS");
+    fs::create_dir_all(&executable_directory)
+        .with_context(|| format!("Failed to create {}", launcher.display()))?;
+
+    let executable = executable_directory.join(EXECUTABLE);
+    fs::write(
+        &executable,
+        format!(
+            "#!/bin/sh\nexec /usr/bin/open -a {}\n",
+            shell_quote(&current_keg.bundle_path().to_string_lossy())
+        ),
+    )?;
+    fs::set_permissions(&executable, fs::Permissions::from_mode(0o755))?;
+
+    let identifier = name
+        .chars()
+        .map(|c| if c.is_ascii_alphanumeric() { c } else { '-' })
+        .collect::<String>();
+    let mut info = plist::Dictionary::new();
+    info.insert("CFBundleExecutable".to_owned(), EXECUTABLE.into());
+    info.insert("CFBundleName".to_owned(), name.into());
+    info.insert(
+        "CFBundleIdentifier".to_owned(),
+        format!("com.kegtui.launcher.{identifier}").into(),
+    );
+    info.insert("CFBundlePackageType".to_owned(), "APPL".into());
+    // The keg shows up in the Dock once it starts, so the launcher doesn't.
+    info.insert("LSUIElement".to_owned(), true.into());
+    if let Some(icon) = keg_icon(current_keg)
+        && let Some(icon_name) = icon.file_name()
+    {
+        let resources = contents.join("Resources");
+        fs::create_dir_all(&resources)?;
+        fs::copy(&icon, resources.join(icon_name))
+            .context("Failed to copy the keg's icon")?;
+        info.insert(
+            "CFBundleIconFile".to_owned(),
+            icon_name.to_string_lossy().into_owned().into(),
+        );
+    }
+    plist::Value::Dictionary(info)
+        .to_file_xml(contents.join("Info.plist"))
+        .context("Failed to write the launcher's Info.plist")?;
+    Ok(launcher)
+}
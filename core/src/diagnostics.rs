@@ -0,0 +1,298 @@
+// Copyright (C) 2026 Ethan Uppal.
+//
+// This program is free software: you can redistribute it and/or modify it under
+// the terms of the GNU General Public License as published by the Free Software
+// Foundation, version 3 of the License only.
+//
+// This program is distributed in the hope that it will be useful, but WITHOUT
+// ANY WARRANTY; without even the implied warranty of MERCHANTABILITY or FITNESS
+// FOR A PARTICULAR PURPOSE. See the GNU General Public License for more
+// details.
+//
+// You should have received a copy of the GNU General Public License along with
+// this program.  If not, see <https://www.gnu.org/licenses/>.
+
+use std::{
+    fmt::Write,
+    fs::{self, File},
+    path::{Path, PathBuf},
+};
+
+use color_eyre::{Result, eyre::Context};
+use tar::Builder;
+use xz2::write::XzEncoder;
+
+use crate::{
+    app::AsyncState, crash_dumps::CrashDump, keg::CurrentKeg,
+    translation_layers::LayerVersion,
+};
+
+/// One line of [`KegReport`], e.g., `Engine: WS12WineSikarugir10.0_4`.
+pub struct ReportField {
+    pub label: &'static str,
+    pub value: String,
+    /// Whether this field describes the one before it, e.g., the engine's
+    /// DXVK version.
+    pub is_detail: bool,
+}
+
+pub struct ReportCrashDump {
+    pub path: PathBuf,
+    pub is_new: bool,
+}
+
+/// Everything the keg info view shows, so it can also be exported.
+pub struct KegReport {
+    pub name: String,
+    pub fields: Vec<ReportField>,
+    pub crash_dumps: Vec<ReportCrashDump>,
+}
+
+#[derive(Debug, Clone, Copy)]
+pub enum ReportFormat {
+    PlainText,
+    Markdown,
+}
+
+impl ReportFormat {
+    pub fn extension(self) -> &'static str {
+        match self {
+            ReportFormat::PlainText => "txt",
+            ReportFormat::Markdown => "md",
+        }
+    }
+}
+
+impl KegReport {
+    pub fn new(
+        keg: &CurrentKeg,
+        state: &AsyncState,
+        crash_dumps: &[CrashDump],
+    ) -> Self {
+        let field = |label, value: String| ReportField {
+            label,
+            value,
+            is_detail: false,
+        };
+        let detail = |label, value: String| ReportField {
+            label,
+            value,
+            is_detail: true,
+        };
+        let mut fields = vec![
+            field("Name", keg.keg.name.clone()),
+            field("Wrapper", keg.keg.flavor.name().to_owned()),
+            field("Program", keg.plist.program_name_and_path.clone()),
+            field(
+                "Engine",
+                match (&keg.keg.engine_version, keg.engine_arch()) {
+                    (Some(version), Some(arch)) => {
+                        format!("{version} [{}]", arch.badge())
+                    }
+                    (Some(version), None) => version.clone(),
+                    (None, _) => "unknown".into(),
+                },
+            ),
+            detail(
+                "Wine",
+                state
+                    .wine_versions
+                    .get(&keg.wine_executable())
+                    .map_or("unknown".to_owned(), |wine_version| {
+                        wine_version.version.clone()
+                    }),
+            ),
+        ];
+        let translation_layers =
+            state.translation_layers.get(&keg.bundle_path());
+        for (label, layer) in [
+            (
+                "MoltenVK",
+                translation_layers.map(|layers| &layers.molten_vk),
+            ),
+            ("DXVK", translation_layers.map(|layers| &layers.dxvk)),
+            (
+                "D3DMetal",
+                translation_layers.map(|layers| &layers.d3d_metal),
+            ),
+        ] {
+            fields.push(detail(
+                label,
+                layer.map_or("unknown", LayerVersion::describe).to_owned(),
+            ));
+        }
+        fields.extend([
+            field(
+                "Prefix",
+                keg.prefix_arch()
+                    .map_or("not created yet", |prefix_arch| prefix_arch.name())
+                    .to_owned(),
+            ),
+            field("Config", keg.keg.config_file.display().to_string()),
+            field("C drive", keg.keg.c_drive.display().to_string()),
+        ]);
+        if let Some(warning) = keg.arch_warning() {
+            fields.push(field("Warning", warning));
+        }
+        if !keg.missing_plist_keys.is_empty() {
+            fields.push(field(
+                "Missing settings",
+                format!(
+                    "{} (using defaults; saved on the next config change)",
+                    keg.missing_plist_keys.join(", ")
+                ),
+            ));
+        }
+        let crash_dumps = crash_dumps
+            .iter()
+            .map(|crash_dump| ReportCrashDump {
+                path: crash_dump.path.clone(),
+                is_new: crash_dump.is_newer_than(keg.last_launch),
+            })
+            .collect();
+        Self {
+            name: keg.keg.name.clone(),
+            fields,
+            crash_dumps,
+        }
+    }
+
+    pub fn new_crash_dump_count(&self) -> usize {
+        self.crash_dumps
+            .iter()
+            .filter(|crash_dump| crash_dump.is_new)
+            .count()
+    }
+
+    pub fn render(&self, format: ReportFormat) -> String {
+        let mut report = String::new();
+        let _ = match format {
+            ReportFormat::PlainText => self.write_plain_text(&mut report),
+            ReportFormat::Markdown => self.write_markdown(&mut report),
+        };
+        report
+    }
+
+    fn write_plain_text(&self, report: &mut String) -> std::fmt::Result {
+        for field in &self.fields {
+            let indent = if field.is_detail { "  " } else { "" };
+            writeln!(report, "{indent}{}: {}", field.label, field.value)?;
+        }
+        writeln!(report)?;
+        if self.crash_dumps.is_empty() {
+            writeln!(report, "No crash dumps found.")?;
+        } else {
+            writeln!(
+                report,
+                "Crash dumps ({} new since last launch):",
+                self.new_crash_dump_count()
+            )?;
+            for crash_dump in &self.crash_dumps {
+                let marker = if crash_dump.is_new {
+                    "  new "
+                } else {
+                    "      "
+                };
+                writeln!(report, "{marker}{}", crash_dump.path.display())?;
+            }
+        }
+        Ok(())
+    }
+
+    fn write_markdown(&self, report: &mut String) -> std::fmt::Result {
+        writeln!(report, "# {}", self.name)?;
+        writeln!(report)?;
+        for field in &self.fields {
+            let indent = if field.is_detail { "  " } else { "" };
+            writeln!(
+                report,
+                "{indent}- **{}:** `{}`",
+                field.label, field.value
+            )?;
+        }
+        writeln!(report)?;
+        writeln!(report, "## Crash dumps")?;
+        writeln!(report)?;
+        if self.crash_dumps.is_empty() {
+            writeln!(report, "None found.")?;
+        }
+        for crash_dump in &self.crash_dumps {
+            writeln!(
+                report,
+                "- `{}`{}",
+                crash_dump.path.display(),
+                if crash_dump.is_new { " (new)" } else { "" }
+            )?;
+        }
+        Ok(())
+    }
+}
+
+/// Writes `report` to `<destination>/<keg name>-info.<extension>`, returning
+/// the path of the file.
+pub fn export_report(
+    report: &KegReport,
+    format: ReportFormat,
+    destination: &Path,
+) -> Result<PathBuf> {
+    let report_path = destination.join(format!(
+        "{}-info.{}",
+        report.name.trim_end_matches(".app"),
+        format.extension()
+    ));
+    fs::write(&report_path, report.render(format))
+        .context(format!("Failed to write {}", report_path.display()))?;
+    Ok(report_path)
+}
+
+/// Bundles the keg's config, logs, and `crash_dumps` into
+/// `<destination>/<keg name>-diagnostics.tar.xz`, returning the path of the
+/// archive.
+pub fn export_diagnostics(
+    keg: &CurrentKeg,
+    crash_dumps: &[CrashDump],
+    destination: &Path,
+) -> Result<PathBuf> {
+    let archive_path = destination.join(format!(
+        "{}-diagnostics.tar.xz",
+        keg.keg.name.trim_end_matches(".app")
+    ));
+    let archive = File::create(&archive_path).context(format!(
+        "Failed to create diagnostics archive {}",
+        archive_path.display()
+    ))?;
+    let mut builder = Builder::new(XzEncoder::new(archive, 6));
+
+    builder
+        .append_path_with_name(&keg.keg.config_file, "Info.plist")
+        .context("Failed to add Info.plist to diagnostics")?;
+    for (i, log_directory) in keg.log_directories().into_iter().enumerate() {
+        if log_directory.is_dir() {
+            builder
+                .append_dir_all(format!("Logs/{i}"), log_directory)
+                .context(format!(
+                    "Failed to add {} to diagnostics",
+                    log_directory.display()
+                ))?;
+        }
+    }
+    for crash_dump in crash_dumps {
+        if let Some(file_name) = crash_dump.path.file_name() {
+            builder
+                .append_path_with_name(
+                    &crash_dump.path,
+                    Path::new("CrashDumps").join(file_name),
+                )
+                .context(format!(
+                    "Failed to add {} to diagnostics",
+                    crash_dump.path.display()
+                ))?;
+        }
+    }
+
+    builder
+        .into_inner()
+        .and_then(|encoder| encoder.finish())
+        .context("Failed to finish writing diagnostics archive")?;
+    Ok(archive_path)
+}
@@ -0,0 +1,180 @@
+// Copyright (C) 2026 Ethan Uppal.
+//
+// This program is free software: you can redistribute it and/or modify it under
+// the terms of the GNU General Public License as published by the Free Software
+// Foundation, version 3 of the License only.
+//
+// This program is distributed in the hope that it will be useful, but WITHOUT
+// ANY WARRANTY; without even the implied warranty of MERCHANTABILITY or FITNESS
+// FOR A PARTICULAR PURPOSE. See the GNU General Public License for more
+// details.
+//
+// You should have received a copy of the GNU General Public License along with
+// this program.  If not, see <https://www.gnu.org/licenses/>.
+
+//! Game discs as `.iso` images, mounted with `hdiutil` and linked into a
+//! keg's prefix as a drive letter, so an installer that looks for its disc
+//! finds it.
+
+use std::{
+    fs,
+    os::unix,
+    path::{Path, PathBuf},
+    process::Command,
+    sync::Arc,
+};
+
+use color_eyre::{
+    Result,
+    eyre::{Context, bail},
+};
+
+use crate::runner::CommandRunner;
+
+/// A mounted image linked into a prefix, unlinked and unmounted when
+/// dropped.
+pub struct MountedImage {
+    runner: Arc<dyn CommandRunner>,
+    mount_point: PathBuf,
+    drive_link: PathBuf,
+    letter: char,
+}
+
+impl MountedImage {
+    /// Mounts `image` read-only and links it into `prefix` as the first free
+    /// drive letter.
+    pub fn mount(
+        runner: Arc<dyn CommandRunner>,
+        image: &Path,
+        prefix: &Path,
+    ) -> Result<Self> {
+        let dosdevices = prefix.join("dosdevices");
+        // After `C:` and the `D:` Wine often maps to the Mac's home folder.
+        let Some(letter) = ('e'..='y').find(|letter| {
+            dosdevices
+                .join(format!("{letter}:"))
+                .symlink_metadata()
+                .is_err()
+        }) else {
+            bail!("The prefix has no free drive letter for the disc");
+        };
+
+        let output = runner
+            .output(
+                Command::new("hdiutil")
+                    .args(["attach", "-readonly", "-nobrowse", "-plist"])
+                    .arg(image),
+            )
+            .context("Failed to run hdiutil")?;
+        if !output.status.success() {
+            bail!(
+                "hdiutil couldn't mount {}: {}",
+                image.display(),
+                String::from_utf8_lossy(&output.stderr).trim()
+            );
+        }
+        let Some(mount_point) = parse_mount_point(&output.stdout) else {
+            bail!("hdiutil mounted the image but reported no volume");
+        };
+
+        let drive_link = dosdevices.join(format!("{letter}:"));
+        let mounted_image = Self {
+            runner,
+            mount_point,
+            drive_link,
+            letter,
+        };
+        unix::fs::symlink(
+            &mounted_image.mount_point,
+            &mounted_image.drive_link,
+        )
+        .context("Failed to link the disc into the prefix")?;
+        Ok(mounted_image)
+    }
+
+    pub fn mount_point(&self) -> &Path {
+        &self.mount_point
+    }
+
+    /// The Windows path of `path` on the mounted disc, e.g., `E:\setup.exe`.
+    pub fn windows_path(&self, path: &Path) -> String {
+        let relative = path.strip_prefix(&self.mount_point).unwrap_or(path);
+        format!(
+            "{}:\\{}",
+            self.letter.to_ascii_uppercase(),
+            relative.to_string_lossy().replace('/', "\\")
+        )
+    }
+
+    /// The installers on the disc, starting with the one its `autorun.inf`
+    /// opens, then the other executables at its top level.
+    pub fn installers(&self) -> Vec<PathBuf> {
+        let mut installers = vec![];
+        if let Some(autorun) = self.autorun_program() {
+            installers.push(autorun);
+        }
+        let mut top_level = fs::read_dir(&self.mount_point)
+            .into_iter()
+            .flatten()
+            .flatten()
+            .map(|entry| entry.path())
+            .filter(|path| {
+                path.extension().is_some_and(|extension| {
+                    extension.eq_ignore_ascii_case("exe")
+                        || extension.eq_ignore_ascii_case("msi")
+                }) && !installers.contains(path)
+            })
+            .collect::<Vec<_>>();
+        top_level.sort();
+        installers.extend(top_level);
+        installers
+    }
+
+    /// The program on the disc that `autorun.inf`'s `open=` names, if any.
+    fn autorun_program(&self) -> Option<PathBuf> {
+        let autorun =
+            fs::read_dir(&self.mount_point)
+                .ok()?
+                .flatten()
+                .find(|entry| {
+                    entry.file_name().eq_ignore_ascii_case("autorun.inf")
+                })?;
+        let contents = String::from_utf8_lossy(&fs::read(autorun.path()).ok()?)
+            .into_owned();
+        let open = contents.lines().find_map(|line| {
+            let (key, value) = line.split_once('=')?;
+            key.trim()
+                .eq_ignore_ascii_case("open")
+                .then(|| value.trim())
+        })?;
+        // The program may be followed by its arguments.
+        let program = open.split_whitespace().next()?.replace('\\', "/");
+        let path = self.mount_point.join(program.trim_start_matches('/'));
+        path.is_file().then_some(path)
+    }
+}
+
+impl Drop for MountedImage {
+    fn drop(&mut self) {
+        let _ = fs::remove_file(&self.drive_link);
+        let _ = self.runner.output(
+            Command::new("hdiutil")
+                .args(["detach", "-force"])
+                .arg(&self.mount_point),
+        );
+    }
+}
+
+/// The first mount point in the property list `hdiutil attach -plist`
+/// prints.
+fn parse_mount_point(plist_output: &[u8]) -> Option<PathBuf> {
+    let info = plist::Value::from_reader_xml(plist_output).ok()?;
+    info.as_dictionary()?
+        .get("system-entities")?
+        .as_array()?
+        .iter()
+        .find_map(|entity| {
+            entity.as_dictionary()?.get("mount-point")?.as_string()
+        })
+        .map(PathBuf::from)
+}
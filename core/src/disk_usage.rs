@@ -0,0 +1,58 @@
+// Copyright (C) 2026 Ethan Uppal.
+//
+// This program is free software: you can redistribute it and/or modify it under
+// the terms of the GNU General Public License as published by the Free Software
+// Foundation, version 3 of the License only.
+//
+// This program is distributed in the hope that it will be useful, but WITHOUT
+// ANY WARRANTY; without even the implied warranty of MERCHANTABILITY or FITNESS
+// FOR A PARTICULAR PURPOSE. See the GNU General Public License for more
+// details.
+//
+// You should have received a copy of the GNU General Public License along with
+// this program.  If not, see <https://www.gnu.org/licenses/>.
+
+use std::{fs, path::Path};
+
+/// The total size of the files under `path`, without following symlinks.
+pub fn directory_size(path: &Path) -> u64 {
+    fs::read_dir(path)
+        .map(|read_dir| {
+            read_dir
+                .flatten()
+                .map(|entry| match entry.file_type() {
+                    Ok(file_type) if file_type.is_dir() => {
+                        directory_size(&entry.path())
+                    }
+                    Ok(file_type) if file_type.is_file() => {
+                        entry.metadata().map_or(0, |metadata| metadata.len())
+                    }
+                    _ => 0,
+                })
+                .sum()
+        })
+        .unwrap_or(0)
+}
+
+/// Formats `bytes` like `12.3 MB`.
+pub fn format_size(bytes: u64) -> String {
+    const UNITS: [&str; 5] = ["B", "KB", "MB", "GB", "TB"];
+    let mut size = bytes as f64;
+    let mut unit = 0;
+    while size >= 1000.0 && unit + 1 < UNITS.len() {
+        size /= 1000.0;
+        unit += 1;
+    }
+    if unit == 0 {
+        format!("{bytes} B")
+    } else {
+        format!("{size:.1} {}", UNITS[unit])
+    }
+}
+
+/// Reads the total `du -sk` printed, in kilobytes, formatted like
+/// [`format_size`].
+pub fn summarize_du(output: &str) -> Option<String> {
+    let kilobytes = output.split_whitespace().next()?.parse::<u64>().ok()?;
+    Some(format_size(kilobytes * 1024))
+}
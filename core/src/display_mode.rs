@@ -0,0 +1,189 @@
+// Copyright (C) 2026 Ethan Uppal.
+//
+// This program is free software: you can redistribute it and/or modify it under
+// the terms of the GNU General Public License as published by the Free Software
+// Foundation, version 3 of the License only.
+//
+// This program is distributed in the hope that it will be useful, but WITHOUT
+// ANY WARRANTY; without even the implied warranty of MERCHANTABILITY or FITNESS
+// FOR A PARTICULAR PURPOSE. See the GNU General Public License for more
+// details.
+//
+// You should have received a copy of the GNU General Public License along with
+// this program.  If not, see <https://www.gnu.org/licenses/>.
+
+//! Forces a keg's program into exclusive fullscreen, borderless fullscreen, or
+//! a window, for older games that crash when they take over the display.
+//! Wine is told through the registry, per program when one is configured, and
+//! games on engines with well-known flags are also told through those.
+
+use std::path::Path;
+
+/// Where the registry changes are written in the C drive for `regedit`.
+pub const REGISTRY_FILE: &str = "/windows/temp/kegtui-display-mode.reg";
+
+/// The size of the virtual desktop when none is given.
+pub const DEFAULT_WINDOW_SIZE: &str = "1280x720";
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum DisplayMode {
+    /// The game takes over the display, as Wine does by default.
+    Fullscreen,
+    /// The game covers the display without changing its resolution.
+    Borderless,
+    /// The game runs in a Wine virtual desktop, in a normal Mac window.
+    Windowed,
+}
+
+impl DisplayMode {
+    pub const ALL: [DisplayMode; 3] = [
+        DisplayMode::Fullscreen,
+        DisplayMode::Borderless,
+        DisplayMode::Windowed,
+    ];
+
+    pub fn name(self) -> &'static str {
+        match self {
+            DisplayMode::Fullscreen => "fullscreen",
+            DisplayMode::Borderless => "borderless",
+            DisplayMode::Windowed => "windowed",
+        }
+    }
+
+    pub fn description(self) -> &'static str {
+        match self {
+            DisplayMode::Fullscreen => {
+                "Fullscreen: the game takes over the display (Wine's default)"
+            }
+            DisplayMode::Borderless => {
+                "Borderless: fullscreen without changing the display's resolution"
+            }
+            DisplayMode::Windowed => {
+                "Windowed: the game runs in a window, inside a virtual desktop"
+            }
+        }
+    }
+}
+
+/// Game engines whose command-line flags for the display mode are known.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum GameEngine {
+    Unity,
+    Unreal,
+}
+
+impl GameEngine {
+    /// Guesses the engine of the game at `program` from the files beside it.
+    pub fn detect(program: &Path) -> Option<Self> {
+        let directory = program.parent()?;
+        if directory.join("UnityPlayer.dll").is_file() {
+            Some(GameEngine::Unity)
+        } else if directory.ends_with("Binaries/Win64")
+            || directory.ends_with("Binaries/Win32")
+            || directory.join("Engine/Binaries").is_dir()
+        {
+            Some(GameEngine::Unreal)
+        } else {
+            None
+        }
+    }
+
+    pub fn name(self) -> &'static str {
+        match self {
+            GameEngine::Unity => "Unity",
+            GameEngine::Unreal => "Unreal Engine",
+        }
+    }
+
+    fn flags(self, mode: DisplayMode) -> &'static [&'static str] {
+        match (self, mode) {
+            (GameEngine::Unity, DisplayMode::Fullscreen) => {
+                &["-screen-fullscreen", "1"]
+            }
+            (GameEngine::Unity, DisplayMode::Borderless) => {
+                &["-screen-fullscreen", "0", "-popupwindow"]
+            }
+            (GameEngine::Unity, DisplayMode::Windowed) => {
+                &["-screen-fullscreen", "0"]
+            }
+            (GameEngine::Unreal, DisplayMode::Fullscreen) => &["-fullscreen"],
+            (GameEngine::Unreal, DisplayMode::Borderless) => {
+                &["-windowed", "-noborder"]
+            }
+            (GameEngine::Unreal, DisplayMode::Windowed) => &["-windowed"],
+        }
+    }
+}
+
+/// Flags any [`GameEngine`] uses for its display mode.
+const DISPLAY_FLAGS: [&str; 6] = [
+    "-screen-fullscreen",
+    "-popupwindow",
+    "-fullscreen",
+    "-windowed",
+    "-noborder",
+    "-window",
+];
+
+/// Replaces the display flags in `program_flags` with those for `mode`,
+/// keeping every other flag.
+pub fn apply_program_flags(
+    program_flags: &str,
+    engine: Option<GameEngine>,
+    mode: DisplayMode,
+) -> String {
+    let mut flags = vec![];
+    let mut tokens = program_flags.split_whitespace().peekable();
+    while let Some(token) = tokens.next() {
+        if token.eq_ignore_ascii_case("-screen-fullscreen") {
+            tokens.next_if(|value| ["0", "1"].contains(value));
+        } else if !DISPLAY_FLAGS
+            .iter()
+            .any(|flag| flag.eq_ignore_ascii_case(token))
+        {
+            flags.push(token);
+        }
+    }
+    if let Some(engine) = engine {
+        flags.extend(engine.flags(mode));
+    }
+    flags.join(" ")
+}
+
+/// Whether `size` is a window size like `1280x720`.
+pub fn is_window_size(size: &str) -> bool {
+    size.split_once('x').is_some_and(|(width, height)| {
+        width.parse::<u16>().is_ok_and(|width| width > 0)
+            && height.parse::<u16>().is_ok_and(|height| height > 0)
+    })
+}
+
+/// A `.reg` file for `wine regedit` that sets `mode` for `program`, e.g.,
+/// `game.exe`, or for every program if there is none.
+pub fn registry_file(
+    program: Option<&str>,
+    mode: DisplayMode,
+    window_size: &str,
+) -> String {
+    let root = match program {
+        Some(program) => {
+            format!("HKEY_CURRENT_USER\\Software\\Wine\\AppDefaults\\{program}")
+        }
+        None => "HKEY_CURRENT_USER\\Software\\Wine".to_owned(),
+    };
+    // "-" deletes a value, which restores Wine's default.
+    let (desktop, capture_displays) = match mode {
+        DisplayMode::Fullscreen => ("-", "-"),
+        DisplayMode::Borderless => ("-", "\"n\""),
+        DisplayMode::Windowed => ("\"Default\"", "-"),
+    };
+    let mut file = format!(
+        "REGEDIT4\n\n[{root}\\Explorer]\n\"Desktop\"={desktop}\n\n[{root}\\Mac Driver]\n\"CaptureDisplaysForFullscreen\"={capture_displays}\n"
+    );
+    if mode == DisplayMode::Windowed {
+        file.push_str(&format!(
+            "\n[HKEY_CURRENT_USER\\Software\\Wine\\Explorer\\Desktops]\n\"Default\"=\"{window_size}\"\n"
+        ));
+    }
+    file
+}
@@ -0,0 +1,100 @@
+// Copyright (C) 2026 Ethan Uppal.
+//
+// This program is free software: you can redistribute it and/or modify it under
+// the terms of the GNU General Public License as published by the Free Software
+// Foundation, version 3 of the License only.
+//
+// This program is distributed in the hope that it will be useful, but WITHOUT
+// ANY WARRANTY; without even the implied warranty of MERCHANTABILITY or FITNESS
+// FOR A PARTICULAR PURPOSE. See the GNU General Public License for more
+// details.
+//
+// You should have received a copy of the GNU General Public License along with
+// this program.  If not, see <https://www.gnu.org/licenses/>.
+
+//! Copies of forms being edited, kept in the state directory until they're
+//! applied, so a crash or a config that doesn't parse loses nothing.
+
+use std::{
+    fs, io,
+    path::{Path, PathBuf},
+    sync::mpsc::{self, RecvTimeoutError},
+    thread,
+    time::{Duration, SystemTime},
+};
+
+use crate::app_config::app_state_directory;
+
+/// How often the file being edited is checked for changes to save.
+const AUTOSAVE_INTERVAL: Duration = Duration::from_secs(2);
+
+pub struct Draft {
+    path: PathBuf,
+}
+
+impl Draft {
+    /// The draft of `form`, e.g., `config`, for the keg named `keg_name`.
+    pub fn new(keg_name: &str, form: &str) -> Self {
+        let keg_name = keg_name.replace(['/', '\\'], "_");
+        Self {
+            path: app_state_directory()
+                .join("drafts")
+                .join(format!("{keg_name}-{form}.txt")),
+        }
+    }
+
+    /// The saved draft and when it was last saved, if there is one.
+    pub fn saved(&self) -> Option<(String, SystemTime)> {
+        let contents = fs::read_to_string(&self.path).ok()?;
+        let modified = fs::metadata(&self.path)
+            .and_then(|metadata| metadata.modified())
+            .unwrap_or_else(|_| SystemTime::now());
+        Some((contents, modified))
+    }
+
+    fn save(&self, contents: &str) -> io::Result<()> {
+        if let Some(parent) = self.path.parent() {
+            fs::create_dir_all(parent)?;
+        }
+        fs::write(&self.path, contents)
+    }
+
+    pub fn discard(&self) {
+        let _ = fs::remove_file(&self.path);
+    }
+
+    /// Runs `edit` while copying `editor_file` into the draft whenever it
+    /// changes, at most every [`AUTOSAVE_INTERVAL`], then saves it once more
+    /// after `edit` returns.
+    pub fn autosave_while<T>(
+        &self,
+        editor_file: &Path,
+        edit: impl FnOnce() -> T,
+    ) -> T {
+        let (sender, receiver) = mpsc::channel::<()>();
+        thread::scope(|scope| {
+            scope.spawn(move || {
+                let mut last_saved = fs::read_to_string(editor_file).ok();
+                loop {
+                    let is_done = !matches!(
+                        receiver.recv_timeout(AUTOSAVE_INTERVAL),
+                        Err(RecvTimeoutError::Timeout)
+                    );
+                    let contents = fs::read_to_string(editor_file).ok();
+                    if contents != last_saved
+                        && let Some(contents) = &contents
+                    {
+                        let _ = self.save(contents);
+                        last_saved = Some(contents.clone());
+                    }
+                    if is_done {
+                        break;
+                    }
+                }
+            });
+            let result = edit();
+            let _ = sender.send(());
+            result
+        })
+    }
+}
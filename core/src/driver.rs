@@ -0,0 +1,192 @@
+// Copyright (C) 2026 Ethan Uppal.
+//
+// This program is free software: you can redistribute it and/or modify it under
+// the terms of the GNU General Public License as published by the Free Software
+// Foundation, version 3 of the License only.
+//
+// This program is distributed in the hope that it will be useful, but WITHOUT
+// ANY WARRANTY; without even the implied warranty of MERCHANTABILITY or FITNESS
+// FOR A PARTICULAR PURPOSE. See the GNU General Public License for more
+// details.
+//
+// You should have received a copy of the GNU General Public License along with
+// this program.  If not, see <https://www.gnu.org/licenses/>.
+
+//! What the [`crate::app::App`] event loop draws to and reads events from:
+//! the real terminal, or a [`TestBackend`] fed scripted keys, which is what
+//! `kegtui --headless` uses to drive the TUI without a terminal.
+
+use std::{
+    collections::VecDeque,
+    fmt::Write,
+    io::{self, Stdout},
+    time::Duration,
+};
+
+use crossterm::{
+    ExecutableCommand,
+    event::{
+        self, DisableMouseCapture, EnableMouseCapture, Event, KeyCode,
+        KeyEvent, KeyModifiers,
+    },
+    terminal::{
+        EnterAlternateScreen, LeaveAlternateScreen, disable_raw_mode,
+        enable_raw_mode,
+    },
+};
+use ratatui::{
+    DefaultTerminal, Terminal,
+    backend::{Backend, CrosstermBackend, TestBackend},
+};
+
+/// Where the TUI is drawn and where its events come from.
+pub trait Driver {
+    type Backend: Backend;
+
+    fn terminal(&mut self) -> &mut Terminal<Self::Backend>;
+
+    /// The next event, waiting at most `timeout` for one.
+    fn next_event(&mut self, timeout: Duration) -> io::Result<Option<Event>>;
+
+    /// Hands the terminal to an external action, which prints and reads
+    /// stdin itself.
+    fn suspend(&mut self) -> io::Result<()>;
+
+    /// Takes the terminal back after an external action.
+    fn resume(&mut self) -> io::Result<()>;
+
+    /// Whether there are no more events to handle, so the event loop should
+    /// stop once it has drawn the last of them.
+    fn is_finished(&self) -> bool {
+        false
+    }
+}
+
+/// Drives the TUI in the terminal kegtui was started in.
+pub struct TerminalDriver {
+    pub terminal: DefaultTerminal,
+}
+
+impl Driver for TerminalDriver {
+    type Backend = CrosstermBackend<Stdout>;
+
+    fn terminal(&mut self) -> &mut DefaultTerminal {
+        &mut self.terminal
+    }
+
+    fn next_event(&mut self, timeout: Duration) -> io::Result<Option<Event>> {
+        if event::poll(timeout)? {
+            event::read().map(Some)
+        } else {
+            Ok(None)
+        }
+    }
+
+    fn suspend(&mut self) -> io::Result<()> {
+        io::stdout().execute(DisableMouseCapture)?;
+        io::stdout().execute(LeaveAlternateScreen)?;
+        disable_raw_mode()
+    }
+
+    fn resume(&mut self) -> io::Result<()> {
+        io::stdout().execute(EnterAlternateScreen)?;
+        io::stdout().execute(EnableMouseCapture)?;
+        enable_raw_mode()?;
+        self.terminal.clear()
+    }
+}
+
+/// Drives the TUI with scripted events, drawing to an in-memory screen
+/// instead of a terminal.
+pub struct HeadlessDriver {
+    terminal: Terminal<TestBackend>,
+    events: VecDeque<Event>,
+}
+
+impl HeadlessDriver {
+    pub fn new(
+        width: u16,
+        height: u16,
+        events: impl IntoIterator<Item = Event>,
+    ) -> io::Result<Self> {
+        Ok(Self {
+            terminal: Terminal::new(TestBackend::new(width, height))?,
+            events: events.into_iter().collect(),
+        })
+    }
+
+    /// The last frame drawn, one line per row with trailing spaces removed.
+    pub fn screen(&self) -> String {
+        let buffer = self.terminal.backend().buffer();
+        let mut screen = String::new();
+        for y in 0..buffer.area.height {
+            let mut line = String::new();
+            for x in 0..buffer.area.width {
+                line.push_str(buffer[(x, y)].symbol());
+            }
+            let _ = writeln!(screen, "{}", line.trim_end());
+        }
+        screen
+    }
+}
+
+impl Driver for HeadlessDriver {
+    type Backend = TestBackend;
+
+    fn terminal(&mut self) -> &mut Terminal<TestBackend> {
+        &mut self.terminal
+    }
+
+    fn next_event(&mut self, _timeout: Duration) -> io::Result<Option<Event>> {
+        Ok(self.events.pop_front())
+    }
+
+    fn suspend(&mut self) -> io::Result<()> {
+        Err(io::Error::other(
+            "External actions need a terminal, so they can't run headless",
+        ))
+    }
+
+    fn resume(&mut self) -> io::Result<()> {
+        Ok(())
+    }
+
+    fn is_finished(&self) -> bool {
+        self.events.is_empty()
+    }
+}
+
+/// Parses keys written like `jj<Enter>l<Esc>q` into key press events, where
+/// a name in angle brackets is a special key and anything else is typed as
+/// is. Returns the unknown name if there is one.
+pub fn parse_keys(script: &str) -> Result<Vec<Event>, String> {
+    let mut events = vec![];
+    let mut rest = script;
+    while let Some(c) = rest.chars().next() {
+        let code = if c == '<'
+            && let Some(end) = rest.find('>')
+            && end > 1
+        {
+            let name = &rest[1..end];
+            rest = &rest[end + 1..];
+            match name.to_lowercase().as_str() {
+                "enter" | "cr" => KeyCode::Enter,
+                "esc" => KeyCode::Esc,
+                "tab" => KeyCode::Tab,
+                "backspace" | "bs" => KeyCode::Backspace,
+                "space" => KeyCode::Char(' '),
+                "up" => KeyCode::Up,
+                "down" => KeyCode::Down,
+                "left" => KeyCode::Left,
+                "right" => KeyCode::Right,
+                "lt" => KeyCode::Char('<'),
+                _ => return Err(name.to_owned()),
+            }
+        } else {
+            rest = &rest[c.len_utf8()..];
+            KeyCode::Char(c)
+        };
+        events.push(Event::Key(KeyEvent::new(code, KeyModifiers::NONE)));
+    }
+    Ok(events)
+}
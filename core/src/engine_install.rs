@@ -0,0 +1,159 @@
+// Copyright (C) 2026 Ethan Uppal.
+//
+// This program is free software: you can redistribute it and/or modify it under
+// the terms of the GNU General Public License as published by the Free Software
+// Foundation, version 3 of the License only.
+//
+// This program is distributed in the hope that it will be useful, but WITHOUT
+// ANY WARRANTY; without even the implied warranty of MERCHANTABILITY or FITNESS
+// FOR A PARTICULAR PURPOSE. See the GNU General Public License for more
+// details.
+//
+// You should have received a copy of the GNU General Public License along with
+// this program.  If not, see <https://www.gnu.org/licenses/>.
+
+use std::{
+    fs::{self, File},
+    io::{self, Read},
+    os::unix::fs::PermissionsExt,
+    path::{Path, PathBuf},
+};
+
+use color_eyre::{
+    Result,
+    eyre::{Context, bail},
+};
+use tar::Archive;
+use walkdir::WalkDir;
+use xz2::read::XzDecoder;
+
+use crate::{journal, keg::Keg, snapshots, tasks::KegLock};
+
+/// Engines are unpacked here first so a failed unpack leaves the keg's
+/// current engine in place.
+const UNPACK_DIRECTORY: &str = ".kegtui-engine";
+
+/// The folder engine tarballs unpack to.
+const ENGINE_BUNDLE: &str = "wswine.bundle";
+
+/// Reports how much of the file it reads from has been read so far.
+struct ProgressReader<R, F> {
+    inner: R,
+    read: u64,
+    total: u64,
+    on_progress: F,
+}
+
+impl<R: Read, F: FnMut(u64, u64)> Read for ProgressReader<R, F> {
+    fn read(&mut self, buf: &mut [u8]) -> io::Result<usize> {
+        let count = self.inner.read(buf)?;
+        self.read += count as u64;
+        (self.on_progress)(self.read, self.total);
+        Ok(count)
+    }
+}
+
+/// Unpacks the engine tarball at `engine` into the keg at `bundle`, replacing
+/// any engine it already has, and returns the new `wine` folder.
+pub fn install_engine(engine: &Path, bundle: &Path) -> Result<PathBuf> {
+    install_engine_with_progress(engine, bundle, |_, _| {})
+}
+
+/// Like [`install_engine`], but calls `on_progress` with how many bytes of
+/// the compressed engine have been unpacked and its size as it goes.
+pub fn install_engine_with_progress(
+    engine: &Path,
+    bundle: &Path,
+    on_progress: impl FnMut(u64, u64),
+) -> Result<PathBuf> {
+    let shared_support = bundle.join("Contents/SharedSupport");
+    let unpack_directory = shared_support.join(UNPACK_DIRECTORY);
+    if unpack_directory.exists() {
+        fs::remove_dir_all(&unpack_directory)?;
+    }
+    fs::create_dir_all(&unpack_directory)
+        .context("Failed to create directory in keg to place engine")?;
+
+    let engine_xz = File::open(engine).context("Failed to open engine")?;
+    let total = engine_xz.metadata().map_or(0, |metadata| metadata.len());
+    let engine_xz = ProgressReader {
+        inner: engine_xz,
+        read: 0,
+        total,
+        on_progress,
+    };
+    Archive::new(XzDecoder::new(engine_xz))
+        .unpack(&unpack_directory)
+        .context("Failed to unpack engine")?;
+    let unpacked_folder = unpack_directory.join(ENGINE_BUNDLE);
+    if !unpacked_folder.is_dir() {
+        bail!("{} has no {ENGINE_BUNDLE}", engine.display());
+    }
+
+    let wine_folder = shared_support.join("wine");
+    if wine_folder.exists() {
+        fs::remove_dir_all(&wine_folder)
+            .context("Failed to remove the previous engine")?;
+    }
+    fs::rename(unpacked_folder, &wine_folder)?;
+    fs::remove_dir_all(&unpack_directory)?;
+
+    let permissions = fs::Permissions::from_mode(0o777);
+    for entry in WalkDir::new(&wine_folder).into_iter().flatten() {
+        if entry.file_type().is_file() {
+            fs::set_permissions(entry.path(), permissions.clone())?;
+            let _ = xattrs::remove_xattr(entry.path(), "com.apple.quarantine");
+        }
+    }
+    fs::set_permissions(&wine_folder, permissions)?;
+    Ok(wine_folder)
+}
+
+/// How upgrading one keg went.
+pub enum UpgradeOutcome {
+    Upgraded {
+        previous_version: Option<String>,
+        snapshot: PathBuf,
+    },
+    Skipped(&'static str),
+    Failed(String),
+}
+
+/// Snapshots `keg`'s current engine and replaces it with `engine`, unless a
+/// task is already changing the keg.
+pub fn upgrade_keg(keg: &Keg, engine: &Path) -> UpgradeOutcome {
+    let bundle = keg.bundle_path();
+    let Some(_keg_lock) = KegLock::try_acquire(&bundle) else {
+        return UpgradeOutcome::Skipped("a task is changing it");
+    };
+    let snapshot = match snapshots::snapshot_engine(&keg.name, &bundle) {
+        Ok(snapshot) => snapshot,
+        Err(error) => {
+            return UpgradeOutcome::Failed(format!(
+                "Failed to snapshot the current engine: {error}"
+            ));
+        }
+    };
+    match install_engine(engine, &bundle) {
+        Ok(_) => {
+            let _ = journal::append(
+                &bundle,
+                "engine",
+                &format!(
+                    "{} → {} (snapshot: {})",
+                    keg.engine_version.as_deref().unwrap_or("unknown engine"),
+                    engine.file_name().unwrap_or_default().to_string_lossy(),
+                    snapshot.display()
+                ),
+            );
+            UpgradeOutcome::Upgraded {
+                previous_version: keg.engine_version.clone(),
+                snapshot,
+            }
+        }
+        Err(error) => UpgradeOutcome::Failed(format!(
+            "{error:#} (the previous engine is in {})",
+            snapshot.display()
+        )),
+    }
+}
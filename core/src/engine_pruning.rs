@@ -0,0 +1,106 @@
+// Copyright (C) 2026 Ethan Uppal.
+//
+// This program is free software: you can redistribute it and/or modify it under
+// the terms of the GNU General Public License as published by the Free Software
+// Foundation, version 3 of the License only.
+//
+// This program is distributed in the hope that it will be useful, but WITHOUT
+// ANY WARRANTY; without even the implied warranty of MERCHANTABILITY or FITNESS
+// FOR A PARTICULAR PURPOSE. See the GNU General Public License for more
+// details.
+//
+// You should have received a copy of the GNU General Public License along with
+// this program.  If not, see <https://www.gnu.org/licenses/>.
+
+//! Decides which engine archives can be deleted: those that are neither
+//! among the newest few nor the engine of any keg. While some keg's engine
+//! can't be read, any engine might be its engine, so none are deleted.
+
+use std::{cmp::Reverse, fs, time::SystemTime};
+
+use crate::keg::{Engine, Keg};
+
+/// Why an engine is kept.
+#[derive(Debug, Clone)]
+pub enum KeepReason {
+    /// The kegs, by name, built from the engine.
+    UsedBy(Vec<String>),
+    /// One of the newest engines, kept in case of new kegs.
+    Newest,
+    /// The kegs, by name, whose engine couldn't be read, so it might be this
+    /// one.
+    MaybeUsedBy(Vec<String>),
+}
+
+#[derive(Debug, Clone)]
+pub struct PrunePlan {
+    pub kept: Vec<(Engine, KeepReason)>,
+    /// The engines to delete, with their sizes in bytes.
+    pub removed: Vec<(Engine, u64)>,
+}
+
+impl PrunePlan {
+    /// The bytes freed by deleting every engine in [`PrunePlan::removed`].
+    pub fn reclaimable(&self) -> u64 {
+        self.removed.iter().map(|(_, size)| size).sum()
+    }
+}
+
+/// The names of the kegs in `kegs` built from `engine`.
+pub fn users(engine: &Engine, kegs: &[Keg]) -> Vec<String> {
+    kegs.iter()
+        .filter(|keg| {
+            keg.engine_version.is_some() && keg.engine_version == engine.name()
+        })
+        .map(|keg| keg.name.clone())
+        .collect()
+}
+
+/// The names of the kegs in `kegs` whose engine couldn't be read, e.g.,
+/// because the version file is missing.
+pub fn unknown_engine_users(kegs: &[Keg]) -> Vec<String> {
+    kegs.iter()
+        .filter(|keg| keg.engine_version.is_none())
+        .map(|keg| keg.name.clone())
+        .collect()
+}
+
+/// Plans which of `engines` to delete, keeping the `keep_newest` most
+/// recently added and every one that a keg in `kegs` was built from.
+pub fn plan(engines: &[Engine], kegs: &[Keg], keep_newest: usize) -> PrunePlan {
+    let mut engines = engines
+        .iter()
+        .map(|engine| {
+            let metadata = fs::metadata(&engine.path).ok();
+            let added = metadata
+                .as_ref()
+                .and_then(|metadata| metadata.modified().ok())
+                .unwrap_or(SystemTime::UNIX_EPOCH);
+            let size = metadata.map_or(0, |metadata| metadata.len());
+            (engine, added, size)
+        })
+        .collect::<Vec<_>>();
+    engines.sort_by_key(|(_, added, _)| Reverse(*added));
+
+    let unknown_engine_users = unknown_engine_users(kegs);
+    let mut plan = PrunePlan {
+        kept: vec![],
+        removed: vec![],
+    };
+    for (index, (engine, _, size)) in engines.into_iter().enumerate() {
+        let users = users(engine, kegs);
+        if !users.is_empty() {
+            plan.kept.push((engine.clone(), KeepReason::UsedBy(users)));
+        } else if index < keep_newest {
+            plan.kept.push((engine.clone(), KeepReason::Newest));
+        } else if !unknown_engine_users.is_empty() {
+            plan.kept.push((
+                engine.clone(),
+                KeepReason::MaybeUsedBy(unknown_engine_users.clone()),
+            ));
+        } else {
+            plan.removed.push((engine.clone(), size));
+        }
+    }
+    plan
+}
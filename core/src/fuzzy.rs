@@ -0,0 +1,50 @@
+// Copyright (C) 2026 Ethan Uppal.
+//
+// This program is free software: you can redistribute it and/or modify it under
+// the terms of the GNU General Public License as published by the Free Software
+// Foundation, version 3 of the License only.
+//
+// This program is distributed in the hope that it will be useful, but WITHOUT
+// ANY WARRANTY; without even the implied warranty of MERCHANTABILITY or FITNESS
+// FOR A PARTICULAR PURPOSE. See the GNU General Public License for more
+// details.
+//
+// You should have received a copy of the GNU General Public License along with
+// this program.  If not, see <https://www.gnu.org/licenses/>.
+
+//! Fuzzy matching for searches where the user types a few letters of what
+//! they want, e.g., "vcr" for `vcrun2019`.
+
+/// Scores how well `query` matches `text` as a subsequence, ignoring case,
+/// where lower is better, or `None` if it doesn't match. Letters matched far
+/// apart or late in the text cost more.
+pub fn score(query: &str, text: &str) -> Option<usize> {
+    let text = text.to_lowercase().chars().collect::<Vec<_>>();
+    let mut score = 0;
+    let mut position = 0;
+    for (i, wanted) in query.to_lowercase().chars().enumerate() {
+        let found = text[position..].iter().position(|&c| c == wanted)?;
+        score += if i == 0 { found } else { found * 2 };
+        position += found + 1;
+    }
+    Some(score)
+}
+
+/// The `items` whose `key` matches `query`, best first, with ties in
+/// alphabetical order ignoring case.
+pub fn rank<'a, T>(
+    query: &str,
+    items: impl IntoIterator<Item = &'a T>,
+    key: impl Fn(&T) -> &str,
+) -> Vec<&'a T> {
+    let mut matches = items
+        .into_iter()
+        .filter_map(|item| Some((score(query, key(item))?, item)))
+        .collect::<Vec<_>>();
+    matches.sort_by(|(a_score, a), (b_score, b)| {
+        a_score
+            .cmp(b_score)
+            .then_with(|| key(*a).to_lowercase().cmp(&key(*b).to_lowercase()))
+    });
+    matches.into_iter().map(|(_, item)| item).collect()
+}
@@ -0,0 +1,102 @@
+// Copyright (C) 2026 Ethan Uppal.
+//
+// This program is free software: you can redistribute it and/or modify it under
+// the terms of the GNU General Public License as published by the Free Software
+// Foundation, version 3 of the License only.
+//
+// This program is distributed in the hope that it will be useful, but WITHOUT
+// ANY WARRANTY; without even the implied warranty of MERCHANTABILITY or FITNESS
+// FOR A PARTICULAR PURPOSE. See the GNU General Public License for more
+// details.
+//
+// You should have received a copy of the GNU General Public License along with
+// this program.  If not, see <https://www.gnu.org/licenses/>.
+
+use std::{process::Command, thread};
+
+use color_eyre::{
+    Result,
+    eyre::{Context, bail},
+};
+
+use crate::runner::CommandRunner;
+
+/// Wine restricts the CPUs a program sees to those listed in this variable,
+/// formatted as `<count>:<cpu>,<cpu>,...`.
+pub const CPU_TOPOLOGY_VARIABLE: &str = "WINE_CPU_TOPOLOGY";
+
+/// A GPU as reported by `system_profiler`.
+#[derive(Debug, Clone, Default)]
+pub struct GpuInfo {
+    pub model: String,
+    /// Dedicated VRAM, absent on Apple Silicon.
+    pub vram: Option<String>,
+    /// GPU core count, only reported on Apple Silicon.
+    pub cores: Option<String>,
+    /// The Metal version the GPU supports, e.g., `Metal 3`.
+    pub metal: Option<String>,
+}
+
+pub fn detect_gpus(runner: &dyn CommandRunner) -> Result<Vec<GpuInfo>> {
+    let output = runner
+        .output(Command::new("system_profiler").arg("SPDisplaysDataType"))
+        .context("Failed to run system_profiler")?;
+    if !output.status.success() {
+        bail!("system_profiler failed ({})", output.status);
+    }
+    Ok(parse_gpus(&String::from_utf8_lossy(&output.stdout)))
+}
+
+fn parse_gpus(output: &str) -> Vec<GpuInfo> {
+    let mut gpus: Vec<GpuInfo> = vec![];
+    for line in output.lines() {
+        let Some((key, value)) = line.trim().split_once(':') else {
+            continue;
+        };
+        let value = value.trim().to_owned();
+        match key {
+            "Chipset Model" => gpus.push(GpuInfo {
+                model: value,
+                ..Default::default()
+            }),
+            "VRAM (Total)" | "VRAM (Dynamic, Max)" => {
+                if let Some(gpu) = gpus.last_mut() {
+                    gpu.vram = Some(value);
+                }
+            }
+            "Total Number of Cores" => {
+                if let Some(gpu) = gpus.last_mut() {
+                    gpu.cores = Some(value);
+                }
+            }
+            // Older macOS calls it the Metal family, e.g., `Supported,
+            // Metal GPUFamily macOS 2`.
+            "Metal Support" | "Metal Family" => {
+                if let Some(gpu) = gpus.last_mut() {
+                    gpu.metal = Some(value);
+                }
+            }
+            _ => {}
+        }
+    }
+    gpus
+}
+
+/// The number of logical CPUs on this Mac.
+pub fn cpu_count() -> usize {
+    thread::available_parallelism().map_or(1, |count| count.get())
+}
+
+/// The [`CPU_TOPOLOGY_VARIABLE`] value exposing the first `count` CPUs.
+pub fn cpu_topology(count: usize) -> String {
+    let cpus = (0..count)
+        .map(|cpu| cpu.to_string())
+        .collect::<Vec<_>>()
+        .join(",");
+    format!("{count}:{cpus}")
+}
+
+/// The CPU count from a [`CPU_TOPOLOGY_VARIABLE`] value.
+pub fn cpu_count_from_topology(topology: &str) -> Option<usize> {
+    topology.split_once(':')?.0.parse().ok()
+}
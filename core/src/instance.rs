@@ -0,0 +1,60 @@
+// Copyright (C) 2026 Ethan Uppal.
+//
+// This program is free software: you can redistribute it and/or modify it under
+// the terms of the GNU General Public License as published by the Free Software
+// Foundation, version 3 of the License only.
+//
+// This program is distributed in the hope that it will be useful, but WITHOUT
+// ANY WARRANTY; without even the implied warranty of MERCHANTABILITY or FITNESS
+// FOR A PARTICULAR PURPOSE. See the GNU General Public License for more
+// details.
+//
+// You should have received a copy of the GNU General Public License along with
+// this program.  If not, see <https://www.gnu.org/licenses/>.
+
+//! Notices other kegtui processes using the same config, whether in a
+//! terminal or embedded in the GUI. Changes to kegs are kept apart by
+//! [`crate::tasks::KegLock`]; this only lets the user know why one of theirs
+//! was refused.
+
+use std::{
+    fs::{File, OpenOptions},
+    io::{self, Read, Seek, SeekFrom, Write},
+    process,
+};
+
+use crate::app_config::app_config_file_path;
+
+/// Held for as long as kegtui runs, by whichever instance started first.
+pub struct InstanceLock {
+    _file: File,
+}
+
+pub enum Instance {
+    /// No other kegtui is running with this config.
+    Only(InstanceLock),
+    /// Another kegtui is, with this process ID if it could be read.
+    Another(Option<u32>),
+    /// Not checked, since the TUI is driven by [`crate::run_scripted`].
+    Unchecked,
+}
+
+/// Locks `kegtui.lock` next to the config file, recording this process's ID
+/// in it.
+pub fn acquire() -> io::Result<Instance> {
+    let mut file = OpenOptions::new()
+        .read(true)
+        .write(true)
+        .create(true)
+        .truncate(false)
+        .open(app_config_file_path().with_extension("lock"))?;
+    if file.try_lock().is_err() {
+        let mut contents = String::new();
+        let _ = file.read_to_string(&mut contents);
+        return Ok(Instance::Another(contents.trim().parse().ok()));
+    }
+    file.set_len(0)?;
+    file.seek(SeekFrom::Start(0))?;
+    write!(file, "{}", process::id())?;
+    Ok(Instance::Only(InstanceLock { _file: file }))
+}
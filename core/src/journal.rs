@@ -0,0 +1,120 @@
+// Copyright (C) 2026 Ethan Uppal.
+//
+// This program is free software: you can redistribute it and/or modify it under
+// the terms of the GNU General Public License as published by the Free Software
+// Foundation, version 3 of the License only.
+//
+// This program is distributed in the hope that it will be useful, but WITHOUT
+// ANY WARRANTY; without even the implied warranty of MERCHANTABILITY or FITNESS
+// FOR A PARTICULAR PURPOSE. See the GNU General Public License for more
+// details.
+//
+// You should have received a copy of the GNU General Public License along with
+// this program.  If not, see <https://www.gnu.org/licenses/>.
+
+//! An append-only record of every change kegtui makes to a keg, and of each
+//! time it launches it, kept inside the keg so it travels with it.
+
+use std::{
+    collections::BTreeSet,
+    fs::{self, OpenOptions},
+    io::{self, Write},
+    path::{Path, PathBuf},
+    time::{Duration, SystemTime},
+};
+
+use plist::Value;
+
+/// Lives outside `Contents/Logs` so maintenance never rotates it away.
+const JOURNAL_FILE: &str = "Contents/kegtui-journal.tsv";
+
+#[derive(Debug, Clone)]
+pub struct JournalEntry {
+    pub time: SystemTime,
+    /// What changed, e.g., `winetricks`, `config`, or `engine`.
+    pub kind: String,
+    pub detail: String,
+}
+
+pub fn journal_path(bundle: &Path) -> PathBuf {
+    bundle.join(JOURNAL_FILE)
+}
+
+/// Records a change to the keg at `bundle`. Each entry is one line of
+/// `<unix time>\t<kind>\t<detail>`.
+pub fn append(bundle: &Path, kind: &str, detail: &str) -> io::Result<()> {
+    let timestamp = SystemTime::now()
+        .duration_since(SystemTime::UNIX_EPOCH)
+        .unwrap_or_default()
+        .as_secs();
+    let detail = detail.replace(['\t', '\n'], " ");
+    let mut file = OpenOptions::new()
+        .create(true)
+        .append(true)
+        .open(journal_path(bundle))?;
+    writeln!(file, "{timestamp}\t{kind}\t{detail}")
+}
+
+/// The journal of the keg at `bundle`, newest first.
+pub fn read(bundle: &Path) -> Vec<JournalEntry> {
+    let contents = fs::read_to_string(journal_path(bundle)).unwrap_or_default();
+    let mut entries = contents
+        .lines()
+        .filter_map(|line| {
+            let mut fields = line.splitn(3, '\t');
+            let timestamp = fields.next()?.parse().ok()?;
+            Some(JournalEntry {
+                time: SystemTime::UNIX_EPOCH + Duration::from_secs(timestamp),
+                kind: fields.next()?.to_owned(),
+                detail: fields.next().unwrap_or_default().to_owned(),
+            })
+        })
+        .collect::<Vec<_>>();
+    entries.reverse();
+    entries
+}
+
+fn describe_value(value: Option<&Value>) -> String {
+    match value {
+        None => "(unset)".to_owned(),
+        Some(Value::String(string)) => format!("\"{string}\""),
+        Some(Value::Boolean(boolean)) => boolean.to_string(),
+        Some(Value::Integer(integer)) => integer.to_string(),
+        Some(Value::Real(real)) => real.to_string(),
+        Some(Value::Dictionary(dictionary)) => {
+            let entries = dictionary
+                .iter()
+                .map(|(key, value)| {
+                    format!("{key} = {}", describe_value(Some(value)))
+                })
+                .collect::<Vec<_>>();
+            format!("{{{}}}", entries.join(", "))
+        }
+        Some(Value::Array(array)) => format!("[{} items]", array.len()),
+        Some(_) => "(data)".to_owned(),
+    }
+}
+
+/// Describes each key that differs between two Info.plists, e.g.,
+/// `DXMT: 0 → 1`.
+pub fn describe_plist_changes(before: &Value, after: &Value) -> Vec<String> {
+    let (Some(before), Some(after)) =
+        (before.as_dictionary(), after.as_dictionary())
+    else {
+        return vec![];
+    };
+    before
+        .keys()
+        .chain(after.keys())
+        .collect::<BTreeSet<_>>()
+        .into_iter()
+        .filter(|key| before.get(key) != after.get(key))
+        .map(|key| {
+            format!(
+                "{key}: {} → {}",
+                describe_value(before.get(key)),
+                describe_value(after.get(key))
+            )
+        })
+        .collect()
+}
@@ -13,11 +13,93 @@
 // this program.  If not, see <https://www.gnu.org/licenses/>.
 
 use std::{
+    collections::BTreeMap,
+    env,
     ffi::OsString,
+    fs,
     path::{Path, PathBuf},
+    time::SystemTime,
 };
 
-use crate::keg_plist::KegPlist;
+use walkdir::WalkDir;
+
+use crate::{
+    arch::{self, EngineArch, PrefixArch},
+    crash_dumps::{CrashDump, find_crash_dumps},
+    journal,
+    keg_plist::{self, KegPlist},
+    plist_validation::{self, SavePlistError},
+    wine_path::WinePath,
+    winetricks_cache,
+};
+
+/// The kind of journal entry recorded each time kegtui launches a keg, from
+/// which [`CurrentKeg::last_launch`] is read back.
+pub const LAST_LAUNCH_KIND: &str = "launch";
+
+/// Variables winetricks inherits from kegtui's own environment. Everything
+/// else is set by [`CurrentKeg::winetricks_environment`].
+const INHERITED_WINETRICKS_VARIABLES: [&str; 6] =
+    ["HOME", "USER", "LOGNAME", "TMPDIR", "LANG", "TERM"];
+
+/// Only system directories go on winetricks' `PATH` after the keg's Wine, so
+/// it can't pick up, e.g., a Homebrew Wine.
+const WINETRICKS_SYSTEM_PATH: &str = "/usr/bin:/bin:/usr/sbin:/sbin";
+
+/// Components a wrapper template may bundle, as their display name and the
+/// file that marks them.
+const WRAPPER_COMPONENTS: [(&str, &str); 5] = [
+    ("MoltenVK", "libMoltenVK.dylib"),
+    ("GStreamer", "GStreamer.framework"),
+    ("D3DMetal", "D3DMetal.framework"),
+    ("Wine Mono", "mono"),
+    ("winetricks", "winetricks"),
+];
+
+/// How deep into a wrapper's `Contents` to look for [`WRAPPER_COMPONENTS`].
+const WRAPPER_SEARCH_DEPTH: usize = 4;
+
+/// Which wrapper a keg was built from. Sikarugir is a fork of Kegworks that
+/// renames the wrapper's executable but otherwise accepts the same `WSS-`
+/// verbs.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum WrapperFlavor {
+    Kegworks,
+    Sikarugir,
+}
+
+impl WrapperFlavor {
+    /// Identifies the wrapper bundle at `path`, or `None` if it isn't one.
+    pub fn detect(path: &Path) -> Option<Self> {
+        if path.join("Contents/MacOS/Sikarugir").exists() {
+            Some(WrapperFlavor::Sikarugir)
+        } else if path.join("Contents/KegworksConfig.app").exists() {
+            Some(WrapperFlavor::Kegworks)
+        } else {
+            None
+        }
+    }
+
+    pub fn name(self) -> &'static str {
+        match self {
+            WrapperFlavor::Kegworks => "Kegworks",
+            WrapperFlavor::Sikarugir => "Sikarugir",
+        }
+    }
+
+    /// The executable in the bundle at `path` that takes `WSS-` verbs.
+    pub fn launcher(self, path: &Path) -> PathBuf {
+        let wineskin_launcher = path.join("Contents/MacOS/wineskinLauncher");
+        match self {
+            WrapperFlavor::Kegworks => wineskin_launcher,
+            // Older Sikarugir wrappers still ship the Kegworks launcher.
+            WrapperFlavor::Sikarugir if wineskin_launcher.exists() => {
+                wineskin_launcher
+            }
+            WrapperFlavor::Sikarugir => path.join("Contents/MacOS/Sikarugir"),
+        }
+    }
+}
 
 #[derive(Debug, Clone)]
 pub struct Keg {
@@ -25,11 +107,52 @@ pub struct Keg {
     /// The parent folder of the keg `.app` used only for display purposes.
     pub enclosing_location: PathBuf,
     pub config_file: PathBuf,
+    pub flavor: WrapperFlavor,
     pub wineskin_launcher: OsString,
     pub c_drive: PathBuf,
     pub log_directory: PathBuf,
     pub winetricks_logfile: PathBuf,
     pub wine_prefix: PathBuf,
+    /// The name of the engine unpacked into this keg, e.g.,
+    /// `WS12WineSikarugir10.0_4`.
+    pub engine_version: Option<String>,
+}
+
+impl Keg {
+    /// The keg's `.app` bundle.
+    pub fn bundle_path(&self) -> PathBuf {
+        self.enclosing_location.join(&self.name)
+    }
+
+    /// The keg's embedded `wine` executable.
+    pub fn wine_executable(&self) -> PathBuf {
+        self.wine_prefix.join("wine")
+    }
+
+    /// Directories where the wrapper and Wine write logs.
+    pub fn log_directories(&self) -> Vec<&Path> {
+        let mut log_directories = vec![self.log_directory.as_path()];
+        if let Some(shared_support_logs) = self.winetricks_logfile.parent() {
+            log_directories.push(shared_support_logs);
+        }
+        log_directories
+    }
+
+    /// Minidumps and backtraces in the keg's prefix and logs, newest first.
+    /// This walks the prefix, so it's kept off the UI thread.
+    pub fn find_crash_dumps(&self) -> Vec<CrashDump> {
+        find_crash_dumps(&self.c_drive, &self.log_directories())
+    }
+}
+
+/// What a keg's `wine --version` printed, e.g., `wine-7.7 (CrossOver FOSS
+/// 22.1.1)`.
+#[derive(Debug, Clone)]
+pub struct WineVersion {
+    pub version: String,
+    /// When the `wine` executable was last modified, so the version is read
+    /// again after an engine upgrade.
+    pub modified: SystemTime,
 }
 
 #[derive(Debug, Clone)]
@@ -37,24 +160,123 @@ pub struct Engine {
     pub path: PathBuf,
 }
 
+impl Engine {
+    /// The engine's name as kegs record it, which is its archive's name
+    /// without the extension, e.g., `WS12WineSikarugir10.0_4`.
+    pub fn name(&self) -> Option<String> {
+        let file_name = self.path.file_name()?.to_string_lossy();
+        Some(
+            file_name
+                .trim_end_matches(".tar.xz")
+                .trim_end_matches(".tar.7z")
+                .to_owned(),
+        )
+    }
+
+    pub fn arch(&self) -> Option<EngineArch> {
+        EngineArch::from_engine_name(&self.path.file_name()?.to_string_lossy())
+    }
+
+    /// The Wine version at the end of the engine's name, e.g., `10.0_4` for
+    /// `WS12WineSikarugir10.0_4`.
+    pub fn version(&self) -> Option<String> {
+        let name = self.name()?;
+        let start = name
+            .rfind(|c: char| !(c.is_ascii_digit() || c == '.' || c == '_'))
+            .map_or(0, |index| index + 1);
+        let version = name[start..].trim_matches(['.', '_']);
+        (!version.is_empty() && start > 0).then(|| version.to_owned())
+    }
+}
+
+fn read_engine_version(path: &Path) -> Option<String> {
+    fs::read_to_string(path.join("Contents/SharedSupport/wine/version"))
+        .ok()
+        .map(|version| version.trim().to_owned())
+        .filter(|version| !version.is_empty())
+}
+
 #[derive(Debug, Clone)]
 pub struct Wrapper {
     pub path: PathBuf,
+    pub flavor: Option<WrapperFlavor>,
+    /// The template's `CFBundleShortVersionString`, or `CFBundleVersion` if
+    /// it has none.
+    pub version: Option<String>,
+    /// The names of the [`WRAPPER_COMPONENTS`] the template bundles.
+    pub components: Vec<&'static str>,
+}
+
+impl Wrapper {
+    pub fn from_path(path: &Path) -> Self {
+        Self {
+            path: path.to_path_buf(),
+            flavor: WrapperFlavor::detect(path),
+            version: read_wrapper_version(path),
+            components: find_wrapper_components(path),
+        }
+    }
+
+    /// Describes the template, e.g., `Kegworks 1.0.8, with MoltenVK,
+    /// GStreamer`.
+    pub fn summary(&self) -> String {
+        let mut summary = self
+            .flavor
+            .map_or("Unknown wrapper", WrapperFlavor::name)
+            .to_owned();
+        if let Some(version) = &self.version {
+            summary.push(' ');
+            summary.push_str(version);
+        }
+        if !self.components.is_empty() {
+            summary.push_str(", with ");
+            summary.push_str(&self.components.join(", "));
+        }
+        summary
+    }
+}
+
+fn read_wrapper_version(path: &Path) -> Option<String> {
+    let info =
+        plist::Value::from_file(path.join("Contents/Info.plist")).ok()?;
+    let info = info.as_dictionary()?;
+    ["CFBundleShortVersionString", "CFBundleVersion"]
+        .into_iter()
+        .find_map(|key| info.get(key)?.as_string())
+        .map(|version| version.trim().to_owned())
+        .filter(|version| !version.is_empty())
+}
+
+fn find_wrapper_components(path: &Path) -> Vec<&'static str> {
+    let names = WalkDir::new(path.join("Contents"))
+        .max_depth(WRAPPER_SEARCH_DEPTH)
+        .into_iter()
+        .flatten()
+        .filter_map(|entry| entry.file_name().to_str().map(str::to_owned))
+        .collect::<Vec<_>>();
+    WRAPPER_COMPONENTS
+        .into_iter()
+        .filter(|(_, marker)| names.iter().any(|name| name == marker))
+        .map(|(component, _)| component)
+        .collect()
 }
 
 pub struct CurrentKeg {
-    pub name: String,
-    pub wineskin_launcher: OsString,
-    pub c_drive: PathBuf,
+    /// Where the keg's files are, which the methods shared with [`Keg`]
+    /// delegate to.
+    pub keg: Keg,
     pub plist: KegPlist,
-    pub config_file: PathBuf,
-    pub log_directory: PathBuf,
-    pub winetricks_logfile: PathBuf,
-    pub wine_prefix: PathBuf,
+    /// When this keg was last launched from kegtui, if at all.
+    pub last_launch: Option<SystemTime>,
+    /// Keys its `Info.plist` lacks, e.g., from an older wrapper, which were
+    /// read as their defaults.
+    pub missing_plist_keys: Vec<String>,
 }
 
 impl Keg {
     pub fn from_path(path: &Path) -> Self {
+        let flavor =
+            WrapperFlavor::detect(path).unwrap_or(WrapperFlavor::Kegworks);
         Self {
             name: path
                 .file_name()
@@ -66,16 +288,25 @@ impl Keg {
                 .expect("Missing Keg name")
                 .to_path_buf(),
             config_file: path.join("Contents/Info.plist"),
-            c_drive: path.join("Contents/SharedSupport/prefix/drive_c"),
-            wineskin_launcher: path
-                .join("Contents/MacOS/wineskinLauncher")
-                .into_os_string(),
+            flavor,
+            c_drive: Self::c_drive_in(path),
+            wineskin_launcher: flavor.launcher(path).into_os_string(),
             log_directory: path.join("Contents/Logs"),
             winetricks_logfile: path
                 .join("Contents/SharedSupport/Logs/Winetricks.log"),
             wine_prefix: path.join("Contents/SharedSupport/wine/bin"),
+            engine_version: read_engine_version(path),
         }
     }
+
+    /// The C drive of the keg whose `.app` bundle is `bundle`.
+    pub fn c_drive_in(bundle: &Path) -> PathBuf {
+        bundle.join("Contents/SharedSupport/prefix/drive_c")
+    }
+
+    pub fn engine_arch(&self) -> Option<EngineArch> {
+        EngineArch::from_engine_name(self.engine_version.as_deref()?)
+    }
 }
 
 impl TryFrom<&Keg> for CurrentKeg {
@@ -83,14 +314,230 @@ impl TryFrom<&Keg> for CurrentKeg {
 
     fn try_from(value: &Keg) -> Result<Self, Self::Error> {
         Ok(Self {
-            name: value.name.clone(),
-            wineskin_launcher: value.wineskin_launcher.clone(),
-            c_drive: value.c_drive.clone(),
+            keg: value.clone(),
             plist: plist::from_file(&value.config_file)?,
-            config_file: value.config_file.clone(),
-            log_directory: value.log_directory.clone(),
-            winetricks_logfile: value.winetricks_logfile.clone(),
-            wine_prefix: value.wine_prefix.clone(),
+            last_launch: journal::read(&value.bundle_path())
+                .into_iter()
+                .find(|entry| entry.kind == LAST_LAUNCH_KIND)
+                .map(|entry| entry.time),
+            missing_plist_keys: plist::Value::from_file(&value.config_file)
+                .ok()
+                .and_then(plist::Value::into_dictionary)
+                .map(|info| keg_plist::missing_keys(&info))
+                .unwrap_or_default(),
+        })
+    }
+}
+
+impl CurrentKeg {
+    pub fn engine_arch(&self) -> Option<EngineArch> {
+        self.keg.engine_arch()
+    }
+
+    /// See [`Keg::wine_executable`].
+    pub fn wine_executable(&self) -> PathBuf {
+        self.keg.wine_executable()
+    }
+
+    /// The complete environment winetricks runs with, so nothing from the
+    /// user's shell, such as a stray `WINEPREFIX`, leaks into the keg.
+    pub fn winetricks_environment(&self) -> BTreeMap<String, OsString> {
+        let mut environment = INHERITED_WINETRICKS_VARIABLES
+            .into_iter()
+            .filter_map(|variable| {
+                Some((variable.to_owned(), env::var_os(variable)?))
+            })
+            .collect::<BTreeMap<_, _>>();
+        let mut path = self.keg.wine_prefix.clone().into_os_string();
+        path.push(":");
+        path.push(WINETRICKS_SYSTEM_PATH);
+        environment.insert("PATH".to_owned(), path);
+        if let Some(prefix) = self.keg.c_drive.parent() {
+            environment.insert("WINEPREFIX".to_owned(), prefix.into());
+        }
+        environment
+            .insert("WINE".to_owned(), self.wine_executable().into_os_string());
+        environment.insert(
+            "W_CACHE".to_owned(),
+            winetricks_cache::cache_directory().into_os_string(),
+        );
+        environment.insert(
+            "WINESERVER".to_owned(),
+            self.keg.wine_prefix.join("wineserver").into_os_string(),
+        );
+        if !self.plist.wine_debug.is_empty() {
+            environment.insert(
+                "WINEDEBUG".to_owned(),
+                self.plist.wine_debug.clone().into(),
+            );
+        }
+        environment
+    }
+
+    pub fn prefix_arch(&self) -> Option<PrefixArch> {
+        PrefixArch::detect(self.keg.c_drive.parent()?)
+    }
+
+    /// Explains why the program will likely crash on launch because its
+    /// architecture doesn't fit the engine, if it will.
+    pub fn arch_warning(&self) -> Option<String> {
+        if self.engine_arch() != Some(EngineArch::SixtyFourBitOnly) {
+            return None;
+        }
+        let engine =
+            self.keg.engine_version.as_deref().unwrap_or("this engine");
+        if self.prefix_arch() == Some(PrefixArch::Win32) {
+            return Some(format!(
+                "The prefix is win32, but {engine} only ships 64-bit Wine, so nothing in it can start. Create a new keg with this engine instead."
+            ));
+        }
+        let program_path = self.program_path();
+        arch::is_32_bit_executable(&program_path)
+            .unwrap_or(false)
+            .then(|| {
+                format!(
+                    "{} is a 32-bit executable, but {engine} only ships 64-bit Wine. It may crash immediately unless the engine supports WoW64.",
+                    program_path.display()
+                )
+            })
+    }
+
+    /// The macOS path of the configured program inside the C drive.
+    pub fn program_path(&self) -> PathBuf {
+        WinePath::from_kegworks(&self.plist.program_name_and_path)
+            .to_host(&self.keg.c_drive)
+    }
+
+    /// The macOS path of the folder the program should start in.
+    pub fn working_directory(&self) -> PathBuf {
+        if self.plist.kegtui_working_directory.is_empty() {
+            let program_path = self.program_path();
+            program_path
+                .parent()
+                .map(Path::to_path_buf)
+                .unwrap_or(program_path)
+        } else {
+            WinePath::from_kegworks(&self.plist.kegtui_working_directory)
+                .to_host(&self.keg.c_drive)
+        }
+    }
+
+    /// See [`Keg::bundle_path`].
+    pub fn bundle_path(&self) -> PathBuf {
+        self.keg.bundle_path()
+    }
+
+    /// Writes [`CurrentKeg::plist`] to the keg and journals each setting that
+    /// `action` changed. The journal is best-effort, like the run logs.
+    ///
+    /// Settings that wouldn't work, like two graphics translations at once,
+    /// aren't written: the saved plist is read back instead and the problems
+    /// are returned, except for any it already had.
+    pub fn save_plist(&mut self, action: &str) -> Result<(), SavePlistError> {
+        save_checked_plist(
+            &self.keg.config_file,
+            &self.keg.c_drive,
+            &mut self.plist,
+            action,
+            true,
+        )
+    }
+
+    /// Like [`CurrentKeg::save_plist`], but allows a program that doesn't
+    /// exist yet, e.g., before a manifest's installers have run.
+    pub fn save_plist_before_installing(
+        &mut self,
+        action: &str,
+    ) -> Result<(), SavePlistError> {
+        save_checked_plist(
+            &self.keg.config_file,
+            &self.keg.c_drive,
+            &mut self.plist,
+            action,
+            false,
+        )
+    }
+
+    /// See [`Keg::log_directories`].
+    pub fn log_directories(&self) -> Vec<&Path> {
+        self.keg.log_directories()
+    }
+
+    /// The log the wrapper rewrites each time it launches the keg.
+    pub fn last_run_log(&self) -> PathBuf {
+        self.keg
+            .winetricks_logfile
+            .with_file_name("LastRunWine.log")
+    }
+
+    /// Rereads the keg from disk, e.g., after it was changed outside kegtui,
+    /// keeping what kegtui itself remembers about it.
+    pub fn reload(&mut self) -> Result<(), plist::Error> {
+        let last_launch = self.last_launch;
+        *self = (&Keg::from_path(&self.bundle_path())).try_into()?;
+        self.last_launch = last_launch;
+        Ok(())
+    }
+}
+
+/// Writes `keg_plist` to `config_file`, the config of the keg whose C drive
+/// is `c_drive`, like [`CurrentKeg::save_plist`], for callers without a
+/// [`CurrentKeg`], e.g., queued tasks. `keg_plist` is reset to the saved one
+/// if it would add problems.
+pub fn save_checked_plist(
+    config_file: &Path,
+    c_drive: &Path,
+    keg_plist: &mut KegPlist,
+    action: &str,
+    is_program_required: bool,
+) -> Result<(), SavePlistError> {
+    let before = plist::Value::from_file(config_file).ok();
+    write_checked_plist(config_file, c_drive, keg_plist, is_program_required)?;
+    if let Some(before) = before
+        && let Ok(after) = plist::Value::from_file(config_file)
+    {
+        let bundle = config_file
+            .parent()
+            .and_then(Path::parent)
+            .unwrap_or(config_file);
+        for change in journal::describe_plist_changes(&before, &after) {
+            let _ = journal::append(
+                bundle,
+                "config",
+                &format!("{change} ({action})"),
+            );
+        }
+    }
+    Ok(())
+}
+
+/// Like [`save_checked_plist`], but leaves the keg's journal alone, for
+/// changes that are put back before the user sees them, e.g., a winetricks
+/// run's flags.
+pub fn write_checked_plist(
+    config_file: &Path,
+    c_drive: &Path,
+    keg_plist: &mut KegPlist,
+    is_program_required: bool,
+) -> Result<(), SavePlistError> {
+    let saved = plist::from_file::<_, KegPlist>(config_file).ok();
+    let existing_problems = saved
+        .as_ref()
+        .map(|saved| {
+            plist_validation::problems(saved, c_drive, is_program_required)
         })
+        .unwrap_or_default();
+    let problems =
+        plist_validation::problems(keg_plist, c_drive, is_program_required)
+            .into_iter()
+            .filter(|problem| !existing_problems.contains(problem))
+            .collect::<Vec<_>>();
+    if !problems.is_empty() {
+        if let Some(saved) = saved {
+            *keg_plist = saved;
+        }
+        return Err(SavePlistError::Invalid(problems));
     }
+    keg_plist.write_over(config_file)?;
+    Ok(())
 }
@@ -12,9 +12,274 @@
 // You should have received a copy of the GNU General Public License along with
 // this program.  If not, see <https://www.gnu.org/licenses/>.
 
+use std::collections::BTreeMap;
+
 use serde::{Deserialize, Serialize};
 
-use crate::keg_plist::KegPlist;
+use crate::{
+    app_config::NewKegDefaults,
+    keg_plist::{KegPlist, LaunchMode},
+};
+
+/// Explains one key of [`KegworksConfig`] as it appears in the config editor.
+pub struct ConfigFieldInfo {
+    /// The dotted path to the key or table, e.g., `translation.dxmt`.
+    pub path: &'static str,
+    pub description: &'static str,
+}
+
+impl ConfigFieldInfo {
+    const fn new(path: &'static str, description: &'static str) -> Self {
+        Self { path, description }
+    }
+}
+
+/// Every key and table of [`KegworksConfig`], in the order the editor shows
+/// them.
+pub const CONFIG_FIELDS: &[ConfigFieldInfo] = &[
+    ConfigFieldInfo::new(
+        "gamma_correction",
+        "Display gamma while the program runs, e.g., \"1.0\"; leave as default unless colors look washed out",
+    ),
+    ConfigFieldInfo::new(
+        "program_flags",
+        "Command-line arguments passed to the program",
+    ),
+    ConfigFieldInfo::new(
+        "program_path",
+        "The program to launch, relative to drive_c",
+    ),
+    ConfigFieldInfo::new(
+        "translation",
+        "Which layer turns DirectX into Metal; enable one of d3d_metal, dxvk, or dxmt",
+    ),
+    ConfigFieldInfo::new(
+        "translation.d3d_metal",
+        "Apple's D3DMetal (from the Game Porting Toolkit): best for DirectX 11/12 games",
+    ),
+    ConfigFieldInfo::new(
+        "translation.dxvk",
+        "DXVK turns DirectX 9-11 into Vulkan, run on Metal by MoltenVK; try it if D3DMetal misrenders",
+    ),
+    ConfigFieldInfo::new(
+        "translation.dxmt",
+        "DXMT translates DirectX 10/11 straight to Metal; often faster than DXVK for older games",
+    ),
+    ConfigFieldInfo::new(
+        "translation.molten_vkcx",
+        "Use MoltenVK-CX, CodeWeavers' MoltenVK fork, for DXVK and Vulkan games",
+    ),
+    ConfigFieldInfo::new(
+        "translation.fast_math",
+        "Let Metal use faster, less precise shader math; turn off if geometry or lighting glitches",
+    ),
+    ConfigFieldInfo::new(
+        "translation.advertise_avx",
+        "Tell programs the CPU supports AVX, which Rosetta 2 emulates on newer macOS; some games require it",
+    ),
+    ConfigFieldInfo::new(
+        "translation.metal_hud",
+        "Show Apple's Metal performance HUD (FPS, GPU time) over the program",
+    ),
+    ConfigFieldInfo::new("wine", "How Wine itself runs"),
+    ConfigFieldInfo::new(
+        "wine.wine_esync",
+        "Eventfd-based synchronization; speeds up many games, but prefer msync on macOS",
+    ),
+    ConfigFieldInfo::new(
+        "wine.wine_msync",
+        "Mach-port synchronization for macOS; usually the fastest choice. Don't combine with esync",
+    ),
+    ConfigFieldInfo::new(
+        "wine.wine_debug",
+        "WINEDEBUG channels, e.g., \"-all\" to silence logs or \"+loaddll\" to trace DLLs",
+    ),
+    ConfigFieldInfo::new(
+        "wine.use_start_exe",
+        "Launch through start.exe, which some launchers need to find their working directory",
+    ),
+    ConfigFieldInfo::new(
+        "keyboard",
+        "How the Mac's modifier keys reach Windows programs",
+    ),
+    ConfigFieldInfo::new(
+        "keyboard.map_option_to_alt",
+        "Both Option keys act as Alt instead of typing special characters",
+    ),
+    ConfigFieldInfo::new(
+        "keyboard.map_command_to_ctrl",
+        "Both Command keys act as Ctrl, e.g., Command-C copies in Windows programs",
+    ),
+    ConfigFieldInfo::new(
+        "winetricks",
+        "Defaults for installing winetricks verbs",
+    ),
+    ConfigFieldInfo::new(
+        "winetricks.winetricks_disable_logging",
+        "Don't write winetricks output to the keg's log file",
+    ),
+    ConfigFieldInfo::new(
+        "winetricks.winetricks_force",
+        "Reinstall verbs even if winetricks thinks they're installed",
+    ),
+    ConfigFieldInfo::new(
+        "winetricks.winetricks_silent",
+        "Run installers unattended where the verb supports it",
+    ),
+    ConfigFieldInfo::new(
+        "winetricks.skip_gecko",
+        "Don't install Wine Gecko (the HTML engine) when creating the prefix",
+    ),
+    ConfigFieldInfo::new(
+        "winetricks.skip_mono",
+        "Don't install Wine Mono (.NET) when creating the prefix",
+    ),
+    ConfigFieldInfo::new(
+        "folders",
+        "Links from the Windows user folders to the macOS ones",
+    ),
+    ConfigFieldInfo::new(
+        "folders.symlinks_in_user_folder",
+        "Replace Desktop, Documents, etc. in the Windows user folder with the symlinks below",
+    ),
+    ConfigFieldInfo::new(
+        "folders.symlink_desktop",
+        "The macOS folder the Windows Desktop points to",
+    ),
+    ConfigFieldInfo::new(
+        "folders.symlink_downloads",
+        "The macOS folder the Windows Downloads points to",
+    ),
+    ConfigFieldInfo::new(
+        "folders.symlink_documents",
+        "The macOS folder the Windows Documents points to",
+    ),
+    ConfigFieldInfo::new(
+        "folders.symlink_music",
+        "The macOS folder the Windows Music points to",
+    ),
+    ConfigFieldInfo::new(
+        "folders.symlink_pictures",
+        "The macOS folder the Windows Pictures points to",
+    ),
+    ConfigFieldInfo::new(
+        "folders.symlink_videos",
+        "The macOS folder the Windows Videos points to",
+    ),
+    ConfigFieldInfo::new(
+        "folders.symlink_templates",
+        "The macOS folder the Windows Templates points to",
+    ),
+    ConfigFieldInfo::new("debug", "Troubleshooting options"),
+    ConfigFieldInfo::new(
+        "debug.debug_mode",
+        "Write a detailed log of each launch to the keg's Logs folder",
+    ),
+    ConfigFieldInfo::new(
+        "debug.disable_cpus",
+        "Limit the program to a single CPU, for old games that break on multicore machines",
+    ),
+    ConfigFieldInfo::new("launch", "How kegtui starts this keg"),
+    ConfigFieldInfo::new(
+        "launch.mode",
+        "\"direct\" runs the wrapper's executable; \"open\" opens the app like Finder does, for proper Dock icon and focus (ignores the start.exe working directory)",
+    ),
+    ConfigFieldInfo::new(
+        "launch.high_priority",
+        "Run with the highest scheduling priority (taskpolicy) for smoother frame pacing; only with the \"direct\" mode",
+    ),
+    ConfigFieldInfo::new(
+        "launch.disable_app_nap",
+        "Keep macOS from throttling the game with App Nap while it's in the background",
+    ),
+    ConfigFieldInfo::new(
+        "environment",
+        "Extra environment variables for the launcher, e.g., DXVK_HUD = \"fps\"",
+    ),
+    ConfigFieldInfo::new(
+        "commands",
+        "Shell commands listed in the keg menu by name, run in the C drive with WINEPREFIX set, e.g., \"Clear Cache\" = \"rm -rf Game/cache\"",
+    ),
+];
+
+/// Looks up the explanation for the key at `path`, e.g., `wine.wine_msync`.
+pub fn describe_config_field(path: &str) -> Option<&'static ConfigFieldInfo> {
+    CONFIG_FIELDS.iter().find(|field| field.path == path)
+}
+
+/// Finds the first key or table in `config_toml` whose path or explanation
+/// contains `query`, ignoring case, returning its field and 1-based line.
+pub fn find_config_field(
+    config_toml: &str,
+    query: &str,
+) -> Option<(&'static ConfigFieldInfo, usize)> {
+    let query = query.trim().to_lowercase();
+    if query.is_empty() {
+        return None;
+    }
+    let mut table: Option<&str> = None;
+    for (index, line) in config_toml.lines().enumerate() {
+        let trimmed = line.trim();
+        if trimmed.starts_with('#') {
+            continue;
+        }
+        let field = if let Some(name) = trimmed
+            .strip_prefix('[')
+            .and_then(|rest| rest.strip_suffix(']'))
+        {
+            table = Some(name);
+            describe_config_field(name)
+        } else if let Some((key, _)) = trimmed.split_once('=') {
+            let key = key.trim();
+            match table {
+                Some(table) => describe_config_field(&format!("{table}.{key}")),
+                None => describe_config_field(key),
+            }
+        } else {
+            None
+        };
+        if let Some(field) = field
+            && (field.path.to_lowercase().contains(&query)
+                || field.description.to_lowercase().contains(&query))
+        {
+            return Some((field, index + 1));
+        }
+    }
+    None
+}
+
+/// Adds each key's explanation from [`CONFIG_FIELDS`] as a comment above it in
+/// `config_toml`, which should be a serialized [`KegworksConfig`].
+pub fn annotate_config(config_toml: &str) -> String {
+    let mut annotated = String::new();
+    let mut table: Option<&str> = None;
+    for line in config_toml.lines() {
+        let trimmed = line.trim();
+        let field = if let Some(name) = trimmed
+            .strip_prefix('[')
+            .and_then(|rest| rest.strip_suffix(']'))
+        {
+            table = Some(name);
+            describe_config_field(name)
+        } else if let Some((key, _)) = trimmed.split_once('=') {
+            let key = key.trim();
+            match table {
+                Some(table) => describe_config_field(&format!("{table}.{key}")),
+                None => describe_config_field(key),
+            }
+        } else {
+            None
+        };
+        if let Some(field) = field {
+            annotated.push_str("# ");
+            annotated.push_str(field.description);
+            annotated.push('\n');
+        }
+        annotated.push_str(line);
+        annotated.push('\n');
+    }
+    annotated
+}
 
 #[derive(Debug, Clone, Serialize, Deserialize, Default)]
 pub struct TranslationConfig {
@@ -36,6 +301,30 @@ pub struct WineConfig {
     //pub try_to_shutdown_gracefully: bool,
 }
 
+/// The subset of [`WinetricksConfig`] that can be overridden for a single
+/// winetricks run.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub struct WinetricksRunFlags {
+    pub winetricks_disable_logging: bool,
+    pub winetricks_force: bool,
+    pub winetricks_silent: bool,
+}
+
+/// The wrapper's bundle metadata shown by Finder, e.g., in Get Info.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct BundleInfo {
+    /// `CFBundleName`.
+    pub name: String,
+    /// `CFBundleShortVersionString`, the user-facing version.
+    pub short_version: String,
+    /// `CFBundleVersion`, the build number.
+    pub version: String,
+    /// `CFBundleDevelopmentRegion`, e.g., `en`.
+    pub development_region: String,
+    /// `LSMinimumSystemVersion`, e.g., `10.15`.
+    pub minimum_system_version: String,
+}
+
 #[derive(Debug, Clone, Serialize, Deserialize, Default)]
 pub struct WinetricksConfig {
     pub winetricks_disable_logging: bool,
@@ -45,11 +334,15 @@ pub struct WinetricksConfig {
     pub skip_mono: bool,
 }
 
-#[derive(Debug, Clone, Serialize, Deserialize, Default)]
+/// How Wine's Mac driver maps the Mac's modifier keys, applied to the keg's
+/// registry by [`crate::keyboard`].
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize, Default)]
 pub struct KeyboardConfig {
     pub map_option_to_alt: bool,
     pub map_command_to_ctrl: bool,
-    pub use_standard_function_keys: bool,
+    // Whether F1-F12 need fn is a macOS-wide setting, not one Wine can change
+    // per prefix.
+    //pub use_standard_function_keys: bool,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize, Default)]
@@ -72,18 +365,40 @@ pub struct DebugConfig {
     pub disable_cpus: bool,
 }
 
+#[derive(Debug, Clone, Serialize, Deserialize, Default)]
+pub struct LaunchConfig {
+    #[serde(default)]
+    pub mode: LaunchMode,
+    #[serde(default)]
+    pub high_priority: bool,
+    #[serde(default)]
+    pub disable_app_nap: bool,
+}
+
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct KegworksConfig {
     pub translation: TranslationConfig,
     pub wine: WineConfig,
     pub winetricks: WinetricksConfig,
-    //pub keyboard: KeyboardConfig,
+    #[serde(default)]
+    pub keyboard: KeyboardConfig,
     pub folders: FolderMappingConfig,
     pub debug: DebugConfig,
+    #[serde(default)]
+    pub launch: LaunchConfig,
 
     pub gamma_correction: String,
     pub program_flags: String,
     pub program_path: String,
+
+    /// Environment variables set for the launcher, e.g., `DXVK_HUD` or
+    /// `MTL_SHADER_VALIDATION`.
+    #[serde(default)]
+    pub environment: BTreeMap<String, String>,
+
+    /// Shell commands listed in the keg menu, by name.
+    #[serde(default)]
+    pub commands: BTreeMap<String, String>,
 }
 
 impl KegPlist {
@@ -101,6 +416,9 @@ impl KegPlist {
         self.wine_debug = config.wine.wine_debug.clone();
         self.use_start_exe = config.wine.use_start_exe;
 
+        self.kegtui_map_option_to_alt = config.keyboard.map_option_to_alt;
+        self.kegtui_map_command_to_ctrl = config.keyboard.map_command_to_ctrl;
+
         self.winetricks_disable_logging =
             config.winetricks.winetricks_disable_logging;
         self.winetricks_force = config.winetricks.winetricks_force;
@@ -123,6 +441,11 @@ impl KegPlist {
         self.gamma_correction = config.gamma_correction.clone();
         self.program_flags = config.program_flags.clone();
         self.program_name_and_path = config.program_path.clone();
+        self.kegtui_environment = config.environment.clone();
+        self.kegtui_commands = config.commands.clone();
+        self.kegtui_launch_mode = config.launch.mode;
+        self.kegtui_high_priority = config.launch.high_priority;
+        self.kegtui_disable_app_nap = config.launch.disable_app_nap;
     }
 
     // Extract config from plist
@@ -143,6 +466,10 @@ impl KegPlist {
                 wine_debug: self.wine_debug.clone(),
                 use_start_exe: self.use_start_exe,
             },
+            keyboard: KeyboardConfig {
+                map_option_to_alt: self.kegtui_map_option_to_alt,
+                map_command_to_ctrl: self.kegtui_map_command_to_ctrl,
+            },
             winetricks: WinetricksConfig {
                 winetricks_disable_logging: self.winetricks_disable_logging,
                 winetricks_force: self.winetricks_force,
@@ -164,9 +491,81 @@ impl KegPlist {
                 debug_mode: self.debug_mode,
                 disable_cpus: self.disable_cpus,
             },
+            launch: LaunchConfig {
+                mode: self.kegtui_launch_mode,
+                high_priority: self.kegtui_high_priority,
+                disable_app_nap: self.kegtui_disable_app_nap,
+            },
             gamma_correction: self.gamma_correction.clone(),
             program_flags: self.program_flags.clone(),
             program_path: self.program_name_and_path.clone(),
+            environment: self.kegtui_environment.clone(),
+            commands: self.kegtui_commands.clone(),
+        }
+    }
+
+    pub fn winetricks_run_flags(&self) -> WinetricksRunFlags {
+        WinetricksRunFlags {
+            winetricks_disable_logging: self.winetricks_disable_logging,
+            winetricks_force: self.winetricks_force,
+            winetricks_silent: self.winetricks_silent,
+        }
+    }
+
+    pub fn set_winetricks_run_flags(&mut self, flags: WinetricksRunFlags) {
+        self.winetricks_disable_logging = flags.winetricks_disable_logging;
+        self.winetricks_force = flags.winetricks_force;
+        self.winetricks_silent = flags.winetricks_silent;
+    }
+
+    pub fn bundle_info(&self) -> BundleInfo {
+        BundleInfo {
+            name: self.cf_bundle_name.clone(),
+            short_version: self.cf_bundle_short_version_string.clone(),
+            version: self.cf_bundle_version.clone(),
+            development_region: self.cf_bundle_development_region.clone(),
+            minimum_system_version: self.ls_minimum_system_version.clone(),
+        }
+    }
+
+    pub fn set_bundle_info(&mut self, info: BundleInfo) {
+        self.cf_bundle_name = info.name;
+        self.cf_bundle_short_version_string = info.short_version;
+        self.cf_bundle_version = info.version;
+        self.cf_bundle_development_region = info.development_region;
+        self.ls_minimum_system_version = info.minimum_system_version;
+    }
+
+    /// Overrides the fields set in `defaults`, leaving the rest untouched.
+    pub fn apply_new_keg_defaults(&mut self, defaults: &NewKegDefaults) {
+        macro_rules! apply {
+            ($($field:ident => $plist_field:ident),* $(,)?) => {
+                $(
+                    if let Some(value) = &defaults.$field {
+                        self.$plist_field = value.to_owned();
+                    }
+                )*
+            };
+        }
+        apply! {
+            d3d_metal => d3d_metal,
+            dxvk => dxvk,
+            dxmt => dxmt,
+            molten_vkcx => molten_vkcx,
+            fast_math => fast_math,
+            advertise_avx => advertise_avx,
+            metal_hud => metal_hud,
+            wine_esync => wine_esync,
+            wine_msync => wine_msync,
+            wine_debug => wine_debug,
+            symlinks_in_user_folder => symlinks_in_user_folder,
+            symlink_desktop => symlink_desktop,
+            symlink_downloads => symlink_downloads,
+            symlink_documents => symlink_my_documents,
+            symlink_music => symlink_my_music,
+            symlink_pictures => symlink_my_pictures,
+            symlink_videos => symlink_my_videos,
+            symlink_templates => symlink_templates,
         }
     }
 }
@@ -0,0 +1,124 @@
+// Copyright (C) 2026 Ethan Uppal.
+//
+// This program is free software: you can redistribute it and/or modify it under
+// the terms of the GNU General Public License as published by the Free Software
+// Foundation, version 3 of the License only.
+//
+// This program is distributed in the hope that it will be useful, but WITHOUT
+// ANY WARRANTY; without even the implied warranty of MERCHANTABILITY or FITNESS
+// FOR A PARTICULAR PURPOSE. See the GNU General Public License for more
+// details.
+//
+// You should have received a copy of the GNU General Public License along with
+// this program.  If not, see <https://www.gnu.org/licenses/>.
+
+//! The steps of creating a keg from a wrapper template and an engine, shared
+//! by the keg creator and building a keg from a manifest.
+
+use std::{
+    fs,
+    os::unix::fs::PermissionsExt,
+    path::{Path, PathBuf},
+    process::Command,
+};
+
+use color_eyre::{
+    Result,
+    eyre::{Context, bail},
+};
+use copy_dir::copy_dir;
+use walkdir::WalkDir;
+
+use crate::{
+    app_config::NewKegDefaults, keg::WrapperFlavor, keg_name,
+    keg_plist::KegPlist, processes, runner::CommandRunner, snapshots,
+    tasks::KegLock,
+};
+
+/// Backs up the keg at `keg_path` so a new one can take its place, unless
+/// it is running, has `pending_tasks` queued, or something else is changing
+/// it. Returns where the backup went.
+pub fn back_up_replaced_keg(
+    runner: &dyn CommandRunner,
+    pending_tasks: usize,
+    keg_path: &Path,
+) -> Result<PathBuf> {
+    if pending_tasks > 0 {
+        bail!(
+            "{} has queued tasks; wait for them to finish first",
+            keg_path.display()
+        );
+    }
+    let Some(_keg_lock) = KegLock::try_acquire(keg_path) else {
+        bail!("Something else is changing {}", keg_path.display());
+    };
+    let bundles = [keg_path.to_path_buf()];
+    if !processes::by_keg(&processes::list(runner), &bundles).is_empty() {
+        bail!(
+            "{} is running; kill its processes first",
+            keg_path.display()
+        );
+    }
+    snapshots::back_up_bundle(keg_path)
+        .with_context(|| format!("Failed to back up {}", keg_path.display()))
+}
+
+/// Copies the wrapper template at `wrapper` to `keg_path`.
+pub fn copy_template(wrapper: &Path, keg_path: &Path) -> Result<()> {
+    copy_dir(wrapper, keg_path).with_context(|| {
+        format!(
+            "Failed to copy wrapper ({}) to keg path ({})",
+            wrapper.display(),
+            keg_path.display()
+        )
+    })?;
+    Ok(())
+}
+
+/// Lets the keg's files, including the engine unpacked to `wine_folder`, be
+/// run and changed, and clears the quarantine a download leaves on them.
+pub fn open_up_permissions(keg_path: &Path, wine_folder: &Path) -> Result<()> {
+    let permissions = fs::Permissions::from_mode(0o777);
+    for entry in WalkDir::new(keg_path).into_iter().flatten() {
+        if entry.file_type().is_file() {
+            fs::set_permissions(entry.path(), permissions.clone())?;
+            let _ = xattrs::remove_xattr(entry.path(), "com.apple.quarantine");
+        }
+    }
+    fs::set_permissions(wine_folder, permissions)?;
+    let _ = xattrs::remove_xattr(keg_path, "com.apple.quarantine");
+    Ok(())
+}
+
+/// The keg's launcher, which takes `WSS-` verbs.
+pub fn wineskin_launcher(keg_path: &Path) -> PathBuf {
+    WrapperFlavor::detect(keg_path)
+        .unwrap_or(WrapperFlavor::Kegworks)
+        .launcher(keg_path)
+}
+
+/// Creates the keg's Wine prefix when run.
+pub fn prefix_create_command(keg_path: &Path) -> Command {
+    let mut command = Command::new(wineskin_launcher(keg_path));
+    command.arg("WSS-wineprefixcreate");
+    command
+}
+
+/// Applies `defaults` to the new keg and names its bundle identifier after
+/// `name`, which must already be a valid keg name.
+pub fn apply_new_keg_defaults(
+    keg_path: &Path,
+    name: &str,
+    defaults: &NewKegDefaults,
+) -> Result<()> {
+    let config_file = keg_path.join("Contents/Info.plist");
+    let mut plist: KegPlist = plist::from_file(&config_file)
+        .context("Failed to read new keg's Info.plist")?;
+    plist.apply_new_keg_defaults(defaults);
+    if let Some(bundle_identifier) = keg_name::bundle_identifier(name) {
+        plist.cf_bundle_identifier = bundle_identifier;
+    }
+    plist
+        .write_over(&config_file)
+        .context("Failed to write new keg defaults")
+}
@@ -0,0 +1,173 @@
+// Copyright (C) 2026 Ethan Uppal.
+//
+// This program is free software: you can redistribute it and/or modify it under
+// the terms of the GNU General Public License as published by the Free Software
+// Foundation, version 3 of the License only.
+//
+// This program is distributed in the hope that it will be useful, but WITHOUT
+// ANY WARRANTY; without even the implied warranty of MERCHANTABILITY or FITNESS
+// FOR A PARTICULAR PURPOSE. See the GNU General Public License for more
+// details.
+//
+// You should have received a copy of the GNU General Public License along with
+// this program.  If not, see <https://www.gnu.org/licenses/>.
+
+//! Compares two kegs' settings and installed winetricks, to explain why a
+//! program works in one keg but not another.
+
+use std::{
+    collections::{BTreeMap, BTreeSet},
+    fs,
+    path::Path,
+};
+
+use color_eyre::{Result, eyre::Context};
+
+use crate::{arch::PrefixArch, keg::Keg, keg_plist::KegPlist};
+
+/// A setting that differs between the kegs, where `None` means unset.
+pub struct ConfigDifference {
+    pub key: String,
+    pub left: Option<String>,
+    pub right: Option<String>,
+}
+
+pub struct KegComparison {
+    pub config: Vec<ConfigDifference>,
+    /// Winetricks verbs installed in only the left keg.
+    pub only_left: Vec<String>,
+    /// Winetricks verbs installed in only the right keg.
+    pub only_right: Vec<String>,
+    /// How many verbs both kegs have installed.
+    pub shared_winetricks: usize,
+}
+
+/// The verbs winetricks has recorded in the prefix's `winetricks.log`.
+pub fn installed_winetricks(prefix: &Path) -> BTreeSet<String> {
+    fs::read_to_string(prefix.join("winetricks.log"))
+        .unwrap_or_default()
+        .lines()
+        .map(str::trim)
+        .filter(|line| !line.is_empty() && !line.starts_with('-'))
+        .map(str::to_owned)
+        .collect()
+}
+
+/// Flattens `value` into dotted keys, e.g., `translation.dxmt`.
+fn flatten(prefix: &str, value: &toml::Value, settings: &mut Settings) {
+    match value {
+        toml::Value::Table(table) => {
+            for (key, value) in table {
+                let key = if prefix.is_empty() {
+                    key.clone()
+                } else {
+                    format!("{prefix}.{key}")
+                };
+                flatten(&key, value, settings);
+            }
+        }
+        toml::Value::String(string) => {
+            settings.insert(prefix.to_owned(), format!("\"{string}\""));
+        }
+        value => {
+            settings.insert(prefix.to_owned(), value.to_string());
+        }
+    }
+}
+
+type Settings = BTreeMap<String, String>;
+
+fn settings(keg: &Keg) -> Result<Settings> {
+    let plist: KegPlist = plist::from_file(&keg.config_file)
+        .context(format!("Failed to read {}", keg.config_file.display()))?;
+    let mut settings = Settings::new();
+    flatten(
+        "",
+        &toml::Value::try_from(plist.extract_config())?,
+        &mut settings,
+    );
+    settings.insert("wrapper".to_owned(), keg.flavor.name().to_owned());
+    if let Some(engine_version) = &keg.engine_version {
+        settings.insert("engine".to_owned(), engine_version.clone());
+    }
+    if let Some(prefix_arch) = keg.c_drive.parent().and_then(PrefixArch::detect)
+    {
+        settings.insert("prefix".to_owned(), prefix_arch.name().to_owned());
+    }
+    Ok(settings)
+}
+
+pub fn compare(left: &Keg, right: &Keg) -> Result<KegComparison> {
+    let left_settings = settings(left)?;
+    let right_settings = settings(right)?;
+    let config = left_settings
+        .keys()
+        .chain(right_settings.keys())
+        .collect::<BTreeSet<_>>()
+        .into_iter()
+        .filter(|key| left_settings.get(*key) != right_settings.get(*key))
+        .map(|key| ConfigDifference {
+            key: key.clone(),
+            left: left_settings.get(key).cloned(),
+            right: right_settings.get(key).cloned(),
+        })
+        .collect();
+
+    let winetricks_of = |keg: &Keg| {
+        keg.c_drive
+            .parent()
+            .map(installed_winetricks)
+            .unwrap_or_default()
+    };
+    let left_winetricks = winetricks_of(left);
+    let right_winetricks = winetricks_of(right);
+    Ok(KegComparison {
+        config,
+        only_left: left_winetricks
+            .difference(&right_winetricks)
+            .cloned()
+            .collect(),
+        only_right: right_winetricks
+            .difference(&left_winetricks)
+            .cloned()
+            .collect(),
+        shared_winetricks: left_winetricks
+            .intersection(&right_winetricks)
+            .count(),
+    })
+}
+
+/// Lays out `rows` as three columns, wrapping each cell to fit `width`.
+pub fn format_columns(rows: &[[String; 3]], width: usize) -> Vec<String> {
+    let key_width = rows
+        .iter()
+        .map(|row| row[0].chars().count())
+        .max()
+        .unwrap_or(0)
+        .min(width / 3);
+    let value_width = (width.saturating_sub(key_width + 6) / 2).max(8);
+    let mut lines = vec![];
+    for row in rows {
+        let cells = [
+            textwrap::wrap(&row[0], key_width.max(1)),
+            textwrap::wrap(&row[1], value_width),
+            textwrap::wrap(&row[2], value_width),
+        ];
+        let height = cells.iter().map(Vec::len).max().unwrap_or(0);
+        for line in 0..height {
+            let cell = |column: usize| {
+                cells[column]
+                    .get(line)
+                    .map(|cell| cell.to_string())
+                    .unwrap_or_default()
+            };
+            lines.push(format!(
+                "{:<key_width$} │ {:<value_width$} │ {}",
+                cell(0),
+                cell(1),
+                cell(2)
+            ));
+        }
+    }
+    lines
+}
@@ -0,0 +1,193 @@
+// Copyright (C) 2026 Ethan Uppal.
+//
+// This program is free software: you can redistribute it and/or modify it under
+// the terms of the GNU General Public License as published by the Free Software
+// Foundation, version 3 of the License only.
+//
+// This program is distributed in the hope that it will be useful, but WITHOUT
+// ANY WARRANTY; without even the implied warranty of MERCHANTABILITY or FITNESS
+// FOR A PARTICULAR PURPOSE. See the GNU General Public License for more
+// details.
+//
+// You should have received a copy of the GNU General Public License along with
+// this program.  If not, see <https://www.gnu.org/licenses/>.
+
+//! `keg-manifest.toml`, a recipe for rebuilding a keg from its engine, config,
+//! winetricks verbs, and installers, which is far smaller to share than the
+//! keg itself.
+
+use std::{
+    fs,
+    path::{Path, PathBuf},
+};
+
+use color_eyre::{
+    Result,
+    eyre::{Context, bail},
+};
+use serde::{Deserialize, Serialize};
+
+use crate::{
+    keg::{CurrentKeg, Engine},
+    keg_config::KegworksConfig,
+    wine_path::WinePath,
+};
+
+/// The manifest format this kegtui writes and understands.
+pub const MANIFEST_FORMAT: u32 = 1;
+
+/// Explains the manifest to whoever opens an exported one.
+const MANIFEST_HEADER: &str = "\
+# A kegtui keg manifest. To rebuild the keg, create a keg with the engine
+# below, then choose Apply Manifest in its menu.
+#
+# Installers run in order after the winetricks verbs, e.g.:
+#
+# [[step]]
+# description = \"Install the game\"
+# ask = \"Path to the game's setup.exe\"
+#
+# [[step]]
+# description = \"Install Steam\"
+# download = \"https://cdn.akamai.steamstatic.com/client/installer/SteamSetup.exe\"
+#
+# [[step]]
+# description = \"Run the bundled setup\"
+# run = \"/Program Files (x86)/Game/setup.exe\"
+# arguments = [\"/S\"]
+
+";
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "kebab-case")]
+pub struct KegManifest {
+    pub format: u32,
+    /// The name of the keg the manifest was exported from.
+    pub name: String,
+    /// The engine the keg was built with, e.g., `WS12WineSikarugir10.0_4`.
+    pub engine: Option<String>,
+    /// Winetricks verbs in the order they were installed.
+    #[serde(default)]
+    pub winetricks: Vec<String>,
+    pub config: KegworksConfig,
+    #[serde(default, rename = "step")]
+    pub steps: Vec<InstallerStep>,
+}
+
+/// Runs a Windows program in the prefix, downloading it first if it comes
+/// from a URL, or asking for it if only the user has it.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "kebab-case")]
+pub struct InstallerStep {
+    pub description: String,
+    /// Asks for a file on this Mac with this question, e.g., for a game's
+    /// installer that can't be downloaded. Overrides `download` and `run`.
+    pub ask: Option<String>,
+    /// Where to download the program from.
+    pub download: Option<String>,
+    /// A program already in the C drive, e.g., `/Program Files/setup.exe`.
+    /// Ignored if `download` is set.
+    pub run: Option<String>,
+    #[serde(default)]
+    pub arguments: Vec<String>,
+}
+
+impl InstallerStep {
+    /// What the step will run, for confirming before applying.
+    pub fn summary(&self) -> String {
+        let program = match (&self.ask, &self.download, &self.run) {
+            (Some(question), _, _) => {
+                format!("run a file you choose (\"{question}\")")
+            }
+            (None, Some(url), _) => format!("download and run {url}"),
+            (None, None, Some(run)) => {
+                format!("run {}", WinePath::from_kegworks(run))
+            }
+            (None, None, None) => {
+                "nothing (no ask, download, or run given)".to_owned()
+            }
+        };
+        format!("{}: {program}", self.description)
+    }
+}
+
+/// The verbs in the prefix's `winetricks.log`, in the order they were first
+/// installed.
+fn winetricks_in_order(prefix: &Path) -> Vec<String> {
+    let mut verbs = Vec::<String>::new();
+    for line in fs::read_to_string(prefix.join("winetricks.log"))
+        .unwrap_or_default()
+        .lines()
+        .map(str::trim)
+        .filter(|line| !line.is_empty() && !line.starts_with('-'))
+    {
+        if !verbs.iter().any(|verb| verb == line) {
+            verbs.push(line.to_owned());
+        }
+    }
+    verbs
+}
+
+impl KegManifest {
+    /// Whether the manifest is for an engine named like `engine`'s archive,
+    /// e.g., `WS12WineSikarugir10.0_4.tar.xz`.
+    pub fn is_for_engine(&self, engine: &Engine) -> bool {
+        self.engine.is_some() && engine.name() == self.engine
+    }
+
+    pub fn from_keg(keg: &CurrentKeg) -> Self {
+        Self {
+            format: MANIFEST_FORMAT,
+            name: keg.keg.name.trim_end_matches(".app").to_owned(),
+            engine: keg.keg.engine_version.clone(),
+            winetricks: keg
+                .keg
+                .c_drive
+                .parent()
+                .map(winetricks_in_order)
+                .unwrap_or_default(),
+            config: keg.plist.extract_config(),
+            steps: vec![],
+        }
+    }
+
+    pub fn read(path: &Path) -> Result<Self> {
+        let contents = fs::read_to_string(path)
+            .with_context(|| format!("Failed to read {}", path.display()))?;
+        let manifest: Self = toml::from_str(&contents)
+            .with_context(|| format!("Failed to parse {}", path.display()))?;
+        if manifest.format > MANIFEST_FORMAT {
+            bail!(
+                "{} uses manifest format {}, but this kegtui only understands up to {MANIFEST_FORMAT}; try updating kegtui",
+                path.display(),
+                manifest.format
+            );
+        }
+        Ok(manifest)
+    }
+
+    /// Writes the manifest into `directory` as `<name>-keg-manifest.toml`.
+    pub fn export(&self, directory: &Path) -> Result<PathBuf> {
+        let path = directory.join(format!("{}-keg-manifest.toml", self.name));
+        let contents =
+            format!("{MANIFEST_HEADER}{}", toml::to_string_pretty(self)?);
+        fs::write(&path, contents)
+            .with_context(|| format!("Failed to write {}", path.display()))?;
+        Ok(path)
+    }
+
+    /// The winetricks verbs not yet installed in `keg`.
+    pub fn missing_winetricks(&self, keg: &CurrentKeg) -> Vec<String> {
+        let installed = keg
+            .keg
+            .c_drive
+            .parent()
+            .map(winetricks_in_order)
+            .unwrap_or_default();
+        self.winetricks
+            .iter()
+            .filter(|verb| !installed.contains(verb))
+            .cloned()
+            .collect()
+    }
+}
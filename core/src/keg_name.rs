@@ -0,0 +1,114 @@
+// Copyright (C) 2026 Ethan Uppal.
+//
+// This program is free software: you can redistribute it and/or modify it under
+// the terms of the GNU General Public License as published by the Free Software
+// Foundation, version 3 of the License only.
+//
+// This program is distributed in the hope that it will be useful, but WITHOUT
+// ANY WARRANTY; without even the implied warranty of MERCHANTABILITY or FITNESS
+// FOR A PARTICULAR PURPOSE. See the GNU General Public License for more
+// details.
+//
+// You should have received a copy of the GNU General Public License along with
+// this program.  If not, see <https://www.gnu.org/licenses/>.
+
+//! Checks names for new kegs, which become both a bundle path and part of the
+//! bundle identifier.
+
+/// Characters that break bundle paths (`/`), Finder and AppleScript (`:`,
+/// `"`, `\`), or the launcher's shell scripts.
+const FORBIDDEN_CHARACTERS: [char; 8] =
+    ['/', ':', '\\', '"', '`', '$', '*', '?'];
+
+/// Leaves room for `.app` in a file name of at most 255 bytes.
+const MAX_NAME_BYTES: usize = 200;
+
+/// What kegtui prefixes to the name to form each keg's bundle identifier.
+const BUNDLE_IDENTIFIER_PREFIX: &str = "com.kegtui.";
+
+/// Explains what's wrong with `name` as a keg name, if anything.
+pub fn problem(name: &str) -> Option<String> {
+    if name.trim().is_empty() {
+        return Some("The name is empty".to_owned());
+    }
+    if name.starts_with('.') {
+        return Some(
+            "A name starting with \".\" makes the keg hidden".to_owned(),
+        );
+    }
+    if name != name.trim() {
+        return Some("The name starts or ends with a space".to_owned());
+    }
+    if let Some(character) = name
+        .chars()
+        .find(|c| FORBIDDEN_CHARACTERS.contains(c) || c.is_control())
+    {
+        return Some(format!(
+            "The name can't contain {}",
+            if character.is_control() {
+                "control characters".to_owned()
+            } else {
+                format!("\"{character}\"")
+            }
+        ));
+    }
+    if name.len() > MAX_NAME_BYTES {
+        return Some(format!("The name is longer than {MAX_NAME_BYTES} bytes"));
+    }
+    if identifier_component(name).is_empty() {
+        return Some(
+            "The name needs a letter or digit from A-Z or 0-9 to form its bundle identifier"
+                .to_owned(),
+        );
+    }
+    None
+}
+
+/// A version of `name` without any [`problem`].
+pub fn sanitize(name: &str) -> String {
+    let mut sanitized = name
+        .chars()
+        .map(|c| {
+            if FORBIDDEN_CHARACTERS.contains(&c) || c.is_control() {
+                '-'
+            } else {
+                c
+            }
+        })
+        .collect::<String>()
+        .trim()
+        .trim_start_matches(['.', ' '])
+        .to_owned();
+    while sanitized.len() > MAX_NAME_BYTES {
+        sanitized.pop();
+    }
+    let sanitized = sanitized.trim_end().to_owned();
+    if identifier_component(&sanitized).is_empty() {
+        format!("Keg {sanitized}").trim_end().to_owned()
+    } else {
+        sanitized
+    }
+}
+
+/// The part of the bundle identifier taken from `name`, which may only use
+/// ASCII letters, digits, `-`, and `.`.
+fn identifier_component(name: &str) -> String {
+    let component = name
+        .chars()
+        .map(|c| if c.is_ascii_alphanumeric() { c } else { '-' })
+        .collect::<String>();
+    let component = component.trim_matches('-');
+    if component.chars().any(|c| c.is_ascii_alphanumeric()) {
+        component.to_owned()
+    } else {
+        String::new()
+    }
+}
+
+/// The bundle identifier for a keg named `name`, e.g., `com.kegtui.Steam`
+/// for `Steam`, or `None` if `name` has no characters to form one.
+pub fn bundle_identifier(name: &str) -> Option<String> {
+    let component = identifier_component(name);
+    (!component.is_empty())
+        .then(|| format!("{BUNDLE_IDENTIFIER_PREFIX}{component}"))
+}
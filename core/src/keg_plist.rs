@@ -12,6 +12,8 @@
 // You should have received a copy of the GNU General Public License along with
 // this program.  If not, see <https://www.gnu.org/licenses/>.
 
+use std::{collections::BTreeMap, path::Path};
+
 use serde::{Deserialize, Deserializer, Serialize, Serializer};
 
 mod bool_as_int {
@@ -37,7 +39,11 @@ fn default_advertise_avx() -> bool {
     true
 }
 
-#[derive(Debug, Serialize, Deserialize)]
+/// A keg's `Info.plist`. Kegs from older wrappers lack some keys, e.g.,
+/// `DXMT`, so every missing key is read as its default and written on the
+/// next save; [`missing_keys`] lists them.
+#[derive(Debug, Default, Serialize, Deserialize)]
+#[serde(default)]
 pub struct KegPlist {
     #[serde(rename = "ADVERTISE_AVX")]
     #[serde(with = "bool_as_int")]
@@ -240,9 +246,120 @@ pub struct KegPlist {
     #[serde(with = "bool_as_int")]
     #[serde(default)]
     pub d9vk: bool,
+
+    /// Extra environment variables kegtui sets when launching. Kegworks itself
+    /// ignores this key.
+    #[serde(rename = "kegtui Environment")]
+    #[serde(default, skip_serializing_if = "BTreeMap::is_empty")]
+    pub kegtui_environment: BTreeMap<String, String>,
+
+    /// Shell commands offered in the keg menu by name, run with the keg's
+    /// environment. Kegworks itself ignores this key.
+    #[serde(rename = "kegtui Commands")]
+    #[serde(default, skip_serializing_if = "BTreeMap::is_empty")]
+    pub kegtui_commands: BTreeMap<String, String>,
+
+    /// Where kegtui starts the launcher when `use start.exe` is on, relative
+    /// to the C drive. Empty means the program's folder. Kegworks itself
+    /// ignores this key.
+    #[serde(rename = "kegtui Working Directory")]
+    #[serde(default, skip_serializing_if = "String::is_empty")]
+    pub kegtui_working_directory: String,
+
+    /// How kegtui starts the keg. Kegworks itself ignores this key.
+    #[serde(rename = "kegtui Launch Mode")]
+    #[serde(default, skip_serializing_if = "LaunchMode::is_direct")]
+    pub kegtui_launch_mode: LaunchMode,
+
+    /// Launch with the highest throughput and latency QoS tiers. Kegworks
+    /// itself ignores this key.
+    #[serde(rename = "kegtui High Priority")]
+    #[serde(default, skip_serializing_if = "std::ops::Not::not")]
+    pub kegtui_high_priority: bool,
+
+    /// Keep macOS from throttling the keg with App Nap while it's in the
+    /// background. Kegworks itself ignores this key.
+    #[serde(rename = "kegtui Disable App Nap")]
+    #[serde(default, skip_serializing_if = "std::ops::Not::not")]
+    pub kegtui_disable_app_nap: bool,
+
+    /// Whether the Option keys act as Alt, applied to the registry when it
+    /// changes. Kegworks itself ignores this key.
+    #[serde(rename = "kegtui Map Option To Alt")]
+    #[serde(default, skip_serializing_if = "std::ops::Not::not")]
+    pub kegtui_map_option_to_alt: bool,
+
+    /// Whether the Command keys act as Ctrl, applied to the registry when it
+    /// changes. Kegworks itself ignores this key.
+    #[serde(rename = "kegtui Map Command To Ctrl")]
+    #[serde(default, skip_serializing_if = "std::ops::Not::not")]
+    pub kegtui_map_command_to_ctrl: bool,
+}
+
+impl KegPlist {
+    /// Writes the modeled keys over those of the plist already at `path`,
+    /// keeping every other key, e.g., from a newer Kegworks, exactly as it
+    /// was, dates and all.
+    pub fn write_over(&self, path: &Path) -> Result<(), plist::Error> {
+        let mut info = plist::Value::from_file(path)
+            .ok()
+            .and_then(plist::Value::into_dictionary)
+            .unwrap_or_default();
+        // Keys skipped when unset, like kegtui's own, are removed by first
+        // removing whatever the saved plist had of the modeled keys.
+        if let Ok(saved) = plist::from_value::<KegPlist>(&info.clone().into())
+            && let plist::Value::Dictionary(saved) = plist::to_value(&saved)?
+        {
+            for key in saved.keys() {
+                info.remove(key);
+            }
+        }
+        if let plist::Value::Dictionary(modeled) = plist::to_value(self)? {
+            info.extend(modeled);
+        }
+        plist::Value::Dictionary(info).to_file_xml(path)
+    }
+}
+
+/// The keys of [`KegPlist`] that `info` lacks, which are read as their
+/// defaults. kegtui's own optional keys aren't counted.
+pub fn missing_keys(info: &plist::Dictionary) -> Vec<String> {
+    let Ok(plist::Value::Dictionary(modeled)) =
+        plist::to_value(&KegPlist::default())
+    else {
+        return vec![];
+    };
+    modeled
+        .keys()
+        .filter(|key| !info.contains_key(key.as_str()))
+        .cloned()
+        .collect()
+}
+
+/// How kegtui starts a keg.
+#[derive(
+    Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize,
+)]
+#[serde(rename_all = "lowercase")]
+pub enum LaunchMode {
+    /// Runs the wrapper's executable directly, so kegtui can capture its
+    /// output and working directory.
+    #[default]
+    Direct,
+    /// Opens the `.app` with `open -a`, so macOS activates it like any other
+    /// app, with a Dock icon and focus. Launch Services starts it, so the
+    /// working directory doesn't apply.
+    Open,
+}
+
+impl LaunchMode {
+    pub fn is_direct(&self) -> bool {
+        *self == LaunchMode::Direct
+    }
 }
 
-#[derive(Debug, Serialize, Deserialize)]
+#[derive(Debug, Default, Serialize, Deserialize)]
+#[serde(default)]
 pub struct CFBundleDocumentType {
     #[serde(rename = "CFBundleTypeExtensions")]
     pub cf_bundle_type_extensions: Vec<String>,
@@ -251,7 +368,8 @@ pub struct CFBundleDocumentType {
     pub cf_bundle_type_role: String,
 }
 
-#[derive(Debug, Serialize, Deserialize)]
+#[derive(Debug, Default, Serialize, Deserialize)]
+#[serde(default)]
 pub struct NSAppTransportSecurity {
     #[serde(rename = "NSAllowsArbitraryLoads")]
     pub ns_allows_arbitrary_loads: bool,
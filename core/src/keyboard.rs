@@ -0,0 +1,57 @@
+// Copyright (C) 2026 Ethan Uppal.
+//
+// This program is free software: you can redistribute it and/or modify it under
+// the terms of the GNU General Public License as published by the Free Software
+// Foundation, version 3 of the License only.
+//
+// This program is distributed in the hope that it will be useful, but WITHOUT
+// ANY WARRANTY; without even the implied warranty of MERCHANTABILITY or FITNESS
+// FOR A PARTICULAR PURPOSE. See the GNU General Public License for more
+// details.
+//
+// You should have received a copy of the GNU General Public License along with
+// this program.  If not, see <https://www.gnu.org/licenses/>.
+
+//! The keg's keyboard settings, which Wine's Mac driver reads from the
+//! registry, so they're applied with `regedit` whenever they change.
+
+use color_eyre::Result;
+
+use crate::{
+    keg::CurrentKeg, keg_config::KeyboardConfig, registry, tasks::TaskQueue,
+};
+
+/// A `.reg` file setting the Mac driver's modifier keys to `keyboard`.
+fn registry_file(keyboard: &KeyboardConfig) -> String {
+    let flag = |is_on: bool| if is_on { "y" } else { "n" };
+    let option = flag(keyboard.map_option_to_alt);
+    let command = flag(keyboard.map_command_to_ctrl);
+    format!(
+        "REGEDIT4\n\n[HKEY_CURRENT_USER\\Software\\Wine\\Mac Driver]\n\
+         \"LeftOptionIsAlt\"=\"{option}\"\n\
+         \"RightOptionIsAlt\"=\"{option}\"\n\
+         \"LeftCommandIsCtrl\"=\"{command}\"\n\
+         \"RightCommandIsCtrl\"=\"{command}\"\n"
+    )
+}
+
+/// Queues applying the keg's keyboard settings to its registry if they
+/// differ from `before`, returning whether they did.
+pub fn apply_if_changed(
+    tasks: &TaskQueue,
+    current_keg: &CurrentKeg,
+    before: &KeyboardConfig,
+) -> Result<bool> {
+    let keyboard = current_keg.plist.extract_config().keyboard;
+    if keyboard == *before {
+        return Ok(false);
+    }
+    registry::queue(
+        tasks,
+        current_keg,
+        &registry_file(&keyboard),
+        "keyboard mapping".to_owned(),
+        "keyboard",
+    )?;
+    Ok(true)
+}
@@ -0,0 +1,190 @@
+// Copyright (C) 2026 Ethan Uppal.
+//
+// This program is free software: you can redistribute it and/or modify it under
+// the terms of the GNU General Public License as published by the Free Software
+// Foundation, version 3 of the License only.
+//
+// This program is distributed in the hope that it will be useful, but WITHOUT
+// ANY WARRANTY; without even the implied warranty of MERCHANTABILITY or FITNESS
+// FOR A PARTICULAR PURPOSE. See the GNU General Public License for more
+// details.
+//
+// You should have received a copy of the GNU General Public License along with
+// this program.  If not, see <https://www.gnu.org/licenses/>.
+
+//! Extra arguments for a single launch, which the wrapper only reads from
+//! `Program Flags`, so they're swapped in for the launch and swapped back out
+//! once it has started. The ones used before are remembered per keg.
+
+use std::{
+    fs, io,
+    path::{Path, PathBuf},
+    sync::atomic::{AtomicBool, Ordering},
+    thread,
+    time::{Duration, Instant, SystemTime},
+};
+
+use serde::{Deserialize, Serialize};
+
+use crate::app_config::app_state_directory;
+
+/// How many earlier argument sets are remembered for each keg.
+const HISTORY_LENGTH: usize = 10;
+
+const PROGRAM_FLAGS_KEY: &str = "Program Flags";
+
+/// How long to wait for the launcher to start before putting the saved flags
+/// back anyway.
+const START_TIMEOUT: Duration = Duration::from_secs(60);
+
+fn history_file(keg_name: &str) -> PathBuf {
+    let keg_name = keg_name.replace(['/', '\\'], "_");
+    app_state_directory()
+        .join("launch-arguments")
+        .join(format!("{keg_name}.txt"))
+}
+
+/// The arguments used before with the keg named `keg_name`, newest first.
+pub fn history(keg_name: &str) -> Vec<String> {
+    fs::read_to_string(history_file(keg_name))
+        .map(|contents| contents.lines().map(str::to_owned).collect())
+        .unwrap_or_default()
+}
+
+/// Moves `arguments` to the front of the keg's history.
+pub fn remember(keg_name: &str, arguments: &str) -> io::Result<()> {
+    let mut history = history(keg_name);
+    history.retain(|earlier| earlier != arguments);
+    history.insert(0, arguments.to_owned());
+    history.truncate(HISTORY_LENGTH);
+    let path = history_file(keg_name);
+    if let Some(parent) = path.parent() {
+        fs::create_dir_all(parent)?;
+    }
+    fs::write(path, history.join("\n") + "\n")
+}
+
+fn write_program_flags(
+    config_file: &Path,
+    program_flags: &str,
+) -> Result<(), plist::Error> {
+    let mut info = plist::Value::from_file(config_file)?;
+    if let Some(dictionary) = info.as_dictionary_mut() {
+        dictionary.insert(PROGRAM_FLAGS_KEY.to_owned(), program_flags.into());
+    }
+    info.to_file_xml(config_file)
+}
+
+/// Where temporary flags not yet restored are recorded, so they can be if
+/// kegtui exits first.
+fn pending_directory() -> PathBuf {
+    app_state_directory()
+        .join("launch-arguments")
+        .join("pending")
+}
+
+/// `Program Flags` with extra arguments added for one launch.
+#[derive(Serialize, Deserialize)]
+pub struct TemporaryFlags {
+    config_file: PathBuf,
+    saved: String,
+    temporary: String,
+    #[serde(skip)]
+    pending_file: PathBuf,
+}
+
+impl TemporaryFlags {
+    /// Appends `arguments` to the `saved` flags of the keg named `keg_name` in
+    /// `config_file`.
+    pub fn apply(
+        keg_name: &str,
+        config_file: &Path,
+        saved: &str,
+        arguments: &str,
+    ) -> io::Result<Self> {
+        let temporary = format!("{saved} {arguments}").trim().to_owned();
+        let temporary_flags = Self {
+            config_file: config_file.to_path_buf(),
+            saved: saved.to_owned(),
+            temporary,
+            pending_file: pending_directory()
+                .join(format!("{}.toml", keg_name.replace(['/', '\\'], "_"))),
+        };
+        // Without the record, the flags could be left in place for good.
+        temporary_flags.record()?;
+        write_program_flags(config_file, &temporary_flags.temporary)
+            .map_err(io::Error::other)?;
+        Ok(temporary_flags)
+    }
+
+    fn record(&self) -> io::Result<()> {
+        fs::create_dir_all(pending_directory())?;
+        let contents = toml::to_string(self).map_err(io::Error::other)?;
+        fs::write(&self.pending_file, contents)
+    }
+
+    /// Puts the saved flags back, unless the flags were changed meanwhile,
+    /// e.g., from Edit Config.
+    pub fn restore(self) -> Result<(), plist::Error> {
+        let info = plist::Value::from_file(&self.config_file)?;
+        let current = info
+            .as_dictionary()
+            .and_then(|dictionary| dictionary.get(PROGRAM_FLAGS_KEY))
+            .and_then(plist::Value::as_string);
+        if current == Some(self.temporary.as_str()) {
+            write_program_flags(&self.config_file, &self.saved)?;
+        }
+        let _ = fs::remove_file(&self.pending_file);
+        Ok(())
+    }
+
+    /// Waits until the launcher has started, i.e., once Wine has written
+    /// `last_run_log` since `launched`, `has_exited` is set, or a minute has
+    /// passed, then puts the saved flags back. By then the wrapper has read
+    /// them, so the keg's next launch, even from Finder, gets only the saved
+    /// ones.
+    pub fn restore_once_started(
+        self,
+        last_run_log: &Path,
+        launched: SystemTime,
+        has_exited: &AtomicBool,
+    ) -> Result<(), plist::Error> {
+        let deadline = Instant::now() + START_TIMEOUT;
+        while !has_exited.load(Ordering::Relaxed)
+            && Instant::now() < deadline
+            && !fs::metadata(last_run_log)
+                .and_then(|metadata| metadata.modified())
+                .is_ok_and(|modified| modified >= launched)
+        {
+            thread::sleep(Duration::from_millis(250));
+        }
+        self.restore()
+    }
+}
+
+/// Puts back the saved flags of launches kegtui exited before restoring.
+/// Only meant to run when no other kegtui is running.
+pub fn restore_leftovers() {
+    let Ok(read_dir) = fs::read_dir(pending_directory()) else {
+        return;
+    };
+    for entry in read_dir.flatten() {
+        let pending_file = entry.path();
+        let Some(mut temporary_flags) =
+            fs::read_to_string(&pending_file).ok().and_then(|contents| {
+                toml::from_str::<TemporaryFlags>(&contents).ok()
+            })
+        else {
+            let _ = fs::remove_file(&pending_file);
+            continue;
+        };
+        // A keg that's gone has nothing left to restore, while one that
+        // can't be read now is tried again next time.
+        if !temporary_flags.config_file.exists() {
+            let _ = fs::remove_file(&pending_file);
+            continue;
+        }
+        temporary_flags.pending_file = pending_file;
+        let _ = temporary_flags.restore();
+    }
+}
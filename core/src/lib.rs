@@ -0,0 +1,246 @@
+// Copyright (C) 2024 Ethan Uppal.
+//
+// This program is free software: you can redistribute it and/or modify it under
+// the terms of the GNU General Public License as published by the Free Software
+// Foundation, version 3 of the License only.
+//
+// This program is distributed in the hope that it will be useful, but WITHOUT
+// ANY WARRANTY; without even the implied warranty of MERCHANTABILITY or FITNESS
+// FOR A PARTICULAR PURPOSE. See the GNU General Public License for more
+// details.
+//
+// You should have received a copy of the GNU General Public License along with
+// this program.  If not, see <https://www.gnu.org/licenses/>.
+
+//! kegtui's TUI, kept in a library so the GUI wrapper can read and write
+//! `kegtui.toml` the same way, and so tests can drive it with
+//! [`run_scripted`].
+
+use std::{
+    env, fs, io,
+    sync::{Arc, RwLock},
+};
+
+use color_eyre::{
+    Result,
+    eyre::{Context, bail},
+};
+use crossterm::{
+    ExecutableCommand,
+    event::{DisableMouseCapture, EnableMouseCapture},
+};
+
+use crate::{
+    app::{App, AsyncState},
+    app_config::{AppConfig, app_config_file_path},
+    cli::{
+        describe_config, print_keg_logs, run_headless, run_maintenance,
+        run_quick_launch,
+    },
+    driver::{HeadlessDriver, TerminalDriver},
+    instance::Instance,
+    navs::register_navs,
+    runner::{CommandRunner, ScriptedRunner, SystemRunner},
+    view::NavContext,
+    worker::{WorkerHandle, spawn_worker},
+};
+
+pub mod actions;
+pub mod adopt;
+pub mod app;
+pub mod app_config;
+pub mod arch;
+pub mod brew;
+pub mod checks;
+pub mod cli;
+pub mod clipboard;
+pub mod config_presets;
+pub mod crash_dumps;
+pub mod desktop_launcher;
+pub mod diagnostics;
+pub mod disc_image;
+pub mod disk_usage;
+pub mod display_mode;
+pub mod drafts;
+pub mod driver;
+pub mod engine_install;
+pub mod engine_pruning;
+pub mod fuzzy;
+pub mod hardware;
+pub mod instance;
+pub mod journal;
+pub mod keg;
+pub mod keg_config;
+pub mod keg_creation;
+pub mod keg_diff;
+pub mod keg_manifest;
+pub mod keg_name;
+pub mod keg_plist;
+pub mod keyboard;
+pub mod launch_arguments;
+pub mod log_tail;
+pub mod maintenance;
+pub mod name_conflict;
+pub mod navs;
+pub mod notify;
+pub mod pe;
+pub mod picker;
+pub mod plist_validation;
+pub mod probe;
+pub mod processes;
+pub mod quick_launch;
+pub mod registry;
+pub mod remote;
+pub mod run_logs;
+pub mod runner;
+pub mod setup;
+pub mod snapshots;
+pub mod tasks;
+pub mod translation_layers;
+pub mod view;
+pub mod views;
+pub mod wine_path;
+pub mod winetricks_cache;
+pub mod winetricks_catalog;
+pub mod winetricks_errors;
+pub mod worker;
+pub mod wrapper_update;
+
+/// Drives the TUI on a `width` by `height` screen, pressing `keys` written
+/// like `jj<Enter>l<Esc>q`, and returns the last frame drawn. `state` stands
+/// in for what the worker would find. No worker runs, the instance isn't
+/// locked, and every command fails instead of running, so the frame only
+/// depends on the arguments.
+pub fn run_scripted(
+    config: &AppConfig,
+    state: AsyncState,
+    width: u16,
+    height: u16,
+    keys: &str,
+) -> Result<String> {
+    let events = match driver::parse_keys(keys) {
+        Ok(events) => events,
+        Err(name) => bail!("Unknown key <{name}>"),
+    };
+    let mut context = NavContext::default();
+    let main_nav = register_navs(&mut context);
+    let worker = WorkerHandle::detached();
+    let mut driver = HeadlessDriver::new(width, height, events)?;
+    App::new(
+        config,
+        Arc::new(ScriptedRunner),
+        Instance::Unchecked,
+        &worker,
+    )
+    .run(
+        &mut context,
+        main_nav,
+        &mut driver,
+        Arc::new(RwLock::new(state)),
+    )?;
+    Ok(driver.screen())
+}
+
+/// Reads `kegtui.toml`, creating an empty one if there is none.
+fn load_app_config() -> Arc<AppConfig> {
+    let app_config_file_path = app_config_file_path();
+    if !app_config_file_path.try_exists().unwrap_or_else(|_| {
+        panic!(
+            "Failed to check existence of {}",
+            app_config_file_path.display()
+        )
+    }) {
+        let parent_directory = app_config_file_path
+            .parent()
+            .expect("app_config_file_path should be a full path to the file");
+        fs::create_dir_all(parent_directory).unwrap_or_else(|_| {
+            panic!("Failed to create directory {}", parent_directory.display())
+        });
+        fs::write(&app_config_file_path, "").unwrap_or_else(|_| {
+            panic!(
+                "Failed to create empty config file at {}",
+                app_config_file_path.display()
+            )
+        });
+    }
+    let app_config_file_contents = fs::read_to_string(&app_config_file_path)
+        .unwrap_or_else(|_| {
+            panic!(
+                "Failed to read config file {} as string",
+                app_config_file_path.display()
+            )
+        });
+    let mut app_config = toml::from_str::<AppConfig>(&app_config_file_contents)
+        .unwrap_or_else(|_| {
+            panic!(
+                "Failed to parse config file {}",
+                app_config_file_path.display()
+            )
+        });
+    app_config.expand_paths();
+    Arc::new(app_config)
+}
+
+/// Runs kegtui as started from the command line.
+pub fn run() -> Result<()> {
+    let args = env::args().skip(1).collect::<Vec<_>>();
+    let args = args.iter().map(String::as_str).collect::<Vec<_>>();
+    match args.as_slice() {
+        ["config", "describe", rest @ ..] => {
+            describe_config(rest.first().copied());
+            Ok(())
+        }
+        ["maintenance", ..] => {
+            run_maintenance(&load_app_config());
+            Ok(())
+        }
+        ["logs", rest @ ..] => {
+            color_eyre::install()?;
+            print_keg_logs(&load_app_config(), rest)
+        }
+        ["--quick", ..] => {
+            color_eyre::install()?;
+            run_quick_launch(&load_app_config())
+        }
+        ["--headless", size, keys] => {
+            color_eyre::install()?;
+            run_headless(&load_app_config(), size, keys)
+        }
+        _ => run_tui(load_app_config()),
+    }
+}
+
+/// Runs the TUI in the terminal, with a worker scanning in the background.
+fn run_tui(app_config: Arc<AppConfig>) -> Result<()> {
+    let mut context = NavContext::default();
+    let main_nav = register_navs(&mut context);
+
+    let instance =
+        instance::acquire().context("Failed to lock the kegtui instance")?;
+    if matches!(instance, Instance::Only(_)) {
+        launch_arguments::restore_leftovers();
+        if app_config.maintenance.run_on_start {
+            run_maintenance(&app_config);
+        }
+    }
+
+    let runner: Arc<dyn CommandRunner> = Arc::new(SystemRunner::default());
+    let (async_state, worker) =
+        spawn_worker(app_config.clone(), runner.clone());
+
+    color_eyre::install()?;
+    let mut driver = TerminalDriver {
+        terminal: ratatui::init(),
+    };
+    io::stdout().execute(EnableMouseCapture)?;
+    let app_result = App::new(&app_config, runner, instance, &worker).run(
+        &mut context,
+        main_nav,
+        &mut driver,
+        async_state,
+    );
+    worker.shutdown();
+    let _ = io::stdout().execute(DisableMouseCapture);
+    ratatui::restore();
+    app_result
+}
@@ -0,0 +1,186 @@
+// Copyright (C) 2026 Ethan Uppal.
+//
+// This program is free software: you can redistribute it and/or modify it under
+// the terms of the GNU General Public License as published by the Free Software
+// Foundation, version 3 of the License only.
+//
+// This program is distributed in the hope that it will be useful, but WITHOUT
+// ANY WARRANTY; without even the implied warranty of MERCHANTABILITY or FITNESS
+// FOR A PARTICULAR PURPOSE. See the GNU General Public License for more
+// details.
+//
+// You should have received a copy of the GNU General Public License along with
+// this program.  If not, see <https://www.gnu.org/licenses/>.
+
+//! `kegtui logs <keg>`, which prints a keg's newest Wine log and, with
+//! `--follow`, keeps printing what's appended to it, for watching a game from
+//! a second terminal.
+
+use std::{
+    collections::HashMap,
+    fs::{self, File},
+    io::{self, BufRead, BufReader, Seek, SeekFrom, Write},
+    path::{Path, PathBuf},
+    thread,
+    time::{Duration, SystemTime},
+};
+
+use color_eyre::{Result, eyre::Context};
+
+use crate::keg::Keg;
+
+/// How often a followed log is checked for new lines.
+const POLL_INTERVAL: Duration = Duration::from_millis(250);
+
+/// Finds the keg named `name`, with or without `.app`, ignoring case.
+pub fn find_keg(
+    kegs: impl IntoIterator<Item = Keg>,
+    name: &str,
+) -> Option<Keg> {
+    let name = name.trim_end_matches(".app");
+    kegs.into_iter().find(|keg| {
+        keg.name.trim_end_matches(".app").eq_ignore_ascii_case(name)
+    })
+}
+
+/// The most recently written log in `keg`'s log directories, other than
+/// winetricks' and rotated copies.
+pub fn newest_wine_log(keg: &Keg) -> Option<PathBuf> {
+    keg.log_directories()
+        .into_iter()
+        .filter_map(|log_directory| fs::read_dir(log_directory).ok())
+        .flat_map(|read_dir| read_dir.flatten())
+        .filter(|entry| {
+            let path = entry.path();
+            path.extension().is_some_and(|extension| extension == "log")
+                && path != keg.winetricks_logfile
+        })
+        .filter_map(|entry| {
+            let metadata = entry.metadata().ok()?;
+            metadata
+                .is_file()
+                .then(|| Some((metadata.modified().ok()?, entry.path())))
+                .flatten()
+        })
+        .max_by_key(|(modified, _): &(SystemTime, PathBuf)| *modified)
+        .map(|(_, path)| path)
+}
+
+/// Keeps lines from the given Wine debug channels, e.g., `d3d`, or classes
+/// of message, e.g., `err`, or both, e.g., `fixme:d3d`. Lines without a
+/// Wine debug prefix, like the program's own output, are kept only when
+/// there are no channels to filter by.
+pub struct ChannelFilter {
+    channels: Vec<String>,
+}
+
+impl ChannelFilter {
+    pub fn new(channels: Vec<String>) -> Self {
+        Self { channels }
+    }
+
+    /// The class and channel of a line like `0024:fixme:d3d:wined3d_...`.
+    fn class_and_channel(line: &str) -> Option<(&str, &str)> {
+        let mut parts = line.splitn(4, ':');
+        let mut class = parts.next()?;
+        // Wine prefixes the thread ID when WINEDEBUG includes +tid or +pid.
+        if class.chars().all(|c| c.is_ascii_hexdigit()) {
+            class = parts.next()?;
+        }
+        let channel = parts.next()?;
+        ["err", "fixme", "warn", "trace"]
+            .contains(&class)
+            .then_some((class, channel))
+    }
+
+    pub fn matches(&self, line: &str) -> bool {
+        if self.channels.is_empty() {
+            return true;
+        }
+        let Some((class, channel)) = Self::class_and_channel(line) else {
+            return false;
+        };
+        self.channels
+            .iter()
+            .any(|wanted| match wanted.split_once(':') {
+                Some((wanted_class, wanted_channel)) => {
+                    wanted_class == class && wanted_channel == channel
+                }
+                None => wanted == class || wanted == channel,
+            })
+    }
+}
+
+/// Prints the lines of `reader` that `filter` keeps, returning how many bytes
+/// were read.
+fn print_lines(
+    reader: &mut impl BufRead,
+    filter: &ChannelFilter,
+    output: &mut impl Write,
+) -> io::Result<u64> {
+    let mut read = 0;
+    let mut line = Vec::new();
+    loop {
+        line.clear();
+        let length = reader.read_until(b'\n', &mut line)?;
+        // A line still being written is left for the next read.
+        if length == 0 || !line.ends_with(b"\n") {
+            return Ok(read);
+        }
+        read += length as u64;
+        let text = String::from_utf8_lossy(&line);
+        if filter.matches(text.trim_end()) {
+            output.write_all(text.as_bytes())?;
+        }
+    }
+}
+
+/// Prints `log` from `start`, returning where it stopped.
+pub fn print_from(
+    log: &Path,
+    start: u64,
+    filter: &ChannelFilter,
+    output: &mut impl Write,
+) -> Result<u64> {
+    let mut file = File::open(log)
+        .with_context(|| format!("Failed to open {}", log.display()))?;
+    file.seek(SeekFrom::Start(start))?;
+    let read = print_lines(&mut BufReader::new(file), filter, output)?;
+    output.flush()?;
+    Ok(start + read)
+}
+
+/// Prints `keg`'s newest Wine log, then, if `follow` is set, what's appended
+/// to it until interrupted. Whichever log was written last is followed, so a
+/// new one, e.g., from relaunching the keg, is switched to as it appears.
+pub fn tail(keg: &Keg, filter: &ChannelFilter, follow: bool) -> Result<()> {
+    let mut output = io::stdout().lock();
+    // Where each log was printed up to, so switching back and forth between
+    // logs never repeats lines.
+    let mut positions = HashMap::<PathBuf, u64>::new();
+    let mut current = None;
+    loop {
+        if let Some(log) = newest_wine_log(keg) {
+            if current.as_ref() != Some(&log) {
+                if follow {
+                    eprintln!("==> {} <==", log.display());
+                }
+                current = Some(log.clone());
+            }
+            let position = positions.entry(log.clone()).or_default();
+            // The wrapper truncates its logs when the keg starts again.
+            if fs::metadata(&log)
+                .is_ok_and(|metadata| metadata.len() < *position)
+            {
+                *position = 0;
+            }
+            *position = print_from(&log, *position, filter, &mut output)?;
+        } else if !follow {
+            eprintln!("{} has no Wine logs yet", keg.name);
+        }
+        if !follow {
+            return Ok(());
+        }
+        thread::sleep(POLL_INTERVAL);
+    }
+}
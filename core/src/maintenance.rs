@@ -0,0 +1,234 @@
+// Copyright (C) 2026 Ethan Uppal.
+//
+// This program is free software: you can redistribute it and/or modify it under
+// the terms of the GNU General Public License as published by the Free Software
+// Foundation, version 3 of the License only.
+//
+// This program is distributed in the hope that it will be useful, but WITHOUT
+// ANY WARRANTY; without even the implied warranty of MERCHANTABILITY or FITNESS
+// FOR A PARTICULAR PURPOSE. See the GNU General Public License for more
+// details.
+//
+// You should have received a copy of the GNU General Public License along with
+// this program.  If not, see <https://www.gnu.org/licenses/>.
+
+use std::{
+    collections::HashMap,
+    fmt::Display,
+    fs, io,
+    path::{Path, PathBuf},
+};
+
+use crate::{app_config::MaintenanceConfig, keg::Keg, run_logs, snapshots};
+
+#[derive(Debug, Default)]
+pub struct MaintenanceReport {
+    pub rotated_logs: Vec<PathBuf>,
+    pub removed_snapshots: Vec<PathBuf>,
+    pub removed_runs: Vec<PathBuf>,
+    pub removed_backups: Vec<PathBuf>,
+    pub removed_temp_files: usize,
+    /// Whether temp files were left alone because the keg is running and
+    /// may still be using them.
+    pub skipped_temp_files: bool,
+    /// What couldn't be done, e.g., a log that couldn't be rotated. The rest
+    /// is still done.
+    pub failures: Vec<String>,
+}
+
+impl MaintenanceReport {
+    pub fn is_empty(&self) -> bool {
+        self.rotated_logs.is_empty()
+            && self.removed_snapshots.is_empty()
+            && self.removed_runs.is_empty()
+            && self.removed_backups.is_empty()
+            && self.removed_temp_files == 0
+            && !self.skipped_temp_files
+            && self.failures.is_empty()
+    }
+
+    fn record_failure(&mut self, what: &str, path: &Path, error: impl Display) {
+        self.failures
+            .push(format!("Failed to {what} {}: {error}", path.display()));
+    }
+}
+
+fn rotated_log_path(log: &Path, index: usize) -> PathBuf {
+    let mut file_name = log.file_name().unwrap_or_default().to_os_string();
+    file_name.push(format!(".{index}"));
+    log.with_file_name(file_name)
+}
+
+/// Whether `path` is itself a rotated copy, i.e., ends in `.<number>`.
+fn is_rotated_log(path: &Path) -> bool {
+    path.extension()
+        .and_then(|extension| extension.to_str())
+        .is_some_and(|extension| extension.parse::<usize>().is_ok())
+}
+
+/// Shifts `log` to `log.1`, `log.1` to `log.2`, and so on, dropping anything
+/// past `keep` copies.
+fn rotate_log(log: &Path, keep: usize) -> io::Result<()> {
+    if keep == 0 {
+        return fs::remove_file(log);
+    }
+    let oldest = rotated_log_path(log, keep);
+    if oldest.exists() {
+        fs::remove_file(&oldest)?;
+    }
+    for index in (1..keep).rev() {
+        let from = rotated_log_path(log, index);
+        if from.exists() {
+            fs::rename(&from, rotated_log_path(log, index + 1))?;
+        }
+    }
+    fs::rename(log, rotated_log_path(log, 1))
+}
+
+fn rotate_logs(
+    keg: &Keg,
+    config: &MaintenanceConfig,
+    report: &mut MaintenanceReport,
+) {
+    for log_directory in keg.log_directories() {
+        let Ok(read_dir) = fs::read_dir(log_directory) else {
+            continue;
+        };
+        for entry in read_dir.flatten() {
+            let path = entry.path();
+            if is_rotated_log(&path) {
+                continue;
+            }
+            let metadata = match entry.metadata() {
+                Ok(metadata) => metadata,
+                Err(error) => {
+                    report.record_failure("read", &path, error);
+                    continue;
+                }
+            };
+            if metadata.is_file() && metadata.len() > config.max_log_size {
+                match rotate_log(&path, config.keep_rotated_logs) {
+                    Ok(()) => report.rotated_logs.push(path),
+                    Err(error) => report.record_failure("rotate", &path, error),
+                }
+            }
+        }
+    }
+}
+
+/// What a snapshot holds, e.g., `prefix.tar`, from its file name of the form
+/// `<unix time>-<kind>`.
+fn snapshot_kind(snapshot: &Path) -> String {
+    let file_name = snapshot.file_name().unwrap_or_default().to_string_lossy();
+    file_name
+        .split_once('-')
+        .map_or(&*file_name, |(_, kind)| kind)
+        .to_owned()
+}
+
+/// Keeps the newest snapshots of each kind, so many engine snapshots can't
+/// crowd out the only prefix snapshot Undo Winetricks needs.
+fn prune_snapshots(
+    keg: &Keg,
+    config: &MaintenanceConfig,
+    report: &mut MaintenanceReport,
+) {
+    let mut kept = HashMap::<String, usize>::new();
+    for snapshot in snapshots::list_snapshots(&keg.name) {
+        let kept = kept.entry(snapshot_kind(&snapshot)).or_default();
+        if *kept < config.keep_snapshots {
+            *kept += 1;
+            continue;
+        }
+        match fs::remove_file(&snapshot) {
+            Ok(()) => report.removed_snapshots.push(snapshot),
+            Err(error) => report.record_failure("remove", &snapshot, error),
+        }
+    }
+}
+
+fn prune_runs(
+    keg: &Keg,
+    config: &MaintenanceConfig,
+    report: &mut MaintenanceReport,
+) {
+    for run in run_logs::list_runs(&keg.log_directory)
+        .into_iter()
+        .skip(config.keep_runs)
+    {
+        match fs::remove_file(&run.path) {
+            Ok(()) => report.removed_runs.push(run.path),
+            Err(error) => report.record_failure("remove", &run.path, error),
+        }
+    }
+}
+
+fn clear_temp_files(keg: &Keg, report: &mut MaintenanceReport) {
+    let mut temp_directories = vec![keg.c_drive.join("windows/temp")];
+    if let Ok(users) = fs::read_dir(keg.c_drive.join("users")) {
+        temp_directories.extend(users.flatten().flat_map(|user| {
+            [
+                user.path().join("Temp"),
+                user.path().join("AppData/Local/Temp"),
+            ]
+        }));
+    }
+    for temp_directory in temp_directories {
+        let Ok(read_dir) = fs::read_dir(&temp_directory) else {
+            continue;
+        };
+        for entry in read_dir.flatten() {
+            let path = entry.path();
+            let result = entry.file_type().and_then(|file_type| {
+                if file_type.is_dir() {
+                    fs::remove_dir_all(&path)
+                } else {
+                    fs::remove_file(&path)
+                }
+            });
+            match result {
+                Ok(()) => report.removed_temp_files += 1,
+                Err(error) => report.record_failure("remove", &path, error),
+            }
+        }
+    }
+}
+
+/// Rotates logs, prunes snapshots and recorded runs, and clears temp files
+/// for `keg` according to `config`. Temp files are kept if `is_running`,
+/// since the running program may still be using them. Anything that fails
+/// is recorded in the report and the rest carries on.
+pub fn maintain_keg(
+    keg: &Keg,
+    config: &MaintenanceConfig,
+    is_running: bool,
+) -> MaintenanceReport {
+    let mut report = MaintenanceReport::default();
+    rotate_logs(keg, config, &mut report);
+    prune_snapshots(keg, config, &mut report);
+    prune_runs(keg, config, &mut report);
+    if config.clear_temp_files {
+        if is_running {
+            report.skipped_temp_files = true;
+        } else {
+            clear_temp_files(keg, &mut report);
+        }
+    }
+    report
+}
+
+/// Removes all but the newest backups of kegs that were overwritten,
+/// according to `config`.
+pub fn prune_backups(config: &MaintenanceConfig) -> MaintenanceReport {
+    let mut report = MaintenanceReport::default();
+    for backup in snapshots::list_backups()
+        .into_iter()
+        .skip(config.keep_backups)
+    {
+        match fs::remove_dir_all(&backup) {
+            Ok(()) => report.removed_backups.push(backup),
+            Err(error) => report.record_failure("remove", &backup, error),
+        }
+    }
+    report
+}
@@ -0,0 +1,155 @@
+// Copyright (C) 2026 Ethan Uppal.
+//
+// This program is free software: you can redistribute it and/or modify it under
+// the terms of the GNU General Public License as published by the Free Software
+// Foundation, version 3 of the License only.
+//
+// This program is distributed in the hope that it will be useful, but WITHOUT
+// ANY WARRANTY; without even the implied warranty of MERCHANTABILITY or FITNESS
+// FOR A PARTICULAR PURPOSE. See the GNU General Public License for more
+// details.
+//
+// You should have received a copy of the GNU General Public License along with
+// this program.  If not, see <https://www.gnu.org/licenses/>.
+
+//! A modal asking what to do when a keg's name is already taken, drawn by TUI
+//! views or, from external actions, by borrowing the terminal back.
+
+use std::{io, path::Path};
+
+use color_eyre::Result;
+use crossterm::{
+    ExecutableCommand,
+    event::{self, Event, KeyCode, KeyEventKind},
+    terminal::{
+        EnterAlternateScreen, LeaveAlternateScreen, disable_raw_mode,
+        enable_raw_mode,
+    },
+};
+use ratatui::{
+    Terminal,
+    backend::CrosstermBackend,
+    prelude::*,
+    widgets::{Block, Borders, Clear, Padding, Paragraph, Wrap},
+};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum NameConflict {
+    PickNewName,
+    /// Replace the existing keg once it's backed up.
+    Overwrite,
+    Cancel,
+}
+
+impl NameConflict {
+    pub const ALL: [Self; 3] =
+        [Self::PickNewName, Self::Overwrite, Self::Cancel];
+
+    fn label(self) -> &'static str {
+        match self {
+            NameConflict::PickNewName => "Pick a new name",
+            NameConflict::Overwrite => {
+                "Overwrite it (the existing keg is backed up first)"
+            }
+            NameConflict::Cancel => "Cancel",
+        }
+    }
+
+    fn shortcut(self) -> char {
+        match self {
+            NameConflict::PickNewName => 'n',
+            NameConflict::Overwrite => 'o',
+            NameConflict::Cancel => 'c',
+        }
+    }
+
+    /// The choice a key picks directly, if any.
+    pub fn from_shortcut(code: KeyCode) -> Option<Self> {
+        match code {
+            KeyCode::Char('n' | 'N') => Some(Self::PickNewName),
+            KeyCode::Char('o' | 'O') => Some(Self::Overwrite),
+            KeyCode::Char('c' | 'C') | KeyCode::Esc => Some(Self::Cancel),
+            _ => None,
+        }
+    }
+}
+
+/// Draws the choices for the taken `path` over the middle of `area`, with the
+/// one at `selected` highlighted.
+pub fn draw_modal(frame: &mut Frame, area: Rect, path: &Path, selected: usize) {
+    let modal_width = area.width.saturating_sub(4).min(60);
+    let mut lines = vec![
+        Line::from(format!("{} already exists.", path.display())),
+        Line::from(""),
+    ];
+    for (index, choice) in NameConflict::ALL.into_iter().enumerate() {
+        let label = format!("[{}] {}", choice.shortcut(), choice.label());
+        lines.push(Line::from(if index == selected {
+            label.yellow().bold()
+        } else {
+            label.into()
+        }));
+    }
+    let modal_height = (lines.len() as u16 + 6).min(area.height);
+    let modal_area = Rect {
+        x: area.x + (area.width - modal_width) / 2,
+        y: area.y + (area.height - modal_height) / 2,
+        width: modal_width,
+        height: modal_height,
+    };
+    frame.render_widget(Clear, modal_area);
+    frame.render_widget(
+        Paragraph::new(lines).wrap(Wrap { trim: false }).block(
+            Block::default()
+                .title(
+                    Span::from(" A keg with this name exists ")
+                        .into_centered_line(),
+                )
+                .borders(Borders::ALL)
+                .padding(Padding::uniform(1)),
+        ),
+        modal_area,
+    );
+}
+
+/// Asks what to do now that `path` is taken. Meant to be called from an
+/// external action.
+pub fn choose(path: &Path) -> Result<NameConflict> {
+    io::stdout().execute(EnterAlternateScreen)?;
+    enable_raw_mode()?;
+    let result = run(path);
+    disable_raw_mode()?;
+    io::stdout().execute(LeaveAlternateScreen)?;
+    result
+}
+
+fn run(path: &Path) -> Result<NameConflict> {
+    let mut terminal = Terminal::new(CrosstermBackend::new(io::stdout()))?;
+    terminal.clear()?;
+    let mut selected = 0;
+    loop {
+        terminal.draw(|frame| {
+            draw_modal(frame, frame.area(), path, selected);
+        })?;
+        let Event::Key(key_event) = event::read()? else {
+            continue;
+        };
+        if key_event.kind != KeyEventKind::Press {
+            continue;
+        }
+        match key_event.code {
+            KeyCode::Up | KeyCode::Char('k') => {
+                selected = selected.saturating_sub(1);
+            }
+            KeyCode::Down | KeyCode::Char('j') => {
+                selected = (selected + 1).min(NameConflict::ALL.len() - 1);
+            }
+            KeyCode::Enter => return Ok(NameConflict::ALL[selected]),
+            code => {
+                if let Some(choice) = NameConflict::from_shortcut(code) {
+                    return Ok(choice);
+                }
+            }
+        }
+    }
+}
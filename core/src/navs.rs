@@ -0,0 +1,508 @@
+// Copyright (C) 2026 Ethan Uppal.
+//
+// This program is free software: you can redistribute it and/or modify it under
+// the terms of the GNU General Public License as published by the Free Software
+// Foundation, version 3 of the License only.
+//
+// This program is distributed in the hope that it will be useful, but WITHOUT
+// ANY WARRANTY; without even the implied warranty of MERCHANTABILITY or FITNESS
+// FOR A PARTICULAR PURPOSE. See the GNU General Public License for more
+// details.
+//
+// You should have received a copy of the GNU General Public License along with
+// this program.  If not, see <https://www.gnu.org/licenses/>.
+
+//! The TUI's menus and the views they open.
+
+use ratatui::{
+    style::Stylize,
+    text::{Line, Text},
+};
+
+use crate::{
+    actions::{
+        archive::{adopt_app, archive_keg, import_keg},
+        bundle::{
+            edit_app_info, measure_keg_size, rename_keg, update_keg_wrapper,
+        },
+        config::{
+            apply_preset, configure_display_mode, configure_hardware,
+            configure_start_exe, edit_config, edit_registry, probe_keg,
+        },
+        engines::{install_from_brew_taps, prune_engines, upgrade_engines},
+        export::{
+            compare_kegs, create_desktop_launcher, export_keg_diagnostics,
+            export_keg_info, export_keg_manifest,
+        },
+        install::install_software,
+        launch::{
+            kill_all_wineservers, kill_wineserver, launch_keg,
+            launch_keg_with_arguments, open_c_drive, open_crash_dumps,
+            resume_executable, run_custom_command,
+        },
+        manifests::{apply_keg_manifest, build_keg_from_manifest},
+        setup::{check_for_updates, install_setup_components},
+        winetricks::{clear_winetricks_cache, undo_winetricks},
+    },
+    app::{App, AsyncState},
+    tasks::TaskStatus,
+    view::{
+        MenuItem, MenuItemAction, NavAction, NavContext, NavID, Requirement,
+        ViewID,
+    },
+    views,
+};
+
+fn main_menu<'a>(app: &App, state: &AsyncState) -> Vec<MenuItem<'a>> {
+    // Reopen the kegs list at the keg that was last selected.
+    let current_keg_index = app
+        .current_keg
+        .as_ref()
+        .and_then(|current_keg| {
+            views::kegs::matching_kegs(app, state)
+                .iter()
+                .position(|keg| {
+                    keg.wineskin_launcher == current_keg.keg.wineskin_launcher
+                })
+        })
+        .unwrap_or(0);
+
+    vec![
+        MenuItem::new(
+            "Kegs",
+            MenuItemAction::LoadViewAt(
+                ViewID::Named("kegs"),
+                current_keg_index,
+            ),
+        )
+        .description("Browse the kegs found in your search paths"),
+        MenuItem::new(
+            "Create Keg",
+            MenuItemAction::LoadView(ViewID::Named("keg_creator")),
+        )
+        .description("Assemble a new keg from an engine and a wrapper")
+        .requires(&[Requirement::Engines]),
+        MenuItem::new(
+            "Build from Manifest",
+            MenuItemAction::External(build_keg_from_manifest),
+        )
+        .description("Creates a keg and sets it up from a keg manifest")
+        .requires(&[Requirement::Engines]),
+        MenuItem::new("Adopt App", MenuItemAction::External(adopt_app))
+            .description("Turns a hand-rolled Wine .app into a keg"),
+        MenuItem::new("Import Keg", MenuItemAction::External(import_keg))
+            .description("Unpacks a keg saved with Archive Keg"),
+        MenuItem::new(
+            "Engines",
+            MenuItemAction::NavAction(NavAction::Push(NavID::Named("engines"))),
+        )
+        .description("Engines and wrappers, and installing more via brew"),
+        MenuItem::new(
+            "Upgrade Engines",
+            MenuItemAction::External(upgrade_engines),
+        )
+        .description("Moves several kegs to a new engine, with snapshots")
+        .requires(&[Requirement::Engines]),
+        MenuItem::new(
+            "Kill All Kegs",
+            MenuItemAction::External(kill_all_wineservers),
+        )
+        .description("Runs WSS-wineserverkill for every keg opened here"),
+        MenuItem::new(
+            "Clear Winetricks Cache",
+            MenuItemAction::External(clear_winetricks_cache),
+        )
+        .description("Deletes the downloaded winetricks and verb list"),
+        MenuItem::new(
+            "Winetricks Downloads",
+            MenuItemAction::LoadView(ViewID::Named("winetricks_cache")),
+        )
+        .description("Shows the verb downloads shared by every keg"),
+        MenuItem::new(
+            "Setup Wizard",
+            MenuItemAction::NavAction(NavAction::Push(NavID::Named("setup"))),
+        )
+        .description("Installs Kegworks, an engine, a wrapper, and more"),
+        MenuItem::new(
+            "About",
+            MenuItemAction::NavAction(NavAction::Push(NavID::Named("about"))),
+        )
+        .description("Version information and update checks"),
+        MenuItem::new(
+            "Credits",
+            MenuItemAction::LoadView(ViewID::Named("credits")),
+        )
+        .description("Open-source projects kegtui is built on"),
+    ]
+}
+
+fn keg_header(app: &App, _state: &AsyncState) -> Option<Text<'static>> {
+    let current_keg = app.current_keg.as_ref()?;
+    let mut lines = vec![
+        Line::from(current_keg.keg.name.clone()).bold(),
+        if app.is_current_keg_running() {
+            Line::from("● running").green()
+        } else {
+            Line::from("○ stopped").dark_gray()
+        },
+    ];
+    let pending_count = app.tasks.pending_count(&current_keg.keg.name);
+    if pending_count > 0 {
+        lines.push(
+            Line::from(format!("◌ {pending_count} task(s) queued")).yellow(),
+        );
+    }
+    if let Some(entry) = app
+        .tasks
+        .entries()
+        .into_iter()
+        .rev()
+        .find(|entry| entry.keg_name == current_keg.keg.name)
+        && let TaskStatus::Finished {
+            problem: Some(problem),
+            ..
+        } = &entry.status
+    {
+        lines.push(
+            Line::from(format!("✗ {}: {problem}", entry.description)).red(),
+        );
+    }
+    Some(Text::from(lines))
+}
+
+fn keg_menu<'a>(app: &App, _state: &AsyncState) -> Vec<MenuItem<'a>> {
+    let is_running = app.is_current_keg_running();
+
+    let mut launch =
+        MenuItem::new("Launch", MenuItemAction::External(launch_keg))
+            .description("Starts the keg's configured program")
+            .requires(&[Requirement::CurrentKeg]);
+    let mut kill_processes = MenuItem::new(
+        if is_running {
+            "Kill Processes (running)"
+        } else {
+            "Kill Processes"
+        },
+        MenuItemAction::External(kill_wineserver),
+    )
+    .description("Runs WSS-wineserverkill to stop all processes from this keg")
+    .requires(&[Requirement::CurrentKeg]);
+    if is_running {
+        kill_processes = kill_processes.default();
+    } else {
+        launch = launch.default();
+    }
+
+    let resume = app
+        .current_keg
+        .as_ref()
+        .and_then(|current_keg| {
+            app.last_executables.get(&current_keg.bundle_path())
+        })
+        .and_then(|executable| executable.file_name())
+        .map(|file_name| {
+            MenuItem::new(
+                format!("Resume {}", file_name.to_string_lossy()),
+                MenuItemAction::External(resume_executable),
+            )
+            .description("Runs the executable last run in this keg again")
+            .requires(&[Requirement::CurrentKeg])
+        });
+    let custom_commands = app
+        .current_keg
+        .iter()
+        .flat_map(|current_keg| &current_keg.plist.kegtui_commands)
+        .enumerate()
+        .map(|(index, (name, command))| {
+            MenuItem::new(
+                name.clone(),
+                MenuItemAction::ExternalAt(run_custom_command, index),
+            )
+            .description(format!("Runs `{command}` in the C drive"))
+            .requires(&[Requirement::CurrentKeg])
+        });
+
+    let mut menu = vec![
+        MenuItem::new("Back", MenuItemAction::NavAction(NavAction::Pop))
+            .description("Return to the main menu"),
+        launch,
+    ];
+    menu.extend(resume);
+    menu.extend(custom_commands);
+    menu.extend([
+        MenuItem::new(
+            "Launch with Arguments",
+            MenuItemAction::External(launch_keg_with_arguments),
+        )
+        .description("Launches once with extra program flags")
+        .requires(&[Requirement::CurrentKeg]),
+        MenuItem::new("Install", MenuItemAction::External(install_software))
+            .description("Runs a Windows installer (.exe or .msi) in this keg")
+            .requires(&[Requirement::CurrentKeg]),
+        MenuItem::new(
+            "Run Executable",
+            MenuItemAction::LoadView(ViewID::Named("executables")),
+        )
+        .description("Runs another .exe in the C drive, e.g., a config tool")
+        .requires(&[Requirement::CurrentKeg]),
+        MenuItem::new(
+            "Live Log",
+            MenuItemAction::LoadView(ViewID::Named("keg_log")),
+        )
+        .description("Follows the log of the keg's last launch"),
+        MenuItem::new(
+            "Winetricks",
+            MenuItemAction::LoadView(ViewID::Named("winetricks_browser")),
+        )
+        .description("Installs winetricks verbs into this keg")
+        .requires(&[Requirement::CurrentKeg]),
+        MenuItem::new(
+            "Undo Winetricks",
+            MenuItemAction::External(undo_winetricks),
+        )
+        .description("Restores the prefix from before the last winetricks")
+        .requires(&[Requirement::CurrentKeg]),
+        MenuItem::new(
+            "Task Queue",
+            MenuItemAction::LoadView(ViewID::Named("tasks")),
+        )
+        .description("Shows queued and recent winetricks installs"),
+        MenuItem::new("Open C Drive", MenuItemAction::External(open_c_drive))
+            .description("Opens drive_c in your explorer")
+            .requires(&[Requirement::CurrentKeg]),
+        MenuItem::new(
+            "Edit Config",
+            MenuItemAction::LoadView(ViewID::Named("config_form")),
+        )
+        .description("Edits this keg's Kegworks settings")
+        .requires(&[Requirement::CurrentKeg]),
+        MenuItem::new(
+            "Edit Config as TOML",
+            MenuItemAction::External(edit_config),
+        )
+        .description(
+            "Edits this keg's settings, including environment variables, as TOML",
+        )
+        .requires(&[Requirement::CurrentKeg]),
+        MenuItem::new("Apply Preset", MenuItemAction::External(apply_preset))
+            .description("Applies a named set of settings, e.g., DXVK gaming")
+            .requires(&[Requirement::CurrentKeg]),
+        MenuItem::new(
+            "GPU and CPU",
+            MenuItemAction::External(configure_hardware),
+        )
+        .description("Shows the GPU and limits how many CPUs Wine sees")
+        .requires(&[Requirement::CurrentKeg]),
+        MenuItem::new("Probe Graphics", MenuItemAction::External(probe_keg))
+            .description("Tests which graphics translations should work")
+            .requires(&[Requirement::CurrentKeg]),
+        MenuItem::new(
+            "start.exe Paths",
+            MenuItemAction::External(configure_start_exe),
+        )
+        .description(
+            "Sets the program path and working directory for start.exe",
+        )
+        .requires(&[Requirement::CurrentKeg]),
+        MenuItem::new(
+            "Display Mode",
+            MenuItemAction::External(configure_display_mode),
+        )
+        .description("Forces the program windowed, borderless, or fullscreen")
+        .requires(&[Requirement::CurrentKeg]),
+        MenuItem::new("Registry", MenuItemAction::External(edit_registry))
+            .description("Applies a .reg snippet or file, or opens regedit")
+            .requires(&[Requirement::CurrentKeg]),
+        MenuItem::new("Measure Size", MenuItemAction::External(measure_keg_size))
+            .description("Adds up the keg's size on disk in the Task Queue")
+            .requires(&[Requirement::CurrentKeg]),
+        MenuItem::new("Rename Keg", MenuItemAction::External(rename_keg))
+            .description("Renames this keg's bundle and bundle identifier")
+            .requires(&[Requirement::CurrentKeg]),
+        MenuItem::new(
+            "Update Wrapper",
+            MenuItemAction::External(update_keg_wrapper),
+        )
+        .description(
+            "Replaces the launcher and frameworks with a newer wrapper's",
+        )
+        .requires(&[Requirement::CurrentKeg]),
+        MenuItem::new("App Info", MenuItemAction::External(edit_app_info))
+            .description("Edits the wrapper's name, version, and macOS minimum")
+            .requires(&[Requirement::CurrentKeg]),
+        MenuItem::new(
+            "Info",
+            MenuItemAction::LoadView(ViewID::Named("keg_info")),
+        )
+        .description("Shows the engine, paths, and crash dumps"),
+        MenuItem::new(
+            "Open Crash Dumps",
+            MenuItemAction::External(open_crash_dumps),
+        )
+        .description("Opens the folder with the newest crash dump")
+        .requires(&[Requirement::CurrentKeg]),
+        MenuItem::new(
+            "Previous Runs",
+            MenuItemAction::LoadView(ViewID::Named("keg_runs")),
+        )
+        .description("Shows the saved output of launches and installs"),
+        MenuItem::new(
+            "History",
+            MenuItemAction::LoadView(ViewID::Named("history")),
+        )
+        .description("Lists every change kegtui made to this keg"),
+        MenuItem::new("Compare", MenuItemAction::External(compare_kegs))
+            .description(
+                "Diffs this keg's settings and winetricks against another's",
+            ),
+        MenuItem::new(
+            "Export Diagnostics",
+            MenuItemAction::External(export_keg_diagnostics),
+        )
+        .description("Bundles config, logs, and crash dumps for a bug report")
+        .requires(&[Requirement::CurrentKeg]),
+        MenuItem::new(
+            "Desktop Launcher",
+            MenuItemAction::External(create_desktop_launcher),
+        )
+        .description("Creates an app on the Desktop that opens this keg")
+        .requires(&[Requirement::CurrentKeg]),
+        MenuItem::new("Archive Keg", MenuItemAction::External(archive_keg))
+            .description(
+                "Saves the whole keg as a .tar.xz in a folder you pick",
+            )
+            .requires(&[Requirement::CurrentKeg]),
+        MenuItem::new("Export Info", MenuItemAction::External(export_keg_info))
+            .description("Saves the Info view as text or Markdown")
+            .requires(&[Requirement::CurrentKeg]),
+        MenuItem::new(
+            "Export Manifest",
+            MenuItemAction::External(export_keg_manifest),
+        )
+        .description("Saves a shareable recipe for rebuilding this keg")
+        .requires(&[Requirement::CurrentKeg]),
+        MenuItem::new(
+            "Apply Manifest",
+            MenuItemAction::External(apply_keg_manifest),
+        )
+        .description(
+            "Applies a keg manifest's config, winetricks, and installers",
+        )
+        .requires(&[Requirement::CurrentKeg]),
+        MenuItem::new(
+            "Delete Keg",
+            MenuItemAction::LoadView(ViewID::Named("delete_keg")),
+        )
+        .description("Kills this keg's processes and deletes its bundle")
+        .requires(&[Requirement::CurrentKeg]),
+        MenuItem::new(
+            "Processes",
+            MenuItemAction::LoadView(ViewID::Named("keg_processes")),
+        )
+        .description("Shows what's running from this keg, updated every second")
+        .requires(&[Requirement::CurrentKeg]),
+        kill_processes,
+    ]);
+    menu
+}
+
+/// Registers every view and nav, returning the main nav the TUI starts in.
+pub fn register_navs<'a>(context: &mut NavContext<'a>) -> NavID<'a> {
+    context.view("kegs", &views::kegs::KegsView);
+    context.view("keg_creator", &views::keg_creator::KegCreatorView);
+    context.view("credits", &views::credits::CreditsView);
+
+    let main_nav = context.dynamic_nav("main", main_menu);
+
+    context.view("keg_info", &views::keg_info::KegInfoView);
+    context.view("config_form", &views::config_form::ConfigFormView);
+    context.view("keg_runs", &views::keg_runs::KegRunsView);
+    context.view("tasks", &views::tasks::TasksView);
+    context.view("history", &views::history::HistoryView);
+    context.view("keg_log", &views::keg_log::KegLogView);
+    context.view("keg_processes", &views::keg_processes::KegProcessesView);
+    context.view("executables", &views::executables::ExecutablesView);
+    context.view("delete_keg", &views::delete_keg::DeleteKegView);
+    context.view(
+        "winetricks_browser",
+        &views::winetricks_browser::WinetricksBrowserView,
+    );
+    context.view(
+        "winetricks_cache",
+        &views::winetricks_cache::WinetricksCacheView,
+    );
+    let keg_nav = context.dynamic_nav("keg", keg_menu);
+    context.nav_header(keg_nav, keg_header);
+
+    let engines_view = context.view("engines", &views::engines::EnginesView);
+    let engine_inventory_view = context.view(
+        "engine_inventory",
+        &views::engine_inventory::EngineInventoryView,
+    );
+    context.nav(
+        "engines",
+        [
+            MenuItem::new("Back", MenuItemAction::NavAction(NavAction::Pop))
+                .description("Return to the main menu"),
+            MenuItem::new("Installed", MenuItemAction::LoadView(engines_view))
+                .description("Engines and wrappers in your search paths")
+                .default(),
+            MenuItem::new(
+                "Inventory",
+                MenuItemAction::LoadView(engine_inventory_view),
+            )
+            .description(
+                "Each engine's version, size, and kegs, to delete old ones",
+            ),
+            MenuItem::new(
+                "Install from Homebrew",
+                MenuItemAction::External(install_from_brew_taps),
+            )
+            .description("Lists and installs packages from your brew-taps")
+            .requires(&[Requirement::Network]),
+            MenuItem::new(
+                "Prune Engines",
+                MenuItemAction::External(prune_engines),
+            )
+            .description("Deletes old engines that no keg uses"),
+        ],
+    );
+
+    let setup_view = context.view("setup", &views::setup::SetupView);
+    context.nav(
+        "setup",
+        [
+            MenuItem::new("Back", MenuItemAction::NavAction(NavAction::Pop))
+                .description("Return to the main menu"),
+            MenuItem::new("Components", MenuItemAction::LoadView(setup_view))
+                .description("Choose what to install")
+                .default(),
+            MenuItem::new(
+                "Install Selected",
+                MenuItemAction::External(install_setup_components),
+            )
+            .description("Installs the checked components in order")
+            .requires(&[Requirement::Network]),
+        ],
+    );
+
+    let about_view = context.view("about", &views::about::AboutView);
+    context.nav(
+        "about",
+        [
+            MenuItem::new("Back", MenuItemAction::NavAction(NavAction::Pop))
+                .description("Return to the main menu"),
+            MenuItem::new("Versions", MenuItemAction::LoadView(about_view))
+                .description("kegtui, Kegworks, and engine versions")
+                .default(),
+            MenuItem::new(
+                "Check for Updates",
+                MenuItemAction::External(check_for_updates),
+            )
+            .description("Asks GitHub for the latest kegtui release")
+            .requires(&[Requirement::Network]),
+        ],
+    );
+
+    main_nav
+}
@@ -0,0 +1,61 @@
+// Copyright (C) 2026 Ethan Uppal.
+//
+// This program is free software: you can redistribute it and/or modify it under
+// the terms of the GNU General Public License as published by the Free Software
+// Foundation, version 3 of the License only.
+//
+// This program is distributed in the hope that it will be useful, but WITHOUT
+// ANY WARRANTY; without even the implied warranty of MERCHANTABILITY or FITNESS
+// FOR A PARTICULAR PURPOSE. See the GNU General Public License for more
+// details.
+//
+// You should have received a copy of the GNU General Public License along with
+// this program.  If not, see <https://www.gnu.org/licenses/>.
+
+//! Tells the user a long task finished, for when they've stopped watching
+//! kegtui, by ringing the terminal bell or posting a macOS notification.
+
+use std::{
+    io::{self, Write},
+    process::Command,
+    sync::Arc,
+    thread,
+    time::Duration,
+};
+
+use crate::{app_config::NotificationsConfig, runner::CommandRunner};
+
+/// Quotes `text` as an AppleScript string literal.
+fn applescript_string(text: &str) -> String {
+    format!("\"{}\"", text.replace('\\', "\\\\").replace('"', "\\\""))
+}
+
+/// Announces `message` as configured if the task took at least as long as
+/// `[notifications] min-seconds`.
+pub fn task_finished(
+    config: &NotificationsConfig,
+    runner: &Arc<dyn CommandRunner>,
+    message: &str,
+    took: Duration,
+) {
+    if took < Duration::from_secs(config.min_seconds) {
+        return;
+    }
+    if config.bell {
+        let mut stdout = io::stdout();
+        let _ = stdout.write_all(b"\x07");
+        let _ = stdout.flush();
+    }
+    if config.macos {
+        let script = format!(
+            "display notification {} with title \"kegtui\"",
+            applescript_string(message)
+        );
+        let runner = runner.clone();
+        // osascript can take a moment, so the TUI doesn't wait on it.
+        thread::spawn(move || {
+            let _ =
+                runner.output(Command::new("osascript").args(["-e", &script]));
+        });
+    }
+}
@@ -0,0 +1,309 @@
+// Copyright (C) 2026 Ethan Uppal.
+//
+// This program is free software: you can redistribute it and/or modify it under
+// the terms of the GNU General Public License as published by the Free Software
+// Foundation, version 3 of the License only.
+//
+// This program is distributed in the hope that it will be useful, but WITHOUT
+// ANY WARRANTY; without even the implied warranty of MERCHANTABILITY or FITNESS
+// FOR A PARTICULAR PURPOSE. See the GNU General Public License for more
+// details.
+//
+// You should have received a copy of the GNU General Public License along with
+// this program.  If not, see <https://www.gnu.org/licenses/>.
+
+//! Reads just enough of a Windows executable's PE headers and version
+//! resource to tell, e.g., `launcher.exe` from `crashhandler.exe`.
+
+use std::{
+    fmt::{self, Display},
+    fs::File,
+    io::{self, Read, Seek, SeekFrom},
+    path::Path,
+};
+
+const IMAGE_FILE_MACHINE_I386: u16 = 0x014c;
+const IMAGE_FILE_MACHINE_AMD64: u16 = 0x8664;
+const IMAGE_FILE_MACHINE_ARM64: u16 = 0xaa64;
+
+const IMAGE_SUBSYSTEM_WINDOWS_GUI: u16 = 2;
+const IMAGE_SUBSYSTEM_WINDOWS_CUI: u16 = 3;
+
+const PE32_MAGIC: u16 = 0x10b;
+const PE32_PLUS_MAGIC: u16 = 0x20b;
+
+const RESOURCE_DIRECTORY_INDEX: u64 = 2;
+const RT_VERSION: u32 = 16;
+const VS_FIXEDFILEINFO_SIGNATURE: [u8; 4] = 0xfeef04bd_u32.to_le_bytes();
+
+/// Version resources are small; anything bigger is likely corrupt.
+const MAX_VERSION_RESOURCE_SIZE: u32 = 64 * 1024;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Machine {
+    X86,
+    X64,
+    Arm64,
+    Other(u16),
+}
+
+impl Display for Machine {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Machine::X86 => write!(f, "x86 (32-bit)"),
+            Machine::X64 => write!(f, "x64 (64-bit)"),
+            Machine::Arm64 => write!(f, "ARM64"),
+            Machine::Other(machine) => write!(f, "machine {machine:#06x}"),
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Subsystem {
+    Gui,
+    Console,
+    Other(u16),
+}
+
+impl Display for Subsystem {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Subsystem::Gui => write!(f, "GUI"),
+            Subsystem::Console => write!(f, "console"),
+            Subsystem::Other(subsystem) => write!(f, "subsystem {subsystem}"),
+        }
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct ExecutableInfo {
+    pub machine: Machine,
+    pub subsystem: Subsystem,
+    /// From the version resource's fixed info, e.g., `1.2.0.0`.
+    pub file_version: Option<String>,
+    pub description: Option<String>,
+    pub product_name: Option<String>,
+}
+
+impl ExecutableInfo {
+    /// A one-line summary, e.g., `x64 (64-bit), GUI, v1.2.0.0, "Game"`.
+    pub fn summary(&self) -> String {
+        let mut parts =
+            vec![self.machine.to_string(), self.subsystem.to_string()];
+        if let Some(file_version) = &self.file_version {
+            parts.push(format!("v{file_version}"));
+        }
+        if let Some(name) =
+            self.description.as_ref().or(self.product_name.as_ref())
+        {
+            parts.push(format!("\"{name}\""));
+        }
+        parts.join(", ")
+    }
+}
+
+struct Section {
+    virtual_address: u32,
+    virtual_size: u32,
+    raw_size: u32,
+    raw_offset: u32,
+}
+
+fn invalid(message: &'static str) -> io::Error {
+    io::Error::new(io::ErrorKind::InvalidData, message)
+}
+
+fn read_at<const N: usize>(
+    file: &mut File,
+    offset: u64,
+) -> io::Result<[u8; N]> {
+    let mut buffer = [0; N];
+    file.seek(SeekFrom::Start(offset))?;
+    file.read_exact(&mut buffer)?;
+    Ok(buffer)
+}
+
+fn read_u16(file: &mut File, offset: u64) -> io::Result<u16> {
+    read_at(file, offset).map(u16::from_le_bytes)
+}
+
+fn read_u32(file: &mut File, offset: u64) -> io::Result<u32> {
+    read_at(file, offset).map(u32::from_le_bytes)
+}
+
+/// Converts a relative virtual address to an offset in the file.
+fn rva_to_offset(sections: &[Section], rva: u32) -> Option<u64> {
+    sections.iter().find_map(|section| {
+        let size = section.virtual_size.max(section.raw_size);
+        (section.virtual_address..section.virtual_address.saturating_add(size))
+            .contains(&rva)
+            .then(|| {
+                u64::from(rva - section.virtual_address)
+                    + u64::from(section.raw_offset)
+            })
+    })
+}
+
+/// Reads the PE headers and version resource of the executable at `path`.
+pub fn inspect(path: &Path) -> io::Result<ExecutableInfo> {
+    let mut file = File::open(path)?;
+    if &read_at::<2>(&mut file, 0)? != b"MZ" {
+        return Err(invalid("Not an MZ executable"));
+    }
+    let pe_offset = u64::from(read_u32(&mut file, 0x3c)?);
+    if &read_at::<4>(&mut file, pe_offset)? != b"PE\0\0" {
+        return Err(invalid("Not a PE executable"));
+    }
+
+    let coff_header = pe_offset + 4;
+    let machine = match read_u16(&mut file, coff_header)? {
+        IMAGE_FILE_MACHINE_I386 => Machine::X86,
+        IMAGE_FILE_MACHINE_AMD64 => Machine::X64,
+        IMAGE_FILE_MACHINE_ARM64 => Machine::Arm64,
+        other => Machine::Other(other),
+    };
+    let section_count = read_u16(&mut file, coff_header + 2)?;
+    let optional_header_size = read_u16(&mut file, coff_header + 16)?;
+
+    let optional_header = coff_header + 20;
+    let subsystem = match read_u16(&mut file, optional_header + 68)? {
+        IMAGE_SUBSYSTEM_WINDOWS_GUI => Subsystem::Gui,
+        IMAGE_SUBSYSTEM_WINDOWS_CUI => Subsystem::Console,
+        other => Subsystem::Other(other),
+    };
+    let data_directories = match read_u16(&mut file, optional_header)? {
+        PE32_MAGIC => optional_header + 96,
+        PE32_PLUS_MAGIC => optional_header + 112,
+        _ => return Err(invalid("Unknown optional header format")),
+    };
+
+    let section_table = optional_header + u64::from(optional_header_size);
+    let sections = (0..u64::from(section_count))
+        .map(|index| {
+            let header = section_table + index * 40;
+            Ok(Section {
+                virtual_size: read_u32(&mut file, header + 8)?,
+                virtual_address: read_u32(&mut file, header + 12)?,
+                raw_size: read_u32(&mut file, header + 16)?,
+                raw_offset: read_u32(&mut file, header + 20)?,
+            })
+        })
+        .collect::<io::Result<Vec<_>>>()?;
+
+    let mut info = ExecutableInfo {
+        machine,
+        subsystem,
+        file_version: None,
+        description: None,
+        product_name: None,
+    };
+    // A missing or malformed version resource still leaves the headers,
+    // which are what matter most.
+    let resource_rva =
+        read_u32(&mut file, data_directories + RESOURCE_DIRECTORY_INDEX * 8)
+            .unwrap_or(0);
+    if resource_rva != 0
+        && let Some(resources) = rva_to_offset(&sections, resource_rva)
+        && let Ok(Some(version)) =
+            read_version_resource(&mut file, &sections, resources)
+    {
+        info.file_version = fixed_file_version(&version);
+        info.description = version_string(&version, "FileDescription");
+        info.product_name = version_string(&version, "ProductName");
+    }
+    Ok(info)
+}
+
+/// Finds the entry for `id` in the resource directory at `directory`, or the
+/// first entry if `id` is `None`, returning its offset from `resources` and
+/// whether it is another directory.
+fn find_resource_entry(
+    file: &mut File,
+    resources: u64,
+    directory: u64,
+    id: Option<u32>,
+) -> io::Result<Option<(u64, bool)>> {
+    let named_count = read_u16(file, resources + directory + 12)?;
+    let id_count = read_u16(file, resources + directory + 14)?;
+    for index in 0..u64::from(named_count) + u64::from(id_count) {
+        let entry = resources + directory + 16 + index * 8;
+        let name = read_u32(file, entry)?;
+        if id.is_none_or(|id| id == name) {
+            let offset = read_u32(file, entry + 4)?;
+            return Ok(Some((
+                u64::from(offset & 0x7fff_ffff),
+                offset & 0x8000_0000 != 0,
+            )));
+        }
+    }
+    Ok(None)
+}
+
+/// Reads the `VS_VERSIONINFO` resource, taking the first name and language.
+fn read_version_resource(
+    file: &mut File,
+    sections: &[Section],
+    resources: u64,
+) -> io::Result<Option<Vec<u8>>> {
+    let mut entry = find_resource_entry(file, resources, 0, Some(RT_VERSION))?;
+    for _ in 0..2 {
+        entry = match entry {
+            Some((directory, true)) => {
+                find_resource_entry(file, resources, directory, None)?
+            }
+            _ => return Ok(None),
+        };
+    }
+    let Some((data_entry, false)) = entry else {
+        return Ok(None);
+    };
+    let data_rva = read_u32(file, resources + data_entry)?;
+    let size = read_u32(file, resources + data_entry + 4)?;
+    let Some(offset) = rva_to_offset(sections, data_rva) else {
+        return Ok(None);
+    };
+    let mut version = vec![0; size.min(MAX_VERSION_RESOURCE_SIZE) as usize];
+    file.seek(SeekFrom::Start(offset))?;
+    file.read_exact(&mut version)?;
+    Ok(Some(version))
+}
+
+fn fixed_file_version(version: &[u8]) -> Option<String> {
+    let signature = version
+        .windows(4)
+        .position(|window| window == VS_FIXEDFILEINFO_SIGNATURE)?;
+    let word = |offset: usize| {
+        let bytes = version.get(signature + offset..signature + offset + 4)?;
+        Some(u32::from_le_bytes(bytes.try_into().ok()?))
+    };
+    let (most, least) = (word(8)?, word(12)?);
+    Some(format!(
+        "{}.{}.{}.{}",
+        most >> 16,
+        most & 0xffff,
+        least >> 16,
+        least & 0xffff
+    ))
+}
+
+/// Reads the value of `key` from the version resource's string table, where
+/// each value follows its UTF-16 key, padded to a 32-bit boundary.
+fn version_string(version: &[u8], key: &str) -> Option<String> {
+    let needle = key
+        .encode_utf16()
+        .chain([0])
+        .flat_map(u16::to_le_bytes)
+        .collect::<Vec<_>>();
+    let key_start = version
+        .windows(needle.len())
+        .position(|window| window == needle.as_slice())?;
+    let value_start = (key_start + needle.len()).next_multiple_of(4);
+    let value = version
+        .get(value_start..)?
+        .chunks_exact(2)
+        .map(|pair| u16::from_le_bytes([pair[0], pair[1]]))
+        .take_while(|&unit| unit != 0)
+        .collect::<Vec<_>>();
+    let value = String::from_utf16_lossy(&value).trim().to_owned();
+    (!value.is_empty()).then_some(value)
+}
@@ -0,0 +1,329 @@
+// Copyright (C) 2026 Ethan Uppal.
+//
+// This program is free software: you can redistribute it and/or modify it under
+// the terms of the GNU General Public License as published by the Free Software
+// Foundation, version 3 of the License only.
+//
+// This program is distributed in the hope that it will be useful, but WITHOUT
+// ANY WARRANTY; without even the implied warranty of MERCHANTABILITY or FITNESS
+// FOR A PARTICULAR PURPOSE. See the GNU General Public License for more
+// details.
+//
+// You should have received a copy of the GNU General Public License along with
+// this program.  If not, see <https://www.gnu.org/licenses/>.
+
+//! A keyboard-driven file and folder picker for external actions, which run
+//! outside the TUI but can borrow the terminal back to show it.
+
+use std::{
+    fs, io,
+    path::{Path, PathBuf},
+};
+
+use color_eyre::Result;
+use crossterm::{
+    ExecutableCommand,
+    event::{self, Event, KeyCode, KeyEventKind},
+    terminal::{
+        EnterAlternateScreen, LeaveAlternateScreen, disable_raw_mode,
+        enable_raw_mode,
+    },
+};
+use ratatui::{
+    Terminal,
+    backend::CrosstermBackend,
+    prelude::*,
+    widgets::{Block, Borders, List, ListItem, ListState, Paragraph},
+};
+
+use crate::{
+    app::SELECTED_FOCUSED_STYLE,
+    pe::{self, ExecutableInfo},
+};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum PickKind {
+    File,
+    Directory,
+}
+
+struct Entry {
+    name: String,
+    is_directory: bool,
+}
+
+enum Row<'a> {
+    /// Picks the folder being browsed, offered only for
+    /// [`PickKind::Directory`].
+    UseThisFolder,
+    Entry(&'a Entry),
+}
+
+struct PathPicker {
+    kind: PickKind,
+    directory: PathBuf,
+    entries: Vec<Entry>,
+    /// Typed text that narrows the entries down.
+    filter: String,
+    show_hidden: bool,
+    selected: usize,
+    /// The last executable inspected, so headers aren't reread every frame.
+    inspected: Option<(PathBuf, Option<ExecutableInfo>)>,
+}
+
+enum Step {
+    Continue,
+    Cancel,
+    Pick(PathBuf),
+}
+
+impl PathPicker {
+    fn new(start: &Path, kind: PickKind) -> Self {
+        let mut picker = Self {
+            kind,
+            directory: start.to_path_buf(),
+            entries: vec![],
+            filter: String::new(),
+            show_hidden: false,
+            selected: 0,
+            inspected: None,
+        };
+        picker.open(start.to_path_buf());
+        picker
+    }
+
+    fn open(&mut self, directory: PathBuf) {
+        self.directory = directory;
+        self.filter.clear();
+        self.selected = 0;
+        self.refresh();
+    }
+
+    fn refresh(&mut self) {
+        let mut entries = fs::read_dir(&self.directory)
+            .map(|read_dir| {
+                read_dir
+                    .flatten()
+                    .map(|entry| Entry {
+                        name: entry.file_name().to_string_lossy().into_owned(),
+                        is_directory: entry.path().is_dir(),
+                    })
+                    .filter(|entry| {
+                        self.show_hidden || !entry.name.starts_with('.')
+                    })
+                    .filter(|entry| {
+                        self.kind == PickKind::File || entry.is_directory
+                    })
+                    .collect::<Vec<_>>()
+            })
+            .unwrap_or_default();
+        entries.sort_by(|a, b| {
+            b.is_directory
+                .cmp(&a.is_directory)
+                .then_with(|| a.name.to_lowercase().cmp(&b.name.to_lowercase()))
+        });
+        self.entries = entries;
+    }
+
+    fn rows(&self) -> Vec<Row<'_>> {
+        let filter = self.filter.to_lowercase();
+        let mut rows = vec![];
+        if self.kind == PickKind::Directory && filter.is_empty() {
+            rows.push(Row::UseThisFolder);
+        }
+        rows.extend(
+            self.entries
+                .iter()
+                .filter(|entry| entry.name.to_lowercase().contains(&filter))
+                .map(Row::Entry),
+        );
+        rows
+    }
+
+    fn handle_key(&mut self, code: KeyCode) -> Step {
+        let row_count = self.rows().len();
+        match code {
+            KeyCode::Esc => return Step::Cancel,
+            KeyCode::Tab => {
+                self.show_hidden = !self.show_hidden;
+                self.selected = 0;
+                self.refresh();
+            }
+            KeyCode::Up => self.selected = self.selected.saturating_sub(1),
+            KeyCode::Down if self.selected + 1 < row_count => {
+                self.selected += 1;
+            }
+            KeyCode::Backspace if !self.filter.is_empty() => {
+                self.filter.pop();
+                self.selected = 0;
+            }
+            KeyCode::Backspace | KeyCode::Left => {
+                if let Some(parent) = self.directory.parent() {
+                    self.open(parent.to_path_buf());
+                }
+            }
+            KeyCode::Enter | KeyCode::Right => {
+                let picked = match self.rows().get(self.selected) {
+                    Some(Row::UseThisFolder) => {
+                        return Step::Pick(self.directory.clone());
+                    }
+                    Some(Row::Entry(entry)) => {
+                        (self.directory.join(&entry.name), entry.is_directory)
+                    }
+                    None => return Step::Continue,
+                };
+                match picked {
+                    (directory, true) => self.open(directory),
+                    (file, false) if code == KeyCode::Enter => {
+                        return Step::Pick(file);
+                    }
+                    _ => {}
+                }
+            }
+            KeyCode::Char(character) => {
+                self.filter.push(character);
+                self.selected = 0;
+            }
+            _ => {}
+        }
+        Step::Continue
+    }
+
+    /// Describes the highlighted file if it is a Windows executable, to help
+    /// tell a game's real binary from its launcher or crash handler.
+    fn selected_executable_summary(&mut self) -> Option<String> {
+        let path = match self.rows().get(self.selected) {
+            Some(Row::Entry(entry))
+                if !entry.is_directory
+                    && entry.name.to_lowercase().ends_with(".exe") =>
+            {
+                self.directory.join(&entry.name)
+            }
+            _ => return None,
+        };
+        if self
+            .inspected
+            .as_ref()
+            .is_none_or(|(inspected, _)| *inspected != path)
+        {
+            let info = pe::inspect(&path).ok();
+            self.inspected = Some((path, info));
+        }
+        let (_, info) = self.inspected.as_ref()?;
+        Some(match info {
+            Some(info) => info.summary(),
+            None => "Not a valid Windows executable".to_owned(),
+        })
+    }
+
+    fn draw(&mut self, frame: &mut Frame) {
+        let block = Block::default()
+            .borders(Borders::ALL)
+            .title(match self.kind {
+                PickKind::File => " Choose a file ",
+                PickKind::Directory => " Choose a folder ",
+            })
+            .title_bottom(
+                Line::from(
+                    " <Enter> open/choose | <Backspace> up | <Tab> hidden files | <Esc> cancel ",
+                )
+                .centered(),
+            );
+        let inner_area = block.inner(frame.area());
+        frame.render_widget(block, frame.area());
+
+        let chunks = Layout::default()
+            .direction(Direction::Vertical)
+            .constraints([
+                Constraint::Length(1),
+                Constraint::Length(1),
+                Constraint::Length(1),
+                Constraint::Fill(1),
+                Constraint::Length(1),
+            ])
+            .split(inner_area);
+
+        let mut breadcrumbs = vec![];
+        let components = self.directory.components().collect::<Vec<_>>();
+        for (i, component) in components.iter().enumerate() {
+            if i > 0 {
+                breadcrumbs.push(" › ".dark_gray());
+            }
+            let name = component.as_os_str().to_string_lossy().into_owned();
+            breadcrumbs.push(if i + 1 == components.len() {
+                name.bold()
+            } else {
+                name.dark_gray()
+            });
+        }
+        frame.render_widget(Line::from(breadcrumbs), chunks[0]);
+        frame.render_widget(
+            Paragraph::new(Line::from(vec![
+                "Filter: ".bold(),
+                self.filter.clone().into(),
+                "_".dark_gray(),
+                if self.show_hidden {
+                    "  (showing hidden files)".dark_gray()
+                } else {
+                    "".into()
+                },
+            ])),
+            chunks[1],
+        );
+
+        let items = self
+            .rows()
+            .into_iter()
+            .map(|row| match row {
+                Row::UseThisFolder => {
+                    ListItem::new("[Use this folder]".green())
+                }
+                Row::Entry(entry) if entry.is_directory => {
+                    ListItem::new(format!("{}/", entry.name).blue())
+                }
+                Row::Entry(entry) => ListItem::new(entry.name.clone()),
+            })
+            .collect::<Vec<_>>();
+        let list = List::new(items)
+            .highlight_style(SELECTED_FOCUSED_STYLE)
+            .highlight_symbol(">> ");
+        frame.render_stateful_widget(
+            list,
+            chunks[3],
+            &mut ListState::default().with_selected(Some(self.selected)),
+        );
+
+        if let Some(summary) = self.selected_executable_summary() {
+            frame.render_widget(Line::from(summary.dark_gray()), chunks[4]);
+        }
+    }
+}
+
+/// Lets the user browse from `start` to a file or folder, returning `None` if
+/// they cancel. Meant to be called from an external action.
+pub fn pick_path(start: &Path, kind: PickKind) -> Result<Option<PathBuf>> {
+    io::stdout().execute(EnterAlternateScreen)?;
+    enable_raw_mode()?;
+    let result = run(start, kind);
+    disable_raw_mode()?;
+    io::stdout().execute(LeaveAlternateScreen)?;
+    result
+}
+
+fn run(start: &Path, kind: PickKind) -> Result<Option<PathBuf>> {
+    let mut terminal = Terminal::new(CrosstermBackend::new(io::stdout()))?;
+    terminal.clear()?;
+    let mut picker = PathPicker::new(start, kind);
+    loop {
+        terminal.draw(|frame| picker.draw(frame))?;
+        if let Event::Key(key_event) = event::read()?
+            && key_event.kind == KeyEventKind::Press
+        {
+            match picker.handle_key(key_event.code) {
+                Step::Continue => {}
+                Step::Cancel => return Ok(None),
+                Step::Pick(path) => return Ok(Some(path)),
+            }
+        }
+    }
+}
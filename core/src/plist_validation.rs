@@ -0,0 +1,110 @@
+// Copyright (C) 2026 Ethan Uppal.
+//
+// This program is free software: you can redistribute it and/or modify it under
+// the terms of the GNU General Public License as published by the Free Software
+// Foundation, version 3 of the License only.
+//
+// This program is distributed in the hope that it will be useful, but WITHOUT
+// ANY WARRANTY; without even the implied warranty of MERCHANTABILITY or FITNESS
+// FOR A PARTICULAR PURPOSE. See the GNU General Public License for more
+// details.
+//
+// You should have received a copy of the GNU General Public License along with
+// this program.  If not, see <https://www.gnu.org/licenses/>.
+
+//! Checks a keg's settings before they're written, so a typo or a clashing
+//! pair of settings is explained instead of breaking the keg's next launch.
+
+use std::{error::Error, fmt, path::Path};
+
+use crate::{keg_plist::KegPlist, wine_path::WinePath};
+
+/// Why [`crate::keg::CurrentKeg::save_plist`] didn't save.
+#[derive(Debug)]
+pub enum SavePlistError {
+    /// Settings that wouldn't work, explained for the user.
+    Invalid(Vec<String>),
+    Write(plist::Error),
+}
+
+impl fmt::Display for SavePlistError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            SavePlistError::Invalid(problems) => write!(
+                f,
+                "The keg's settings weren't saved: {}",
+                problems.join("; ")
+            ),
+            SavePlistError::Write(error) => {
+                write!(f, "Failed to write the keg's settings: {error}")
+            }
+        }
+    }
+}
+
+impl Error for SavePlistError {}
+
+impl From<plist::Error> for SavePlistError {
+    fn from(error: plist::Error) -> Self {
+        SavePlistError::Write(error)
+    }
+}
+
+/// What's wrong with `plist` for the keg whose C drive is `c_drive`, or
+/// nothing if it would work. The program is only required to exist if
+/// `is_program_required`, since it may be set before it's installed.
+pub fn problems(
+    plist: &KegPlist,
+    c_drive: &Path,
+    is_program_required: bool,
+) -> Vec<String> {
+    let mut problems = vec![];
+    if is_program_required && !plist.program_name_and_path.is_empty() {
+        let program = WinePath::from_kegworks(&plist.program_name_and_path)
+            .to_host(c_drive);
+        if !program.is_file() {
+            problems.push(format!(
+                "The program {} isn't in drive_c",
+                plist.program_name_and_path
+            ));
+        }
+    }
+
+    let gamma = plist.gamma_correction.trim();
+    if !gamma.is_empty()
+        && !gamma.eq_ignore_ascii_case("default")
+        && !gamma.parse::<f64>().is_ok_and(|gamma| gamma > 0.0)
+    {
+        problems.push(format!(
+            "Gamma correction \"{gamma}\" isn't a positive number like 1.0 or \"default\""
+        ));
+    }
+
+    let translations = [
+        ("D3DMetal", plist.d3d_metal),
+        ("DXVK", plist.dxvk),
+        ("DXMT", plist.dxmt),
+    ]
+    .into_iter()
+    .filter_map(|(name, is_on)| is_on.then_some(name))
+    .collect::<Vec<_>>();
+    if translations.len() > 1 {
+        problems.push(format!(
+            "Only one graphics translation can be on, but {} are",
+            translations.join(" and ")
+        ));
+    }
+    if plist.wine_esync && plist.wine_msync {
+        problems.push(
+            "esync and msync can't both be on; prefer msync on macOS"
+                .to_owned(),
+        );
+    }
+    if plist.kegtui_high_priority && !plist.kegtui_launch_mode.is_direct() {
+        problems.push(
+            "High priority only works with the \"direct\" launch mode"
+                .to_owned(),
+        );
+    }
+    problems
+}
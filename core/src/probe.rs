@@ -0,0 +1,456 @@
+// Copyright (C) 2026 Ethan Uppal.
+//
+// This program is free software: you can redistribute it and/or modify it under
+// the terms of the GNU General Public License as published by the Free Software
+// Foundation, version 3 of the License only.
+//
+// This program is distributed in the hope that it will be useful, but WITHOUT
+// ANY WARRANTY; without even the implied warranty of MERCHANTABILITY or FITNESS
+// FOR A PARTICULAR PURPOSE. See the GNU General Public License for more
+// details.
+//
+// You should have received a copy of the GNU General Public License along with
+// this program.  If not, see <https://www.gnu.org/licenses/>.
+
+//! Probes what a keg's engine can do on this Mac, by running small programs
+//! inside the keg and inspecting what the engine bundles, to tell which
+//! graphics translation is worth enabling.
+
+use std::{
+    fs,
+    path::{Path, PathBuf},
+    process::Command,
+    time::Duration,
+};
+
+use walkdir::WalkDir;
+
+use crate::{
+    arch, hardware,
+    keg::CurrentKeg,
+    runner::{CommandRunner, Completion},
+    translation_layers::{LayerVersion, inspect_engine},
+    wine_path::WinePath,
+};
+
+/// How long each program run inside the keg may take. The first run may
+/// also have to update the prefix.
+const PROBE_TIMEOUT: Duration = Duration::from_secs(90);
+
+/// How deep into the engine to look for Wine's own libraries.
+const ENGINE_SEARCH_DEPTH: usize = 6;
+
+/// Where `dxdiag` writes its report, relative to the C drive.
+const DXDIAG_REPORT: &str = "kegtui-dxdiag.txt";
+
+/// D3DMetal needs macOS Sonoma or later.
+const D3D_METAL_MINIMUM_MACOS: u32 = 14;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Verdict {
+    Works,
+    Fails,
+    Unknown,
+}
+
+impl Verdict {
+    pub fn symbol(self) -> &'static str {
+        match self {
+            Verdict::Works => "✓",
+            Verdict::Fails => "✗",
+            Verdict::Unknown => "?",
+        }
+    }
+}
+
+pub struct Finding {
+    pub name: &'static str,
+    pub verdict: Verdict,
+    pub detail: String,
+    /// The config key that turns this graphics backend on, if any, e.g.,
+    /// `translation.dxmt`.
+    pub config_key: Option<&'static str>,
+}
+
+impl Finding {
+    fn new(name: &'static str, verdict: Verdict, detail: String) -> Self {
+        Self {
+            name,
+            verdict,
+            detail,
+            config_key: None,
+        }
+    }
+
+    fn enabled_by(mut self, config_key: &'static str) -> Self {
+        self.config_key = Some(config_key);
+        self
+    }
+}
+
+pub struct ProbeReport {
+    /// What the Mac, engine, and prefix support.
+    pub checks: Vec<Finding>,
+    /// Each graphics backend, fastest first.
+    pub backends: Vec<Finding>,
+}
+
+impl ProbeReport {
+    /// The fastest backend that should work and has a config key.
+    pub fn recommendation(&self) -> Option<&Finding> {
+        self.backends.iter().find(|backend| {
+            backend.verdict == Verdict::Works && backend.config_key.is_some()
+        })
+    }
+}
+
+fn find_in_engine(engine: &Path, names: &[&str]) -> Option<PathBuf> {
+    WalkDir::new(engine)
+        .max_depth(ENGINE_SEARCH_DEPTH)
+        .into_iter()
+        .flatten()
+        .find(|entry| {
+            entry
+                .file_name()
+                .to_str()
+                .is_some_and(|name| names.contains(&name))
+        })
+        .map(|entry| entry.into_path())
+}
+
+/// Whether the file at `path` mentions `needle`, e.g., an environment
+/// variable only builds with some feature read.
+fn file_mentions(path: &Path, needle: &[u8]) -> bool {
+    fs::read(path).is_ok_and(|bytes| {
+        bytes.windows(needle.len()).any(|window| window == needle)
+    })
+}
+
+/// The major version of macOS, e.g., `15`.
+fn macos_major_version(runner: &dyn CommandRunner) -> Option<u32> {
+    let output = runner
+        .output(Command::new("sw_vers").arg("-productVersion"))
+        .ok()?;
+    String::from_utf8_lossy(&output.stdout)
+        .trim()
+        .split('.')
+        .next()?
+        .parse()
+        .ok()
+}
+
+/// The Metal version in `system_profiler`'s description, e.g., `3` for
+/// `Metal 3`.
+fn metal_version(metal: &str) -> Option<u32> {
+    metal
+        .split_once("Metal ")?
+        .1
+        .split(|c: char| !c.is_ascii_digit())
+        .next()?
+        .parse()
+        .ok()
+}
+
+/// Decodes a report Wine wrote, which may be UTF-16 with a byte order mark.
+fn decode_report(bytes: &[u8]) -> String {
+    match bytes {
+        [0xff, 0xfe, rest @ ..] => String::from_utf16_lossy(
+            &rest
+                .chunks_exact(2)
+                .map(|pair| u16::from_le_bytes([pair[0], pair[1]]))
+                .collect::<Vec<_>>(),
+        ),
+        _ => String::from_utf8_lossy(bytes).into_owned(),
+    }
+}
+
+/// The value after `key` on the first line that starts with it.
+fn report_value(report: &str, key: &str) -> Option<String> {
+    report.lines().find_map(|line| {
+        let value = line.trim().strip_prefix(key)?;
+        let value = value.trim_start_matches([':', '=', ' ']).trim();
+        (!value.is_empty()).then(|| value.to_owned())
+    })
+}
+
+struct Wine<'a> {
+    keg: &'a CurrentKeg,
+    runner: &'a dyn CommandRunner,
+    is_cancelled: &'a dyn Fn() -> bool,
+}
+
+impl Wine<'_> {
+    /// Runs `wine` with `arguments` in the keg, returning its output if it
+    /// exited successfully.
+    fn run(&self, arguments: &[&str]) -> Option<String> {
+        let mut command = Command::new(self.keg.wine_executable());
+        command
+            .env_clear()
+            .envs(self.keg.winetricks_environment())
+            .env("WINEDEBUG", "-all")
+            .args(arguments);
+        let run = self
+            .runner
+            .run_in_background(
+                &mut command,
+                Some(PROBE_TIMEOUT),
+                self.is_cancelled,
+            )
+            .ok()?;
+        match run.completion {
+            Completion::Exited(status) if status.success() => {
+                Some(String::from_utf8_lossy(&run.stdout).into_owned())
+            }
+            _ => None,
+        }
+    }
+}
+
+/// Probes `keg`, running programs inside it until `is_cancelled` returns
+/// `true`.
+pub fn probe(
+    keg: &CurrentKeg,
+    runner: &dyn CommandRunner,
+    is_cancelled: &dyn Fn() -> bool,
+) -> ProbeReport {
+    let engine = keg.keg.wine_prefix.parent().unwrap_or(&keg.keg.wine_prefix);
+    let wine = Wine {
+        keg,
+        runner,
+        is_cancelled,
+    };
+    let mut checks = vec![];
+
+    let is_apple_silicon = arch::host_is_apple_silicon(runner);
+    let macos = macos_major_version(runner);
+    checks.push(Finding::new(
+        "Mac",
+        Verdict::Works,
+        format!(
+            "{}, macOS {}",
+            if is_apple_silicon {
+                "Apple Silicon"
+            } else {
+                "Intel"
+            },
+            macos.map_or("(unknown version)".to_owned(), |macos| {
+                macos.to_string()
+            })
+        ),
+    ));
+
+    let metal = hardware::detect_gpus(runner)
+        .ok()
+        .and_then(|gpus| gpus.into_iter().find_map(|gpu| gpu.metal));
+    let metal_generation = metal.as_deref().and_then(metal_version);
+    checks.push(match &metal {
+        Some(metal) => Finding::new("Metal", Verdict::Works, metal.clone()),
+        None => Finding::new(
+            "Metal",
+            Verdict::Unknown,
+            "system_profiler didn't report Metal support".to_owned(),
+        ),
+    });
+
+    let version = wine.run(&["cmd", "/c", "ver"]);
+    let is_wine_working = version.is_some();
+    checks.push(match version {
+        Some(version) => Finding::new(
+            "Wine",
+            Verdict::Works,
+            format!("starts ({})", version.trim()),
+        ),
+        None => Finding::new(
+            "Wine",
+            Verdict::Fails,
+            "couldn't run cmd /c ver in the keg".to_owned(),
+        ),
+    });
+
+    let ntdll = find_in_engine(engine, &["ntdll.so", "ntdll.dll.so"]);
+    for (name, variable) in [("msync", "WINEMSYNC"), ("esync", "WINEESYNC")] {
+        checks.push(match &ntdll {
+            Some(ntdll) if file_mentions(ntdll, variable.as_bytes()) => {
+                Finding::new(
+                    name,
+                    Verdict::Works,
+                    format!("the engine reads {variable}"),
+                )
+            }
+            Some(_) => Finding::new(
+                name,
+                Verdict::Fails,
+                "the engine wasn't built with it".to_owned(),
+            ),
+            None => Finding::new(
+                name,
+                Verdict::Unknown,
+                "couldn't find the engine's ntdll".to_owned(),
+            ),
+        });
+    }
+
+    let report_path = WinePath::from_kegworks(DXDIAG_REPORT);
+    let c_drive_report = report_path.to_host(&keg.keg.c_drive);
+    let card = is_wine_working
+        .then(|| {
+            wine.run(&["dxdiag", "/t", &report_path.to_windows()])?;
+            let report = decode_report(&fs::read(&c_drive_report).ok()?);
+            let _ = fs::remove_file(&c_drive_report);
+            report_value(&report, "Card name")
+        })
+        .flatten();
+    checks.push(match &card {
+        Some(card) => Finding::new(
+            "Direct3D",
+            Verdict::Works,
+            format!("dxdiag sees {card}"),
+        ),
+        None => Finding::new(
+            "Direct3D",
+            Verdict::Unknown,
+            "dxdiag didn't report a display device".to_owned(),
+        ),
+    });
+
+    let vulkan_device = is_wine_working
+        .then(|| {
+            let vulkaninfo = find_in_engine(engine, &["vulkaninfo.exe"])
+                .or_else(|| {
+                    let system = keg.keg.c_drive.join("windows/system32");
+                    let vulkaninfo = system.join("vulkaninfo.exe");
+                    vulkaninfo.is_file().then_some(vulkaninfo)
+                })?;
+            let vulkaninfo = vulkaninfo.to_string_lossy().into_owned();
+            let summary = wine.run(&[vulkaninfo.as_str(), "--summary"])?;
+            report_value(&summary, "deviceName")
+        })
+        .flatten();
+    let has_winevulkan =
+        find_in_engine(engine, &["winevulkan.dll", "winevulkan.so"]).is_some();
+    checks.push(match (&vulkan_device, has_winevulkan) {
+        (Some(device), _) => Finding::new(
+            "Vulkan",
+            Verdict::Works,
+            format!("vulkaninfo sees {device}"),
+        ),
+        (None, true) => Finding::new(
+            "Vulkan",
+            Verdict::Unknown,
+            "winevulkan is bundled, but vulkaninfo.exe isn't there to test it"
+                .to_owned(),
+        ),
+        (None, false) => Finding::new(
+            "Vulkan",
+            Verdict::Fails,
+            "the engine has no winevulkan".to_owned(),
+        ),
+    });
+
+    let mut backends = vec![];
+    let layers = inspect_engine(engine);
+    backends.push(
+        if layers.d3d_metal == LayerVersion::NotBundled {
+            Finding::new(
+                "D3DMetal",
+                Verdict::Fails,
+                "not bundled with this engine".to_owned(),
+            )
+        } else if !is_apple_silicon {
+            Finding::new(
+                "D3DMetal",
+                Verdict::Fails,
+                "needs an Apple Silicon Mac".to_owned(),
+            )
+        } else if macos.is_some_and(|macos| macos < D3D_METAL_MINIMUM_MACOS) {
+            Finding::new(
+                "D3DMetal",
+                Verdict::Fails,
+                format!("needs macOS {D3D_METAL_MINIMUM_MACOS} or later"),
+            )
+        } else {
+            Finding::new(
+                "D3DMetal",
+                Verdict::Works,
+                format!(
+                    "Direct3D 11 and 12 via Metal ({})",
+                    layers.d3d_metal.describe()
+                ),
+            )
+        }
+        .enabled_by("translation.d3d_metal"),
+    );
+    let has_dxmt =
+        find_in_engine(engine, &["winemetal.dll", "winemetal.so"]).is_some();
+    backends.push(
+        if !has_dxmt {
+            Finding::new(
+                "DXMT",
+                Verdict::Fails,
+                "not bundled with this engine".to_owned(),
+            )
+        } else if metal_generation.is_some_and(|version| version < 3) {
+            Finding::new(
+                "DXMT",
+                Verdict::Fails,
+                "needs a GPU that supports Metal 3".to_owned(),
+            )
+        } else {
+            Finding::new(
+                "DXMT",
+                if metal_generation.is_some() {
+                    Verdict::Works
+                } else {
+                    Verdict::Unknown
+                },
+                "Direct3D 10 and 11 via Metal".to_owned(),
+            )
+        }
+        .enabled_by("translation.dxmt"),
+    );
+    backends.push(
+        if layers.dxvk == LayerVersion::NotBundled {
+            Finding::new(
+                "DXVK",
+                Verdict::Fails,
+                "not bundled with this engine".to_owned(),
+            )
+        } else if layers.molten_vk == LayerVersion::NotBundled
+            || !has_winevulkan
+        {
+            Finding::new(
+                "DXVK",
+                Verdict::Fails,
+                "needs MoltenVK and winevulkan, which aren't both bundled"
+                    .to_owned(),
+            )
+        } else {
+            Finding::new(
+                "DXVK",
+                if vulkan_device.is_some() {
+                    Verdict::Works
+                } else {
+                    Verdict::Unknown
+                },
+                format!(
+                    "Direct3D 9 to 11 via Vulkan on MoltenVK {}",
+                    layers.molten_vk.describe()
+                ),
+            )
+        }
+        .enabled_by("translation.dxvk"),
+    );
+    backends.push(Finding::new(
+        "WineD3D",
+        match (is_wine_working, &card) {
+            (false, _) => Verdict::Fails,
+            (true, Some(_)) => Verdict::Works,
+            (true, None) => Verdict::Unknown,
+        },
+        "Wine's own OpenGL translation, used when the others are off; \
+         slowest, but the most compatible"
+            .to_owned(),
+    ));
+
+    ProbeReport { checks, backends }
+}
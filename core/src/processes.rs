@@ -0,0 +1,128 @@
+// Copyright (C) 2026 Ethan Uppal.
+//
+// This program is free software: you can redistribute it and/or modify it under
+// the terms of the GNU General Public License as published by the Free Software
+// Foundation, version 3 of the License only.
+//
+// This program is distributed in the hope that it will be useful, but WITHOUT
+// ANY WARRANTY; without even the implied warranty of MERCHANTABILITY or FITNESS
+// FOR A PARTICULAR PURPOSE. See the GNU General Public License for more
+// details.
+//
+// You should have received a copy of the GNU General Public License along with
+// this program.  If not, see <https://www.gnu.org/licenses/>.
+
+//! The processes running from each keg, found with `ps`. Wine renames a
+//! Windows program's process to its Windows path, so processes are matched by
+//! their ancestry as well as by the keg's path in their command.
+
+use std::{collections::HashMap, path::PathBuf, process::Command};
+
+use crate::runner::CommandRunner;
+
+#[derive(Debug, Clone)]
+pub struct KegProcess {
+    pub pid: u32,
+    parent_pid: u32,
+    /// How many of the keg's processes it descends from, for drawing the
+    /// tree.
+    pub depth: usize,
+    /// Resident memory in bytes.
+    pub memory: u64,
+    /// How long it has run, as `ps` formats it, e.g., `01:02:03`.
+    pub elapsed: String,
+    pub command: String,
+}
+
+/// Every process `ps` lists.
+pub fn list(runner: &dyn CommandRunner) -> Vec<KegProcess> {
+    let Ok(output) = runner.output(
+        Command::new("ps").args(["-axo", "pid=,ppid=,rss=,etime=,command="]),
+    ) else {
+        return vec![];
+    };
+    String::from_utf8_lossy(&output.stdout)
+        .lines()
+        .filter_map(|line| {
+            let mut fields = line.split_whitespace();
+            let pid = fields.next()?.parse().ok()?;
+            let parent_pid = fields.next()?.parse().ok()?;
+            let memory = fields.next()?.parse::<u64>().ok()? * 1024;
+            let elapsed = fields.next()?.to_owned();
+            Some(KegProcess {
+                pid,
+                parent_pid,
+                depth: 0,
+                memory,
+                elapsed,
+                command: fields.collect::<Vec<_>>().join(" "),
+            })
+        })
+        .collect()
+}
+
+/// The processes from each of `bundles`, keyed by bundle, each as a tree in
+/// depth-first order: those whose command mentions the bundle, then what they
+/// started.
+pub fn by_keg(
+    processes: &[KegProcess],
+    bundles: &[PathBuf],
+) -> HashMap<PathBuf, Vec<KegProcess>> {
+    let mut children = HashMap::<u32, Vec<&KegProcess>>::new();
+    for process in processes {
+        children
+            .entry(process.parent_pid)
+            .or_default()
+            .push(process);
+    }
+    let by_pid = processes
+        .iter()
+        .map(|process| (process.pid, process))
+        .collect::<HashMap<_, _>>();
+    bundles
+        .iter()
+        .filter_map(|bundle| {
+            let bundle_text = bundle.to_string_lossy();
+            let is_from_keg =
+                |process: &KegProcess| process.command.contains(&*bundle_text);
+            let has_keg_ancestor = |process: &KegProcess| {
+                let mut ancestor = by_pid.get(&process.parent_pid).copied();
+                // Bounded, since the snapshot may have a cycle of reused
+                // PIDs.
+                for _ in 0..processes.len() {
+                    let Some(parent) = ancestor else {
+                        return false;
+                    };
+                    if is_from_keg(parent) {
+                        return true;
+                    }
+                    ancestor = by_pid.get(&parent.parent_pid).copied();
+                }
+                false
+            };
+            let roots = processes.iter().filter(|process| {
+                is_from_keg(process) && !has_keg_ancestor(process)
+            });
+            let mut tree = vec![];
+            let mut stack =
+                roots.rev().map(|root| (root, 0)).collect::<Vec<_>>();
+            while let Some((process, depth)) = stack.pop() {
+                // Guards against a cycle of reused PIDs, as above.
+                if tree.iter().any(|seen: &KegProcess| seen.pid == process.pid)
+                {
+                    continue;
+                }
+                tree.push(KegProcess {
+                    depth,
+                    ..process.clone()
+                });
+                if let Some(children) = children.get(&process.pid) {
+                    stack.extend(
+                        children.iter().rev().map(|child| (*child, depth + 1)),
+                    );
+                }
+            }
+            (!tree.is_empty()).then(|| (bundle.clone(), tree))
+        })
+        .collect()
+}
@@ -0,0 +1,170 @@
+// Copyright (C) 2026 Ethan Uppal.
+//
+// This program is free software: you can redistribute it and/or modify it under
+// the terms of the GNU General Public License as published by the Free Software
+// Foundation, version 3 of the License only.
+//
+// This program is distributed in the hope that it will be useful, but WITHOUT
+// ANY WARRANTY; without even the implied warranty of MERCHANTABILITY or FITNESS
+// FOR A PARTICULAR PURPOSE. See the GNU General Public License for more
+// details.
+//
+// You should have received a copy of the GNU General Public License along with
+// this program.  If not, see <https://www.gnu.org/licenses/>.
+
+//! The `kegtui --quick` launcher: a fuzzy search over the kegs and nothing
+//! else, for binding to a hotkey.
+
+use color_eyre::Result;
+use crossterm::event::{self, Event, KeyCode, KeyEventKind, KeyModifiers};
+use ratatui::{
+    DefaultTerminal,
+    prelude::*,
+    widgets::{Block, Borders, List, ListItem, ListState, Paragraph},
+};
+
+use crate::{app::SELECTED_FOCUSED_STYLE, fuzzy, keg::Keg};
+
+struct QuickLauncher {
+    kegs: Vec<Keg>,
+    query: String,
+    selected: usize,
+}
+
+enum Step {
+    Continue,
+    Cancel,
+    Launch,
+}
+
+impl QuickLauncher {
+    /// The kegs matching the query, best first.
+    fn matches(&self) -> Vec<&Keg> {
+        fuzzy::rank(self.query.trim(), &self.kegs, |keg| &keg.name)
+    }
+
+    fn handle_key(&mut self, code: KeyCode, modifiers: KeyModifiers) -> Step {
+        let count = self.matches().len();
+        match code {
+            KeyCode::Esc => return Step::Cancel,
+            KeyCode::Char('c') if modifiers.contains(KeyModifiers::CONTROL) => {
+                return Step::Cancel;
+            }
+            KeyCode::Enter if count > 0 => return Step::Launch,
+            KeyCode::Up => self.selected = self.selected.saturating_sub(1),
+            KeyCode::Char('p') if modifiers.contains(KeyModifiers::CONTROL) => {
+                self.selected = self.selected.saturating_sub(1);
+            }
+            KeyCode::Down => {
+                self.selected =
+                    (self.selected + 1).min(count.saturating_sub(1));
+            }
+            KeyCode::Char('n') if modifiers.contains(KeyModifiers::CONTROL) => {
+                self.selected =
+                    (self.selected + 1).min(count.saturating_sub(1));
+            }
+            KeyCode::Backspace => {
+                self.query.pop();
+                self.selected = 0;
+            }
+            KeyCode::Char(c) => {
+                self.query.push(c);
+                self.selected = 0;
+            }
+            _ => {}
+        }
+        Step::Continue
+    }
+
+    fn draw(&self, frame: &mut Frame) {
+        let block = Block::default()
+            .borders(Borders::ALL)
+            .title(" Launch a keg ")
+            .title_bottom(
+                Line::from(
+                    " <Enter> launch | <Up>/<Down> choose | <Esc> cancel ",
+                )
+                .centered(),
+            );
+        let inner_area = block.inner(frame.area());
+        frame.render_widget(block, frame.area());
+
+        let chunks = Layout::default()
+            .direction(Direction::Vertical)
+            .constraints([
+                Constraint::Length(1),
+                Constraint::Length(1),
+                Constraint::Fill(1),
+            ])
+            .split(inner_area);
+        frame.render_widget(
+            Paragraph::new(Line::from(vec![
+                "> ".bold(),
+                self.query.clone().into(),
+                "_".dark_gray(),
+            ])),
+            chunks[0],
+        );
+
+        let matches = self.matches();
+        if matches.is_empty() {
+            frame.render_widget(
+                Line::from(if self.kegs.is_empty() {
+                    "No kegs found in the search paths".dark_gray()
+                } else {
+                    "No kegs match".dark_gray()
+                }),
+                chunks[2],
+            );
+            return;
+        }
+        let items = matches
+            .iter()
+            .map(|keg| {
+                ListItem::new(Line::from(vec![
+                    keg.name.clone().into(),
+                    format!("  {}", keg.enclosing_location.display())
+                        .dark_gray(),
+                ]))
+            })
+            .collect::<Vec<_>>();
+        let list = List::new(items)
+            .highlight_style(SELECTED_FOCUSED_STYLE)
+            .highlight_symbol(">> ");
+        frame.render_stateful_widget(
+            list,
+            chunks[2],
+            &mut ListState::default().with_selected(Some(self.selected)),
+        );
+    }
+}
+
+/// Lets the user fuzzy-search `kegs` for one to launch, returning `None` if
+/// they cancel.
+pub fn pick_keg(
+    terminal: &mut DefaultTerminal,
+    kegs: Vec<Keg>,
+) -> Result<Option<Keg>> {
+    let mut launcher = QuickLauncher {
+        kegs,
+        query: String::new(),
+        selected: 0,
+    };
+    loop {
+        terminal.draw(|frame| launcher.draw(frame))?;
+        if let Event::Key(key_event) = event::read()?
+            && key_event.kind == KeyEventKind::Press
+        {
+            match launcher.handle_key(key_event.code, key_event.modifiers) {
+                Step::Continue => {}
+                Step::Cancel => return Ok(None),
+                Step::Launch => {
+                    return Ok(launcher
+                        .matches()
+                        .get(launcher.selected)
+                        .map(|keg| (*keg).clone()));
+                }
+            }
+        }
+    }
+}
@@ -0,0 +1,132 @@
+// Copyright (C) 2026 Ethan Uppal.
+//
+// This program is free software: you can redistribute it and/or modify it under
+// the terms of the GNU General Public License as published by the Free Software
+// Foundation, version 3 of the License only.
+//
+// This program is distributed in the hope that it will be useful, but WITHOUT
+// ANY WARRANTY; without even the implied warranty of MERCHANTABILITY or FITNESS
+// FOR A PARTICULAR PURPOSE. See the GNU General Public License for more
+// details.
+//
+// You should have received a copy of the GNU General Public License along with
+// this program.  If not, see <https://www.gnu.org/licenses/>.
+
+//! Registry tweaks pasted or loaded from a `.reg` file, checked and written
+//! out for `regedit /S` to apply inside a keg's prefix.
+
+use std::{fs, process::Command, time::SystemTime};
+
+use color_eyre::{
+    Result,
+    eyre::{Context, bail},
+};
+
+use crate::{
+    keg::CurrentKeg,
+    tasks::{PrefixTask, TaskKind, TaskQueue},
+    wine_path::WinePath,
+};
+
+/// The headers `regedit` accepts at the top of a `.reg` file.
+const HEADERS: [&str; 2] = ["REGEDIT4", "Windows Registry Editor Version 5.00"];
+
+/// Decodes a `.reg` file, which Windows' `regedit` exports as UTF-16 with a
+/// byte order mark and most guides share as plain text.
+pub fn decode(bytes: &[u8]) -> Result<String> {
+    if let Some(utf16) = bytes.strip_prefix(&[0xFF, 0xFE]) {
+        let units = utf16
+            .chunks_exact(2)
+            .map(|pair| u16::from_le_bytes([pair[0], pair[1]]))
+            .collect::<Vec<_>>();
+        return Ok(String::from_utf16(&units)?);
+    }
+    let bytes = bytes.strip_prefix(b"\xEF\xBB\xBF").unwrap_or(bytes);
+    Ok(String::from_utf8(bytes.to_vec())?)
+}
+
+/// The keys `contents` changes, e.g., `HKEY_CURRENT_USER\Software\Wine`, with
+/// a leading `-` for the ones it deletes.
+pub fn keys(contents: &str) -> Vec<&str> {
+    contents
+        .lines()
+        .map(str::trim)
+        .filter_map(|line| line.strip_prefix('[')?.strip_suffix(']'))
+        .collect()
+}
+
+/// Turns a pasted snippet or `.reg` file into one `regedit` accepts, adding
+/// the header a snippet copied from a guide often leaves out, and failing if
+/// it changes nothing `regedit` could apply.
+pub fn registry_file(snippet: &str) -> Result<String> {
+    let snippet = snippet.replace("\r\n", "\n");
+    let snippet = snippet.trim();
+    let keys = keys(snippet);
+    if keys.is_empty() {
+        bail!(
+            "There's no [key] to change, e.g., [HKEY_CURRENT_USER\\Software\\Wine]"
+        );
+    }
+    if let Some(key) = keys
+        .iter()
+        .find(|key| !key.trim_start_matches('-').starts_with("HKEY_"))
+    {
+        bail!("[{key}] isn't a registry key; keys start with HKEY_");
+    }
+    let mut file = String::new();
+    if !HEADERS.iter().any(|header| snippet.starts_with(header)) {
+        file.push_str("REGEDIT4\n\n");
+    }
+    file.push_str(snippet);
+    file.push('\n');
+    Ok(file)
+}
+
+/// Writes `registry_file` into the keg's Windows temp folder and queues
+/// `regedit` to apply it, returning how many changes to the keg run first.
+pub fn queue(
+    tasks: &TaskQueue,
+    current_keg: &CurrentKeg,
+    registry_file: &str,
+    description: String,
+    run_label: &'static str,
+) -> Result<usize> {
+    // Each change gets its own file, since an earlier one may still be
+    // waiting in the Task Queue.
+    let timestamp = SystemTime::now()
+        .duration_since(SystemTime::UNIX_EPOCH)
+        .unwrap_or_default()
+        .as_nanos();
+    let registry_path = WinePath::from_kegworks(&format!(
+        "/windows/temp/kegtui-{run_label}-{timestamp}.reg"
+    ));
+    let registry_path_on_host = registry_path.to_host(&current_keg.keg.c_drive);
+    if let Some(parent) = registry_path_on_host.parent() {
+        fs::create_dir_all(parent)?;
+    }
+    fs::write(&registry_path_on_host, registry_file)
+        .context("Failed to write the registry changes")?;
+    let mut command = Command::new(current_keg.wine_executable());
+    command
+        .env_clear()
+        .envs(current_keg.winetricks_environment())
+        .args(["regedit", "/S"])
+        .arg(registry_path.to_windows());
+    Ok(tasks.enqueue(PrefixTask {
+        keg_name: current_keg.keg.name.clone(),
+        bundle: current_keg.bundle_path(),
+        kind: TaskKind::Mutation,
+        description,
+        run_label,
+        command,
+        wineskin_launcher: current_keg.keg.wineskin_launcher.clone(),
+        log_directory: current_keg.keg.log_directory.clone(),
+        config_file: current_keg.keg.config_file.clone(),
+        winetricks_run_flags: None,
+        timeout: None,
+        output_log: None,
+        explain_failure: None,
+        summarize: None,
+        snapshot_prefix: false,
+    }))
+}
@@ -0,0 +1,178 @@
+// Copyright (C) 2026 Ethan Uppal.
+//
+// This program is free software: you can redistribute it and/or modify it under
+// the terms of the GNU General Public License as published by the Free Software
+// Foundation, version 3 of the License only.
+//
+// This program is distributed in the hope that it will be useful, but WITHOUT
+// ANY WARRANTY; without even the implied warranty of MERCHANTABILITY or FITNESS
+// FOR A PARTICULAR PURPOSE. See the GNU General Public License for more
+// details.
+//
+// You should have received a copy of the GNU General Public License along with
+// this program.  If not, see <https://www.gnu.org/licenses/>.
+
+use std::{
+    fs::{self, File},
+    path::{Path, PathBuf},
+    process::Command,
+};
+
+use color_eyre::{
+    Result,
+    eyre::{Context, bail},
+};
+use tar::Archive;
+use xz2::read::XzDecoder;
+
+use crate::runner::CommandRunner;
+
+/// Wrappers are unpacked here first so a partial download never shows up as a
+/// wrapper in the search path.
+const UNPACK_DIRECTORY: &str = ".kegtui-unpack";
+
+pub fn is_url(choice: &str) -> bool {
+    choice.starts_with("https://") || choice.starts_with("http://")
+}
+
+/// The last path segment of `url`, without any query or fragment.
+pub fn file_name_from_url(url: &str) -> Option<&str> {
+    url.split(['?', '#'])
+        .next()?
+        .rsplit('/')
+        .next()
+        .filter(|name| !name.is_empty())
+}
+
+/// Downloads `url` to `destination` with curl, showing its progress bar.
+pub fn download(
+    runner: &dyn CommandRunner,
+    url: &str,
+    destination: &Path,
+) -> Result<()> {
+    let status = runner
+        .status(
+            Command::new("curl")
+                .args(["-fL", "--progress-bar", "-o"])
+                .arg(destination)
+                .arg(url),
+        )
+        .context("Failed to run curl")?;
+    if !status.success() {
+        bail!("Failed to download {url} ({status})");
+    }
+    Ok(())
+}
+
+/// Like [`download`], but without printing anything, so it can run while the
+/// TUI owns the terminal.
+pub fn download_quietly(
+    runner: &dyn CommandRunner,
+    url: &str,
+    destination: &Path,
+) -> Result<()> {
+    let output = runner
+        .output(
+            Command::new("curl")
+                .args(["-fsSL", "-o"])
+                .arg(destination)
+                .arg(url),
+        )
+        .context("Failed to run curl")?;
+    if !output.status.success() {
+        bail!("Failed to download {url} ({})", output.status);
+    }
+    Ok(())
+}
+
+/// The tag of the latest GitHub release of `repository`, e.g., `v1.0.0`.
+pub fn latest_release_tag(
+    runner: &dyn CommandRunner,
+    repository: &str,
+) -> Result<String> {
+    let url =
+        format!("https://api.github.com/repos/{repository}/releases/latest");
+    let output = runner
+        .output(Command::new("curl").args(["-fsSL", "--max-time", "10", &url]))
+        .context("Failed to run curl")?;
+    if !output.status.success() {
+        bail!("Failed to fetch {url} ({})", output.status);
+    }
+    // Only one field is needed, so this avoids a JSON dependency.
+    let response = String::from_utf8_lossy(&output.stdout);
+    let Some(tag) = response
+        .split_once("\"tag_name\"")
+        .and_then(|(_, rest)| rest.split('"').nth(1))
+    else {
+        bail!("No tag_name in the response from {url}");
+    };
+    Ok(tag.to_owned())
+}
+
+/// The hex SHA-256 digest of the file at `path`.
+pub fn sha256(runner: &dyn CommandRunner, path: &Path) -> Result<String> {
+    let output = runner
+        .output(Command::new("shasum").args(["-a", "256"]).arg(path))
+        .context("Failed to run shasum")?;
+    if !output.status.success() {
+        bail!("Failed to checksum {}", path.display());
+    }
+    Ok(String::from_utf8_lossy(&output.stdout)
+        .split_whitespace()
+        .next()
+        .unwrap_or_default()
+        .to_lowercase())
+}
+
+/// Unpacks a `.tar.xz` or `.zip` wrapper `archive` into `directory`,
+/// returning the path of the `.app` it contained.
+pub fn unpack_wrapper(
+    runner: &dyn CommandRunner,
+    archive: &Path,
+    directory: &Path,
+) -> Result<PathBuf> {
+    let unpack_directory = directory.join(UNPACK_DIRECTORY);
+    if unpack_directory.exists() {
+        fs::remove_dir_all(&unpack_directory)?;
+    }
+    fs::create_dir_all(&unpack_directory)?;
+
+    let archive_name = archive.to_string_lossy();
+    if archive_name.ends_with(".tar.xz") {
+        let file = File::open(archive).context("Failed to open wrapper")?;
+        Archive::new(XzDecoder::new(file))
+            .unpack(&unpack_directory)
+            .context("Failed to unpack wrapper")?;
+    } else if archive_name.ends_with(".zip") {
+        let status = runner
+            .status(
+                Command::new("ditto")
+                    .args(["-x", "-k"])
+                    .arg(archive)
+                    .arg(&unpack_directory),
+            )
+            .context("Failed to run ditto")?;
+        if !status.success() {
+            bail!("Failed to unzip wrapper ({status})");
+        }
+    } else {
+        bail!("Wrappers must be .tar.xz or .zip archives");
+    }
+
+    let Some(app) = fs::read_dir(&unpack_directory)?
+        .flatten()
+        .map(|entry| entry.path())
+        .find(|path| {
+            path.extension().is_some_and(|extension| extension == "app")
+        })
+    else {
+        bail!("No .app found in {}", archive.display());
+    };
+    let wrapper = directory.join(app.file_name().unwrap_or_default());
+    if wrapper.exists() {
+        bail!("{} already exists", wrapper.display());
+    }
+    fs::rename(&app, &wrapper)?;
+    fs::remove_dir_all(&unpack_directory)?;
+    Ok(wrapper)
+}
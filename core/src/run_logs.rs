@@ -0,0 +1,167 @@
+// Copyright (C) 2026 Ethan Uppal.
+//
+// This program is free software: you can redistribute it and/or modify it under
+// the terms of the GNU General Public License as published by the Free Software
+// Foundation, version 3 of the License only.
+//
+// This program is distributed in the hope that it will be useful, but WITHOUT
+// ANY WARRANTY; without even the implied warranty of MERCHANTABILITY or FITNESS
+// FOR A PARTICULAR PURPOSE. See the GNU General Public License for more
+// details.
+//
+// You should have received a copy of the GNU General Public License along with
+// this program.  If not, see <https://www.gnu.org/licenses/>.
+
+use std::{
+    cmp::Reverse,
+    fmt::Display,
+    fs::{self, File, OpenOptions},
+    io::{self, Write},
+    path::{Path, PathBuf},
+    process::{Command, ExitStatus, Stdio},
+    time::{Duration, SystemTime},
+};
+
+use crate::runner::{self, CommandRunner};
+
+/// Runs are kept in their own folder so log rotation and crash dump scanning
+/// don't mistake them for the wrapper's logs.
+const RUNS_DIRECTORY: &str = "kegtui-runs";
+
+/// The recorded output of one command kegtui ran for a keg.
+#[derive(Debug, Clone)]
+pub struct RunLog {
+    pub path: PathBuf,
+    /// What was run, e.g., `launch` or `winetricks`.
+    pub label: String,
+    pub started: SystemTime,
+    /// Tells apart runs with the same label that started in the same second.
+    sequence: u32,
+}
+
+impl RunLog {
+    /// Recovers a run from a file name of the form `<unix time>-<label>.log`,
+    /// or `<unix time>.<sequence>-<label>.log` for a later run in the same
+    /// second.
+    fn from_path(path: PathBuf) -> Option<Self> {
+        let (timestamp, label) = path.file_stem()?.to_str()?.split_once('-')?;
+        let (seconds, sequence) = match timestamp.split_once('.') {
+            Some((seconds, sequence)) => (seconds, sequence.parse().ok()?),
+            None => (timestamp, 0),
+        };
+        let started =
+            SystemTime::UNIX_EPOCH + Duration::from_secs(seconds.parse().ok()?);
+        let label = label.to_owned();
+        Some(Self {
+            path,
+            label,
+            started,
+            sequence,
+        })
+    }
+}
+
+pub fn runs_directory(log_directory: &Path) -> PathBuf {
+    log_directory.join(RUNS_DIRECTORY)
+}
+
+/// Creates the file the run labeled `label` that started at `started` is
+/// recorded in, along with its folder. Another run that started in the same
+/// second gets the next sequence number instead of overwriting it.
+fn create_run_file(
+    log_directory: &Path,
+    label: &str,
+    started: SystemTime,
+) -> io::Result<(PathBuf, File)> {
+    let directory = runs_directory(log_directory);
+    fs::create_dir_all(&directory)?;
+    let timestamp = started
+        .duration_since(SystemTime::UNIX_EPOCH)
+        .unwrap_or_default()
+        .as_secs();
+    for sequence in 0_u32.. {
+        let path = directory.join(if sequence == 0 {
+            format!("{timestamp}-{label}.log")
+        } else {
+            format!("{timestamp}.{sequence}-{label}.log")
+        });
+        match OpenOptions::new().write(true).create_new(true).open(&path) {
+            Ok(file) => return Ok((path, file)),
+            Err(error) if error.kind() == io::ErrorKind::AlreadyExists => {}
+            Err(error) => return Err(error),
+        }
+    }
+    Err(io::Error::other("Ran out of run log names"))
+}
+
+/// Saves the output of `command`, which was started at `started` and ended
+/// with `outcome`, under the keg's `log_directory`.
+pub fn record(
+    log_directory: &Path,
+    label: &str,
+    started: SystemTime,
+    command: &Command,
+    outcome: impl Display,
+    stdout: &[u8],
+    stderr: &[u8],
+) -> io::Result<PathBuf> {
+    let (path, mut file) = create_run_file(log_directory, label, started)?;
+    let mut contents = format!("$ {}\n{outcome}\n", runner::describe(command));
+    contents.push_str("\n== STDOUT ==\n");
+    contents.push_str(&String::from_utf8_lossy(stdout));
+    contents.push_str("\n== STDERR ==\n");
+    contents.push_str(&String::from_utf8_lossy(stderr));
+    file.write_all(contents.as_bytes())?;
+    Ok(path)
+}
+
+/// Runs `command` with its stdout and stderr written straight to a new run
+/// under the keg's `log_directory`, then adds how it ended. Only `command`
+/// itself is waited on, so processes it leaves running with its output open,
+/// like `wineserver`, don't hold up the run.
+pub fn record_streaming(
+    runner: &dyn CommandRunner,
+    log_directory: &Path,
+    label: &str,
+    command: &mut Command,
+) -> io::Result<ExitStatus> {
+    let (_, mut file) =
+        create_run_file(log_directory, label, SystemTime::now())?;
+    writeln!(file, "$ {}\n\n== OUTPUT ==", runner::describe(command))?;
+    let mut child = runner.spawn(
+        command
+            .stdin(Stdio::null())
+            .stdout(file.try_clone()?)
+            .stderr(file.try_clone()?),
+    )?;
+    let status = child.wait()?;
+    writeln!(file, "\n== {status} ==")?;
+    Ok(status)
+}
+
+/// Runs recorded under the keg's `log_directory`, newest first.
+pub fn list_runs(log_directory: &Path) -> Vec<RunLog> {
+    let Ok(read_dir) = fs::read_dir(runs_directory(log_directory)) else {
+        return vec![];
+    };
+    let mut runs = read_dir
+        .flatten()
+        .filter_map(|entry| RunLog::from_path(entry.path()))
+        .collect::<Vec<_>>();
+    runs.sort_by_key(|run| Reverse((run.started, run.sequence)));
+    runs
+}
+
+/// Roughly how long ago `time` was, e.g., `5 min ago`.
+pub fn describe_age(time: SystemTime) -> String {
+    let seconds = SystemTime::now()
+        .duration_since(time)
+        .unwrap_or_default()
+        .as_secs();
+    match seconds {
+        0..60 => "just now".into(),
+        60..3600 => format!("{} min ago", seconds / 60),
+        3600..86400 => format!("{} h ago", seconds / 3600),
+        _ => format!("{} d ago", seconds / 86400),
+    }
+}
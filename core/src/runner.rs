@@ -0,0 +1,384 @@
+// Copyright (C) 2026 Ethan Uppal.
+//
+// This program is free software: you can redistribute it and/or modify it under
+// the terms of the GNU General Public License as published by the Free Software
+// Foundation, version 3 of the License only.
+//
+// This program is distributed in the hope that it will be useful, but WITHOUT
+// ANY WARRANTY; without even the implied warranty of MERCHANTABILITY or FITNESS
+// FOR A PARTICULAR PURPOSE. See the GNU General Public License for more
+// details.
+//
+// You should have received a copy of the GNU General Public License along with
+// this program.  If not, see <https://www.gnu.org/licenses/>.
+
+use std::{
+    fmt::{self, Display},
+    fs::{self, OpenOptions},
+    io::{self, Read, Write},
+    path::PathBuf,
+    process::{Child, Command, ExitStatus, Output, Stdio},
+    sync::{Arc, Mutex},
+    thread::{self, JoinHandle},
+    time::{Duration, Instant, SystemTime},
+};
+
+use crate::app_config::app_state_directory;
+
+/// How often a cancellable command is checked for completion.
+const POLL_INTERVAL: Duration = Duration::from_millis(100);
+
+/// How long to keep collecting output after a command exits. Wine processes
+/// that outlive the command, like `wineserver`, can hold its pipes open
+/// indefinitely.
+const DRAIN_TIMEOUT: Duration = Duration::from_millis(500);
+
+/// How a cancellable command finished.
+#[derive(Debug, Clone, Copy)]
+pub enum Completion {
+    Exited(ExitStatus),
+    /// The command was killed after running past its time limit.
+    TimedOut,
+    /// The command was killed at the user's request.
+    Cancelled,
+}
+
+impl Completion {
+    /// Describes why the command was killed, if it was.
+    pub fn interruption(&self) -> Option<&'static str> {
+        match self {
+            Completion::Exited(_) => None,
+            Completion::TimedOut => Some("timed out"),
+            Completion::Cancelled => Some("was cancelled"),
+        }
+    }
+}
+
+impl Display for Completion {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Completion::Exited(status) => write!(f, "{status}"),
+            Completion::TimedOut => write!(f, "timed out"),
+            Completion::Cancelled => write!(f, "cancelled"),
+        }
+    }
+}
+
+/// A finished [`CommandRunner::run_cancellable`] call.
+pub struct CancellableRun {
+    pub completion: Completion,
+    pub stdout: Vec<u8>,
+    pub stderr: Vec<u8>,
+}
+
+/// Forwards a child's output stream to ours while keeping a copy.
+struct Tee {
+    captured: Arc<Mutex<Vec<u8>>>,
+    thread: Option<JoinHandle<()>>,
+}
+
+impl Tee {
+    fn spawn(
+        source: Option<impl Read + Send + 'static>,
+        mut sink: impl Write + Send + 'static,
+    ) -> Self {
+        let captured = Arc::new(Mutex::new(vec![]));
+        let thread = source.map(|mut source| {
+            let captured = captured.clone();
+            thread::spawn(move || {
+                let mut buffer = [0; 4096];
+                loop {
+                    match source.read(&mut buffer) {
+                        Ok(0) | Err(_) => break,
+                        Ok(count) => {
+                            let _ = sink.write_all(&buffer[..count]);
+                            let _ = sink.flush();
+                            if let Ok(mut captured) = captured.lock() {
+                                captured.extend_from_slice(&buffer[..count]);
+                            }
+                        }
+                    }
+                }
+            })
+        });
+        Self { captured, thread }
+    }
+
+    /// Waits up to [`DRAIN_TIMEOUT`] for the stream to close and returns what
+    /// was captured. The thread is left running if the stream stays open.
+    fn finish(self) -> Vec<u8> {
+        let deadline = Instant::now() + DRAIN_TIMEOUT;
+        if let Some(thread) = &self.thread {
+            while !thread.is_finished() && Instant::now() < deadline {
+                thread::sleep(Duration::from_millis(10));
+            }
+        }
+        self.captured
+            .lock()
+            .map(|captured| captured.clone())
+            .unwrap_or_default()
+    }
+}
+
+/// A process started by [`CommandRunner::spawn`], so a fake runner can hand
+/// back one that never ran.
+pub trait ChildProcess: Send {
+    fn id(&self) -> u32;
+
+    /// The write end of its stdin, if it was piped and not taken yet.
+    fn take_stdin(&mut self) -> Option<Box<dyn Write + Send>>;
+
+    /// The read end of its stdout, if it was piped and not taken yet.
+    fn take_stdout(&mut self) -> Option<Box<dyn Read + Send>>;
+
+    /// The read end of its stderr, if it was piped and not taken yet.
+    fn take_stderr(&mut self) -> Option<Box<dyn Read + Send>>;
+
+    /// Its exit status if it has exited, without blocking.
+    fn try_wait(&mut self) -> io::Result<Option<ExitStatus>>;
+
+    fn wait(&mut self) -> io::Result<ExitStatus>;
+
+    fn kill(&mut self) -> io::Result<()>;
+}
+
+impl ChildProcess for Child {
+    fn id(&self) -> u32 {
+        Child::id(self)
+    }
+
+    fn take_stdin(&mut self) -> Option<Box<dyn Write + Send>> {
+        self.stdin
+            .take()
+            .map(|stdin| Box::new(stdin) as Box<dyn Write + Send>)
+    }
+
+    fn take_stdout(&mut self) -> Option<Box<dyn Read + Send>> {
+        self.stdout
+            .take()
+            .map(|stdout| Box::new(stdout) as Box<dyn Read + Send>)
+    }
+
+    fn take_stderr(&mut self) -> Option<Box<dyn Read + Send>> {
+        self.stderr
+            .take()
+            .map(|stderr| Box::new(stderr) as Box<dyn Read + Send>)
+    }
+
+    fn try_wait(&mut self) -> io::Result<Option<ExitStatus>> {
+        Child::try_wait(self)
+    }
+
+    fn wait(&mut self) -> io::Result<ExitStatus> {
+        Child::wait(self)
+    }
+
+    fn kill(&mut self) -> io::Result<()> {
+        Child::kill(self)
+    }
+}
+
+/// Runs the external programs kegtui depends on. Every spawned process should
+/// go through a runner so logging and process policies apply uniformly, and
+/// so a fake can stand in for the real programs.
+pub trait CommandRunner: Send + Sync {
+    /// Runs `command` to completion with inherited stdio.
+    fn status(&self, command: &mut Command) -> io::Result<ExitStatus>;
+
+    /// Runs `command` to completion, capturing its stdout and stderr.
+    fn output(&self, command: &mut Command) -> io::Result<Output>;
+
+    /// Starts `command` without waiting for it to finish.
+    fn spawn(&self, command: &mut Command)
+    -> io::Result<Box<dyn ChildProcess>>;
+
+    /// Runs `command`, killing it once `timeout` elapses or `is_cancelled`
+    /// returns `true`. Its output is shown as it arrives and also captured.
+    /// Its stdin is closed so that keypresses stay with kegtui.
+    fn run_cancellable(
+        &self,
+        command: &mut Command,
+        timeout: Option<Duration>,
+        is_cancelled: &dyn Fn() -> bool,
+    ) -> io::Result<CancellableRun> {
+        let mut child = self.spawn(
+            command
+                .stdin(Stdio::null())
+                .stdout(Stdio::piped())
+                .stderr(Stdio::piped()),
+        )?;
+        let stdout = Tee::spawn(child.take_stdout(), io::stdout());
+        let stderr = Tee::spawn(child.take_stderr(), io::stderr());
+        wait_cancellable(child, stdout, stderr, timeout, is_cancelled)
+    }
+
+    /// Like [`CommandRunner::run_cancellable`], but the output is only
+    /// captured, so it can run while the TUI owns the terminal.
+    fn run_in_background(
+        &self,
+        command: &mut Command,
+        timeout: Option<Duration>,
+        is_cancelled: &dyn Fn() -> bool,
+    ) -> io::Result<CancellableRun> {
+        let mut child = self.spawn(
+            command
+                .stdin(Stdio::null())
+                .stdout(Stdio::piped())
+                .stderr(Stdio::piped()),
+        )?;
+        let stdout = Tee::spawn(child.take_stdout(), io::sink());
+        let stderr = Tee::spawn(child.take_stderr(), io::sink());
+        wait_cancellable(child, stdout, stderr, timeout, is_cancelled)
+    }
+}
+
+fn wait_cancellable(
+    mut child: Box<dyn ChildProcess>,
+    stdout: Tee,
+    stderr: Tee,
+    timeout: Option<Duration>,
+    is_cancelled: &dyn Fn() -> bool,
+) -> io::Result<CancellableRun> {
+    let start = Instant::now();
+    let completion = loop {
+        if let Some(status) = child.try_wait()? {
+            break Completion::Exited(status);
+        }
+        let completion = if is_cancelled() {
+            Completion::Cancelled
+        } else if timeout.is_some_and(|timeout| start.elapsed() >= timeout) {
+            Completion::TimedOut
+        } else {
+            thread::sleep(POLL_INTERVAL);
+            continue;
+        };
+        child.kill()?;
+        child.wait()?;
+        break completion;
+    };
+    Ok(CancellableRun {
+        completion,
+        stdout: stdout.finish(),
+        stderr: stderr.finish(),
+    })
+}
+
+/// Runs commands on the host, recording each one in `commands.log` under the
+/// [`app_state_directory`].
+pub struct SystemRunner {
+    log_file: PathBuf,
+}
+
+impl Default for SystemRunner {
+    fn default() -> Self {
+        Self {
+            log_file: app_state_directory().join("commands.log"),
+        }
+    }
+}
+
+/// Renders `command` roughly as it would be typed in a shell.
+pub fn describe(command: &Command) -> String {
+    let mut description = command.get_program().to_string_lossy().to_string();
+    for arg in command.get_args() {
+        description.push(' ');
+        description.push_str(&arg.to_string_lossy());
+    }
+    description
+}
+
+impl SystemRunner {
+    /// Logging is best-effort: a read-only state directory shouldn't stop
+    /// commands from running.
+    fn log(&self, command: &Command, outcome: impl Display) {
+        let timestamp = SystemTime::now()
+            .duration_since(SystemTime::UNIX_EPOCH)
+            .unwrap_or_default()
+            .as_secs();
+        if let Some(parent) = self.log_file.parent() {
+            let _ = fs::create_dir_all(parent);
+        }
+        if let Ok(mut file) = OpenOptions::new()
+            .create(true)
+            .append(true)
+            .open(&self.log_file)
+        {
+            let _ =
+                writeln!(file, "{timestamp} {}: {outcome}", describe(command));
+        }
+    }
+
+    fn log_result<T>(
+        &self,
+        command: &Command,
+        start: Instant,
+        result: &io::Result<T>,
+        status: impl Fn(&T) -> ExitStatus,
+    ) {
+        match result {
+            Ok(value) => self.log(
+                command,
+                format!("{} in {:?}", status(value), start.elapsed()),
+            ),
+            Err(error) => self.log(command, format!("failed to run: {error}")),
+        }
+    }
+}
+
+impl CommandRunner for SystemRunner {
+    fn status(&self, command: &mut Command) -> io::Result<ExitStatus> {
+        let start = Instant::now();
+        let result = command.status();
+        self.log_result(command, start, &result, |status| *status);
+        result
+    }
+
+    fn output(&self, command: &mut Command) -> io::Result<Output> {
+        let start = Instant::now();
+        let result = command.output();
+        self.log_result(command, start, &result, |output| output.status);
+        result
+    }
+
+    fn spawn(
+        &self,
+        command: &mut Command,
+    ) -> io::Result<Box<dyn ChildProcess>> {
+        let result = command.spawn();
+        match &result {
+            Ok(child) => self.log(command, format!("spawned {}", child.id())),
+            Err(error) => self.log(command, format!("failed to run: {error}")),
+        }
+        Ok(Box::new(result?))
+    }
+}
+
+/// Fails every command instead of running it, so a TUI driven by
+/// [`crate::run_scripted`] can't touch the host.
+pub struct ScriptedRunner;
+
+impl ScriptedRunner {
+    fn refuse(command: &Command) -> io::Error {
+        io::Error::other(format!(
+            "{} wasn't run since the TUI is scripted",
+            describe(command)
+        ))
+    }
+}
+
+impl CommandRunner for ScriptedRunner {
+    fn status(&self, command: &mut Command) -> io::Result<ExitStatus> {
+        Err(Self::refuse(command))
+    }
+
+    fn output(&self, command: &mut Command) -> io::Result<Output> {
+        Err(Self::refuse(command))
+    }
+
+    fn spawn(
+        &self,
+        command: &mut Command,
+    ) -> io::Result<Box<dyn ChildProcess>> {
+        Err(Self::refuse(command))
+    }
+}
@@ -0,0 +1,180 @@
+// Copyright (C) 2026 Ethan Uppal.
+//
+// This program is free software: you can redistribute it and/or modify it under
+// the terms of the GNU General Public License as published by the Free Software
+// Foundation, version 3 of the License only.
+//
+// This program is distributed in the hope that it will be useful, but WITHOUT
+// ANY WARRANTY; without even the implied warranty of MERCHANTABILITY or FITNESS
+// FOR A PARTICULAR PURPOSE. See the GNU General Public License for more
+// details.
+//
+// You should have received a copy of the GNU General Public License along with
+// this program.  If not, see <https://www.gnu.org/licenses/>.
+
+use std::{
+    fs,
+    path::{Path, PathBuf},
+    process::Command,
+};
+
+use color_eyre::{
+    Result,
+    eyre::{Context, bail},
+};
+
+use crate::{
+    app::AsyncState,
+    app_config::AppConfig,
+    checks::brew_executable,
+    remote,
+    runner::{CommandRunner, describe},
+};
+
+/// Where kegtui keeps the winetricks script it copies into kegs.
+pub const WINETRICKS_SCRIPT: &str = "/tmp/kegworks_winetricks.sh";
+
+const HOMEBREW_INSTALL_COMMAND: &str = "/bin/bash -c \"$(curl -fsSL https://raw.githubusercontent.com/Homebrew/install/HEAD/install.sh)\"";
+const KEGWORKS_CASK: &str = "Kegworks-App/kegworks/kegworks";
+const ENGINE_URL: &str = "https://github.com/Sikarugir-App/Engines/releases/download/v1.0/WS12WineSikarugir10.0_4.tar.xz";
+const WRAPPER_URL: &str = "https://github.com/Sikarugir-App/Wrapper/releases/download/v1.0/Template-1.0.10.tar.xz";
+const WINETRICKS_URL: &str = "https://raw.githubusercontent.com/ethanuppal/winetricks/refs/heads/master/src/winetricks";
+
+/// Something the setup wizard can install, in the order they should be
+/// installed.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum SetupComponent {
+    Homebrew,
+    KegworksCask,
+    Engine,
+    Wrapper,
+    Winetricks,
+}
+
+impl SetupComponent {
+    pub const ALL: [SetupComponent; 5] = [
+        SetupComponent::Homebrew,
+        SetupComponent::KegworksCask,
+        SetupComponent::Engine,
+        SetupComponent::Wrapper,
+        SetupComponent::Winetricks,
+    ];
+
+    pub fn name(self) -> &'static str {
+        match self {
+            SetupComponent::Homebrew => "Homebrew",
+            SetupComponent::KegworksCask => "Kegworks",
+            SetupComponent::Engine => "Engine",
+            SetupComponent::Wrapper => "Wrapper",
+            SetupComponent::Winetricks => "Winetricks",
+        }
+    }
+
+    pub fn description(self) -> &'static str {
+        match self {
+            SetupComponent::Homebrew => "Package manager needed for Kegworks",
+            SetupComponent::KegworksCask => "Kegworks Winery, via Homebrew",
+            SetupComponent::Engine => "WS12WineSikarugir10.0_4",
+            SetupComponent::Wrapper => "Sikarugir Template-1.0.10",
+            SetupComponent::Winetricks => {
+                "Script used by the Winetricks action"
+            }
+        }
+    }
+
+    /// Whether this component is already present, as far as the worker's
+    /// last scans can tell.
+    pub fn is_installed(self, state: &AsyncState) -> bool {
+        match self {
+            SetupComponent::Homebrew => state.is_brew_installed,
+            SetupComponent::KegworksCask => state.is_kegworks_installed,
+            SetupComponent::Engine => !state.engines.is_empty(),
+            SetupComponent::Wrapper => !state.wrappers.is_empty(),
+            SetupComponent::Winetricks => {
+                Path::new(WINETRICKS_SCRIPT).is_file()
+            }
+        }
+    }
+
+    pub fn install(
+        self,
+        runner: &dyn CommandRunner,
+        config: &AppConfig,
+    ) -> Result<()> {
+        match self {
+            SetupComponent::Homebrew => run_checked(
+                runner,
+                Command::new("/bin/sh").args(["-c", HOMEBREW_INSTALL_COMMAND]),
+            ),
+            SetupComponent::KegworksCask => run_checked(
+                runner,
+                Command::new(brew_executable()).args([
+                    "install",
+                    "--cask",
+                    "--no-quarantine",
+                    KEGWORKS_CASK,
+                ]),
+            ),
+            SetupComponent::Engine => {
+                let directory = first_search_path(&config.engine_search_paths)?;
+                let file_name =
+                    remote::file_name_from_url(ENGINE_URL).unwrap_or_default();
+                // The worker picks up anything named `.tar.xz`, so only
+                // rename the engine into place once it's complete.
+                let partial = directory.join(format!("{file_name}.part"));
+                remote::download(runner, ENGINE_URL, &partial)?;
+                fs::rename(&partial, directory.join(file_name))?;
+                Ok(())
+            }
+            SetupComponent::Wrapper => {
+                let directory =
+                    first_search_path(&config.wrapper_search_paths)?;
+                let file_name =
+                    remote::file_name_from_url(WRAPPER_URL).unwrap_or_default();
+                let archive = directory.join(file_name);
+                remote::download(runner, WRAPPER_URL, &archive)?;
+                remote::unpack_wrapper(runner, &archive, &directory)?;
+                fs::remove_file(&archive)?;
+                Ok(())
+            }
+            SetupComponent::Winetricks => fetch_winetricks(runner),
+        }
+    }
+}
+
+fn run_checked(
+    runner: &dyn CommandRunner,
+    command: &mut Command,
+) -> Result<()> {
+    let status = runner
+        .status(command)
+        .context(format!("Failed to run {}", describe(command)))?;
+    if !status.success() {
+        bail!("{} failed ({status})", describe(command));
+    }
+    Ok(())
+}
+
+fn first_search_path(search_paths: &[PathBuf]) -> Result<PathBuf> {
+    let Some(directory) = search_paths.first() else {
+        bail!("No search path is configured to download into");
+    };
+    fs::create_dir_all(directory)
+        .context(format!("Failed to create {}", directory.display()))?;
+    Ok(directory.clone())
+}
+
+/// Downloads the winetricks script to [`WINETRICKS_SCRIPT`].
+pub fn fetch_winetricks(runner: &dyn CommandRunner) -> Result<()> {
+    remote::download(runner, WINETRICKS_URL, Path::new(WINETRICKS_SCRIPT))
+}
+
+/// Like [`fetch_winetricks`], but without a progress bar, for fetching it in
+/// the background.
+pub fn fetch_winetricks_quietly(runner: &dyn CommandRunner) -> Result<()> {
+    remote::download_quietly(
+        runner,
+        WINETRICKS_URL,
+        Path::new(WINETRICKS_SCRIPT),
+    )
+}
@@ -0,0 +1,308 @@
+// Copyright (C) 2026 Ethan Uppal.
+//
+// This program is free software: you can redistribute it and/or modify it under
+// the terms of the GNU General Public License as published by the Free Software
+// Foundation, version 3 of the License only.
+//
+// This program is distributed in the hope that it will be useful, but WITHOUT
+// ANY WARRANTY; without even the implied warranty of MERCHANTABILITY or FITNESS
+// FOR A PARTICULAR PURPOSE. See the GNU General Public License for more
+// details.
+//
+// You should have received a copy of the GNU General Public License along with
+// this program.  If not, see <https://www.gnu.org/licenses/>.
+
+use std::{
+    cmp::Reverse,
+    fs::{self, File},
+    io,
+    path::{Path, PathBuf},
+    time::SystemTime,
+};
+
+use copy_dir::copy_dir;
+use tar::{Archive, Builder};
+use xz2::{read::XzDecoder, write::XzEncoder};
+
+use crate::app_config::app_state_directory;
+
+/// Moves the bundle at `path` into kegtui's backups directory, returning its
+/// new location. The bundle is left where it was if any of it fails to
+/// copy.
+pub fn back_up_bundle(path: &Path) -> io::Result<PathBuf> {
+    let backups = app_state_directory().join("backups");
+    fs::create_dir_all(&backups)?;
+    let timestamp = SystemTime::now()
+        .duration_since(SystemTime::UNIX_EPOCH)
+        .unwrap_or_default()
+        .as_secs();
+    let backup = backups.join(format!(
+        "{timestamp}-{}",
+        path.file_name().unwrap_or_default().to_string_lossy()
+    ));
+    // Renaming fails across volumes, e.g., for kegs on an external drive.
+    if fs::rename(path, &backup).is_err() {
+        let errors = copy_dir(path, &backup)?;
+        if let Some(error) = errors.into_iter().next() {
+            let _ = fs::remove_dir_all(&backup);
+            return Err(io::Error::new(
+                error.kind(),
+                format!(
+                    "Failed to copy part of the keg to its backup: {error}"
+                ),
+            ));
+        }
+        fs::remove_dir_all(path)?;
+    }
+    Ok(backup)
+}
+
+/// Backups made by [`back_up_bundle`], newest first.
+pub fn list_backups() -> Vec<PathBuf> {
+    let Ok(read_dir) = fs::read_dir(app_state_directory().join("backups"))
+    else {
+        return vec![];
+    };
+    let mut backups = read_dir
+        .flatten()
+        .filter_map(|entry| {
+            let file_name = entry.file_name();
+            let (timestamp, _) = file_name.to_str()?.split_once('-')?;
+            Some((entry.path(), timestamp.parse::<u64>().ok()?))
+        })
+        .collect::<Vec<_>>();
+    backups.sort_by_key(|(_, timestamp)| Reverse(*timestamp));
+    backups.into_iter().map(|(path, _)| path).collect()
+}
+
+/// Where snapshots of the keg named `keg_name` are stored.
+pub fn snapshot_directory(keg_name: &str) -> PathBuf {
+    app_state_directory().join("snapshots").join(keg_name)
+}
+
+/// Archives the engine of the keg at `bundle` into its snapshot directory,
+/// returning the archive's path.
+pub fn snapshot_engine(keg_name: &str, bundle: &Path) -> io::Result<PathBuf> {
+    let directory = snapshot_directory(keg_name);
+    fs::create_dir_all(&directory)?;
+    let timestamp = SystemTime::now()
+        .duration_since(SystemTime::UNIX_EPOCH)
+        .unwrap_or_default()
+        .as_secs();
+    let snapshot = directory.join(format!("{timestamp}-engine.tar"));
+    let mut builder = Builder::new(File::create(&snapshot)?);
+    builder
+        .append_dir_all("wine", bundle.join("Contents/SharedSupport/wine"))?;
+    builder.into_inner()?;
+    Ok(snapshot)
+}
+
+/// Archives `entries`, given relative to the `Contents` of the keg at
+/// `bundle`, into its snapshot directory before they're replaced by a newer
+/// wrapper, returning the archive's path.
+pub fn snapshot_wrapper(
+    keg_name: &str,
+    bundle: &Path,
+    entries: &[PathBuf],
+) -> io::Result<PathBuf> {
+    let directory = snapshot_directory(keg_name);
+    fs::create_dir_all(&directory)?;
+    let timestamp = SystemTime::now()
+        .duration_since(SystemTime::UNIX_EPOCH)
+        .unwrap_or_default()
+        .as_secs();
+    let snapshot = directory.join(format!("{timestamp}-wrapper.tar"));
+    let mut builder = Builder::new(File::create(&snapshot)?);
+    builder.follow_symlinks(false);
+    let contents = bundle.join("Contents");
+    let result = entries
+        .iter()
+        .try_for_each(|entry| {
+            let path = contents.join(entry);
+            match path.symlink_metadata() {
+                Ok(metadata) if metadata.is_dir() => {
+                    builder.append_dir_all(entry, &path)
+                }
+                Ok(_) => builder.append_path_with_name(&path, entry),
+                Err(_) => Ok(()),
+            }
+        })
+        .and_then(|_| builder.into_inner().map(drop));
+    if let Err(error) = result {
+        let _ = fs::remove_file(&snapshot);
+        return Err(error);
+    }
+    Ok(snapshot)
+}
+
+/// Puts `entries` of the keg at `bundle` back the way [`snapshot_wrapper`]
+/// archived them in `snapshot`, removing any that weren't there then.
+pub fn restore_wrapper(
+    bundle: &Path,
+    snapshot: &Path,
+    entries: &[PathBuf],
+) -> io::Result<()> {
+    let contents = bundle.join("Contents");
+    for entry in entries {
+        let path = contents.join(entry);
+        match path.symlink_metadata() {
+            Ok(metadata) if metadata.is_dir() => fs::remove_dir_all(&path)?,
+            Ok(_) => fs::remove_file(&path)?,
+            Err(_) => {}
+        }
+    }
+    Archive::new(File::open(snapshot)?).unpack(&contents)
+}
+
+/// Ends the file name of every prefix snapshot.
+const PREFIX_SNAPSHOT_SUFFIX: &str = "-prefix.tar";
+
+/// Archives the Wine prefix of the keg at `bundle` into its snapshot
+/// directory, returning the archive's path.
+pub fn snapshot_prefix(keg_name: &str, bundle: &Path) -> io::Result<PathBuf> {
+    let directory = snapshot_directory(keg_name);
+    fs::create_dir_all(&directory)?;
+    let timestamp = SystemTime::now()
+        .duration_since(SystemTime::UNIX_EPOCH)
+        .unwrap_or_default()
+        .as_secs();
+    let snapshot =
+        directory.join(format!("{timestamp}{PREFIX_SNAPSHOT_SUFFIX}"));
+    let mut builder = Builder::new(File::create(&snapshot)?);
+    // dosdevices links to the whole Mac, so links are stored, not followed.
+    builder.follow_symlinks(false);
+    let result = builder
+        .append_dir_all("prefix", bundle.join("Contents/SharedSupport/prefix"))
+        .and_then(|_| builder.into_inner().map(drop));
+    if let Err(error) = result {
+        let _ = fs::remove_file(&snapshot);
+        return Err(error);
+    }
+    Ok(snapshot)
+}
+
+/// Replaces the prefix of the keg at `bundle` with the one in `snapshot`.
+/// The current prefix is only removed once the snapshot has been unpacked,
+/// so a failed restore leaves it as it was.
+pub fn restore_prefix(bundle: &Path, snapshot: &Path) -> io::Result<()> {
+    let shared_support = bundle.join("Contents/SharedSupport");
+    let unpack_directory = shared_support.join("kegtui-restore");
+    if unpack_directory.exists() {
+        fs::remove_dir_all(&unpack_directory)?;
+    }
+    fs::create_dir_all(&unpack_directory)?;
+    if let Err(error) =
+        Archive::new(File::open(snapshot)?).unpack(&unpack_directory)
+    {
+        let _ = fs::remove_dir_all(&unpack_directory);
+        return Err(error);
+    }
+    let prefix = shared_support.join("prefix");
+    if prefix.exists() {
+        fs::remove_dir_all(&prefix)?;
+    }
+    fs::rename(unpack_directory.join("prefix"), &prefix)?;
+    fs::remove_dir_all(&unpack_directory)
+}
+
+/// Prefix snapshots of the keg named `keg_name`, newest first.
+pub fn list_prefix_snapshots(keg_name: &str) -> Vec<PathBuf> {
+    list_snapshots(keg_name)
+        .into_iter()
+        .filter(|snapshot| {
+            snapshot.file_name().is_some_and(|name| {
+                name.to_string_lossy().ends_with(PREFIX_SNAPSHOT_SUFFIX)
+            })
+        })
+        .collect()
+}
+
+/// Snapshots of the keg named `keg_name`, newest first.
+pub fn list_snapshots(keg_name: &str) -> Vec<PathBuf> {
+    let Ok(read_dir) = fs::read_dir(snapshot_directory(keg_name)) else {
+        return vec![];
+    };
+    let mut snapshots = read_dir
+        .flatten()
+        .filter(|entry| entry.file_type().is_ok_and(|kind| kind.is_file()))
+        .map(|entry| {
+            let modified = entry
+                .metadata()
+                .and_then(|metadata| metadata.modified())
+                .unwrap_or(SystemTime::UNIX_EPOCH);
+            (entry.path(), modified)
+        })
+        .collect::<Vec<_>>();
+    snapshots.sort_by_key(|(_, modified)| Reverse(*modified));
+    snapshots.into_iter().map(|(path, _)| path).collect()
+}
+
+/// Ends the file name of every keg archive.
+pub const KEG_ARCHIVE_SUFFIX: &str = ".keg.tar.xz";
+
+/// Keg archives are unpacked here first, in the folder they're imported into,
+/// so a partly unpacked keg never shows up in the kegs list.
+const UNPACK_DIRECTORY: &str = ".kegtui-import";
+
+/// Archives the whole keg at `bundle` into an xz-compressed tarball in
+/// `destination`, named after the keg and the time, returning its path.
+pub fn archive_bundle(
+    bundle: &Path,
+    destination: &Path,
+) -> io::Result<PathBuf> {
+    let name = bundle.file_name().unwrap_or_default();
+    let timestamp = SystemTime::now()
+        .duration_since(SystemTime::UNIX_EPOCH)
+        .unwrap_or_default()
+        .as_secs();
+    let archive = destination.join(format!(
+        "{}-{timestamp}{KEG_ARCHIVE_SUFFIX}",
+        name.to_string_lossy().trim_end_matches(".app")
+    ));
+    let mut builder = Builder::new(XzEncoder::new(File::create(&archive)?, 6));
+    // dosdevices links to the whole Mac, so links are stored, not followed.
+    builder.follow_symlinks(false);
+    let result = builder
+        .append_dir_all(name, bundle)
+        .and_then(|_| builder.into_inner())
+        .and_then(|encoder| encoder.finish().map(drop));
+    if let Err(error) = result {
+        let _ = fs::remove_file(&archive);
+        return Err(error);
+    }
+    Ok(archive)
+}
+
+/// Unpacks a keg archive made by [`archive_bundle`] into a hidden folder in
+/// `directory`, returning the path of the `.app` it contained, which the
+/// caller moves into place and then removes the folder with
+/// [`discard_unpacked`].
+pub fn unpack_bundle(archive: &Path, directory: &Path) -> io::Result<PathBuf> {
+    let unpack_directory = directory.join(UNPACK_DIRECTORY);
+    discard_unpacked(directory)?;
+    fs::create_dir_all(&unpack_directory)?;
+    if let Err(error) = Archive::new(XzDecoder::new(File::open(archive)?))
+        .unpack(&unpack_directory)
+    {
+        let _ = fs::remove_dir_all(&unpack_directory);
+        return Err(error);
+    }
+    fs::read_dir(&unpack_directory)?
+        .flatten()
+        .map(|entry| entry.path())
+        .find(|path| {
+            path.extension().is_some_and(|extension| extension == "app")
+        })
+        .ok_or_else(|| {
+            io::Error::other(format!("No .app found in {}", archive.display()))
+        })
+}
+
+/// Removes whatever [`unpack_bundle`] left in `directory`.
+pub fn discard_unpacked(directory: &Path) -> io::Result<()> {
+    let unpack_directory = directory.join(UNPACK_DIRECTORY);
+    if unpack_directory.exists() {
+        fs::remove_dir_all(&unpack_directory)?;
+    }
+    Ok(())
+}
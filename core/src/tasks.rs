@@ -0,0 +1,539 @@
+// Copyright (C) 2026 Ethan Uppal.
+//
+// This program is free software: you can redistribute it and/or modify it under
+// the terms of the GNU General Public License as published by the Free Software
+// Foundation, version 3 of the License only.
+//
+// This program is distributed in the hope that it will be useful, but WITHOUT
+// ANY WARRANTY; without even the implied warranty of MERCHANTABILITY or FITNESS
+// FOR A PARTICULAR PURPOSE. See the GNU General Public License for more
+// details.
+//
+// You should have received a copy of the GNU General Public License along with
+// this program.  If not, see <https://www.gnu.org/licenses/>.
+
+//! Runs commands against a keg's Wine prefix in the background. At most one
+//! command that changes a keg runs at a time, guarded by a [`KegLock`], so
+//! two of them never talk to the same wineserver at once, even from separate
+//! kegtui processes; read-only commands run alongside them.
+
+use std::{
+    collections::VecDeque,
+    ffi::OsString,
+    fs::{self, File, OpenOptions},
+    io::{Read, Seek, SeekFrom},
+    path::{Path, PathBuf},
+    process::Command,
+    sync::{Arc, Condvar, Mutex, MutexGuard, PoisonError},
+    thread::{self, JoinHandle},
+    time::{Duration, Instant, SystemTime},
+};
+
+use color_eyre::{Result, eyre::Context};
+
+use crate::{
+    journal,
+    keg::{Keg, write_checked_plist},
+    keg_config::WinetricksRunFlags,
+    keg_plist::KegPlist,
+    run_logs,
+    runner::{CommandRunner, Completion},
+    snapshots,
+};
+
+/// How many finished tasks stay listed after they're done.
+const MAX_FINISHED_TASKS: usize = 20;
+
+/// How often waiting tasks are retried, in case a keg was unlocked outside
+/// the queue.
+const RECHECK_INTERVAL: Duration = Duration::from_millis(500);
+
+/// Locked inside a keg's bundle so other kegtui processes see the keg is
+/// busy.
+const KEG_LOCK_FILE: &str = "Contents/kegtui.lock";
+
+/// The bundles of kegs this process holds a [`KegLock`] on.
+static LOCKED_KEGS: Mutex<Vec<PathBuf>> = Mutex::new(Vec::new());
+
+/// Held by whatever is changing a keg's prefix or bundle, whether a queued
+/// task or an external action like an engine upgrade. Released on drop.
+pub struct KegLock {
+    bundle: PathBuf,
+    _file: File,
+}
+
+impl KegLock {
+    /// Locks the keg at `bundle`, or returns `None` if something else, in
+    /// this process or another, holds it.
+    pub fn try_acquire(bundle: &Path) -> Option<Self> {
+        let mut locked_kegs =
+            LOCKED_KEGS.lock().unwrap_or_else(PoisonError::into_inner);
+        if locked_kegs.iter().any(|locked| locked == bundle) {
+            return None;
+        }
+        let file = OpenOptions::new()
+            .write(true)
+            .create(true)
+            .truncate(false)
+            .open(bundle.join(KEG_LOCK_FILE))
+            .ok()?;
+        file.try_lock().ok()?;
+        locked_kegs.push(bundle.to_path_buf());
+        Some(Self {
+            bundle: bundle.to_path_buf(),
+            _file: file,
+        })
+    }
+
+    /// Whether another kegtui process is changing the keg at `bundle`. A lock
+    /// held by this process doesn't count, since its tasks are careful not to
+    /// lose edits made meanwhile.
+    pub fn is_held_elsewhere(bundle: &Path) -> bool {
+        let is_held_here = LOCKED_KEGS
+            .lock()
+            .unwrap_or_else(PoisonError::into_inner)
+            .iter()
+            .any(|locked| locked == bundle);
+        !is_held_here && Self::try_acquire(bundle).is_none()
+    }
+}
+
+impl Drop for KegLock {
+    fn drop(&mut self) {
+        LOCKED_KEGS
+            .lock()
+            .unwrap_or_else(PoisonError::into_inner)
+            .retain(|locked| *locked != self.bundle);
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum TaskKind {
+    /// Changes the prefix, so it waits for the keg's [`KegLock`].
+    Mutation,
+    /// Only reads the keg, so it starts as soon as it is queued.
+    ReadOnly,
+}
+
+/// A command run against a keg's prefix.
+pub struct PrefixTask {
+    pub keg_name: String,
+    /// The keg's `.app` bundle, whose journal records finished mutations.
+    pub bundle: PathBuf,
+    pub kind: TaskKind,
+    /// Shown in the queue, e.g., the verbs being installed.
+    pub description: String,
+    /// Names the run in the keg's previous runs.
+    pub run_label: &'static str,
+    pub command: Command,
+    pub wineskin_launcher: OsString,
+    pub log_directory: PathBuf,
+    pub config_file: PathBuf,
+    /// Written to the keg's config for the duration of the task, since
+    /// WSS-winetricks reads its flags from there, and put back afterward
+    /// unless they were changed meanwhile.
+    pub winetricks_run_flags: Option<WinetricksRunFlags>,
+    pub timeout: Option<Duration>,
+    /// A log the command appends to besides its own output, like the
+    /// wrapper's `Winetricks.log`.
+    pub output_log: Option<PathBuf>,
+    /// Picks the reason the command failed out of its output, given whether
+    /// it exited successfully.
+    pub explain_failure: Option<fn(&str, bool) -> Option<String>>,
+    /// Picks what to show for a successful run out of its output, e.g., the
+    /// size a read-only task measured.
+    pub summarize: Option<fn(&str) -> Option<String>>,
+    /// Snapshots the prefix right before the command runs, so the task can
+    /// be undone. Only mutations are snapshotted.
+    pub snapshot_prefix: bool,
+}
+
+#[derive(Debug, Clone)]
+pub enum TaskStatus {
+    Queued,
+    Running,
+    Finished {
+        completion: Completion,
+        run_log: Option<PathBuf>,
+        /// Why the command failed, as found in its output.
+        problem: Option<String>,
+        /// What the command found, e.g., a keg's size.
+        summary: Option<String>,
+    },
+    Failed(String),
+}
+
+impl TaskStatus {
+    pub fn is_pending(&self) -> bool {
+        matches!(self, TaskStatus::Queued | TaskStatus::Running)
+    }
+
+    /// How the task ended, e.g., `done`, or `None` if it hasn't.
+    pub fn outcome(&self) -> Option<String> {
+        match self {
+            TaskStatus::Queued | TaskStatus::Running => None,
+            TaskStatus::Finished {
+                problem: Some(_), ..
+            }
+            | TaskStatus::Failed(_) => Some("failed".to_owned()),
+            TaskStatus::Finished {
+                completion: Completion::Exited(status),
+                summary,
+                ..
+            } if status.success() => Some(match summary {
+                Some(summary) => format!("done: {summary}"),
+                None => "done".to_owned(),
+            }),
+            TaskStatus::Finished { completion, .. } => {
+                Some(completion.to_string())
+            }
+        }
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct TaskEntry {
+    pub id: u64,
+    pub keg_name: String,
+    pub kind: TaskKind,
+    pub description: String,
+    pub status: TaskStatus,
+    /// When the task started running.
+    pub started: Option<Instant>,
+}
+
+#[derive(Default)]
+struct QueueState {
+    /// Every listed task, oldest first.
+    entries: Vec<TaskEntry>,
+    pending: VecDeque<(u64, PrefixTask)>,
+    next_id: u64,
+    /// Running tasks the user asked to stop.
+    cancelled: Vec<u64>,
+    is_quitting: bool,
+}
+
+impl QueueState {
+    fn set_status(&mut self, id: u64, status: TaskStatus) {
+        if let Some(entry) =
+            self.entries.iter_mut().find(|entry| entry.id == id)
+        {
+            if matches!(status, TaskStatus::Running) {
+                entry.started = Some(Instant::now());
+            }
+            entry.status = status;
+        }
+    }
+
+    fn forget_old_tasks(&mut self) {
+        let mut excess = self
+            .entries
+            .iter()
+            .filter(|entry| !entry.status.is_pending())
+            .count()
+            .saturating_sub(MAX_FINISHED_TASKS);
+        self.entries.retain(|entry| {
+            let is_forgotten = excess > 0 && !entry.status.is_pending();
+            if is_forgotten {
+                excess -= 1;
+            }
+            !is_forgotten
+        });
+    }
+}
+
+type SharedQueue = Arc<(Mutex<QueueState>, Condvar)>;
+
+/// Owns the thread that starts [`PrefixTask`]s in the order they were queued,
+/// as their kegs become free. Dropping it cancels the running tasks and waits
+/// for them to stop.
+pub struct TaskQueue {
+    shared: SharedQueue,
+    thread: Option<JoinHandle<()>>,
+}
+
+impl TaskQueue {
+    pub fn spawn(runner: Arc<dyn CommandRunner>) -> Self {
+        let shared = SharedQueue::default();
+        let thread = {
+            let shared = shared.clone();
+            thread::spawn(move || dispatch_tasks(&shared, &runner))
+        };
+        Self {
+            shared,
+            thread: Some(thread),
+        }
+    }
+
+    fn lock(&self) -> MutexGuard<'_, QueueState> {
+        lock_state(&self.shared)
+    }
+
+    /// Queues `task` and returns how many changes to its keg will run before
+    /// it.
+    pub fn enqueue(&self, task: PrefixTask) -> usize {
+        let mut state = self.lock();
+        let ahead = match task.kind {
+            TaskKind::Mutation => state
+                .entries
+                .iter()
+                .filter(|entry| {
+                    entry.keg_name == task.keg_name
+                        && entry.kind == TaskKind::Mutation
+                        && entry.status.is_pending()
+                })
+                .count(),
+            TaskKind::ReadOnly => 0,
+        };
+        let id = state.next_id;
+        state.next_id += 1;
+        state.entries.push(TaskEntry {
+            id,
+            keg_name: task.keg_name.clone(),
+            kind: task.kind,
+            description: task.description.clone(),
+            status: TaskStatus::Queued,
+            started: None,
+        });
+        state.pending.push_back((id, task));
+        self.shared.1.notify_one();
+        ahead
+    }
+
+    /// The listed tasks, oldest first.
+    pub fn entries(&self) -> Vec<TaskEntry> {
+        self.lock().entries.clone()
+    }
+
+    /// How many of `keg_name`'s tasks are queued or running.
+    pub fn pending_count(&self, keg_name: &str) -> usize {
+        self.lock()
+            .entries
+            .iter()
+            .filter(|entry| {
+                entry.keg_name == keg_name && entry.status.is_pending()
+            })
+            .count()
+    }
+
+    /// Removes the task with `id` from the queue, or stops it if it is
+    /// already running.
+    pub fn cancel(&self, id: u64) {
+        let mut state = self.lock();
+        if let Some(position) = state
+            .pending
+            .iter()
+            .position(|(pending_id, _)| *pending_id == id)
+        {
+            state.pending.remove(position);
+            state.entries.retain(|entry| entry.id != id);
+        } else if state.entries.iter().any(|entry| {
+            entry.id == id && matches!(entry.status, TaskStatus::Running)
+        }) {
+            state.cancelled.push(id);
+        }
+    }
+}
+
+impl Drop for TaskQueue {
+    fn drop(&mut self) {
+        self.lock().is_quitting = true;
+        self.shared.1.notify_all();
+        if let Some(thread) = self.thread.take() {
+            let _ = thread.join();
+        }
+    }
+}
+
+fn lock_state(shared: &SharedQueue) -> MutexGuard<'_, QueueState> {
+    shared.0.lock().unwrap_or_else(PoisonError::into_inner)
+}
+
+fn dispatch_tasks(shared: &SharedQueue, runner: &Arc<dyn CommandRunner>) {
+    let mut workers: Vec<JoinHandle<()>> = vec![];
+    let mut state = lock_state(shared);
+    while !state.is_quitting {
+        // Tasks are tried oldest first, so a keg's mutations still run in the
+        // order they were queued.
+        let mut index = 0;
+        while index < state.pending.len() {
+            let keg_lock = match state.pending[index].1.kind {
+                TaskKind::ReadOnly => None,
+                TaskKind::Mutation => {
+                    match KegLock::try_acquire(&state.pending[index].1.bundle) {
+                        Some(keg_lock) => Some(keg_lock),
+                        None => {
+                            index += 1;
+                            continue;
+                        }
+                    }
+                }
+            };
+            let Some((id, task)) = state.pending.remove(index) else {
+                break;
+            };
+            state.set_status(id, TaskStatus::Running);
+            let shared = shared.clone();
+            let runner = runner.clone();
+            workers.push(thread::spawn(move || {
+                let status = run_task(task, &*runner, &|| {
+                    let state = lock_state(&shared);
+                    state.is_quitting || state.cancelled.contains(&id)
+                })
+                .unwrap_or_else(|error| {
+                    TaskStatus::Failed(format!("{error:#}"))
+                });
+                drop(keg_lock);
+                let mut state = lock_state(&shared);
+                state.set_status(id, status);
+                state.cancelled.retain(|cancelled| *cancelled != id);
+                state.forget_old_tasks();
+                shared.1.notify_all();
+            }));
+        }
+        workers.retain(|worker| !worker.is_finished());
+
+        state = shared
+            .1
+            .wait_timeout(state, RECHECK_INTERVAL)
+            .unwrap_or_else(PoisonError::into_inner)
+            .0;
+    }
+    drop(state);
+    for worker in workers {
+        let _ = worker.join();
+    }
+}
+
+fn run_task(
+    mut task: PrefixTask,
+    runner: &dyn CommandRunner,
+    is_cancelled: &dyn Fn() -> bool,
+) -> Result<TaskStatus> {
+    let is_mutation = task.kind == TaskKind::Mutation;
+    if is_mutation && task.snapshot_prefix {
+        let snapshot = snapshots::snapshot_prefix(&task.keg_name, &task.bundle)
+            .context("Failed to snapshot the prefix, so nothing was run")?;
+        let _ = journal::append(
+            &task.bundle,
+            "snapshot",
+            &format!(
+                "prefix before {} ({})",
+                task.description,
+                snapshot.display()
+            ),
+        );
+    }
+
+    // The saved flags are restored into a freshly read config afterward, so
+    // edits made while the task ran aren't lost.
+    let mut overridden_flags = None;
+    let c_drive = Keg::c_drive_in(&task.bundle);
+    if let Some(run_flags) = task.winetricks_run_flags.filter(|_| is_mutation) {
+        let mut plist: KegPlist = plist::from_file(&task.config_file)
+            .context("Failed to read the keg's config")?;
+        let saved_flags = plist.winetricks_run_flags();
+        if saved_flags != run_flags {
+            plist.set_winetricks_run_flags(run_flags);
+            write_checked_plist(
+                &task.config_file,
+                &c_drive,
+                &mut plist,
+                false,
+            )?;
+            overridden_flags = Some((saved_flags, run_flags));
+        }
+    }
+
+    let output_log_start = task
+        .output_log
+        .as_ref()
+        .and_then(|output_log| fs::metadata(output_log).ok())
+        .map_or(0, |metadata| metadata.len());
+    let started = SystemTime::now();
+    let run =
+        runner.run_in_background(&mut task.command, task.timeout, is_cancelled);
+
+    if let Some((saved_flags, run_flags)) = overridden_flags {
+        let mut plist: KegPlist = plist::from_file(&task.config_file)
+            .context("Failed to read the keg's config")?;
+        // Flags changed while the task ran, e.g., from Edit Config, are the
+        // user's new choice, so they're kept.
+        if plist.winetricks_run_flags() == run_flags {
+            plist.set_winetricks_run_flags(saved_flags);
+            write_checked_plist(
+                &task.config_file,
+                &c_drive,
+                &mut plist,
+                false,
+            )?;
+        }
+    }
+
+    let run = run?;
+    let run_log = run_logs::record(
+        &task.log_directory,
+        task.run_label,
+        started,
+        &task.command,
+        run.completion,
+        &run.stdout,
+        &run.stderr,
+    )
+    .ok();
+
+    let mut output = String::from_utf8_lossy(&run.stdout).into_owned();
+    output.push_str(&String::from_utf8_lossy(&run.stderr));
+    if let Some(output_log) = &task.output_log {
+        output.push_str(&read_from(output_log, output_log_start));
+    }
+    let exited_successfully = matches!(
+        run.completion,
+        Completion::Exited(status) if status.success()
+    );
+    let problem = task.explain_failure.and_then(|explain_failure| {
+        explain_failure(&output, exited_successfully)
+    });
+    let summary = task
+        .summarize
+        .filter(|_| exited_successfully)
+        .and_then(|summarize| summarize(&output));
+
+    if is_mutation {
+        let outcome = match &problem {
+            Some(problem) => format!("{}: {problem}", run.completion),
+            None => run.completion.to_string(),
+        };
+        let _ = journal::append(
+            &task.bundle,
+            task.run_label,
+            &format!("{} ({outcome})", task.description),
+        );
+    }
+
+    // Killing the wineserver would also stop a mutation running alongside
+    // an interrupted read-only task.
+    if is_mutation && run.completion.interruption().is_some() {
+        runner.output(
+            Command::new(&task.wineskin_launcher).arg("WSS-wineserverkill"),
+        )?;
+    }
+
+    Ok(TaskStatus::Finished {
+        completion: run.completion,
+        run_log,
+        problem,
+        summary,
+    })
+}
+
+/// What was appended to the file at `path` after it was `start` bytes long.
+fn read_from(path: &Path, start: u64) -> String {
+    let mut contents = vec![];
+    if let Ok(mut file) = File::open(path)
+        && file.seek(SeekFrom::Start(start)).is_ok()
+    {
+        let _ = file.read_to_end(&mut contents);
+    }
+    String::from_utf8_lossy(&contents).into_owned()
+}
@@ -0,0 +1,156 @@
+// Copyright (C) 2026 Ethan Uppal.
+//
+// This program is free software: you can redistribute it and/or modify it under
+// the terms of the GNU General Public License as published by the Free Software
+// Foundation, version 3 of the License only.
+//
+// This program is distributed in the hope that it will be useful, but WITHOUT
+// ANY WARRANTY; without even the implied warranty of MERCHANTABILITY or FITNESS
+// FOR A PARTICULAR PURPOSE. See the GNU General Public License for more
+// details.
+//
+// You should have received a copy of the GNU General Public License along with
+// this program.  If not, see <https://www.gnu.org/licenses/>.
+
+use std::{
+    fs,
+    path::{Path, PathBuf},
+};
+
+use walkdir::WalkDir;
+
+/// How deep into an unpacked engine to look for translation layers. Engines
+/// keep them under `lib/external` or `lib/wine/<arch>-windows`.
+const ENGINE_SEARCH_DEPTH: usize = 6;
+
+const MH_MAGIC_64: u32 = 0xfeedfacf;
+const FAT_MAGIC: u32 = 0xcafebabe;
+const LC_ID_DYLIB: u32 = 0xd;
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum LayerVersion {
+    NotBundled,
+    Unknown,
+    Version(String),
+}
+
+impl LayerVersion {
+    pub fn describe(&self) -> &str {
+        match self {
+            LayerVersion::NotBundled => "not bundled",
+            LayerVersion::Unknown => "bundled (unknown version)",
+            LayerVersion::Version(version) => version,
+        }
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct TranslationLayerVersions {
+    pub molten_vk: LayerVersion,
+    pub dxvk: LayerVersion,
+    pub d3d_metal: LayerVersion,
+}
+
+fn read_u32(bytes: &[u8], offset: usize, big_endian: bool) -> Option<u32> {
+    let word = bytes.get(offset..offset + 4)?.try_into().ok()?;
+    Some(if big_endian {
+        u32::from_be_bytes(word)
+    } else {
+        u32::from_le_bytes(word)
+    })
+}
+
+/// Reads the `current_version` recorded in a dylib's `LC_ID_DYLIB` load
+/// command, picking the first slice of a universal binary.
+fn dylib_current_version(path: &Path) -> Option<String> {
+    let bytes = fs::read(path).ok()?;
+    let mut start = 0;
+    if read_u32(&bytes, 0, true)? == FAT_MAGIC {
+        // `fat_arch` is five big-endian words after the eight-byte header.
+        start = read_u32(&bytes, 8 + 8, true)? as usize;
+    }
+    if read_u32(&bytes, start, false)? != MH_MAGIC_64 {
+        return None;
+    }
+    let command_count = read_u32(&bytes, start + 16, false)?;
+    let mut offset = start + 32;
+    for _ in 0..command_count {
+        let command = read_u32(&bytes, offset, false)?;
+        let size = read_u32(&bytes, offset + 4, false)?;
+        if command == LC_ID_DYLIB {
+            let version = read_u32(&bytes, offset + 16, false)?;
+            return Some(format!(
+                "{}.{}.{}",
+                version >> 16,
+                (version >> 8) & 0xff,
+                version & 0xff
+            ));
+        }
+        offset += size as usize;
+    }
+    None
+}
+
+fn bundle_short_version(framework: &Path) -> Option<String> {
+    ["Resources/Info.plist", "Versions/A/Resources/Info.plist"]
+        .into_iter()
+        .find_map(|info_plist| {
+            plist::Value::from_file(framework.join(info_plist))
+                .ok()?
+                .as_dictionary()?
+                .get("CFBundleShortVersionString")?
+                .as_string()
+                .map(str::to_owned)
+        })
+}
+
+fn find_in_engine(
+    engine: &Path,
+    matches: impl Fn(&str) -> bool,
+) -> Option<PathBuf> {
+    WalkDir::new(engine)
+        .max_depth(ENGINE_SEARCH_DEPTH)
+        .into_iter()
+        .flatten()
+        .find(|entry| entry.file_name().to_str().is_some_and(&matches))
+        .map(|entry| entry.into_path())
+}
+
+fn molten_vk_version(engine: &Path) -> LayerVersion {
+    match find_in_engine(engine, |name| name == "libMoltenVK.dylib") {
+        Some(dylib) => dylib_current_version(&dylib)
+            .map_or(LayerVersion::Unknown, LayerVersion::Version),
+        None => LayerVersion::NotBundled,
+    }
+}
+
+/// DXVK builds carry no version metadata in their DLLs, so this relies on the
+/// `version` file some engines place next to them.
+fn dxvk_version(engine: &Path) -> LayerVersion {
+    match find_in_engine(engine, |name| name.eq_ignore_ascii_case("dxvk")) {
+        Some(dxvk) => fs::read_to_string(dxvk.join("version"))
+            .ok()
+            .map(|version| version.trim().to_owned())
+            .filter(|version| !version.is_empty())
+            .map_or(LayerVersion::Unknown, LayerVersion::Version),
+        None => LayerVersion::NotBundled,
+    }
+}
+
+fn d3d_metal_version(engine: &Path) -> LayerVersion {
+    match find_in_engine(engine, |name| name == "D3DMetal.framework") {
+        Some(framework) => bundle_short_version(&framework)
+            .map_or(LayerVersion::Unknown, LayerVersion::Version),
+        None => LayerVersion::NotBundled,
+    }
+}
+
+/// Inspects the unpacked engine at `engine`, e.g., a keg's
+/// `Contents/SharedSupport/wine`.
+pub fn inspect_engine(engine: &Path) -> TranslationLayerVersions {
+    TranslationLayerVersions {
+        molten_vk: molten_vk_version(engine),
+        dxvk: dxvk_version(engine),
+        d3d_metal: d3d_metal_version(engine),
+    }
+}
@@ -15,9 +15,18 @@
 use std::{borrow::Cow, collections::HashMap};
 
 use color_eyre::eyre::Result;
-use ratatui::{Frame, layout::Rect};
+use crossterm::event::{KeyCode, KeyEvent};
+use ratatui::{
+    Frame,
+    layout::{Constraint, Direction, Layout, Rect},
+    style::Stylize,
+    text::{Line, Text},
+    widgets::{List, ListItem, ListState, Paragraph, Wrap},
+};
 
-use crate::app::{App, AsyncState};
+use crate::app::{
+    App, AsyncState, SELECTED_FOCUSED_STYLE, SELECTED_UNFOCUSED_STYLE,
+};
 
 pub mod prelude {
     pub use super::*;
@@ -53,13 +62,47 @@ pub enum NavAction<'a> {
 pub enum MenuItemAction<'a> {
     NavAction(NavAction<'a>),
     LoadView(ViewID<'a>),
+    /// Like [`MenuItemAction::LoadView`], but starts with the given item
+    /// selected (or scrolled to) instead of the first.
+    LoadViewAt(ViewID<'a>, usize),
     External(fn(&mut App, &AsyncState) -> Result<()>),
+    /// Like [`MenuItemAction::External`], for generated items sharing one
+    /// action, e.g., one per custom command, which is told the given index.
+    ExternalAt(fn(&mut App, &AsyncState, usize) -> Result<()>, usize),
 }
 
+/// Something a menu item needs before its action can do anything useful.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Requirement {
+    CurrentKeg,
+    Network,
+    Engines,
+}
+
+impl Requirement {
+    /// Why the action can't run, shown when the requirement is unmet.
+    pub fn explanation(self) -> &'static str {
+        match self {
+            Requirement::CurrentKeg => {
+                "No keg is selected. Choose one from the Kegs list first."
+            }
+            Requirement::Network => {
+                "This needs an internet connection, but github.com couldn't be reached."
+            }
+            Requirement::Engines => {
+                "No engines were found. Install one from the Setup Wizard or the Engines menu, or add a directory containing one to engine-search-paths."
+            }
+        }
+    }
+}
+
+#[derive(Clone)]
 pub struct MenuItem<'a> {
     name: Cow<'a, str>,
+    description: Option<Cow<'a, str>>,
     is_default: bool,
     action: MenuItemAction<'a>,
+    requirements: &'static [Requirement],
 }
 
 impl<'a> MenuItem<'a> {
@@ -69,8 +112,10 @@ impl<'a> MenuItem<'a> {
     ) -> Self {
         Self {
             name: name.into(),
+            description: None,
             is_default: false,
             action,
+            requirements: &[],
         }
     }
 
@@ -79,27 +124,74 @@ impl<'a> MenuItem<'a> {
         self
     }
 
+    /// Explains what the item does, shown in the footer while it is
+    /// highlighted.
+    pub fn description(mut self, description: impl Into<Cow<'a, str>>) -> Self {
+        self.description = Some(description.into());
+        self
+    }
+
+    /// Checked in order before the action runs; the first unmet one is
+    /// explained instead of running the action.
+    pub fn requires(mut self, requirements: &'static [Requirement]) -> Self {
+        self.requirements = requirements;
+        self
+    }
+
     pub fn name(&self) -> &str {
         &self.name
     }
 
+    pub fn get_description(&self) -> Option<&str> {
+        self.description.as_deref()
+    }
+
     pub fn action(&self) -> &MenuItemAction<'a> {
         &self.action
     }
+
+    pub fn requirements(&self) -> &'static [Requirement] {
+        self.requirements
+    }
+}
+
+/// Builds a menu from the current app state each time it is shown.
+pub type MenuGenerator<'a> = fn(&App, &AsyncState) -> Vec<MenuItem<'a>>;
+
+/// Builds text shown above a nav's menu, e.g., to say what it operates on.
+pub type HeaderGenerator = fn(&App, &AsyncState) -> Option<Text<'static>>;
+
+enum NavMenu<'a> {
+    Static(Vec<MenuItem<'a>>),
+    Dynamic(MenuGenerator<'a>),
 }
 
 pub struct Nav<'a> {
-    menu: Vec<MenuItem<'a>>,
-    default_item: usize,
+    menu: NavMenu<'a>,
+    header: Option<HeaderGenerator>,
 }
 
 impl<'a> Nav<'a> {
-    pub fn menu(&self) -> &[MenuItem<'a>] {
-        &self.menu
+    pub fn menu(&self, app: &App, state: &AsyncState) -> Vec<MenuItem<'a>> {
+        match &self.menu {
+            NavMenu::Static(menu) => menu.clone(),
+            NavMenu::Dynamic(generator) => generator(app, state),
+        }
+    }
+
+    pub fn header(
+        &self,
+        app: &App,
+        state: &AsyncState,
+    ) -> Option<Text<'static>> {
+        self.header.and_then(|generator| generator(app, state))
     }
 
-    pub fn default_item_index(&self) -> usize {
-        self.default_item
+    pub fn default_item_index(&self, app: &App, state: &AsyncState) -> usize {
+        self.menu(app, state)
+            .iter()
+            .position(|item| item.is_default)
+            .unwrap_or(0)
     }
 }
 
@@ -137,17 +229,196 @@ pub trait View {
         Ok(ViewInteractivity::None)
     }
 
+    /// The lines that visual mode (<V>) can select and copy, if this view
+    /// supports it.
+    fn selectable_lines(
+        &self,
+        app: &App,
+        state: &AsyncState,
+    ) -> Option<Vec<String>> {
+        let _ = (app, state);
+        None
+    }
+
+    /// Handles a key pressed while the view is focused, before the app's own
+    /// keybinds, e.g., to type into a field. Returns whether it was used.
+    fn handle_key(
+        &self,
+        app: &mut App,
+        state: &AsyncState,
+        key_event: KeyEvent,
+    ) -> Result<bool> {
+        let _ = (app, state, key_event);
+        Ok(false)
+    }
+
+    /// Returns what to do once the item at `index` is clicked, if anything.
     fn click(
         &self,
         app: &mut App,
         state: &AsyncState,
         index: usize,
-    ) -> Result<Option<NavAction<'_>>> {
+    ) -> Result<Option<MenuItemAction<'_>>> {
         let _ = (app, state, index);
         Ok(None)
     }
 }
 
+pub enum FormValue {
+    Toggle(bool),
+    Text(String),
+}
+
+pub struct FormField {
+    pub label: &'static str,
+    /// Shown below the form while the field is highlighted.
+    pub description: &'static str,
+    pub value: FormValue,
+}
+
+/// What clicking one of a [`Form`]'s clickables asks its view to do.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum FormAction {
+    None,
+    Save,
+    Cancel,
+}
+
+/// Toggles and text inputs followed by a save/cancel footer, for views that
+/// edit settings in place. Its clickables are its fields, then Save, then
+/// Cancel; clicking a text input starts typing into it.
+pub struct Form {
+    fields: Vec<FormField>,
+    /// The text input being typed into.
+    editing: Option<usize>,
+}
+
+impl Form {
+    pub fn new(fields: Vec<FormField>) -> Self {
+        Self {
+            fields,
+            editing: None,
+        }
+    }
+
+    pub fn fields(&self) -> &[FormField] {
+        &self.fields
+    }
+
+    pub fn clickables(&self) -> usize {
+        self.fields.len() + 2
+    }
+
+    pub fn click(&mut self, index: usize) -> FormAction {
+        match index.checked_sub(self.fields.len()) {
+            Some(0) => return FormAction::Save,
+            Some(1) => return FormAction::Cancel,
+            _ => {}
+        }
+        match self.fields.get_mut(index).map(|field| &mut field.value) {
+            Some(FormValue::Toggle(value)) => *value ^= true,
+            Some(FormValue::Text(_)) => self.editing = Some(index),
+            None => {}
+        }
+        FormAction::None
+    }
+
+    /// Types into the text input being edited, if any, returning whether the
+    /// key was used. Enter or Esc stops typing.
+    pub fn handle_key(&mut self, key_event: KeyEvent) -> bool {
+        let Some(index) = self.editing else {
+            return false;
+        };
+        let Some(FormValue::Text(text)) =
+            self.fields.get_mut(index).map(|field| &mut field.value)
+        else {
+            self.editing = None;
+            return false;
+        };
+        match key_event.code {
+            KeyCode::Enter | KeyCode::Esc => self.editing = None,
+            KeyCode::Backspace => {
+                text.pop();
+            }
+            KeyCode::Char(c) => text.push(c),
+            _ => {}
+        }
+        true
+    }
+
+    /// Draws the fields and footer with the clickable at `selected`
+    /// highlighted.
+    pub fn draw(
+        &self,
+        frame: &mut Frame,
+        area: Rect,
+        selected: usize,
+        is_focused: bool,
+    ) {
+        let chunks = Layout::default()
+            .direction(Direction::Vertical)
+            .constraints([Constraint::Fill(1), Constraint::Length(3)])
+            .split(area);
+        let label_width = self
+            .fields
+            .iter()
+            .map(|field| field.label.len())
+            .max()
+            .unwrap_or_default();
+        let mut items = self
+            .fields
+            .iter()
+            .enumerate()
+            .map(|(index, field)| {
+                let label = format!("{:<label_width$}  ", field.label);
+                ListItem::new(match &field.value {
+                    FormValue::Toggle(value) => Line::from(vec![
+                        if *value { "[x] " } else { "[ ] " }.into(),
+                        label.into(),
+                    ]),
+                    FormValue::Text(text) => {
+                        let mut line = Line::from(vec![
+                            "    ".into(),
+                            label.into(),
+                            text.clone().bold(),
+                        ]);
+                        if self.editing == Some(index) {
+                            line.push_span("_".dark_gray());
+                        }
+                        line
+                    }
+                })
+            })
+            .collect::<Vec<_>>();
+        items.push(ListItem::new(Line::from("[ Save ]").bold()));
+        items.push(ListItem::new(Line::from("[ Cancel ]").bold()));
+
+        let mut list_state = ListState::default();
+        list_state.select(Some(selected));
+        let list = List::new(items)
+            .highlight_style(if is_focused {
+                SELECTED_FOCUSED_STYLE
+            } else {
+                SELECTED_UNFOCUSED_STYLE
+            })
+            .highlight_symbol(">> ");
+        frame.render_stateful_widget(list, chunks[0], &mut list_state);
+
+        let help = match self.fields.get(selected) {
+            Some(field) if self.editing == Some(selected) => format!(
+                "{} Press <Enter> when you're done typing.",
+                field.description
+            ),
+            Some(field) => field.description.to_owned(),
+            None => "Saving writes every change to the keg at once.".to_owned(),
+        };
+        frame.render_widget(
+            Paragraph::new(help.dark_gray()).wrap(Wrap { trim: false }),
+            chunks[1],
+        );
+    }
+}
+
 #[derive(Default)]
 pub struct NavContext<'a> {
     views: Vec<&'a dyn View>,
@@ -174,18 +445,37 @@ impl<'a> NavContext<'a> {
         menu: impl IntoIterator<Item = MenuItem<'a>>,
     ) -> NavID<'a> {
         let menu = menu.into_iter().collect::<Vec<_>>();
-        let default_item = menu
-            .iter()
-            .enumerate()
-            .find(|(_, item)| item.is_default)
-            .map(|(index, _)| index)
-            .unwrap_or(0);
         assert!(!menu.is_empty());
-        self.navs.push(Nav { menu, default_item });
+        self.push_nav_definition(name, NavMenu::Static(menu))
+    }
+
+    /// Like [`NavContext::nav`], but the menu is regenerated from the app
+    /// state whenever it is drawn or navigated. The generator should never
+    /// return an empty menu.
+    pub fn dynamic_nav(
+        &mut self,
+        name: &'a str,
+        generator: MenuGenerator<'a>,
+    ) -> NavID<'a> {
+        self.push_nav_definition(name, NavMenu::Dynamic(generator))
+    }
+
+    fn push_nav_definition(
+        &mut self,
+        name: &'a str,
+        menu: NavMenu<'a>,
+    ) -> NavID<'a> {
+        self.navs.push(Nav { menu, header: None });
         self.named_nav_ids.insert(name, self.navs.len() - 1);
         NavID::Index(self.navs.len() - 1)
     }
 
+    /// Shows the text from `generator` above the menu of `nav`.
+    pub fn nav_header(&mut self, nav: NavID<'a>, generator: HeaderGenerator) {
+        let index = self.get_nav_index(nav);
+        self.navs[index].header = Some(generator);
+    }
+
     pub fn push_nav(&mut self, nav: NavID<'a>) {
         self.stack.push(nav);
     }
@@ -12,5 +12,21 @@
 // You should have received a copy of the GNU General Public License along with
 // this program.  If not, see <https://www.gnu.org/licenses/>.
 
+pub mod about;
+pub mod config_form;
 pub mod credits;
+pub mod delete_keg;
+pub mod engine_inventory;
+pub mod engines;
+pub mod executables;
+pub mod history;
+pub mod keg_creator;
+pub mod keg_info;
+pub mod keg_log;
+pub mod keg_processes;
+pub mod keg_runs;
 pub mod kegs;
+pub mod setup;
+pub mod tasks;
+pub mod winetricks_browser;
+pub mod winetricks_cache;
@@ -0,0 +1,105 @@
+// Copyright (C) 2026 Ethan Uppal.
+//
+// This program is free software: you can redistribute it and/or modify it under
+// the terms of the GNU General Public License as published by the Free Software
+// Foundation, version 3 of the License only.
+//
+// This program is distributed in the hope that it will be useful, but WITHOUT
+// ANY WARRANTY; without even the implied warranty of MERCHANTABILITY or FITNESS
+// FOR A PARTICULAR PURPOSE. See the GNU General Public License for more
+// details.
+//
+// You should have received a copy of the GNU General Public License along with
+// this program.  If not, see <https://www.gnu.org/licenses/>.
+
+use crate::{
+    app::{App, AsyncState},
+    view::prelude::*,
+};
+
+pub const VERSION: &str = env!("CARGO_PKG_VERSION");
+pub const COMMIT: &str = env!("KEGTUI_COMMIT");
+pub const BUILD_DATE: &str = env!("KEGTUI_BUILD_DATE");
+/// Where kegtui's releases are published on GitHub.
+pub const REPOSITORY: &str = "ethanuppal/kegtui";
+
+pub struct AboutView;
+
+impl View for AboutView {
+    fn draw_content(
+        &self,
+        app: &App,
+        state: &AsyncState,
+        frame: &mut Frame,
+        area: Rect,
+        _is_focused: bool,
+    ) -> Result<()> {
+        let mut lines = vec![
+            Line::from(vec!["kegtui ".bold(), VERSION.into()]),
+            Line::from(vec!["Commit: ".bold(), COMMIT.into()]),
+            Line::from(vec!["Built: ".bold(), BUILD_DATE.into()]),
+            Line::from(""),
+            Line::from(vec![
+                "Kegworks: ".bold(),
+                match (&state.kegworks_version, state.is_kegworks_installed) {
+                    (Some(version), _) => version.clone(),
+                    (None, true) => "installed, version unknown".to_owned(),
+                    (None, false) => "not installed".to_owned(),
+                }
+                .into(),
+            ]),
+        ];
+        if let Some(current_keg) = &app.current_keg {
+            lines.push(Line::from(vec![
+                "Current keg's engine: ".bold(),
+                current_keg
+                    .keg
+                    .engine_version
+                    .clone()
+                    .unwrap_or_else(|| "unknown".to_owned())
+                    .into(),
+            ]));
+        }
+        lines.push(Line::from("Engines:".bold()));
+        if state.engines.is_empty() {
+            lines.push(Line::from("  None found".dark_gray()));
+        }
+        for engine in &state.engines {
+            lines.push(Line::from(format!(
+                "  {}",
+                engine
+                    .path
+                    .file_name()
+                    .unwrap_or_default()
+                    .to_string_lossy()
+            )));
+        }
+
+        let scrollbar = Scrollbar::new(ScrollbarOrientation::VerticalRight);
+        let mut scrollbar_state =
+            ScrollbarState::new(lines.len()).position(app.interaction_state());
+
+        frame.render_widget(
+            Paragraph::new(lines).scroll((app.interaction_state() as u16, 0)),
+            area,
+        );
+        frame.render_stateful_widget(
+            scrollbar,
+            area.inner(Margin {
+                vertical: 1,
+                horizontal: 0,
+            }),
+            &mut scrollbar_state,
+        );
+
+        Ok(())
+    }
+
+    fn interactivity(
+        &self,
+        _app: &App,
+        _state: &AsyncState,
+    ) -> Result<ViewInteractivity> {
+        Ok(ViewInteractivity::Scrollable)
+    }
+}
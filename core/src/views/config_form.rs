@@ -0,0 +1,230 @@
+// Copyright (C) 2026 Ethan Uppal.
+//
+// This program is free software: you can redistribute it and/or modify it under
+// the terms of the GNU General Public License as published by the Free Software
+// Foundation, version 3 of the License only.
+//
+// This program is distributed in the hope that it will be useful, but WITHOUT
+// ANY WARRANTY; without even the implied warranty of MERCHANTABILITY or FITNESS
+// FOR A PARTICULAR PURPOSE. See the GNU General Public License for more
+// details.
+//
+// You should have received a copy of the GNU General Public License along with
+// this program.  If not, see <https://www.gnu.org/licenses/>.
+
+use crossterm::event::KeyEvent;
+use ratatui::widgets::Wrap;
+
+use crate::{
+    app::{App, AsyncState},
+    keg::CurrentKeg,
+    keg_config::{CONFIG_FIELDS, KegworksConfig},
+    keyboard,
+    plist_validation::SavePlistError,
+    tasks::{KegLock, TaskQueue},
+    view::prelude::*,
+};
+
+/// The current keg's settings as edited in the form, kept in [`App`] so the
+/// edits survive leaving the view until they're saved or cancelled.
+#[derive(Default)]
+pub struct ConfigForm {
+    /// The edited settings, or `None` while they're the keg's own.
+    form: Option<Form>,
+    /// What happened to the last save, or why it failed.
+    status: Option<Result<String, String>>,
+}
+
+impl ConfigForm {
+    pub fn reset(&mut self) {
+        *self = Self::default();
+    }
+}
+
+/// The form's fields for `config`, in the order of [`CONFIG_FIELDS`]: every
+/// setting that's a switch or a line of text. The environment variables and
+/// custom commands are tables, so they're left to Edit Config as TOML.
+fn fields(config: &KegworksConfig) -> Vec<FormField> {
+    let Ok(root) = toml::Value::try_from(config) else {
+        return vec![];
+    };
+    CONFIG_FIELDS
+        .iter()
+        .filter_map(|field| {
+            let value = match field
+                .path
+                .split('.')
+                .try_fold(&root, |value, key| value.get(key))?
+            {
+                toml::Value::Boolean(value) => FormValue::Toggle(*value),
+                toml::Value::String(text) => FormValue::Text(text.clone()),
+                _ => return None,
+            };
+            Some(FormField {
+                label: field.path,
+                description: field.description,
+                value,
+            })
+        })
+        .collect()
+}
+
+/// `config` with the values in `form`, or why they aren't valid settings.
+fn apply(
+    form: &Form,
+    config: &KegworksConfig,
+) -> Result<KegworksConfig, String> {
+    let mut root = toml::Value::try_from(config)
+        .map_err(|error| format!("Failed to read the config: {error}"))?;
+    for field in form.fields() {
+        let Some(slot) = field
+            .label
+            .split('.')
+            .try_fold(&mut root, |value, key| value.get_mut(key))
+        else {
+            continue;
+        };
+        *slot = match &field.value {
+            FormValue::Toggle(value) => toml::Value::Boolean(*value),
+            FormValue::Text(text) => toml::Value::String(text.clone()),
+        };
+    }
+    root.try_into()
+        .map_err(|error| format!("A setting has the wrong type: {error}"))
+}
+
+fn save(
+    tasks: &TaskQueue,
+    current_keg: &mut CurrentKeg,
+    form: &Form,
+) -> Result<String, SavePlistError> {
+    if KegLock::is_held_elsewhere(&current_keg.bundle_path()) {
+        return Err(SavePlistError::Invalid(vec![
+            "Another kegtui is changing this keg; try again once it's done"
+                .to_owned(),
+        ]));
+    }
+    let config = apply(form, &current_keg.plist.extract_config())
+        .map_err(|error| SavePlistError::Invalid(vec![error]))?;
+    let keyboard = current_keg.plist.extract_config().keyboard;
+    current_keg.plist.update_from_config(&config);
+    current_keg.save_plist("Edit Config")?;
+    Ok(
+        match keyboard::apply_if_changed(tasks, current_keg, &keyboard) {
+            Ok(_) => "Saved; the changes apply the next time the keg launches"
+                .to_owned(),
+            Err(error) => format!(
+                "Saved, but the keyboard mapping couldn't be queued: {error:#}"
+            ),
+        },
+    )
+}
+
+pub struct ConfigFormView;
+
+impl View for ConfigFormView {
+    fn draw_content(
+        &self,
+        app: &App,
+        _state: &AsyncState,
+        frame: &mut Frame,
+        area: Rect,
+        is_focused: bool,
+    ) -> Result<()> {
+        let Some(current_keg) = &app.current_keg else {
+            frame.render_widget("No keg is selected", area);
+            return Ok(());
+        };
+        let config_form = &app.config_form;
+        let chunks = Layout::default()
+            .direction(Direction::Vertical)
+            .constraints([Constraint::Length(2), Constraint::Fill(1)])
+            .split(area);
+        let status = match &config_form.status {
+            Some(Ok(status)) => Line::from(status.clone().green()),
+            Some(Err(error)) => Line::from(error.clone().red()),
+            None if config_form.form.is_some() => Line::from(
+                "You have unsaved changes. Choose Save to write them to the keg."
+                    .yellow(),
+            ),
+            None => Line::from(
+                "Press <Enter> to toggle a setting or type into it. Environment variables are edited with Edit Config as TOML."
+                    .dark_gray(),
+            ),
+        };
+        frame.render_widget(
+            Paragraph::new(status).wrap(Wrap { trim: false }),
+            chunks[0],
+        );
+        let selected = app.interaction_state();
+        match &config_form.form {
+            Some(form) => form.draw(frame, chunks[1], selected, is_focused),
+            None => Form::new(fields(&current_keg.plist.extract_config()))
+                .draw(frame, chunks[1], selected, is_focused),
+        }
+        Ok(())
+    }
+
+    fn interactivity(
+        &self,
+        app: &App,
+        _state: &AsyncState,
+    ) -> Result<ViewInteractivity> {
+        let Some(current_keg) = &app.current_keg else {
+            return Ok(ViewInteractivity::None);
+        };
+        Ok(ViewInteractivity::Clickables(match &app.config_form.form {
+            Some(form) => form.clickables(),
+            None => fields(&current_keg.plist.extract_config()).len() + 2,
+        }))
+    }
+
+    fn handle_key(
+        &self,
+        app: &mut App,
+        _state: &AsyncState,
+        key_event: KeyEvent,
+    ) -> Result<bool> {
+        Ok(app
+            .config_form
+            .form
+            .as_mut()
+            .is_some_and(|form| form.handle_key(key_event)))
+    }
+
+    fn click(
+        &self,
+        app: &mut App,
+        _state: &AsyncState,
+        index: usize,
+    ) -> Result<Option<MenuItemAction<'_>>> {
+        let Some(current_keg) = &mut app.current_keg else {
+            return Ok(None);
+        };
+        let config_form = &mut app.config_form;
+        let form = config_form.form.get_or_insert_with(|| {
+            Form::new(fields(&current_keg.plist.extract_config()))
+        });
+        config_form.status = None;
+        match form.click(index) {
+            FormAction::None => {}
+            FormAction::Save => match save(&app.tasks, current_keg, form) {
+                Ok(status) => {
+                    config_form.form = None;
+                    config_form.status = Some(Ok(status));
+                }
+                // The edits stay in the form to be fixed.
+                Err(SavePlistError::Invalid(problems)) => {
+                    app.show_plist_problems(problems);
+                }
+                Err(error) => config_form.status = Some(Err(error.to_string())),
+            },
+            FormAction::Cancel => {
+                config_form.form = None;
+                config_form.status =
+                    Some(Ok("Discarded your changes".to_owned()));
+            }
+        }
+        Ok(None)
+    }
+}
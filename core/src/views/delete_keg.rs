@@ -0,0 +1,318 @@
+// Copyright (C) 2026 Ethan Uppal.
+//
+// This program is free software: you can redistribute it and/or modify it under
+// the terms of the GNU General Public License as published by the Free Software
+// Foundation, version 3 of the License only.
+//
+// This program is distributed in the hope that it will be useful, but WITHOUT
+// ANY WARRANTY; without even the implied warranty of MERCHANTABILITY or FITNESS
+// FOR A PARTICULAR PURPOSE. See the GNU General Public License for more
+// details.
+//
+// You should have received a copy of the GNU General Public License along with
+// this program.  If not, see <https://www.gnu.org/licenses/>.
+
+use std::{
+    ffi::OsStr,
+    fs,
+    path::Path,
+    process::Command,
+    sync::{Arc, Mutex},
+    thread,
+};
+
+use color_eyre::eyre::{Context, bail};
+use crossterm::event::{KeyCode, KeyEvent};
+use ratatui::widgets::{Block, Borders, Clear, Padding, Wrap};
+
+use crate::{
+    app::{App, AsyncState},
+    keg::CurrentKeg,
+    runner::CommandRunner,
+    tasks::KegLock,
+    view::prelude::*,
+};
+
+/// What has been typed to confirm deleting the current keg, kept in [`App`]
+/// so it can be cleared when another keg is chosen.
+#[derive(Default)]
+pub struct KegDeletion {
+    typed: String,
+    /// Why the last attempt to delete the keg failed.
+    error: Option<String>,
+    deleting: Option<Arc<Mutex<Progress>>>,
+}
+
+/// How far deleting a keg has gotten, shared with the thread deleting it.
+#[derive(Default)]
+struct Progress {
+    status: &'static str,
+    /// Set once deleting the keg stops, with why if it failed.
+    outcome: Option<Result<(), String>>,
+}
+
+impl KegDeletion {
+    pub fn reset(&mut self) {
+        *self = Self::default();
+    }
+
+    /// Whether the keg is being deleted or is already gone.
+    fn is_underway(&self) -> bool {
+        self.deleting.as_ref().is_some_and(|progress| {
+            progress
+                .lock()
+                .is_ok_and(|progress| !matches!(progress.outcome, Some(Err(_))))
+        })
+    }
+}
+
+/// The name to type to confirm deleting `current_keg`.
+fn confirmation_name(current_keg: &CurrentKeg) -> &str {
+    current_keg.keg.name.trim_end_matches(".app")
+}
+
+/// Starts killing the keg's processes and removing its bundle on another
+/// thread, once nothing else is changing the keg.
+fn start_deletion(
+    app: &App,
+    current_keg: &CurrentKeg,
+) -> Result<Arc<Mutex<Progress>>> {
+    if app.tasks.pending_count(&current_keg.keg.name) > 0 {
+        bail!(
+            "Wait for this keg's queued tasks to finish or cancel them first"
+        );
+    }
+    let bundle = current_keg.bundle_path();
+    let Some(keg_lock) = KegLock::try_acquire(&bundle) else {
+        bail!("Something else is changing this keg; try again once it's done");
+    };
+    let progress = Arc::new(Mutex::new(Progress::default()));
+    let runner = app.runner.clone();
+    let wineskin_launcher = current_keg.keg.wineskin_launcher.clone();
+    {
+        let progress = progress.clone();
+        thread::spawn(move || {
+            let result =
+                delete(&*runner, &wineskin_launcher, &bundle, |status| {
+                    if let Ok(mut progress) = progress.lock() {
+                        progress.status = status;
+                    }
+                });
+            drop(keg_lock);
+            if let Ok(mut progress) = progress.lock() {
+                progress.outcome =
+                    Some(result.map_err(|error| format!("{error:#}")));
+            }
+        });
+    }
+    Ok(progress)
+}
+
+/// Kills the keg's processes and removes its bundle, reporting each step to
+/// `set_status`.
+fn delete(
+    runner: &dyn CommandRunner,
+    wineskin_launcher: &OsStr,
+    bundle: &Path,
+    set_status: impl Fn(&'static str),
+) -> Result<()> {
+    set_status("Killing the keg's processes...");
+    runner
+        .output(Command::new(wineskin_launcher).arg("WSS-wineserverkill"))
+        .context("Failed to kill the keg's processes")?;
+    set_status("Removing the keg...");
+    fs::remove_dir_all(bundle)
+        .with_context(|| format!("Failed to delete {}", bundle.display()))
+}
+
+pub struct DeleteKegView;
+
+impl View for DeleteKegView {
+    fn draw_content(
+        &self,
+        app: &App,
+        _state: &AsyncState,
+        frame: &mut Frame,
+        area: Rect,
+        is_focused: bool,
+    ) -> Result<()> {
+        let Some(current_keg) = &app.current_keg else {
+            return Ok(());
+        };
+        let deletion = &app.keg_deletion;
+        let name = confirmation_name(current_keg);
+        let is_confirmed = deletion.typed == name;
+
+        let mut lines = vec![
+            Line::from(format!(
+                "This permanently deletes {}, including its prefix and everything installed in it, and kills its processes.",
+                current_keg.bundle_path().display()
+            )),
+            Line::from(""),
+            Line::from(vec![
+                "Type ".into(),
+                name.to_owned().bold(),
+                " to confirm:".into(),
+            ]),
+            Line::from(vec![deletion.typed.clone().into(), "_".dark_gray()]),
+            Line::from(""),
+        ];
+        let progress = deletion
+            .deleting
+            .as_ref()
+            .and_then(|progress| progress.lock().ok());
+        match progress.as_deref() {
+            Some(Progress {
+                outcome: None,
+                status,
+            }) => {
+                lines.push(Line::from((*status).yellow()));
+            }
+            Some(Progress {
+                outcome: Some(Ok(())),
+                ..
+            }) => {
+                let button = "[ Done ]".bold();
+                lines.push(Line::from(vec![
+                    "Deleted the keg. ".green(),
+                    if is_focused {
+                        button.reversed()
+                    } else {
+                        button
+                    },
+                ]));
+            }
+            // A failure is shown below and the button retries.
+            Some(Progress {
+                outcome: Some(Err(_)),
+                ..
+            })
+            | None => {
+                let button = if is_confirmed {
+                    "[ Delete ]".red().bold()
+                } else {
+                    "[ Delete ]".dark_gray()
+                };
+                lines.push(Line::from(if is_focused && is_confirmed {
+                    button.reversed()
+                } else {
+                    button
+                }));
+            }
+        }
+        if let Some(Progress {
+            outcome: Some(Err(error)),
+            ..
+        }) = progress.as_deref()
+        {
+            lines.push(Line::from(""));
+            lines.push(Line::from(error.clone().red()));
+        } else if let Some(error) = &deletion.error {
+            lines.push(Line::from(""));
+            lines.push(Line::from(error.clone().red()));
+        }
+
+        let modal_width = area.width.saturating_sub(4).min(60);
+        let modal_height = area.height.saturating_sub(2).min(16);
+        let modal_area = Rect {
+            x: area.x + (area.width - modal_width) / 2,
+            y: area.y + (area.height - modal_height) / 2,
+            width: modal_width,
+            height: modal_height,
+        };
+        frame.render_widget(Clear, modal_area);
+        frame.render_widget(
+            Paragraph::new(lines).wrap(Wrap { trim: false }).block(
+                Block::default()
+                    .title(Span::from(" Delete Keg ").into_centered_line())
+                    .borders(Borders::ALL)
+                    .padding(Padding::uniform(1)),
+            ),
+            modal_area,
+        );
+        Ok(())
+    }
+
+    fn interactivity(
+        &self,
+        _app: &App,
+        _state: &AsyncState,
+    ) -> Result<ViewInteractivity> {
+        Ok(ViewInteractivity::Clickables(1))
+    }
+
+    fn handle_key(
+        &self,
+        app: &mut App,
+        _state: &AsyncState,
+        key_event: KeyEvent,
+    ) -> Result<bool> {
+        let deletion = &mut app.keg_deletion;
+        if deletion.is_underway() {
+            // The typed name can't change once the keg is going away, but
+            // Enter still presses the button and Esc still returns to the
+            // menu.
+            return Ok(!matches!(
+                key_event.code,
+                KeyCode::Enter | KeyCode::Esc
+            ));
+        }
+        match key_event.code {
+            KeyCode::Backspace => {
+                deletion.typed.pop();
+            }
+            KeyCode::Char(c) => deletion.typed.push(c),
+            // Enter presses the button and Esc returns to the menu as usual.
+            _ => return Ok(false),
+        }
+        deletion.error = None;
+        deletion.deleting = None;
+        Ok(true)
+    }
+
+    fn click(
+        &self,
+        app: &mut App,
+        _state: &AsyncState,
+        _index: usize,
+    ) -> Result<Option<MenuItemAction<'_>>> {
+        let Some(current_keg) = &app.current_keg else {
+            return Ok(None);
+        };
+        let outcome = app.keg_deletion.deleting.as_ref().map(|progress| {
+            progress
+                .lock()
+                .ok()
+                .and_then(|progress| progress.outcome.clone())
+        });
+        match outcome {
+            Some(None) => return Ok(None),
+            Some(Some(Ok(()))) => {}
+            // Nothing has been deleted yet or the last attempt failed, so
+            // (re)try.
+            Some(Some(Err(_))) | None => {
+                let deletion = &mut app.keg_deletion;
+                deletion.deleting = None;
+                deletion.error = None;
+                if deletion.typed != confirmation_name(current_keg) {
+                    return Ok(None);
+                }
+                match start_deletion(app, current_keg) {
+                    Ok(progress) => {
+                        app.keg_deletion.deleting = Some(progress);
+                    }
+                    Err(error) => {
+                        app.keg_deletion.error = Some(format!("{error:#}"));
+                    }
+                }
+                return Ok(None);
+            }
+        }
+        let wineskin_launcher = current_keg.keg.wineskin_launcher.clone();
+        app.open_kegs_wineskin_launchers.remove(&wineskin_launcher);
+        app.current_keg = None;
+        app.keg_deletion.reset();
+        app.refresh();
+        Ok(Some(MenuItemAction::NavAction(NavAction::Pop)))
+    }
+}
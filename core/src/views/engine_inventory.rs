@@ -0,0 +1,242 @@
+// Copyright (C) 2026 Ethan Uppal.
+//
+// This program is free software: you can redistribute it and/or modify it under
+// the terms of the GNU General Public License as published by the Free Software
+// Foundation, version 3 of the License only.
+//
+// This program is distributed in the hope that it will be useful, but WITHOUT
+// ANY WARRANTY; without even the implied warranty of MERCHANTABILITY or FITNESS
+// FOR A PARTICULAR PURPOSE. See the GNU General Public License for more
+// details.
+//
+// You should have received a copy of the GNU General Public License along with
+// this program.  If not, see <https://www.gnu.org/licenses/>.
+
+use std::{fs, path::PathBuf};
+
+use ratatui::widgets::{List, ListItem, ListState, Wrap};
+
+use crate::{
+    app::{App, AsyncState, SELECTED_FOCUSED_STYLE, SELECTED_UNFOCUSED_STYLE},
+    disk_usage::format_size,
+    engine_pruning,
+    run_logs::describe_age,
+    view::prelude::*,
+};
+
+/// Which engine the inventory is about to delete, kept in [`App`] so deleting
+/// takes pressing enter twice on the same engine.
+#[derive(Default)]
+pub struct EngineInventory {
+    armed: Option<PathBuf>,
+    status: Option<Result<String, String>>,
+}
+
+pub struct EngineInventoryView;
+
+impl View for EngineInventoryView {
+    fn draw_content(
+        &self,
+        app: &App,
+        state: &AsyncState,
+        frame: &mut Frame,
+        area: Rect,
+        is_focused: bool,
+    ) -> Result<()> {
+        if state.engines.is_empty() {
+            frame.render_widget(
+                Paragraph::new(
+                    "No engines found. Install one from the Setup Wizard or Install from Homebrew.",
+                )
+                .wrap(Wrap { trim: false }),
+                area,
+            );
+            return Ok(());
+        }
+
+        let chunks = Layout::default()
+            .direction(Direction::Vertical)
+            .constraints([
+                Constraint::Length(1),
+                Constraint::Length(2),
+                Constraint::Fill(1),
+            ])
+            .split(area);
+        let inventory = &app.engine_inventory;
+        let status = match &inventory.status {
+            Some(Ok(status)) => Line::from(status.clone().green()),
+            Some(Err(error)) => Line::from(error.clone().red()),
+            None => Line::from(
+                "Press <Enter> on an engine no keg uses to delete it."
+                    .dark_gray(),
+            ),
+        };
+        let total = state
+            .engines
+            .iter()
+            .filter_map(|engine| fs::metadata(&engine.path).ok())
+            .map(|metadata| metadata.len())
+            .sum();
+        frame.render_widget(
+            Line::from(vec![
+                format_size(total).bold(),
+                format!(" in {} engine(s)", state.engines.len()).into(),
+            ]),
+            chunks[0],
+        );
+        frame.render_widget(
+            Paragraph::new(status).wrap(Wrap { trim: false }),
+            chunks[1],
+        );
+
+        let unknown_engine_users =
+            engine_pruning::unknown_engine_users(&state.kegs);
+        let items = state
+            .engines
+            .iter()
+            .map(|engine| {
+                let metadata = fs::metadata(&engine.path).ok();
+                let mut first_line = vec![
+                    format!(
+                        "{:>9}  ",
+                        metadata
+                            .as_ref()
+                            .map(|metadata| format_size(metadata.len()))
+                            .unwrap_or_default()
+                    )
+                    .into(),
+                    engine.name().unwrap_or_default().bold(),
+                ];
+                if let Some(version) = engine.version() {
+                    first_line.push(format!("  Wine {version}").into());
+                }
+                if let Some(engine_arch) = engine.arch() {
+                    first_line.push(
+                        format!("  [{}]", engine_arch.badge()).dark_gray(),
+                    );
+                }
+                if let Some(modified) =
+                    metadata.and_then(|metadata| metadata.modified().ok())
+                {
+                    first_line.push(
+                        format!("  added {}", describe_age(modified))
+                            .dark_gray(),
+                    );
+                }
+                let users = engine_pruning::users(engine, &state.kegs);
+                let second_line = if inventory.armed.as_ref()
+                    == Some(&engine.path)
+                {
+                    "Press <Enter> again to delete it".red()
+                } else if users.is_empty() && !unknown_engine_users.is_empty() {
+                    format!(
+                        "Maybe used by {}, whose engine is unknown",
+                        unknown_engine_users.join(", ")
+                    )
+                    .into()
+                } else if users.is_empty() {
+                    "Used by no keg".dark_gray()
+                } else {
+                    format!("Used by {}", users.join(", ")).into()
+                };
+                let mut lines = vec![
+                    Line::from(first_line),
+                    Line::from(vec!["           ".into(), second_line]),
+                ];
+                // Only an unpacked engine can be inspected, so this describes
+                // the copy in the first keg using it.
+                if let Some(layers) = state
+                    .kegs
+                    .iter()
+                    .filter(|keg| {
+                        keg.engine_version.is_some()
+                            && keg.engine_version == engine.name()
+                    })
+                    .find_map(|keg| {
+                        state.translation_layers.get(&keg.bundle_path())
+                    })
+                {
+                    lines.push(Line::from(vec![
+                        "           ".into(),
+                        format!(
+                            "MoltenVK {}, DXVK {}, D3DMetal {}",
+                            layers.molten_vk.describe(),
+                            layers.dxvk.describe(),
+                            layers.d3d_metal.describe()
+                        )
+                        .dark_gray(),
+                    ]));
+                }
+                ListItem::new(lines)
+            })
+            .collect::<Vec<_>>();
+        let mut list_state = ListState::default();
+        list_state.select(Some(app.interaction_state()));
+        let list = List::new(items)
+            .highlight_style(if is_focused {
+                SELECTED_FOCUSED_STYLE
+            } else {
+                SELECTED_UNFOCUSED_STYLE
+            })
+            .highlight_symbol(">> ");
+        frame.render_stateful_widget(list, chunks[2], &mut list_state);
+
+        Ok(())
+    }
+
+    fn interactivity(
+        &self,
+        _app: &App,
+        state: &AsyncState,
+    ) -> Result<ViewInteractivity> {
+        Ok(if state.engines.is_empty() {
+            ViewInteractivity::None
+        } else {
+            ViewInteractivity::Clickables(state.engines.len())
+        })
+    }
+
+    fn click(
+        &self,
+        app: &mut App,
+        state: &AsyncState,
+        index: usize,
+    ) -> Result<Option<MenuItemAction<'_>>> {
+        let Some(engine) = state.engines.get(index) else {
+            return Ok(None);
+        };
+        let inventory = &mut app.engine_inventory;
+        let users = engine_pruning::users(engine, &state.kegs);
+        let unknown_engine_users =
+            engine_pruning::unknown_engine_users(&state.kegs);
+        if !users.is_empty() {
+            inventory.armed = None;
+            inventory.status = Some(Err(format!(
+                "{} is used by {}, so it's kept",
+                engine.name().unwrap_or_default(),
+                users.join(", ")
+            )));
+        } else if !unknown_engine_users.is_empty() {
+            inventory.armed = None;
+            inventory.status = Some(Err(format!(
+                "The engine of {} is unknown and might be {}, so it's kept",
+                unknown_engine_users.join(", "),
+                engine.name().unwrap_or_default()
+            )));
+        } else if inventory.armed.as_ref() != Some(&engine.path) {
+            inventory.armed = Some(engine.path.clone());
+            inventory.status = None;
+        } else {
+            inventory.armed = None;
+            inventory.status = Some(match fs::remove_file(&engine.path) {
+                Ok(()) => Ok(format!("Deleted {}", engine.path.display())),
+                Err(error) => Err(format!(
+                    "Failed to delete {}: {error}",
+                    engine.path.display()
+                )),
+            });
+            app.refresh();
+        }
+        Ok(None)
+    }
+}
@@ -0,0 +1,106 @@
+// Copyright (C) 2026 Ethan Uppal.
+//
+// This program is free software: you can redistribute it and/or modify it under
+// the terms of the GNU General Public License as published by the Free Software
+// Foundation, version 3 of the License only.
+//
+// This program is distributed in the hope that it will be useful, but WITHOUT
+// ANY WARRANTY; without even the implied warranty of MERCHANTABILITY or FITNESS
+// FOR A PARTICULAR PURPOSE. See the GNU General Public License for more
+// details.
+//
+// You should have received a copy of the GNU General Public License along with
+// this program.  If not, see <https://www.gnu.org/licenses/>.
+
+use crate::{
+    app::{App, AsyncState},
+    view::prelude::*,
+};
+
+pub struct EnginesView;
+
+impl View for EnginesView {
+    fn draw_content(
+        &self,
+        app: &App,
+        state: &AsyncState,
+        frame: &mut Frame,
+        area: Rect,
+        _is_focused: bool,
+    ) -> Result<()> {
+        let mut lines = vec![Line::from("Engines:".bold())];
+        if state.engines.is_empty() {
+            lines.push(Line::from("  None found".dark_gray()));
+        }
+        for engine in &state.engines {
+            let mut line = Line::from(format!(
+                "  {}",
+                engine
+                    .path
+                    .file_name()
+                    .unwrap_or_default()
+                    .to_string_lossy()
+            ));
+            if let Some(engine_arch) = engine.arch() {
+                line.push_span(
+                    format!(" [{}]", engine_arch.badge()).dark_gray(),
+                );
+            }
+            lines.push(line);
+        }
+
+        lines.push(Line::from(""));
+        lines.push(Line::from("Wrappers:".bold()));
+        if state.wrappers.is_empty() {
+            lines.push(Line::from("  None found".dark_gray()));
+        }
+        for wrapper in &state.wrappers {
+            lines.push(Line::from(format!(
+                "  {}",
+                wrapper
+                    .path
+                    .file_name()
+                    .unwrap_or_default()
+                    .to_string_lossy()
+            )));
+            lines.push(Line::from(
+                format!("    {}", wrapper.summary()).dark_gray(),
+            ));
+        }
+
+        lines.push(Line::from(""));
+        lines.push(Line::from(if app.config.brew_taps.is_empty() {
+            "No Homebrew taps are configured (see brew-taps in the config)"
+                .to_owned()
+        } else {
+            format!("Homebrew taps: {}", app.config.brew_taps.join(", "))
+        }));
+
+        let scrollbar = Scrollbar::new(ScrollbarOrientation::VerticalRight);
+        let mut scrollbar_state =
+            ScrollbarState::new(lines.len()).position(app.interaction_state());
+
+        frame.render_widget(
+            Paragraph::new(lines).scroll((app.interaction_state() as u16, 0)),
+            area,
+        );
+        frame.render_stateful_widget(
+            scrollbar,
+            area.inner(Margin {
+                vertical: 1,
+                horizontal: 0,
+            }),
+            &mut scrollbar_state,
+        );
+
+        Ok(())
+    }
+
+    fn interactivity(
+        &self,
+        _app: &App,
+        _state: &AsyncState,
+    ) -> Result<ViewInteractivity> {
+        Ok(ViewInteractivity::Scrollable)
+    }
+}
@@ -0,0 +1,361 @@
+// Copyright (C) 2026 Ethan Uppal.
+//
+// This program is free software: you can redistribute it and/or modify it under
+// the terms of the GNU General Public License as published by the Free Software
+// Foundation, version 3 of the License only.
+//
+// This program is distributed in the hope that it will be useful, but WITHOUT
+// ANY WARRANTY; without even the implied warranty of MERCHANTABILITY or FITNESS
+// FOR A PARTICULAR PURPOSE. See the GNU General Public License for more
+// details.
+//
+// You should have received a copy of the GNU General Public License along with
+// this program.  If not, see <https://www.gnu.org/licenses/>.
+
+use std::{
+    collections::HashMap,
+    path::{Path, PathBuf},
+    process::Command,
+    sync::{Arc, Mutex},
+    thread,
+};
+
+use crossterm::event::{KeyCode, KeyEvent};
+use ratatui::widgets::{List, ListItem, ListState, Wrap};
+use walkdir::WalkDir;
+
+use crate::{
+    app::{App, AsyncState, SELECTED_FOCUSED_STYLE, SELECTED_UNFOCUSED_STYLE},
+    pe, run_logs,
+    view::prelude::*,
+    wine_path::WinePath,
+};
+
+#[derive(Default)]
+enum Scan {
+    #[default]
+    NotScanned,
+    Scanning,
+    Scanned(Vec<PathBuf>),
+}
+
+/// The executables found in the current keg's C drive and the search over
+/// them, kept in [`App`] so they survive leaving the view.
+#[derive(Default)]
+pub struct ExecutableBrowser {
+    /// Scanned on another thread the first time the view is drawn, since a
+    /// game's files can take a while to walk.
+    scan: Arc<Mutex<Scan>>,
+    /// What each executable's PE headers say, like the file picker shows,
+    /// read on the scanning thread once the list is shown.
+    summaries: Arc<Mutex<HashMap<PathBuf, String>>>,
+    query: String,
+    is_searching: bool,
+    /// What was launched last.
+    launched: Option<String>,
+}
+
+impl ExecutableBrowser {
+    fn start_scanning(&self, c_drive: &Path) {
+        let Ok(mut scan) = self.scan.lock() else {
+            return;
+        };
+        if !matches!(*scan, Scan::NotScanned) {
+            return;
+        }
+        *scan = Scan::Scanning;
+        if let Ok(mut summaries) = self.summaries.lock() {
+            summaries.clear();
+        }
+        let shared = self.scan.clone();
+        let summaries = self.summaries.clone();
+        let c_drive = c_drive.to_path_buf();
+        thread::spawn(move || {
+            let executables = find_executables(&c_drive);
+            if let Ok(mut scan) = shared.lock() {
+                *scan = Scan::Scanned(executables.clone());
+            }
+            for executable in executables {
+                let summary = match pe::inspect(&executable) {
+                    Ok(info) => info.summary(),
+                    Err(_) => "not a valid Windows executable".to_owned(),
+                };
+                if let Ok(mut summaries) = summaries.lock() {
+                    summaries.insert(executable, summary);
+                }
+            }
+        });
+    }
+
+    /// The scanned executables whose path contains the search, ignoring
+    /// case.
+    fn matching_executables(&self) -> Vec<PathBuf> {
+        let query = self.query.trim().to_lowercase();
+        match self.scan.lock().as_deref() {
+            Ok(Scan::Scanned(executables)) => executables
+                .iter()
+                .filter(|path| {
+                    path.to_string_lossy().to_lowercase().contains(&query)
+                })
+                .cloned()
+                .collect(),
+            _ => vec![],
+        }
+    }
+
+    /// Forgets what was found, e.g., after choosing another keg or installing
+    /// something.
+    pub fn reset(&mut self) {
+        *self = Self::default();
+    }
+
+    /// Scans the C drive again the next time the view is drawn.
+    pub fn rescan(&mut self) {
+        if let Ok(mut scan) = self.scan.lock()
+            && !matches!(*scan, Scan::Scanning)
+        {
+            *scan = Scan::NotScanned;
+        }
+    }
+}
+
+/// Every `.exe` in `c_drive` outside `windows`, which only holds Wine's own.
+pub fn find_executables(c_drive: &Path) -> Vec<PathBuf> {
+    let mut executables = WalkDir::new(c_drive)
+        .into_iter()
+        .filter_entry(|entry| {
+            entry.depth() != 1
+                || !entry.file_name().eq_ignore_ascii_case("windows")
+        })
+        .flatten()
+        .filter(|entry| {
+            entry.file_type().is_file()
+                && entry.path().extension().is_some_and(|extension| {
+                    extension.eq_ignore_ascii_case("exe")
+                })
+        })
+        .map(|entry| entry.into_path())
+        .collect::<Vec<_>>();
+    executables.sort();
+    executables
+}
+
+/// Runs `executable` in the current keg from its own folder, as games'
+/// launchers and tools expect, recording the run like a launch. It's offered
+/// as Resume in the keg menu afterward.
+pub fn launch(app: &mut App, executable: &Path) {
+    let Some(current_keg) = &app.current_keg else {
+        return;
+    };
+    let mut command = Command::new(current_keg.wine_executable());
+    command
+        .env_clear()
+        .envs(current_keg.winetricks_environment())
+        .envs(&current_keg.plist.kegtui_environment)
+        .arg(executable);
+    if let Some(folder) = executable.parent() {
+        command.current_dir(folder);
+    }
+    let log_directory = current_keg.keg.log_directory.clone();
+    let runner = app.runner.clone();
+    thread::spawn(move || {
+        let _ = run_logs::record_streaming(
+            &*runner,
+            &log_directory,
+            "exe",
+            &mut command,
+        );
+    });
+    app.open_kegs_wineskin_launchers
+        .insert(current_keg.keg.wineskin_launcher.clone());
+    app.last_executables
+        .insert(current_keg.bundle_path(), executable.to_path_buf());
+    app.executable_browser.launched = Some(format!(
+        "Launched {}; its output is saved in Previous Runs",
+        executable.display()
+    ));
+}
+
+pub struct ExecutablesView;
+
+impl View for ExecutablesView {
+    fn draw_content(
+        &self,
+        app: &App,
+        _state: &AsyncState,
+        frame: &mut Frame,
+        area: Rect,
+        is_focused: bool,
+    ) -> Result<()> {
+        let Some(current_keg) = &app.current_keg else {
+            frame.render_widget("No keg is selected", area);
+            return Ok(());
+        };
+        let browser = &app.executable_browser;
+        browser.start_scanning(&current_keg.keg.c_drive);
+
+        let chunks = Layout::default()
+            .direction(Direction::Vertical)
+            .constraints([
+                Constraint::Length(1),
+                Constraint::Length(1),
+                Constraint::Length(2),
+                Constraint::Fill(1),
+            ])
+            .split(area);
+        frame.render_widget(
+            Line::from(vec![
+                "Search: ".bold(),
+                if browser.query.is_empty() && !browser.is_searching {
+                    "press / to search by path".dark_gray()
+                } else {
+                    browser.query.clone().into()
+                },
+                if browser.is_searching {
+                    "_".dark_gray()
+                } else {
+                    "".into()
+                },
+            ]),
+            chunks[0],
+        );
+        frame.render_widget(
+            Line::from(
+                "Press <Enter> to run an executable inside the keg."
+                    .dark_gray(),
+            ),
+            chunks[1],
+        );
+        if let Some(launched) = &browser.launched {
+            frame.render_widget(
+                Paragraph::new(launched.clone().green())
+                    .wrap(Wrap { trim: false }),
+                chunks[2],
+            );
+        }
+
+        let executables = browser.matching_executables();
+        if executables.is_empty() {
+            let message = match browser.scan.lock().as_deref() {
+                Ok(Scan::NotScanned | Scan::Scanning) => {
+                    "Looking for executables in the C drive...".to_owned()
+                }
+                _ if !browser.query.trim().is_empty() => {
+                    format!("No executables match \"{}\"", browser.query.trim())
+                }
+                _ => {
+                    "The C drive has no executables outside windows".to_owned()
+                }
+            };
+            frame.render_widget(
+                Paragraph::new(message.dark_gray()).wrap(Wrap { trim: false }),
+                chunks[3],
+            );
+            return Ok(());
+        }
+
+        let configured_program = WinePath::parse(
+            &current_keg.plist.program_name_and_path,
+            &current_keg.keg.c_drive,
+        )
+        .map(|path| path.to_host(&current_keg.keg.c_drive));
+        let summaries = browser.summaries.lock().ok();
+        let items = executables
+            .iter()
+            .map(|path| {
+                let windows_path =
+                    WinePath::from_host(path, &current_keg.keg.c_drive)
+                        .map(|path| path.to_windows())
+                        .unwrap_or_else(|| path.display().to_string());
+                let (folder, file_name) = windows_path
+                    .rsplit_once('\\')
+                    .unwrap_or(("", windows_path.as_str()));
+                let mut line = vec![
+                    format!("{folder}\\").dark_gray(),
+                    file_name.to_owned().bold(),
+                ];
+                if configured_program.as_ref() == Some(path) {
+                    line.push("  (the keg's program)".dark_gray());
+                }
+                if let Some(summary) =
+                    summaries.as_ref().and_then(|summaries| summaries.get(path))
+                {
+                    line.push(format!("  {summary}").dark_gray());
+                }
+                ListItem::new(Line::from(line))
+            })
+            .collect::<Vec<_>>();
+        let mut list_state = ListState::default();
+        list_state.select(Some(app.interaction_state()));
+        let list = List::new(items)
+            .highlight_style(if is_focused {
+                SELECTED_FOCUSED_STYLE
+            } else {
+                SELECTED_UNFOCUSED_STYLE
+            })
+            .highlight_symbol(">> ");
+        frame.render_stateful_widget(list, chunks[3], &mut list_state);
+
+        Ok(())
+    }
+
+    fn interactivity(
+        &self,
+        app: &App,
+        _state: &AsyncState,
+    ) -> Result<ViewInteractivity> {
+        let count = app.executable_browser.matching_executables().len();
+        Ok(if count == 0 {
+            ViewInteractivity::None
+        } else {
+            ViewInteractivity::Clickables(count)
+        })
+    }
+
+    fn handle_key(
+        &self,
+        app: &mut App,
+        _state: &AsyncState,
+        key_event: KeyEvent,
+    ) -> Result<bool> {
+        let browser = &mut app.executable_browser;
+        if !browser.is_searching {
+            if key_event.code == KeyCode::Char('/') {
+                browser.is_searching = true;
+                browser.query.clear();
+                return Ok(true);
+            }
+            return Ok(false);
+        }
+        match key_event.code {
+            KeyCode::Enter | KeyCode::Esc => {
+                browser.is_searching = false;
+                app.select_clickable(0);
+            }
+            KeyCode::Backspace => {
+                browser.query.pop();
+            }
+            KeyCode::Char(c) => browser.query.push(c),
+            _ => {}
+        }
+        Ok(true)
+    }
+
+    fn click(
+        &self,
+        app: &mut App,
+        _state: &AsyncState,
+        index: usize,
+    ) -> Result<Option<MenuItemAction<'_>>> {
+        let Some(executable) = app
+            .executable_browser
+            .matching_executables()
+            .get(index)
+            .cloned()
+        else {
+            return Ok(None);
+        };
+        launch(app, &executable);
+        Ok(None)
+    }
+}
@@ -0,0 +1,87 @@
+// Copyright (C) 2026 Ethan Uppal.
+//
+// This program is free software: you can redistribute it and/or modify it under
+// the terms of the GNU General Public License as published by the Free Software
+// Foundation, version 3 of the License only.
+//
+// This program is distributed in the hope that it will be useful, but WITHOUT
+// ANY WARRANTY; without even the implied warranty of MERCHANTABILITY or FITNESS
+// FOR A PARTICULAR PURPOSE. See the GNU General Public License for more
+// details.
+//
+// You should have received a copy of the GNU General Public License along with
+// this program.  If not, see <https://www.gnu.org/licenses/>.
+
+use ratatui::widgets::Wrap;
+
+use crate::{
+    app::{App, AsyncState},
+    journal,
+    run_logs::describe_age,
+    view::prelude::*,
+};
+
+pub struct HistoryView;
+
+impl View for HistoryView {
+    fn draw_content(
+        &self,
+        app: &App,
+        _state: &AsyncState,
+        frame: &mut Frame,
+        area: Rect,
+        _is_focused: bool,
+    ) -> Result<()> {
+        let Some(current_keg) = &app.current_keg else {
+            return Ok(());
+        };
+
+        let entries = journal::read(&current_keg.bundle_path());
+        if entries.is_empty() {
+            frame.render_widget(
+                Paragraph::new(
+                    "No changes recorded yet. Winetricks installs, config edits, and engine upgrades made from kegtui are listed here.",
+                )
+                .wrap(Wrap { trim: false }),
+                area,
+            );
+            return Ok(());
+        }
+
+        let lines = entries
+            .iter()
+            .map(|entry| {
+                Line::from(vec![
+                    format!("{:>10} ", describe_age(entry.time)).dark_gray(),
+                    format!("{:<11}", entry.kind).bold(),
+                    entry.detail.clone().into(),
+                ])
+            })
+            .collect::<Vec<_>>();
+        let scrollbar = Scrollbar::new(ScrollbarOrientation::VerticalRight);
+        let mut scrollbar_state =
+            ScrollbarState::new(lines.len()).position(app.interaction_state());
+        frame.render_widget(
+            Paragraph::new(lines).scroll((app.interaction_state() as u16, 0)),
+            area,
+        );
+        frame.render_stateful_widget(
+            scrollbar,
+            area.inner(Margin {
+                vertical: 1,
+                horizontal: 0,
+            }),
+            &mut scrollbar_state,
+        );
+
+        Ok(())
+    }
+
+    fn interactivity(
+        &self,
+        _app: &App,
+        _state: &AsyncState,
+    ) -> Result<ViewInteractivity> {
+        Ok(ViewInteractivity::Scrollable)
+    }
+}
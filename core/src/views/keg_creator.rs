@@ -0,0 +1,672 @@
+// Copyright (C) 2026 Ethan Uppal.
+//
+// This program is free software: you can redistribute it and/or modify it under
+// the terms of the GNU General Public License as published by the Free Software
+// Foundation, version 3 of the License only.
+//
+// This program is distributed in the hope that it will be useful, but WITHOUT
+// ANY WARRANTY; without even the implied warranty of MERCHANTABILITY or FITNESS
+// FOR A PARTICULAR PURPOSE. See the GNU General Public License for more
+// details.
+//
+// You should have received a copy of the GNU General Public License along with
+// this program.  If not, see <https://www.gnu.org/licenses/>.
+
+use std::{
+    path::PathBuf,
+    process::Command,
+    sync::{
+        Arc, Mutex,
+        atomic::{AtomicBool, Ordering},
+    },
+    thread,
+    time::{Instant, SystemTime},
+};
+
+use color_eyre::eyre::bail;
+use crossterm::event::{KeyCode, KeyEvent};
+use ratatui::widgets::{Gauge, List, ListItem, ListState, Wrap};
+
+use crate::{
+    actions,
+    app::{App, AsyncState, SELECTED_FOCUSED_STYLE, SELECTED_UNFOCUSED_STYLE},
+    app_config::{
+        NewKegDefaults, NotificationsConfig, TimeoutsConfig,
+        default_keg_location, expand_path,
+    },
+    engine_install,
+    keg::{Engine, Wrapper},
+    keg_creation, keg_name,
+    name_conflict::{self, NameConflict},
+    notify, run_logs,
+    runner::{CommandRunner, Completion},
+    view::prelude::*,
+};
+
+/// A step of creating a keg, in the order they run.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Default)]
+enum Step {
+    #[default]
+    CopyingWrapper,
+    UnpackingEngine,
+    CreatingPrefix,
+    ApplyingDefaults,
+}
+
+impl Step {
+    const ALL: [Self; 4] = [
+        Self::CopyingWrapper,
+        Self::UnpackingEngine,
+        Self::CreatingPrefix,
+        Self::ApplyingDefaults,
+    ];
+
+    fn description(self) -> &'static str {
+        match self {
+            Step::CopyingWrapper => "Copy the wrapper",
+            Step::UnpackingEngine => "Unpack the engine",
+            Step::CreatingPrefix => "Create the Wine prefix",
+            Step::ApplyingDefaults => "Apply new keg defaults",
+        }
+    }
+}
+
+/// How far creating a keg has gotten, shared with the thread creating it.
+#[derive(Default)]
+struct Progress {
+    step: Step,
+    /// Bytes of the compressed engine unpacked so far, and its size.
+    unpacked: (u64, u64),
+    /// Set once creating the keg stops, with why if it failed.
+    outcome: Option<Result<(), String>>,
+}
+
+struct Creation {
+    keg_path: PathBuf,
+    progress: Arc<Mutex<Progress>>,
+    is_cancelled: Arc<AtomicBool>,
+}
+
+/// The keg creator's choices, kept in [`App`] so they survive leaving the
+/// view.
+#[derive(Default)]
+pub struct KegCreator {
+    engine: Option<PathBuf>,
+    wrapper: Option<PathBuf>,
+    name: String,
+    is_editing_name: bool,
+    /// The highlighted choice while asking what to do about a taken name.
+    name_conflict: Option<usize>,
+    creation: Option<Creation>,
+}
+
+impl KegCreator {
+    pub fn select_engine(&mut self, engine: PathBuf) {
+        self.engine = Some(engine);
+    }
+
+    pub fn select_wrapper(&mut self, wrapper: PathBuf) {
+        self.wrapper = Some(wrapper);
+    }
+
+    /// Where a keg with the typed name would go, or why it can't. The path
+    /// may be taken, which is asked about once the user chooses Create.
+    fn keg_path(&self) -> Result<PathBuf, String> {
+        let name = self.name.trim().trim_end_matches(".app");
+        if let Some(problem) = keg_name::problem(name) {
+            return Err(problem);
+        }
+        Ok(PathBuf::from(expand_path(default_keg_location()))
+            .join(format!("{name}.app")))
+    }
+
+    /// Why the keg can't be created yet, if it can't.
+    fn missing_choice(&self) -> Option<String> {
+        if self.engine.is_none() {
+            Some("Choose an engine".to_owned())
+        } else if self.wrapper.is_none() {
+            Some("Choose a wrapper".to_owned())
+        } else {
+            self.keg_path().err()
+        }
+    }
+}
+
+/// The rows of the form that can be chosen.
+enum Field<'s> {
+    Engine(&'s Engine),
+    Wrapper(&'s Wrapper),
+    Download,
+    Name,
+    Create,
+}
+
+fn fields(state: &AsyncState) -> Vec<Field<'_>> {
+    let mut fields =
+        state.engines.iter().map(Field::Engine).collect::<Vec<_>>();
+    fields.extend(state.wrappers.iter().map(Field::Wrapper));
+    fields.extend([Field::Download, Field::Name, Field::Create]);
+    fields
+}
+
+fn checkbox(is_checked: bool) -> Span<'static> {
+    if is_checked {
+        "(x) ".into()
+    } else {
+        "( ) ".into()
+    }
+}
+
+/// Everything the thread creating a keg needs.
+struct Job {
+    engine: PathBuf,
+    wrapper: PathBuf,
+    name: String,
+    keg_path: PathBuf,
+    /// Whether a keg already at `keg_path` is backed up and replaced.
+    overwrite: bool,
+    /// How many tasks were queued for the keg being replaced, which must be
+    /// none.
+    pending_tasks: usize,
+    runner: Arc<dyn CommandRunner>,
+    prefix_create_timeout: u64,
+    new_keg_defaults: NewKegDefaults,
+    notifications: NotificationsConfig,
+}
+
+impl Job {
+    /// Creates the keg, reporting to `progress`, and notifies the user once
+    /// it's done.
+    fn run(&self, progress: &Mutex<Progress>, is_cancelled: &AtomicBool) {
+        let started = Instant::now();
+        let result = self.create(progress, is_cancelled);
+        notify::task_finished(
+            &self.notifications,
+            &self.runner,
+            &format!(
+                "{}: keg creation {}",
+                self.name,
+                if result.is_ok() { "done" } else { "failed" }
+            ),
+            started.elapsed(),
+        );
+        if let Ok(mut progress) = progress.lock() {
+            progress.outcome =
+                Some(result.map_err(|error| format!("{error:#}")));
+        }
+    }
+
+    fn create(
+        &self,
+        progress: &Mutex<Progress>,
+        is_cancelled: &AtomicBool,
+    ) -> color_eyre::Result<()> {
+        let Self {
+            engine,
+            wrapper,
+            name,
+            keg_path,
+            runner,
+            ..
+        } = self;
+        let set_step = |step| {
+            if let Ok(mut progress) = progress.lock() {
+                progress.step = step;
+            }
+        };
+
+        if self.overwrite {
+            keg_creation::back_up_replaced_keg(
+                &**runner,
+                self.pending_tasks,
+                keg_path,
+            )?;
+        }
+        keg_creation::copy_template(wrapper, keg_path)?;
+
+        set_step(Step::UnpackingEngine);
+        let wine_folder = engine_install::install_engine_with_progress(
+            engine,
+            keg_path,
+            |read, total| {
+                if let Ok(mut progress) = progress.lock() {
+                    progress.unpacked = (read, total);
+                }
+            },
+        )?;
+        keg_creation::open_up_permissions(keg_path, &wine_folder)?;
+        if is_cancelled.load(Ordering::Relaxed) {
+            bail!(
+                "Cancelled, so the keg at {} is incomplete",
+                keg_path.display()
+            );
+        }
+
+        set_step(Step::CreatingPrefix);
+        let mut command = keg_creation::prefix_create_command(keg_path);
+        let started = SystemTime::now();
+        let run = runner.run_in_background(
+            &mut command,
+            TimeoutsConfig::limit(self.prefix_create_timeout),
+            &|| is_cancelled.load(Ordering::Relaxed),
+        )?;
+        let run_log = run_logs::record(
+            &keg_path.join("Contents/Logs"),
+            "prefix-create",
+            started,
+            &command,
+            run.completion,
+            &run.stdout,
+            &run.stderr,
+        )?;
+        if let Some(reason) = run.completion.interruption() {
+            let _ = runner.output(
+                Command::new(keg_creation::wineskin_launcher(keg_path))
+                    .arg("WSS-wineserverkill"),
+            );
+            bail!(
+                "Prefix creation {reason}, so the keg at {} is incomplete; output saved to {}",
+                keg_path.display(),
+                run_log.display()
+            );
+        } else if let Completion::Exited(status) = run.completion
+            && !status.success()
+        {
+            bail!(
+                "Prefix creation failed ({status}); output saved to {}",
+                run_log.display()
+            );
+        }
+
+        set_step(Step::ApplyingDefaults);
+        keg_creation::apply_new_keg_defaults(
+            keg_path,
+            name,
+            &self.new_keg_defaults,
+        )
+    }
+}
+
+/// Starts creating the keg on another thread, first backing up whatever is
+/// at its path if `overwrite` is set.
+fn start_creation(
+    app: &mut App,
+    engine: PathBuf,
+    wrapper: PathBuf,
+    overwrite: bool,
+) {
+    let Ok(keg_path) = app.keg_creator.keg_path() else {
+        return;
+    };
+    let name = app
+        .keg_creator
+        .name
+        .trim()
+        .trim_end_matches(".app")
+        .to_owned();
+    let pending_tasks = app.tasks.pending_count(&format!("{name}.app"));
+    let job = Job {
+        engine,
+        wrapper,
+        name,
+        keg_path: keg_path.clone(),
+        overwrite,
+        pending_tasks,
+        runner: app.runner.clone(),
+        prefix_create_timeout: app.config.timeouts.prefix_create,
+        new_keg_defaults: app.config.new_keg_defaults.clone(),
+        notifications: app.config.notifications.clone(),
+    };
+    let progress = Arc::new(Mutex::new(Progress::default()));
+    let is_cancelled = Arc::new(AtomicBool::new(false));
+    {
+        let progress = progress.clone();
+        let is_cancelled = is_cancelled.clone();
+        thread::spawn(move || job.run(&progress, &is_cancelled));
+    }
+    app.keg_creator.creation = Some(Creation {
+        keg_path,
+        progress,
+        is_cancelled,
+    });
+}
+
+pub struct KegCreatorView;
+
+impl KegCreatorView {
+    fn draw_form(
+        &self,
+        app: &App,
+        state: &AsyncState,
+        frame: &mut Frame,
+        area: Rect,
+        is_focused: bool,
+    ) {
+        let creator = &app.keg_creator;
+        let chunks = Layout::default()
+            .direction(Direction::Vertical)
+            .constraints([Constraint::Length(2), Constraint::Fill(1)])
+            .split(area);
+        frame.render_widget(
+            "Choose an engine and a wrapper, name the keg, then choose Create:",
+            chunks[0],
+        );
+
+        let mut items = vec![];
+        let mut selected = app.interaction_state();
+        let mut heading =
+            |items: &mut Vec<ListItem>, index, text: &'static str| {
+                items.push(ListItem::new(Line::from(text).bold()));
+                if selected >= index {
+                    selected += 1;
+                }
+            };
+        for (index, field) in fields(state).into_iter().enumerate() {
+            let line = match field {
+                Field::Engine(engine) => {
+                    if index == 0 {
+                        heading(&mut items, index, "Engine");
+                    }
+                    let mut line = Line::from(vec![
+                        checkbox(creator.engine.as_ref() == Some(&engine.path)),
+                        engine.path.display().to_string().into(),
+                    ]);
+                    if let Some(engine_arch) = engine.arch() {
+                        line.push_span(
+                            format!(" {}", engine_arch.badge()).dark_gray(),
+                        );
+                    }
+                    line
+                }
+                Field::Wrapper(wrapper) => {
+                    if index == state.engines.len() {
+                        heading(&mut items, index, "Wrapper");
+                    }
+                    Line::from(vec![
+                        checkbox(
+                            creator.wrapper.as_ref() == Some(&wrapper.path),
+                        ),
+                        wrapper.path.display().to_string().into(),
+                        format!(" {}", wrapper.summary()).dark_gray(),
+                    ])
+                }
+                Field::Download => {
+                    Line::from("Download an engine or wrapper from a URL...")
+                        .italic()
+                }
+                Field::Name => {
+                    heading(&mut items, index, "Name");
+                    let mut line = Line::from(
+                        if creator.name.is_empty() && !creator.is_editing_name {
+                            "Press enter to type a name".dark_gray()
+                        } else {
+                            creator.name.clone().into()
+                        },
+                    );
+                    if creator.is_editing_name {
+                        line.push_span("_".dark_gray());
+                    }
+                    line
+                }
+                Field::Create => match creator.missing_choice() {
+                    Some(missing) => Line::from(vec![
+                        "[ Create ] ".dark_gray(),
+                        missing.red(),
+                    ]),
+                    None if creator
+                        .keg_path()
+                        .is_ok_and(|keg_path| keg_path.exists()) =>
+                    {
+                        Line::from(vec![
+                            "[ Create ]".bold(),
+                            " A keg with this name exists".yellow(),
+                        ])
+                    }
+                    None => Line::from("[ Create ]").bold(),
+                },
+            };
+            items.push(ListItem::new(line));
+        }
+
+        let mut list_state = ListState::default();
+        list_state.select(Some(selected));
+        let list = List::new(items)
+            .highlight_style(if is_focused {
+                SELECTED_FOCUSED_STYLE
+            } else {
+                SELECTED_UNFOCUSED_STYLE
+            })
+            .highlight_symbol(">> ");
+        frame.render_stateful_widget(list, chunks[1], &mut list_state);
+    }
+
+    fn draw_creation(
+        &self,
+        creation: &Creation,
+        frame: &mut Frame,
+        area: Rect,
+    ) {
+        let Ok(progress) = creation.progress.lock() else {
+            return;
+        };
+        let chunks = Layout::default()
+            .direction(Direction::Vertical)
+            .constraints([
+                Constraint::Length(2),
+                Constraint::Length(Step::ALL.len() as u16 + 1),
+                Constraint::Length(1),
+                Constraint::Length(1),
+                Constraint::Fill(1),
+            ])
+            .split(area);
+        frame.render_widget(
+            format!("Creating {}", creation.keg_path.display()),
+            chunks[0],
+        );
+
+        let lines = Step::ALL
+            .into_iter()
+            .map(|step| {
+                let status = if step < progress.step
+                    || progress.outcome.as_ref().is_some_and(Result::is_ok)
+                {
+                    "done ".green()
+                } else if step > progress.step {
+                    "     ".into()
+                } else if progress.outcome.is_some() {
+                    "fail ".red()
+                } else {
+                    "...  ".yellow()
+                };
+                Line::from(vec![status, step.description().into()])
+            })
+            .collect::<Vec<_>>();
+        frame.render_widget(Text::from(lines), chunks[1]);
+
+        let (read, total) = progress.unpacked;
+        let ratio = if progress.step > Step::UnpackingEngine {
+            1.0
+        } else if total > 0 {
+            (read as f64 / total as f64).min(1.0)
+        } else {
+            0.0
+        };
+        frame.render_widget(
+            Gauge::default()
+                .gauge_style(Style::new().fg(Color::Yellow))
+                .label(format!("Engine unpacked {:.0}%", ratio * 100.0))
+                .ratio(ratio),
+            chunks[2],
+        );
+
+        let footer = match &progress.outcome {
+            None if creation.is_cancelled.load(Ordering::Relaxed) => {
+                Line::from("Cancelling...".dark_gray())
+            }
+            None => Line::from(vec!["[ Cancel ]".bold()]),
+            Some(Ok(())) => Line::from(vec![
+                "Created your keg! ".green(),
+                "[ Done ]".bold(),
+            ]),
+            Some(Err(error)) => {
+                Line::from(vec![format!("{error} ").red(), "[ Done ]".bold()])
+            }
+        };
+        frame.render_widget(
+            Paragraph::new(footer).wrap(Wrap { trim: false }),
+            chunks[4],
+        );
+    }
+}
+
+impl View for KegCreatorView {
+    fn draw_content(
+        &self,
+        app: &App,
+        state: &AsyncState,
+        frame: &mut Frame,
+        area: Rect,
+        is_focused: bool,
+    ) -> Result<()> {
+        match &app.keg_creator.creation {
+            Some(creation) => self.draw_creation(creation, frame, area),
+            None => {
+                self.draw_form(app, state, frame, area, is_focused);
+                if let Some(selected) = app.keg_creator.name_conflict
+                    && let Ok(keg_path) = app.keg_creator.keg_path()
+                {
+                    name_conflict::draw_modal(frame, area, &keg_path, selected);
+                }
+            }
+        }
+        Ok(())
+    }
+
+    fn interactivity(
+        &self,
+        app: &App,
+        state: &AsyncState,
+    ) -> Result<ViewInteractivity> {
+        Ok(ViewInteractivity::Clickables(
+            if app.keg_creator.creation.is_some() {
+                1
+            } else {
+                fields(state).len()
+            },
+        ))
+    }
+
+    fn handle_key(
+        &self,
+        app: &mut App,
+        _state: &AsyncState,
+        key_event: KeyEvent,
+    ) -> Result<bool> {
+        let creator = &mut app.keg_creator;
+        if let Some(selected) = &mut creator.name_conflict {
+            let choice = match key_event.code {
+                KeyCode::Up | KeyCode::Char('k') => {
+                    *selected = selected.saturating_sub(1);
+                    None
+                }
+                KeyCode::Down | KeyCode::Char('j') => {
+                    *selected =
+                        (*selected + 1).min(NameConflict::ALL.len() - 1);
+                    None
+                }
+                KeyCode::Enter => Some(NameConflict::ALL[*selected]),
+                code => NameConflict::from_shortcut(code),
+            };
+            match choice {
+                Some(NameConflict::PickNewName) => {
+                    creator.name_conflict = None;
+                    creator.is_editing_name = true;
+                }
+                Some(NameConflict::Overwrite) => {
+                    creator.name_conflict = None;
+                    if let (Some(engine), Some(wrapper)) =
+                        (creator.engine.clone(), creator.wrapper.clone())
+                    {
+                        start_creation(app, engine, wrapper, true);
+                    }
+                }
+                Some(NameConflict::Cancel) => creator.name_conflict = None,
+                None => {}
+            }
+            return Ok(true);
+        }
+        if !creator.is_editing_name {
+            return Ok(false);
+        }
+        match key_event.code {
+            KeyCode::Enter | KeyCode::Esc => creator.is_editing_name = false,
+            KeyCode::Backspace => {
+                creator.name.pop();
+            }
+            KeyCode::Char(c) => creator.name.push(c),
+            _ => {}
+        }
+        Ok(true)
+    }
+
+    fn click(
+        &self,
+        app: &mut App,
+        state: &AsyncState,
+        index: usize,
+    ) -> Result<Option<MenuItemAction<'_>>> {
+        if let Some(creation) = &app.keg_creator.creation {
+            let is_finished = creation
+                .progress
+                .lock()
+                .is_ok_and(|progress| progress.outcome.is_some());
+            if is_finished {
+                let succeeded =
+                    creation.progress.lock().is_ok_and(|progress| {
+                        matches!(progress.outcome, Some(Ok(())))
+                    });
+                app.keg_creator.creation = None;
+                if succeeded {
+                    app.keg_creator.name.clear();
+                }
+                app.refresh();
+            } else {
+                creation.is_cancelled.store(true, Ordering::Relaxed);
+            }
+            return Ok(None);
+        }
+
+        let creator = &mut app.keg_creator;
+        match fields(state).get(index) {
+            Some(Field::Engine(engine)) => {
+                creator.engine = Some(engine.path.clone());
+            }
+            Some(Field::Wrapper(wrapper)) => {
+                creator.wrapper = Some(wrapper.path.clone());
+            }
+            Some(Field::Download) => {
+                return Ok(Some(MenuItemAction::External(
+                    actions::create::download_for_keg_creator,
+                )));
+            }
+            Some(Field::Name) => creator.is_editing_name = true,
+            Some(Field::Create) => {
+                if creator.missing_choice().is_none()
+                    && let (Some(engine), Some(wrapper)) =
+                        (creator.engine.clone(), creator.wrapper.clone())
+                {
+                    if creator
+                        .keg_path()
+                        .is_ok_and(|keg_path| keg_path.exists())
+                    {
+                        creator.name_conflict = Some(0);
+                    } else {
+                        start_creation(app, engine, wrapper, false);
+                    }
+                }
+            }
+            None => {}
+        }
+        Ok(None)
+    }
+}
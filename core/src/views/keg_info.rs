@@ -0,0 +1,102 @@
+// Copyright (C) 2026 Ethan Uppal.
+//
+// This program is free software: you can redistribute it and/or modify it under
+// the terms of the GNU General Public License as published by the Free Software
+// Foundation, version 3 of the License only.
+//
+// This program is distributed in the hope that it will be useful, but WITHOUT
+// ANY WARRANTY; without even the implied warranty of MERCHANTABILITY or FITNESS
+// FOR A PARTICULAR PURPOSE. See the GNU General Public License for more
+// details.
+//
+// You should have received a copy of the GNU General Public License along with
+// this program.  If not, see <https://www.gnu.org/licenses/>.
+
+use crate::{
+    app::{App, AsyncState},
+    diagnostics::KegReport,
+    view::prelude::*,
+};
+
+pub struct KegInfoView;
+
+impl View for KegInfoView {
+    fn draw_content(
+        &self,
+        app: &App,
+        state: &AsyncState,
+        frame: &mut Frame,
+        area: Rect,
+        _is_focused: bool,
+    ) -> Result<()> {
+        let Some(current_keg) = &app.current_keg else {
+            return Ok(());
+        };
+
+        app.watch_crash_dumps();
+        let crash_dumps = state.crash_dumps.get(&current_keg.bundle_path());
+        let report = KegReport::new(
+            current_keg,
+            state,
+            crash_dumps.map_or(&[], Vec::as_slice),
+        );
+        let mut lines = report
+            .fields
+            .iter()
+            .map(|field| {
+                let indent = if field.is_detail { "  " } else { "" };
+                Line::from(vec![
+                    format!("{indent}{}: ", field.label).bold(),
+                    field.value.clone().into(),
+                ])
+            })
+            .collect::<Vec<_>>();
+        lines.push(Line::from(""));
+
+        if crash_dumps.is_none() {
+            lines.push(Line::from("Looking for crash dumps..."));
+        } else if report.crash_dumps.is_empty() {
+            lines.push(Line::from("No crash dumps found."));
+        } else {
+            lines.push(Line::from(format!(
+                "Crash dumps ({} new since last launch):",
+                report.new_crash_dump_count()
+            )));
+            for crash_dump in &report.crash_dumps {
+                let path = crash_dump.path.display().to_string();
+                lines.push(if crash_dump.is_new {
+                    Line::from(vec!["  new ".red().bold(), path.into()])
+                } else {
+                    Line::from(vec!["      ".into(), path.into()])
+                });
+            }
+        }
+
+        let scrollbar = Scrollbar::new(ScrollbarOrientation::VerticalRight);
+        let mut scrollbar_state =
+            ScrollbarState::new(lines.len()).position(app.interaction_state());
+
+        frame.render_widget(
+            Paragraph::new(lines).scroll((app.interaction_state() as u16, 0)),
+            area,
+        );
+        frame.render_stateful_widget(
+            scrollbar,
+            area.inner(Margin {
+                vertical: 1,
+                horizontal: 0,
+            }),
+            &mut scrollbar_state,
+        );
+
+        Ok(())
+    }
+
+    fn interactivity(
+        &self,
+        _app: &App,
+        _state: &AsyncState,
+    ) -> Result<ViewInteractivity> {
+        Ok(ViewInteractivity::Scrollable)
+    }
+}
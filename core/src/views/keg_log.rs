@@ -0,0 +1,205 @@
+// Copyright (C) 2026 Ethan Uppal.
+//
+// This program is free software: you can redistribute it and/or modify it under
+// the terms of the GNU General Public License as published by the Free Software
+// Foundation, version 3 of the License only.
+//
+// This program is distributed in the hope that it will be useful, but WITHOUT
+// ANY WARRANTY; without even the implied warranty of MERCHANTABILITY or FITNESS
+// FOR A PARTICULAR PURPOSE. See the GNU General Public License for more
+// details.
+//
+// You should have received a copy of the GNU General Public License along with
+// this program.  If not, see <https://www.gnu.org/licenses/>.
+
+use std::{
+    collections::VecDeque,
+    fs,
+    path::PathBuf,
+    sync::{
+        Arc, Mutex,
+        atomic::{AtomicBool, Ordering},
+    },
+    thread,
+    time::{Duration, SystemTime},
+};
+
+use ratatui::widgets::Wrap;
+
+use crate::{
+    app::{App, AsyncState},
+    log_tail::{self, ChannelFilter},
+    view::prelude::*,
+};
+
+/// How often the followed log is checked for new lines.
+const POLL_INTERVAL: Duration = Duration::from_millis(250);
+
+/// The most lines kept, so a chatty game can't grow the pane without bound.
+const MAX_LINES: usize = 5000;
+
+#[derive(Default)]
+struct Followed {
+    lines: VecDeque<String>,
+    /// Whether the wrapper has started writing the log for this launch.
+    has_started: bool,
+    error: Option<String>,
+}
+
+impl Followed {
+    fn push(&mut self, text: &str) {
+        self.lines.extend(text.lines().map(str::to_owned));
+        let excess = self.lines.len().saturating_sub(MAX_LINES);
+        self.lines.drain(..excess);
+    }
+}
+
+/// The log of the last launch, read on another thread while the keg runs,
+/// kept in [`App`] so leaving the view doesn't stop following it.
+#[derive(Default)]
+pub struct LiveLog {
+    log: Option<PathBuf>,
+    followed: Arc<Mutex<Followed>>,
+    /// Tells the reader thread to stop, e.g., once another launch is
+    /// followed.
+    is_stopped: Arc<AtomicBool>,
+}
+
+impl LiveLog {
+    /// Follows `log` from a launch at `launched`. The wrapper rewrites the
+    /// log when it starts, so what's there from an earlier launch is skipped
+    /// until it's rewritten.
+    pub fn follow(&mut self, log: PathBuf, launched: SystemTime) {
+        self.stop();
+        let followed = Arc::new(Mutex::new(Followed::default()));
+        let is_stopped = Arc::new(AtomicBool::new(false));
+        self.log = Some(log.clone());
+        self.followed = followed.clone();
+        self.is_stopped = is_stopped.clone();
+        thread::spawn(move || {
+            let filter = ChannelFilter::new(vec![]);
+            let mut position = None;
+            while !is_stopped.load(Ordering::Relaxed) {
+                thread::sleep(POLL_INTERVAL);
+                let Ok(metadata) = fs::metadata(&log) else {
+                    continue;
+                };
+                let start = match position {
+                    Some(position) if metadata.len() >= position => position,
+                    // The log was rewritten by launching the keg again.
+                    Some(_) => 0,
+                    None if metadata
+                        .modified()
+                        .is_ok_and(|modified| modified >= launched) =>
+                    {
+                        0
+                    }
+                    None => continue,
+                };
+                let mut read = vec![];
+                let result =
+                    log_tail::print_from(&log, start, &filter, &mut read);
+                let Ok(mut followed) = followed.lock() else {
+                    return;
+                };
+                followed.has_started = true;
+                match result {
+                    Ok(end) => {
+                        followed.push(&String::from_utf8_lossy(&read));
+                        position = Some(end);
+                    }
+                    Err(error) => {
+                        followed.error = Some(format!("{error:#}"));
+                        return;
+                    }
+                }
+            }
+        });
+    }
+
+    /// Stops following the log, if one is followed.
+    pub fn stop(&mut self) {
+        self.is_stopped.store(true, Ordering::Relaxed);
+        self.log = None;
+    }
+}
+
+pub struct KegLogView;
+
+impl View for KegLogView {
+    fn draw_content(
+        &self,
+        app: &App,
+        _state: &AsyncState,
+        frame: &mut Frame,
+        area: Rect,
+        _is_focused: bool,
+    ) -> Result<()> {
+        let live_log = &app.keg_log;
+        let Some(log) = &live_log.log else {
+            frame.render_widget(
+                Paragraph::new(
+                    "Nothing is being followed. Launch the keg to watch its log here.",
+                )
+                .wrap(Wrap { trim: false }),
+                area,
+            );
+            return Ok(());
+        };
+
+        let chunks = Layout::default()
+            .direction(Direction::Vertical)
+            .constraints([Constraint::Length(2), Constraint::Fill(1)])
+            .split(area);
+        frame.render_widget(
+            Paragraph::new(vec![
+                Line::from(vec![
+                    "Following ".into(),
+                    log.display().to_string().bold(),
+                ]),
+                Line::from(
+                    "Press <Esc> to return; the keg keeps running.".dark_gray(),
+                ),
+            ]),
+            chunks[0],
+        );
+
+        let Ok(followed) = live_log.followed.lock() else {
+            return Ok(());
+        };
+        let mut lines = vec![];
+        if !followed.has_started {
+            lines.push(Line::from(
+                "Waiting for the keg to start writing its log...".dark_gray(),
+            ));
+        }
+        // Only the newest lines fit, so the pane follows the end of the log.
+        let shown = followed
+            .lines
+            .len()
+            .min(chunks[1].height.saturating_sub(1) as usize);
+        lines.extend(
+            followed
+                .lines
+                .range(followed.lines.len() - shown..)
+                .map(|line| Line::from(line.clone())),
+        );
+        if let Some(error) = &followed.error {
+            lines.push(Line::from(
+                format!("Stopped following the log: {error}").red(),
+            ));
+        }
+        frame.render_widget(Paragraph::new(lines), chunks[1]);
+
+        Ok(())
+    }
+
+    fn selectable_lines(
+        &self,
+        app: &App,
+        _state: &AsyncState,
+    ) -> Option<Vec<String>> {
+        let followed = app.keg_log.followed.lock().ok()?;
+        Some(followed.lines.iter().cloned().collect())
+    }
+}
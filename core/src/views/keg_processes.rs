@@ -0,0 +1,221 @@
+// Copyright (C) 2026 Ethan Uppal.
+//
+// This program is free software: you can redistribute it and/or modify it under
+// the terms of the GNU General Public License as published by the Free Software
+// Foundation, version 3 of the License only.
+//
+// This program is distributed in the hope that it will be useful, but WITHOUT
+// ANY WARRANTY; without even the implied warranty of MERCHANTABILITY or FITNESS
+// FOR A PARTICULAR PURPOSE. See the GNU General Public License for more
+// details.
+//
+// You should have received a copy of the GNU General Public License along with
+// this program.  If not, see <https://www.gnu.org/licenses/>.
+
+use std::process::Command;
+
+use ratatui::widgets::{List, ListItem, ListState, Wrap};
+
+use crate::{
+    app::{App, AsyncState, SELECTED_FOCUSED_STYLE, SELECTED_UNFOCUSED_STYLE},
+    disk_usage::format_size,
+    processes::KegProcess,
+    view::prelude::*,
+};
+
+/// Which process the view is about to kill, kept in [`App`] so killing takes
+/// pressing enter twice on the same process.
+#[derive(Default)]
+pub struct ProcessKiller {
+    armed: Option<u32>,
+    /// The processes already asked to quit, which are killed outright the
+    /// next time.
+    asked_to_quit: Vec<u32>,
+    status: Option<Result<String, String>>,
+}
+
+impl ProcessKiller {
+    pub fn reset(&mut self) {
+        *self = Self::default();
+    }
+}
+
+/// The current keg's processes, or none if it has none.
+fn current_processes<'a>(app: &App, state: &'a AsyncState) -> &'a [KegProcess] {
+    app.current_keg
+        .as_ref()
+        .and_then(|current_keg| {
+            state.keg_processes.get(&current_keg.bundle_path())
+        })
+        .map(Vec::as_slice)
+        .unwrap_or_default()
+}
+
+fn describe(process: &KegProcess) -> String {
+    format!(
+        "{:>7} {:>9} {:>11}  {}{}",
+        process.pid,
+        format_size(process.memory),
+        process.elapsed,
+        "  ".repeat(process.depth),
+        process.command
+    )
+}
+
+pub struct KegProcessesView;
+
+impl View for KegProcessesView {
+    fn draw_content(
+        &self,
+        app: &App,
+        state: &AsyncState,
+        frame: &mut Frame,
+        area: Rect,
+        is_focused: bool,
+    ) -> Result<()> {
+        app.watch_processes();
+        let processes = current_processes(app, state);
+        if processes.is_empty() {
+            frame.render_widget(
+                Paragraph::new(
+                    "Nothing is running from this keg. This updates every second.",
+                )
+                .wrap(Wrap { trim: false }),
+                area,
+            );
+            return Ok(());
+        }
+
+        let chunks = Layout::default()
+            .direction(Direction::Vertical)
+            .constraints([
+                Constraint::Length(1),
+                Constraint::Length(2),
+                Constraint::Length(1),
+                Constraint::Fill(1),
+            ])
+            .split(area);
+        let killer = &app.process_killer;
+        let memory = processes.iter().map(|process| process.memory).sum();
+        frame.render_widget(
+            Line::from(vec![
+                format!("{} process(es)", processes.len()).bold(),
+                format!(" using {}", format_size(memory)).into(),
+            ]),
+            chunks[0],
+        );
+        let status = match &killer.status {
+            Some(Ok(status)) => Line::from(status.clone().green()),
+            Some(Err(error)) => Line::from(error.clone().red()),
+            None => Line::from(
+                "Updated every second. Press <Enter> on a process to kill only it, or use Kill Processes in the menu to stop them all."
+                    .dark_gray(),
+            ),
+        };
+        frame.render_widget(
+            Paragraph::new(status).wrap(Wrap { trim: false }),
+            chunks[1],
+        );
+        frame.render_widget(
+            Line::from(
+                format!(
+                    "   {:>7} {:>9} {:>11}  {}",
+                    "PID", "MEMORY", "ELAPSED", "COMMAND"
+                )
+                .dark_gray(),
+            ),
+            chunks[2],
+        );
+
+        let items = processes
+            .iter()
+            .map(|process| {
+                if killer.armed == Some(process.pid) {
+                    ListItem::new(Line::from(vec![
+                        describe(process).into(),
+                        "  press <Enter> again to kill it".red(),
+                    ]))
+                } else {
+                    ListItem::new(describe(process))
+                }
+            })
+            .collect::<Vec<_>>();
+        let mut list_state = ListState::default();
+        list_state.select(Some(app.interaction_state()));
+        let list = List::new(items)
+            .highlight_style(if is_focused {
+                SELECTED_FOCUSED_STYLE
+            } else {
+                SELECTED_UNFOCUSED_STYLE
+            })
+            .highlight_symbol(">> ");
+        frame.render_stateful_widget(list, chunks[3], &mut list_state);
+
+        Ok(())
+    }
+
+    fn interactivity(
+        &self,
+        app: &App,
+        state: &AsyncState,
+    ) -> Result<ViewInteractivity> {
+        let count = current_processes(app, state).len();
+        Ok(if count == 0 {
+            ViewInteractivity::None
+        } else {
+            ViewInteractivity::Clickables(count)
+        })
+    }
+
+    fn selectable_lines(
+        &self,
+        app: &App,
+        state: &AsyncState,
+    ) -> Option<Vec<String>> {
+        Some(current_processes(app, state).iter().map(describe).collect())
+    }
+
+    fn click(
+        &self,
+        app: &mut App,
+        state: &AsyncState,
+        index: usize,
+    ) -> Result<Option<MenuItemAction<'_>>> {
+        let Some(process) = current_processes(app, state).get(index) else {
+            return Ok(None);
+        };
+        let pid = process.pid;
+        let killer = &mut app.process_killer;
+        if killer.armed != Some(pid) {
+            killer.armed = Some(pid);
+            killer.status = None;
+            return Ok(None);
+        }
+        killer.armed = None;
+        // Asking first lets the program save, but a hung one ignores it.
+        let is_forced = killer.asked_to_quit.contains(&pid);
+        let signal = if is_forced { "-KILL" } else { "-TERM" };
+        let result = app
+            .runner
+            .output(Command::new("kill").arg(signal).arg(pid.to_string()));
+        let killer = &mut app.process_killer;
+        killer.status = Some(match result {
+            Ok(output) if output.status.success() => {
+                if is_forced {
+                    Ok(format!("Killed {pid}"))
+                } else {
+                    killer.asked_to_quit.push(pid);
+                    Ok(format!(
+                        "Asked {pid} to quit; if it doesn't, kill it again to force it"
+                    ))
+                }
+            }
+            Ok(output) => Err(format!(
+                "Failed to kill {pid}: {}",
+                String::from_utf8_lossy(&output.stderr).trim()
+            )),
+            Err(error) => Err(format!("Failed to run kill: {error}")),
+        });
+        Ok(None)
+    }
+}
@@ -0,0 +1,168 @@
+// Copyright (C) 2026 Ethan Uppal.
+//
+// This program is free software: you can redistribute it and/or modify it under
+// the terms of the GNU General Public License as published by the Free Software
+// Foundation, version 3 of the License only.
+//
+// This program is distributed in the hope that it will be useful, but WITHOUT
+// ANY WARRANTY; without even the implied warranty of MERCHANTABILITY or FITNESS
+// FOR A PARTICULAR PURPOSE. See the GNU General Public License for more
+// details.
+//
+// You should have received a copy of the GNU General Public License along with
+// this program.  If not, see <https://www.gnu.org/licenses/>.
+
+use std::fs;
+
+use ratatui::widgets::{List, ListItem, ListState, Wrap};
+
+use crate::{
+    app::{App, AsyncState, SELECTED_FOCUSED_STYLE, SELECTED_UNFOCUSED_STYLE},
+    run_logs::{describe_age, list_runs},
+    view::prelude::*,
+};
+
+/// The most runs listed at once; the rest of the area previews the selected
+/// run's output.
+const MAX_LISTED_RUNS: u16 = 8;
+
+/// The output of the run selected in `app`, line by line.
+fn selected_run_lines(app: &App) -> Vec<String> {
+    let Some(current_keg) = &app.current_keg else {
+        return vec![];
+    };
+    list_runs(&current_keg.keg.log_directory)
+        .get(app.interaction_state())
+        .and_then(|run| fs::read(&run.path).ok())
+        .map(|bytes| {
+            String::from_utf8_lossy(&bytes)
+                .lines()
+                .map(str::to_owned)
+                .collect()
+        })
+        .unwrap_or_default()
+}
+
+pub struct KegRunsView;
+
+impl View for KegRunsView {
+    fn draw_content(
+        &self,
+        app: &App,
+        _state: &AsyncState,
+        frame: &mut Frame,
+        area: Rect,
+        is_focused: bool,
+    ) -> Result<()> {
+        let Some(current_keg) = &app.current_keg else {
+            return Ok(());
+        };
+
+        let runs = list_runs(&current_keg.keg.log_directory);
+        if runs.is_empty() {
+            frame.render_widget(
+                Paragraph::new(
+                    "No recorded runs yet. Launches, winetricks installs, and prefix creation are saved here.",
+                )
+                .wrap(Wrap { trim: false }),
+                area,
+            );
+            return Ok(());
+        }
+
+        let chunks = Layout::default()
+            .direction(Direction::Vertical)
+            .constraints([
+                Constraint::Length((runs.len() as u16).min(MAX_LISTED_RUNS)),
+                Constraint::Length(1),
+                Constraint::Fill(1),
+            ])
+            .split(area);
+        let (list_area, preview_area) = (chunks[0], chunks[2]);
+
+        let run_items = runs
+            .iter()
+            .map(|run| {
+                ListItem::new(Line::from(vec![
+                    run.label.clone().into(),
+                    format!(" ({})", describe_age(run.started)).dark_gray(),
+                ]))
+            })
+            .collect::<Vec<_>>();
+        let mut list_state = ListState::default();
+        list_state.select(Some(app.interaction_state()));
+        let list = List::new(run_items)
+            .highlight_style(if is_focused {
+                SELECTED_FOCUSED_STYLE
+            } else {
+                SELECTED_UNFOCUSED_STYLE
+            })
+            .highlight_symbol(">> ");
+        frame.render_stateful_widget(list, list_area, &mut list_state);
+
+        frame.render_widget(
+            Line::from(if app.visual_selection().is_some() {
+                "-- VISUAL -- <J>/<K> extend, <Y> copy, <Esc> cancel"
+            } else {
+                "<V> select lines to copy"
+            })
+            .dark_gray(),
+            chunks[1],
+        );
+
+        // Show the end of the output, which is where errors usually are,
+        // unless the visual mode cursor is above it.
+        let lines = selected_run_lines(app);
+        let height = preview_area.height as usize;
+        let mut start = lines.len().saturating_sub(height);
+        if let Some(cursor) = app.visual_cursor() {
+            start = start.min(cursor);
+        }
+        let selection = app.visual_selection();
+        let shown = lines
+            .iter()
+            .enumerate()
+            .skip(start)
+            .take(height)
+            .map(|(index, line)| {
+                let line = Line::from(line.as_str());
+                if selection
+                    .as_ref()
+                    .is_some_and(|selection| selection.contains(&index))
+                {
+                    line.reversed()
+                } else {
+                    line
+                }
+            })
+            .collect::<Vec<_>>();
+        frame.render_widget(Paragraph::new(shown), preview_area);
+
+        Ok(())
+    }
+
+    fn selectable_lines(
+        &self,
+        app: &App,
+        _state: &AsyncState,
+    ) -> Option<Vec<String>> {
+        Some(selected_run_lines(app))
+    }
+
+    fn interactivity(
+        &self,
+        app: &App,
+        _state: &AsyncState,
+    ) -> Result<ViewInteractivity> {
+        let run_count = app
+            .current_keg
+            .as_ref()
+            .map(|current_keg| list_runs(&current_keg.keg.log_directory).len())
+            .unwrap_or(0);
+        Ok(if run_count == 0 {
+            ViewInteractivity::None
+        } else {
+            ViewInteractivity::Clickables(run_count)
+        })
+    }
+}
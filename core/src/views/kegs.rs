@@ -12,15 +12,86 @@
 // You should have received a copy of the GNU General Public License along with
 // this program.  If not, see <https://www.gnu.org/licenses/>.
 
-use ratatui::widgets::{List, ListItem, ListState, Wrap};
+use crossterm::event::{KeyCode, KeyEvent};
+use ratatui::widgets::{
+    Cell, List, ListItem, ListState, Row, Table, TableState, Wrap,
+};
 
 use crate::{
+    actions,
     app::{App, AsyncState, SELECTED_FOCUSED_STYLE, SELECTED_UNFOCUSED_STYLE},
+    app_config::KegColumn,
+    arch,
+    disk_usage::format_size,
+    fuzzy,
+    keg::Keg,
+    run_logs::{self, describe_age},
     view::prelude::*,
 };
 
+/// The filter typed over the kegs list, kept in [`App`] so it survives
+/// opening a keg and coming back.
+#[derive(Default)]
+pub struct KegFilter {
+    query: String,
+    is_searching: bool,
+}
+
+/// The kegs whose name fuzzily matches the filter, best first, or every keg
+/// in order if there's no filter.
+pub fn matching_kegs<'a>(app: &App, state: &'a AsyncState) -> Vec<&'a Keg> {
+    let query = app.keg_filter.query.trim();
+    if query.is_empty() {
+        return state.kegs.iter().collect();
+    }
+    fuzzy::rank(query, &state.kegs, |keg| &keg.name)
+}
+
 pub struct KegsView;
 
+/// Shown instead of the list when no kegs are found.
+const EMPTY_STATE_BUTTONS: [&str; 3] =
+    ["Run the Setup Wizard", "Add a Search Path", "Create a Keg"];
+
+/// What `keg` shows in `column`, or an empty string if it isn't known.
+fn column_text(
+    app: &App,
+    state: &AsyncState,
+    keg: &Keg,
+    column: KegColumn,
+) -> String {
+    match column {
+        KegColumn::Location => keg.enclosing_location.display().to_string(),
+        KegColumn::Wrapper => keg.flavor.name().to_owned(),
+        KegColumn::Arch => keg
+            .engine_arch()
+            .map(|engine_arch| engine_arch.badge().to_owned())
+            .unwrap_or_default(),
+        KegColumn::Wine => state
+            .wine_versions
+            .get(&keg.wine_executable())
+            .map(|wine_version| wine_version.version.clone())
+            .unwrap_or_default(),
+        KegColumn::Engine => keg.engine_version.clone().unwrap_or_default(),
+        KegColumn::Size => state
+            .keg_sizes
+            .get(&keg.bundle_path())
+            .map(|size| format_size(*size))
+            .unwrap_or_default(),
+        KegColumn::LastPlayed => run_logs::list_runs(&keg.log_directory)
+            .into_iter()
+            .find(|run| run.label == "launch")
+            .map(|run| describe_age(run.started))
+            .unwrap_or_default(),
+        KegColumn::Tags => app
+            .config
+            .keg_tags
+            .get(&keg.name)
+            .map(|tags| tags.join(", "))
+            .unwrap_or_default(),
+    }
+}
+
 fn oxford_comma(items: Vec<String>, if_empty: impl Into<String>) -> String {
     match items.len() {
         0 => if_empty.into(),
@@ -51,9 +122,18 @@ impl View for KegsView {
                 .collect(),
             "nowhere (you'll need to specify paths in the config file)",
         );
-        let text = format!(
+        let mut text = format!(
             "Select a Keg (kegs are searched under {search_locations}):"
         );
+        if arch::host_is_apple_silicon(&*app.runner) {
+            text = if arch::is_rosetta_installed() {
+                format!("{text} [Apple Silicon: kegs run under Rosetta 2]")
+            } else {
+                format!(
+                    "{text} [Apple Silicon: Rosetta 2 is NOT installed, so kegs will not run]"
+                )
+            };
+        }
         let wrapped = textwrap::wrap(&text, area.width as usize);
 
         let title_paragraph =
@@ -68,37 +148,134 @@ impl View for KegsView {
             },
         );
 
-        let list_area = Rect {
+        let mut list_area = Rect {
             x: area.x,
             y: area.y + (wrapped.len() as u16),
             width: area.width,
             height: area.height.saturating_sub(wrapped.len() as u16),
         };
 
+        let filter = &app.keg_filter;
+        let kegs = matching_kegs(app, state);
         if !state.kegs.is_empty() {
-            let keg_items = state
-                .kegs
+            frame.render_widget(
+                Line::from(vec![
+                    "Filter: ".bold(),
+                    if filter.query.is_empty() && !filter.is_searching {
+                        "press / to filter by name".dark_gray()
+                    } else {
+                        filter.query.clone().into()
+                    },
+                    if filter.is_searching {
+                        "_".dark_gray()
+                    } else {
+                        "".into()
+                    },
+                    if filter.query.trim().is_empty() {
+                        "".into()
+                    } else {
+                        format!("  {} of {}", kegs.len(), state.kegs.len())
+                            .dark_gray()
+                    },
+                ]),
+                list_area,
+            );
+            list_area.y += 1;
+            list_area.height = list_area.height.saturating_sub(1);
+        }
+
+        if !state.kegs.is_empty() && kegs.is_empty() {
+            frame.render_widget(
+                format!("No kegs match \"{}\"", filter.query.trim())
+                    .dark_gray(),
+                list_area,
+            );
+        } else if !state.kegs.is_empty() {
+            let columns = &app.config.keg_list_columns;
+            let cells =
+                kegs.iter()
+                    .map(|keg| {
+                        let mut cells = vec![keg.name.clone()];
+                        cells.extend(columns.iter().map(|column| {
+                            column_text(app, state, keg, *column)
+                        }));
+                        cells
+                    })
+                    .collect::<Vec<_>>();
+            let header = ["Name"]
+                .into_iter()
+                .chain(columns.iter().map(|column| column.title()))
+                .collect::<Vec<_>>();
+            let widths = header
                 .iter()
-                .cloned()
-                .map(|keg| {
-                    ListItem::new(Span::from(format!(
-                        "{} ({})",
-                        keg.name,
-                        keg.enclosing_location.display()
-                    )))
+                .enumerate()
+                .map(|(index, title)| {
+                    let width = cells
+                        .iter()
+                        .map(|row| row[index].chars().count())
+                        .chain([title.len()])
+                        .max()
+                        .unwrap_or(0);
+                    Constraint::Length(width as u16)
                 })
                 .collect::<Vec<_>>();
 
+            let rows = cells.into_iter().map(|row| {
+                Row::new(row.into_iter().enumerate().map(|(index, text)| {
+                    if index == 0 {
+                        Cell::from(text)
+                    } else {
+                        Cell::from(text).dark_gray()
+                    }
+                }))
+            });
+            let mut table_state = TableState::default();
+            table_state.select(Some(app.interaction_state()));
+            let table = Table::new(rows, widths)
+                .header(
+                    Row::new(header).style(Style::new().bold().underlined()),
+                )
+                .column_spacing(2)
+                .row_highlight_style(if is_focused {
+                    SELECTED_FOCUSED_STYLE
+                } else {
+                    SELECTED_UNFOCUSED_STYLE
+                })
+                .highlight_symbol(">> ");
+            frame.render_stateful_widget(table, list_area, &mut table_state);
+        } else {
+            let mut lines =
+                vec![Line::from(""), Line::from("No kegs were found in:")];
+            for path in &app.config.keg_search_paths {
+                lines.push(Line::from(vec![
+                    format!("  {} ", path.display()).into(),
+                    if path.is_dir() {
+                        "(exists)".dark_gray()
+                    } else {
+                        "(missing)".red()
+                    },
+                ]));
+            }
+            lines.push(Line::from(""));
+            let chunks = Layout::default()
+                .direction(Direction::Vertical)
+                .constraints([
+                    Constraint::Length(lines.len() as u16),
+                    Constraint::Fill(1),
+                ])
+                .split(list_area);
+            frame.render_widget(Paragraph::new(lines), chunks[0]);
+
             let mut list_state = ListState::default();
             list_state.select(Some(app.interaction_state()));
-            let list = List::new(keg_items)
+            let list = List::new(EMPTY_STATE_BUTTONS.map(ListItem::new))
                 .highlight_style(if is_focused {
                     SELECTED_FOCUSED_STYLE
                 } else {
                     SELECTED_UNFOCUSED_STYLE
                 })
                 .highlight_symbol(">> ");
-            frame.render_stateful_widget(list, list_area, &mut list_state);
+            frame.render_stateful_widget(list, chunks[1], &mut list_state);
         }
 
         Ok(())
@@ -106,27 +283,94 @@ impl View for KegsView {
 
     fn interactivity(
         &self,
-        _app: &App,
+        app: &App,
         state: &AsyncState,
     ) -> Result<ViewInteractivity> {
-        Ok(if state.kegs.is_empty() {
+        if state.kegs.is_empty() {
+            return Ok(ViewInteractivity::Clickables(
+                EMPTY_STATE_BUTTONS.len(),
+            ));
+        }
+        let count = matching_kegs(app, state).len();
+        Ok(if count == 0 {
             ViewInteractivity::None
         } else {
-            ViewInteractivity::Clickables(state.kegs.len())
+            ViewInteractivity::Clickables(count)
         })
     }
 
+    fn handle_key(
+        &self,
+        app: &mut App,
+        _state: &AsyncState,
+        key_event: KeyEvent,
+    ) -> Result<bool> {
+        let filter = &mut app.keg_filter;
+        if !filter.is_searching {
+            match key_event.code {
+                KeyCode::Char('/') => {
+                    filter.is_searching = true;
+                    filter.query.clear();
+                    app.select_clickable(0);
+                    return Ok(true);
+                }
+                // Clears the filter before leaving the list.
+                KeyCode::Esc if !filter.query.is_empty() => {
+                    filter.query.clear();
+                    app.select_clickable(0);
+                    return Ok(true);
+                }
+                _ => return Ok(false),
+            }
+        }
+        match key_event.code {
+            KeyCode::Enter => filter.is_searching = false,
+            KeyCode::Esc => {
+                filter.is_searching = false;
+                filter.query.clear();
+            }
+            KeyCode::Backspace => {
+                filter.query.pop();
+            }
+            KeyCode::Char(c) => filter.query.push(c),
+            _ => return Ok(true),
+        }
+        app.select_clickable(0);
+        Ok(true)
+    }
+
     fn click(
         &self,
         app: &mut App,
         state: &AsyncState,
         index: usize,
-    ) -> Result<Option<NavAction<'_>>> {
-        Ok(if !state.kegs.is_empty() {
-            app.current_keg = Some((&state.kegs[index]).try_into()?);
-            Some(NavAction::Push(NavID::Named("keg")))
-        } else {
-            None
-        })
+    ) -> Result<Option<MenuItemAction<'_>>> {
+        if state.kegs.is_empty() {
+            return Ok(match index {
+                0 => Some(MenuItemAction::NavAction(NavAction::Push(
+                    NavID::Named("setup"),
+                ))),
+                1 => Some(MenuItemAction::External(
+                    actions::create::add_keg_search_path,
+                )),
+                2 => {
+                    Some(MenuItemAction::LoadView(ViewID::Named("keg_creator")))
+                }
+                _ => None,
+            });
+        }
+        // The selection may be left over from the empty state's buttons.
+        let Some(keg) = matching_kegs(app, state).get(index).copied() else {
+            return Ok(None);
+        };
+        app.current_keg = Some(keg.try_into()?);
+        app.keg_deletion.reset();
+        app.config_form.reset();
+        app.keg_log.stop();
+        app.executable_browser.reset();
+        app.process_killer.reset();
+        Ok(Some(MenuItemAction::NavAction(NavAction::Push(
+            NavID::Named("keg"),
+        ))))
     }
 }
@@ -0,0 +1,97 @@
+// Copyright (C) 2026 Ethan Uppal.
+//
+// This program is free software: you can redistribute it and/or modify it under
+// the terms of the GNU General Public License as published by the Free Software
+// Foundation, version 3 of the License only.
+//
+// This program is distributed in the hope that it will be useful, but WITHOUT
+// ANY WARRANTY; without even the implied warranty of MERCHANTABILITY or FITNESS
+// FOR A PARTICULAR PURPOSE. See the GNU General Public License for more
+// details.
+//
+// You should have received a copy of the GNU General Public License along with
+// this program.  If not, see <https://www.gnu.org/licenses/>.
+
+use ratatui::widgets::{List, ListItem, ListState};
+
+use crate::{
+    app::{App, AsyncState, SELECTED_FOCUSED_STYLE, SELECTED_UNFOCUSED_STYLE},
+    setup::SetupComponent,
+    view::prelude::*,
+};
+
+pub struct SetupView;
+
+impl View for SetupView {
+    fn draw_content(
+        &self,
+        app: &App,
+        state: &AsyncState,
+        frame: &mut Frame,
+        area: Rect,
+        is_focused: bool,
+    ) -> Result<()> {
+        let chunks = Layout::default()
+            .direction(Direction::Vertical)
+            .constraints([Constraint::Length(2), Constraint::Fill(1)])
+            .split(area);
+
+        frame.render_widget(
+            "Press enter to toggle a component, then choose Install Selected:",
+            chunks[0],
+        );
+
+        let component_items = SetupComponent::ALL
+            .into_iter()
+            .map(|component| {
+                let mut line = Line::from(vec![
+                    if app.setup_selection.contains(&component) {
+                        "[x] ".into()
+                    } else {
+                        "[ ] ".into()
+                    },
+                    component.name().bold(),
+                    format!(" {}", component.description()).into(),
+                ]);
+                if component.is_installed(state) {
+                    line.push_span(" (installed)".green());
+                }
+                ListItem::new(line)
+            })
+            .collect::<Vec<_>>();
+
+        let mut list_state = ListState::default();
+        list_state.select(Some(app.interaction_state()));
+        let list = List::new(component_items)
+            .highlight_style(if is_focused {
+                SELECTED_FOCUSED_STYLE
+            } else {
+                SELECTED_UNFOCUSED_STYLE
+            })
+            .highlight_symbol(">> ");
+        frame.render_stateful_widget(list, chunks[1], &mut list_state);
+
+        Ok(())
+    }
+
+    fn interactivity(
+        &self,
+        _app: &App,
+        _state: &AsyncState,
+    ) -> Result<ViewInteractivity> {
+        Ok(ViewInteractivity::Clickables(SetupComponent::ALL.len()))
+    }
+
+    fn click(
+        &self,
+        app: &mut App,
+        _state: &AsyncState,
+        index: usize,
+    ) -> Result<Option<MenuItemAction<'_>>> {
+        let component = SetupComponent::ALL[index];
+        if !app.setup_selection.remove(&component) {
+            app.setup_selection.insert(component);
+        }
+        Ok(None)
+    }
+}
@@ -0,0 +1,161 @@
+// Copyright (C) 2026 Ethan Uppal.
+//
+// This program is free software: you can redistribute it and/or modify it under
+// the terms of the GNU General Public License as published by the Free Software
+// Foundation, version 3 of the License only.
+//
+// This program is distributed in the hope that it will be useful, but WITHOUT
+// ANY WARRANTY; without even the implied warranty of MERCHANTABILITY or FITNESS
+// FOR A PARTICULAR PURPOSE. See the GNU General Public License for more
+// details.
+//
+// You should have received a copy of the GNU General Public License along with
+// this program.  If not, see <https://www.gnu.org/licenses/>.
+
+use ratatui::widgets::{List, ListItem, ListState, Wrap};
+
+use crate::{
+    app::{App, AsyncState, SELECTED_FOCUSED_STYLE, SELECTED_UNFOCUSED_STYLE},
+    runner::Completion,
+    tasks::TaskStatus,
+    view::prelude::*,
+};
+
+fn status_span(status: &TaskStatus) -> Span<'static> {
+    match status {
+        TaskStatus::Queued => "queued".dark_gray(),
+        TaskStatus::Running => "running".yellow(),
+        TaskStatus::Finished {
+            problem: Some(_), ..
+        } => "failed".red(),
+        TaskStatus::Finished {
+            completion: Completion::Exited(status),
+            ..
+        } if status.success() => "done".green(),
+        TaskStatus::Finished { completion, .. } => completion.to_string().red(),
+        TaskStatus::Failed(_) => "failed".red(),
+    }
+}
+
+pub struct TasksView;
+
+impl View for TasksView {
+    fn draw_content(
+        &self,
+        app: &App,
+        _state: &AsyncState,
+        frame: &mut Frame,
+        area: Rect,
+        is_focused: bool,
+    ) -> Result<()> {
+        let entries = app.tasks.entries();
+        if entries.is_empty() {
+            frame.render_widget(
+                Paragraph::new(
+                    "No tasks. Winetricks installs are queued here and run one at a time.",
+                )
+                .wrap(Wrap { trim: false }),
+                area,
+            );
+            return Ok(());
+        }
+
+        let chunks = Layout::default()
+            .direction(Direction::Vertical)
+            .constraints([
+                Constraint::Length(1),
+                Constraint::Length(1),
+                Constraint::Fill(1),
+            ])
+            .split(area);
+        frame.render_widget(
+            Line::from(
+                "Press <Enter> on a queued or running task to cancel it."
+                    .dark_gray(),
+            ),
+            chunks[0],
+        );
+
+        let items = entries
+            .iter()
+            .map(|entry| {
+                let mut lines = vec![Line::from(vec![
+                    status_span(&entry.status),
+                    "  ".into(),
+                    entry.keg_name.clone().bold(),
+                    ": ".into(),
+                    entry.description.clone().into(),
+                ])];
+                match &entry.status {
+                    TaskStatus::Finished {
+                        run_log,
+                        problem,
+                        summary,
+                        ..
+                    } => {
+                        if let Some(problem) = problem {
+                            lines.push(format!("    {problem}").red().into());
+                        }
+                        if let Some(summary) = summary {
+                            lines.push(format!("    {summary}").into());
+                        }
+                        if let Some(run_log) = run_log {
+                            lines.push(
+                                format!(
+                                    "    Output saved to {}",
+                                    run_log.display()
+                                )
+                                .dark_gray()
+                                .into(),
+                            );
+                        }
+                    }
+                    TaskStatus::Failed(error) => {
+                        lines.push(format!("    {error}").red().into());
+                    }
+                    _ => {}
+                }
+                ListItem::new(lines)
+            })
+            .collect::<Vec<_>>();
+        let mut list_state = ListState::default();
+        list_state.select(Some(app.interaction_state()));
+        let list = List::new(items)
+            .highlight_style(if is_focused {
+                SELECTED_FOCUSED_STYLE
+            } else {
+                SELECTED_UNFOCUSED_STYLE
+            })
+            .highlight_symbol(">> ");
+        frame.render_stateful_widget(list, chunks[2], &mut list_state);
+
+        Ok(())
+    }
+
+    fn interactivity(
+        &self,
+        app: &App,
+        _state: &AsyncState,
+    ) -> Result<ViewInteractivity> {
+        let count = app.tasks.entries().len();
+        Ok(if count == 0 {
+            ViewInteractivity::None
+        } else {
+            ViewInteractivity::Clickables(count)
+        })
+    }
+
+    fn click(
+        &self,
+        app: &mut App,
+        _state: &AsyncState,
+        index: usize,
+    ) -> Result<Option<MenuItemAction<'_>>> {
+        if let Some(entry) = app.tasks.entries().get(index)
+            && entry.status.is_pending()
+        {
+            app.tasks.cancel(entry.id);
+        }
+        Ok(None)
+    }
+}
@@ -0,0 +1,436 @@
+// Copyright (C) 2026 Ethan Uppal.
+//
+// This program is free software: you can redistribute it and/or modify it under
+// the terms of the GNU General Public License as published by the Free Software
+// Foundation, version 3 of the License only.
+//
+// This program is distributed in the hope that it will be useful, but WITHOUT
+// ANY WARRANTY; without even the implied warranty of MERCHANTABILITY or FITNESS
+// FOR A PARTICULAR PURPOSE. See the GNU General Public License for more
+// details.
+//
+// You should have received a copy of the GNU General Public License along with
+// this program.  If not, see <https://www.gnu.org/licenses/>.
+
+use std::{
+    collections::BTreeSet,
+    fs,
+    path::Path,
+    process::Command,
+    sync::{Arc, Mutex},
+    thread,
+};
+
+use crossterm::event::{KeyCode, KeyEvent};
+use ratatui::widgets::{List, ListItem, ListState, Wrap};
+
+use crate::{
+    actions::winetricks,
+    app::{App, AsyncState, SELECTED_FOCUSED_STYLE, SELECTED_UNFOCUSED_STYLE},
+    keg::CurrentKeg,
+    keg_config::WinetricksRunFlags,
+    keg_diff,
+    setup::{self, WINETRICKS_SCRIPT},
+    view::prelude::*,
+    winetricks_catalog::{self, Verb},
+};
+
+/// Clickables before the verbs: the install button and the three run flags.
+const FIXED_CLICKABLES: usize = 4;
+
+#[derive(Default)]
+enum Catalog {
+    #[default]
+    NotLoaded,
+    Loading,
+    Loaded(Vec<Verb>),
+    Failed(String),
+}
+
+/// The winetricks browser's search and selection, kept in [`App`] so they
+/// survive leaving the view.
+#[derive(Default)]
+pub struct WinetricksBrowser {
+    /// Loaded on another thread the first time the view is drawn, since
+    /// listing the verbs runs winetricks four times.
+    catalog: Arc<Mutex<Catalog>>,
+    query: String,
+    is_searching: bool,
+    /// The chosen verbs, in the order they were chosen, which is the order
+    /// winetricks installs them in.
+    selected: Vec<String>,
+    /// Flags for the next install only, if they differ from the keg's.
+    run_flags: Option<WinetricksRunFlags>,
+    /// What happened to the last install, or why it couldn't be queued.
+    status: Option<Result<String, String>>,
+}
+
+impl WinetricksBrowser {
+    fn start_loading(&self, app: &App) {
+        let Ok(mut catalog) = self.catalog.lock() else {
+            return;
+        };
+        if !matches!(*catalog, Catalog::NotLoaded) {
+            return;
+        }
+        let Some(current_keg) = &app.current_keg else {
+            return;
+        };
+        *catalog = Catalog::Loading;
+        let shared = self.catalog.clone();
+        let runner = app.runner.clone();
+        let environment = current_keg.winetricks_environment();
+        thread::spawn(move || {
+            let loaded = match winetricks_catalog::load(&*runner, &environment)
+            {
+                Ok(verbs) => Catalog::Loaded(verbs),
+                Err(error) => Catalog::Failed(format!("{error:#}")),
+            };
+            if let Ok(mut catalog) = shared.lock() {
+                *catalog = loaded;
+            }
+        });
+    }
+
+    /// The loaded verbs matching the search, best first, or none if they
+    /// aren't loaded.
+    fn matching_verbs(&self) -> Vec<Verb> {
+        let catalog = self.catalog.lock();
+        let Ok(Catalog::Loaded(verbs)) = catalog.as_deref() else {
+            return vec![];
+        };
+        let query = self.query.trim();
+        let mut matches = verbs
+            .iter()
+            .filter_map(|verb| Some((verb.search_score(query)?, verb)))
+            .collect::<Vec<_>>();
+        // Stable, so equally good matches stay in catalog order.
+        matches.sort_by_key(|(score, _)| *score);
+        matches.into_iter().map(|(_, verb)| verb.clone()).collect()
+    }
+
+    fn toggle(&mut self, verb: &str) {
+        if let Some(index) = self.selected.iter().position(|name| name == verb)
+        {
+            self.selected.remove(index);
+        } else {
+            self.selected.push(verb.to_owned());
+        }
+    }
+
+    /// Forgets the loaded verbs so they're listed again, e.g., after the
+    /// cache was cleared.
+    pub fn reload(&mut self) {
+        if let Ok(mut catalog) = self.catalog.lock()
+            && !matches!(*catalog, Catalog::Loading)
+        {
+            *catalog = Catalog::NotLoaded;
+        }
+    }
+}
+
+/// The verbs winetricks has already installed into `current_keg`.
+fn installed_verbs(current_keg: &CurrentKeg) -> BTreeSet<String> {
+    current_keg
+        .keg
+        .c_drive
+        .parent()
+        .map(keg_diff::installed_winetricks)
+        .unwrap_or_default()
+}
+
+fn checkbox(is_checked: bool) -> Span<'static> {
+    if is_checked {
+        "[x] ".into()
+    } else {
+        "[ ] ".into()
+    }
+}
+
+/// Queues installing the selected verbs into the current keg with the chosen
+/// run flags, and says where to follow it.
+fn install(app: &mut App) -> Result<()> {
+    let Some(current_keg) = &app.current_keg else {
+        return Ok(());
+    };
+    let browser = &app.winetricks_browser;
+    if browser.selected.is_empty() {
+        return Ok(());
+    }
+    if !Path::new(WINETRICKS_SCRIPT).is_file() {
+        setup::fetch_winetricks_quietly(&*app.runner)?;
+    }
+    winetricks::prepare_winetricks(app, current_keg)?;
+    let saved_flags = current_keg.plist.winetricks_run_flags();
+    let run_flags = browser.run_flags.unwrap_or(saved_flags);
+    let output_log = winetricks::winetricks_output_log(current_keg, run_flags);
+    if let Some(output_log) = &output_log {
+        if !output_log.try_exists()? {
+            fs::write(output_log, "")?;
+        }
+        app.runner.spawn(Command::new("open").arg(output_log))?;
+    }
+    let count = browser.selected.len();
+    let ahead = winetricks::queue_winetricks(
+        app,
+        current_keg,
+        browser.selected.clone(),
+        (run_flags != saved_flags).then_some(run_flags),
+    );
+    let mut status = format!(
+        "Queued winetricks for {count} verb(s); follow it from Task Queue"
+    );
+    if ahead > 0 {
+        status.push_str(&format!(" (after {ahead} earlier task(s))"));
+    }
+    let destination = match &output_log {
+        Some(output_log) => {
+            format!("Output is written to {}", output_log.display())
+        }
+        None => format!(
+            "Logging is disabled, so output is kept only in the run log in {}",
+            current_keg.keg.log_directory.display()
+        ),
+    };
+    status.push_str(&format!(". {destination}"));
+    let browser = &mut app.winetricks_browser;
+    browser.status = Some(Ok(status));
+    browser.selected.clear();
+    browser.run_flags = None;
+    Ok(())
+}
+
+pub struct WinetricksBrowserView;
+
+impl View for WinetricksBrowserView {
+    fn draw_content(
+        &self,
+        app: &App,
+        _state: &AsyncState,
+        frame: &mut Frame,
+        area: Rect,
+        is_focused: bool,
+    ) -> Result<()> {
+        let Some(current_keg) = &app.current_keg else {
+            frame.render_widget("No keg is selected", area);
+            return Ok(());
+        };
+        let browser = &app.winetricks_browser;
+        browser.start_loading(app);
+
+        let chunks = Layout::default()
+            .direction(Direction::Vertical)
+            .constraints([
+                Constraint::Length(1),
+                Constraint::Length(1),
+                Constraint::Length(2),
+                Constraint::Fill(1),
+            ])
+            .split(area);
+        frame.render_widget(
+            Line::from(vec![
+                "Search: ".bold(),
+                if browser.query.is_empty() && !browser.is_searching {
+                    "press / to search by name or description".dark_gray()
+                } else {
+                    browser.query.clone().into()
+                },
+                if browser.is_searching {
+                    "_".dark_gray()
+                } else {
+                    "".into()
+                },
+            ]),
+            chunks[0],
+        );
+        frame.render_widget(
+            Line::from(
+                "Press <Enter> to check a verb, then choose Install."
+                    .dark_gray(),
+            ),
+            chunks[1],
+        );
+        if let Some(status) = &browser.status {
+            frame.render_widget(
+                Paragraph::new(match status {
+                    Ok(status) => status.clone().green(),
+                    Err(error) => error.clone().red(),
+                })
+                .wrap(Wrap { trim: false }),
+                chunks[2],
+            );
+        }
+
+        let run_flags = browser
+            .run_flags
+            .unwrap_or_else(|| current_keg.plist.winetricks_run_flags());
+        let mut items = vec![
+            ListItem::new(if browser.selected.is_empty() {
+                Line::from("[ Install ] nothing checked yet".dark_gray())
+            } else {
+                Line::from(vec![
+                    "[ Install ] ".bold(),
+                    browser.selected.join(" ").into(),
+                    format!(" into {}", current_keg.keg.name).dark_gray(),
+                ])
+            }),
+            ListItem::new(Line::from(vec![
+                checkbox(run_flags.winetricks_force),
+                "Force reinstalling".into(),
+            ])),
+            ListItem::new(Line::from(vec![
+                checkbox(run_flags.winetricks_silent),
+                "Install silently".into(),
+            ])),
+            ListItem::new(Line::from(vec![
+                checkbox(run_flags.winetricks_disable_logging),
+                "Disable logging".into(),
+            ])),
+        ];
+        match browser.catalog.lock().as_deref() {
+            Ok(Catalog::NotLoaded | Catalog::Loading) => items.push(
+                ListItem::new("Loading winetricks verbs...".dark_gray()),
+            ),
+            Ok(Catalog::Failed(error)) => items.push(ListItem::new(
+                format!(
+                    "Failed to load winetricks verbs (press r to retry): {error}"
+                )
+                .red(),
+            )),
+            Ok(Catalog::Loaded(_)) | Err(_) => {}
+        }
+        let verbs = browser.matching_verbs();
+        if verbs.is_empty() && !browser.query.trim().is_empty() {
+            items.push(ListItem::new(
+                format!("No winetricks match \"{}\"", browser.query.trim())
+                    .dark_gray(),
+            ));
+        }
+        let installed = installed_verbs(current_keg);
+        items.extend(verbs.iter().map(|verb| {
+            if installed.contains(&verb.name)
+                && !browser.selected.contains(&verb.name)
+            {
+                return ListItem::new(Line::from(vec![
+                    "[✓] ".dark_gray(),
+                    format!("{:<8}", verb.category.name()).dark_gray(),
+                    verb.name.clone().dark_gray(),
+                    format!("  installed; {}", verb.description).dark_gray(),
+                ]));
+            }
+            ListItem::new(Line::from(vec![
+                checkbox(browser.selected.contains(&verb.name)),
+                format!("{:<8}", verb.category.name()).dark_gray(),
+                verb.name.clone().bold(),
+                format!("  {}", verb.description).into(),
+            ]))
+        }));
+
+        let mut list_state = ListState::default();
+        list_state.select(Some(app.interaction_state()));
+        let list = List::new(items)
+            .highlight_style(if is_focused {
+                SELECTED_FOCUSED_STYLE
+            } else {
+                SELECTED_UNFOCUSED_STYLE
+            })
+            .highlight_symbol(">> ");
+        frame.render_stateful_widget(list, chunks[3], &mut list_state);
+
+        Ok(())
+    }
+
+    fn interactivity(
+        &self,
+        app: &App,
+        _state: &AsyncState,
+    ) -> Result<ViewInteractivity> {
+        Ok(ViewInteractivity::Clickables(
+            FIXED_CLICKABLES + app.winetricks_browser.matching_verbs().len(),
+        ))
+    }
+
+    fn handle_key(
+        &self,
+        app: &mut App,
+        _state: &AsyncState,
+        key_event: KeyEvent,
+    ) -> Result<bool> {
+        let browser = &mut app.winetricks_browser;
+        if !browser.is_searching {
+            if key_event.code == KeyCode::Char('/') {
+                browser.is_searching = true;
+                browser.query.clear();
+                return Ok(true);
+            }
+            return Ok(false);
+        }
+        match key_event.code {
+            KeyCode::Enter | KeyCode::Esc => {
+                browser.is_searching = false;
+                app.select_clickable(FIXED_CLICKABLES);
+            }
+            KeyCode::Backspace => {
+                browser.query.pop();
+                app.select_clickable(FIXED_CLICKABLES);
+            }
+            KeyCode::Char(c) => {
+                browser.query.push(c);
+                app.select_clickable(FIXED_CLICKABLES);
+            }
+            _ => {}
+        }
+        Ok(true)
+    }
+
+    fn click(
+        &self,
+        app: &mut App,
+        _state: &AsyncState,
+        index: usize,
+    ) -> Result<Option<MenuItemAction<'_>>> {
+        let Some(current_keg) = &app.current_keg else {
+            return Ok(None);
+        };
+        let saved_flags = current_keg.plist.winetricks_run_flags();
+        if index == 0 {
+            if let Err(error) = install(app) {
+                app.winetricks_browser.status =
+                    Some(Err(format!("Failed to queue winetricks: {error:#}")));
+            }
+            return Ok(None);
+        }
+        let browser = &mut app.winetricks_browser;
+        browser.status = None;
+        let run_flags = browser.run_flags.get_or_insert(saved_flags);
+        match index {
+            1 => run_flags.winetricks_force ^= true,
+            2 => run_flags.winetricks_silent ^= true,
+            3 => run_flags.winetricks_disable_logging ^= true,
+            _ => {
+                let is_forced = run_flags.winetricks_force;
+                let Some(verb) = browser
+                    .matching_verbs()
+                    .get(index - FIXED_CLICKABLES)
+                    .cloned()
+                else {
+                    return Ok(None);
+                };
+                // Winetricks skips installed verbs unless forced, so checking
+                // one would do nothing.
+                if !is_forced
+                    && !browser.selected.contains(&verb.name)
+                    && installed_verbs(current_keg).contains(&verb.name)
+                {
+                    browser.status = Some(Err(format!(
+                        "{} is already installed; check Force reinstalling to install it again",
+                        verb.name
+                    )));
+                } else {
+                    browser.toggle(&verb.name);
+                }
+            }
+        }
+        Ok(None)
+    }
+}
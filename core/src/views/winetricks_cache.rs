@@ -0,0 +1,136 @@
+// Copyright (C) 2026 Ethan Uppal.
+//
+// This program is free software: you can redistribute it and/or modify it under
+// the terms of the GNU General Public License as published by the Free Software
+// Foundation, version 3 of the License only.
+//
+// This program is distributed in the hope that it will be useful, but WITHOUT
+// ANY WARRANTY; without even the implied warranty of MERCHANTABILITY or FITNESS
+// FOR A PARTICULAR PURPOSE. See the GNU General Public License for more
+// details.
+//
+// You should have received a copy of the GNU General Public License along with
+// this program.  If not, see <https://www.gnu.org/licenses/>.
+
+use ratatui::widgets::{List, ListItem, ListState, Wrap};
+
+use crate::{
+    app::{App, AsyncState, SELECTED_FOCUSED_STYLE, SELECTED_UNFOCUSED_STYLE},
+    disk_usage::format_size,
+    run_logs::describe_age,
+    view::prelude::*,
+    winetricks_cache,
+};
+
+pub struct WinetricksCacheView;
+
+impl View for WinetricksCacheView {
+    fn draw_content(
+        &self,
+        app: &App,
+        state: &AsyncState,
+        frame: &mut Frame,
+        area: Rect,
+        is_focused: bool,
+    ) -> Result<()> {
+        app.watch_winetricks_cache();
+        let cache_directory = winetricks_cache::cache_directory();
+        let Some(entries) = &state.winetricks_cache else {
+            frame.render_widget("Measuring the winetricks cache...", area);
+            return Ok(());
+        };
+        if entries.is_empty() {
+            frame.render_widget(
+                Paragraph::new(format!(
+                    "Nothing downloaded yet. Every keg's winetricks shares downloads in {}, so each verb is only downloaded once.",
+                    cache_directory.display()
+                ))
+                .wrap(Wrap { trim: false }),
+                area,
+            );
+            return Ok(());
+        }
+
+        let chunks = Layout::default()
+            .direction(Direction::Vertical)
+            .constraints([
+                Constraint::Length(1),
+                Constraint::Length(1),
+                Constraint::Length(1),
+                Constraint::Fill(1),
+            ])
+            .split(area);
+        let total = entries.iter().map(|entry| entry.size).sum();
+        frame.render_widget(
+            Line::from(vec![
+                format_size(total).bold(),
+                format!(" in {}", cache_directory.display()).into(),
+            ]),
+            chunks[0],
+        );
+        frame.render_widget(
+            Line::from(
+                "Press <Enter> on a verb to delete its downloads.".dark_gray(),
+            ),
+            chunks[1],
+        );
+
+        let items = entries
+            .iter()
+            .map(|entry| {
+                let mut spans = vec![
+                    format!("{:>9}  ", format_size(entry.size)).into(),
+                    entry.verb.clone().bold(),
+                ];
+                if let Some(modified) = entry.modified {
+                    spans.push(
+                        format!("  {}", describe_age(modified)).dark_gray(),
+                    );
+                }
+                ListItem::new(Line::from(spans))
+            })
+            .collect::<Vec<_>>();
+        let mut list_state = ListState::default();
+        list_state.select(Some(app.interaction_state()));
+        let list = List::new(items)
+            .highlight_style(if is_focused {
+                SELECTED_FOCUSED_STYLE
+            } else {
+                SELECTED_UNFOCUSED_STYLE
+            })
+            .highlight_symbol(">> ");
+        frame.render_stateful_widget(list, chunks[3], &mut list_state);
+
+        Ok(())
+    }
+
+    fn interactivity(
+        &self,
+        _app: &App,
+        state: &AsyncState,
+    ) -> Result<ViewInteractivity> {
+        let count = state.winetricks_cache.as_ref().map_or(0, Vec::len);
+        Ok(if count == 0 {
+            ViewInteractivity::None
+        } else {
+            ViewInteractivity::Clickables(count)
+        })
+    }
+
+    fn click(
+        &self,
+        _app: &mut App,
+        state: &AsyncState,
+        index: usize,
+    ) -> Result<Option<MenuItemAction<'_>>> {
+        if let Some(entry) = state
+            .winetricks_cache
+            .as_ref()
+            .and_then(|entries| entries.get(index))
+        {
+            // Whatever is gone is simply downloaded again next time.
+            let _ = winetricks_cache::remove(&entry.verb);
+        }
+        Ok(None)
+    }
+}
@@ -0,0 +1,99 @@
+// Copyright (C) 2026 Ethan Uppal.
+//
+// This program is free software: you can redistribute it and/or modify it under
+// the terms of the GNU General Public License as published by the Free Software
+// Foundation, version 3 of the License only.
+//
+// This program is distributed in the hope that it will be useful, but WITHOUT
+// ANY WARRANTY; without even the implied warranty of MERCHANTABILITY or FITNESS
+// FOR A PARTICULAR PURPOSE. See the GNU General Public License for more
+// details.
+//
+// You should have received a copy of the GNU General Public License along with
+// this program.  If not, see <https://www.gnu.org/licenses/>.
+
+//! Paths on a keg's C drive, which are written three ways: as Windows paths
+//! (`C:\Games\game.exe`) in the registry and command lines, as the
+//! `/Games/game.exe` form Kegworks stores in `Program Name and Path`, and as
+//! macOS paths under `drive_c`.
+
+use std::{
+    fmt,
+    path::{Path, PathBuf},
+};
+
+/// A path on the C drive, independent of how it was written.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct WinePath {
+    /// The path's components below `C:\`.
+    components: Vec<String>,
+}
+
+impl WinePath {
+    /// Reads a path in the Kegworks form, e.g., `/Games/game.exe`. A missing
+    /// leading slash and backslashes are tolerated, since people type both.
+    pub fn from_kegworks(path: &str) -> Self {
+        Self {
+            components: path
+                .split(['/', '\\'])
+                .filter(|component| !component.is_empty() && *component != ".")
+                .map(str::to_owned)
+                .collect(),
+        }
+    }
+
+    /// Reads a Windows path on the C drive, e.g., `C:\Games\game.exe`, or
+    /// returns `None` for a path on another drive or without one.
+    pub fn from_windows(path: &str) -> Option<Self> {
+        let (drive, rest) = path.split_once(':')?;
+        drive
+            .eq_ignore_ascii_case("c")
+            .then(|| Self::from_kegworks(rest))
+    }
+
+    /// Reads a macOS path inside `c_drive`, or returns `None` for one outside
+    /// it.
+    pub fn from_host(path: &Path, c_drive: &Path) -> Option<Self> {
+        let relative = path.strip_prefix(c_drive).ok()?;
+        Some(Self::from_kegworks(&relative.to_string_lossy()))
+    }
+
+    /// Reads `path` however the user wrote it: as a Windows path, a macOS
+    /// path inside `c_drive`, or a path relative to the C drive. Surrounding
+    /// quotes are ignored. Returns `None` for paths on other Windows drives.
+    pub fn parse(path: &str, c_drive: &Path) -> Option<Self> {
+        let path = path.trim().trim_matches('"');
+        if path.contains(':') {
+            Self::from_windows(path)
+        } else if path.starts_with('/')
+            && let Some(wine_path) = Self::from_host(Path::new(path), c_drive)
+        {
+            Some(wine_path)
+        } else {
+            Some(Self::from_kegworks(path))
+        }
+    }
+
+    /// Where the path is on this Mac.
+    pub fn to_host(&self, c_drive: &Path) -> PathBuf {
+        let mut path = c_drive.to_path_buf();
+        path.extend(&self.components);
+        path
+    }
+
+    /// The path as Windows programs see it, e.g., `C:\Games\game.exe`.
+    pub fn to_windows(&self) -> String {
+        format!("C:\\{}", self.components.join("\\"))
+    }
+
+    /// The path as Kegworks stores it, e.g., `/Games/game.exe`.
+    pub fn to_kegworks(&self) -> String {
+        format!("/{}", self.components.join("/"))
+    }
+}
+
+impl fmt::Display for WinePath {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(&self.to_windows())
+    }
+}
@@ -0,0 +1,76 @@
+// Copyright (C) 2026 Ethan Uppal.
+//
+// This program is free software: you can redistribute it and/or modify it under
+// the terms of the GNU General Public License as published by the Free Software
+// Foundation, version 3 of the License only.
+//
+// This program is distributed in the hope that it will be useful, but WITHOUT
+// ANY WARRANTY; without even the implied warranty of MERCHANTABILITY or FITNESS
+// FOR A PARTICULAR PURPOSE. See the GNU General Public License for more
+// details.
+//
+// You should have received a copy of the GNU General Public License along with
+// this program.  If not, see <https://www.gnu.org/licenses/>.
+
+//! The download cache every keg's winetricks shares through `W_CACHE`, so a
+//! verb like `vcrun2019` is downloaded once no matter how many kegs install
+//! it. Winetricks keeps one folder per verb.
+
+use std::{env, fs, io, path::PathBuf, time::SystemTime};
+
+use crate::disk_usage::directory_size;
+
+#[derive(Debug, Clone)]
+pub struct CacheEntry {
+    pub verb: String,
+    pub size: u64,
+    pub modified: Option<SystemTime>,
+}
+
+/// Where winetricks downloads go, following the XDG cache directory.
+pub fn cache_directory() -> PathBuf {
+    let cache_home_guess = PathBuf::from(
+        env::var("HOME").expect("User does not have $HOME directory set"),
+    )
+    .join(".cache");
+
+    env::var("XDG_CACHE_HOME")
+        .map(PathBuf::from)
+        .unwrap_or(cache_home_guess)
+        .join("kegtui/winetricks")
+}
+
+/// The cached verbs, largest first.
+pub fn entries() -> Vec<CacheEntry> {
+    let mut entries = fs::read_dir(cache_directory())
+        .map(|read_dir| {
+            read_dir
+                .flatten()
+                .filter(|entry| entry.path().is_dir())
+                .map(|entry| CacheEntry {
+                    verb: entry.file_name().to_string_lossy().into_owned(),
+                    size: directory_size(&entry.path()),
+                    modified: entry
+                        .metadata()
+                        .and_then(|metadata| metadata.modified())
+                        .ok(),
+                })
+                .collect::<Vec<_>>()
+        })
+        .unwrap_or_default();
+    entries.sort_by(|a, b| b.size.cmp(&a.size).then(a.verb.cmp(&b.verb)));
+    entries
+}
+
+/// Deletes the downloads for `verb`.
+pub fn remove(verb: &str) -> io::Result<()> {
+    fs::remove_dir_all(cache_directory().join(verb))
+}
+
+/// Deletes every download.
+pub fn clear() -> io::Result<()> {
+    match fs::remove_dir_all(cache_directory()) {
+        Err(error) if error.kind() == io::ErrorKind::NotFound => Ok(()),
+        result => result,
+    }
+}
@@ -0,0 +1,174 @@
+// Copyright (C) 2026 Ethan Uppal.
+//
+// This program is free software: you can redistribute it and/or modify it under
+// the terms of the GNU General Public License as published by the Free Software
+// Foundation, version 3 of the License only.
+//
+// This program is distributed in the hope that it will be useful, but WITHOUT
+// ANY WARRANTY; without even the implied warranty of MERCHANTABILITY or FITNESS
+// FOR A PARTICULAR PURPOSE. See the GNU General Public License for more
+// details.
+//
+// You should have received a copy of the GNU General Public License along with
+// this program.  If not, see <https://www.gnu.org/licenses/>.
+
+//! The verbs winetricks can install, listed by running winetricks once and
+//! then cached, since listing them takes a while.
+
+use std::{
+    collections::BTreeMap, ffi::OsString, fs, path::Path, process::Command,
+};
+
+use color_eyre::{Result, eyre::Context};
+
+use crate::{
+    fuzzy,
+    runner::CommandRunner,
+    setup::{self, WINETRICKS_SCRIPT},
+};
+
+/// One verb per line, as its category, name, and description separated by
+/// tabs.
+pub const CACHE_FILE: &str = "/tmp/kegtui_winetricks_catalog.tsv";
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Category {
+    App,
+    Dll,
+    Font,
+    Setting,
+}
+
+impl Category {
+    pub const ALL: [Self; 4] =
+        [Self::App, Self::Dll, Self::Font, Self::Setting];
+
+    pub fn name(self) -> &'static str {
+        match self {
+            Category::App => "app",
+            Category::Dll => "dll",
+            Category::Font => "font",
+            Category::Setting => "setting",
+        }
+    }
+
+    /// The argument winetricks lists the category's verbs for.
+    fn list_argument(self) -> &'static str {
+        match self {
+            Category::App => "apps",
+            Category::Dll => "dlls",
+            Category::Font => "fonts",
+            Category::Setting => "settings",
+        }
+    }
+
+    fn from_name(name: &str) -> Option<Self> {
+        Self::ALL
+            .into_iter()
+            .find(|category| category.name() == name)
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct Verb {
+    pub category: Category,
+    pub name: String,
+    pub description: String,
+}
+
+impl Verb {
+    /// Scores how well the verb matches `query`, where lower is better, or
+    /// `None` if it doesn't match. The name is matched fuzzily, e.g., "vcr"
+    /// finds `vcrun2019`, and otherwise the description must contain the
+    /// query, ignoring case, so that, e.g., "visual c++" finds the `vcrun`
+    /// verbs, but after every verb whose name matches.
+    pub fn search_score(&self, query: &str) -> Option<usize> {
+        if let Some(score) = fuzzy::score(query, &self.name) {
+            return Some(score);
+        }
+        self.description
+            .to_lowercase()
+            .contains(&query.to_lowercase())
+            .then_some(usize::MAX)
+    }
+}
+
+/// Parses the output of `winetricks <category> list`, where each line is a
+/// verb followed by its description.
+fn parse_list(category: Category, output: &str) -> Vec<Verb> {
+    output
+        .lines()
+        .filter_map(|line| line.split_once(' '))
+        .map(|(name, description)| Verb {
+            category,
+            name: name.trim().to_owned(),
+            description: description.trim().to_owned(),
+        })
+        .filter(|verb| !verb.name.is_empty())
+        .collect()
+}
+
+fn read_cache() -> Option<Vec<Verb>> {
+    let contents = fs::read_to_string(CACHE_FILE).ok()?;
+    contents
+        .lines()
+        .map(|line| {
+            let mut fields = line.splitn(3, '\t');
+            Some(Verb {
+                category: Category::from_name(fields.next()?)?,
+                name: fields.next()?.to_owned(),
+                description: fields.next().unwrap_or_default().to_owned(),
+            })
+        })
+        .collect()
+}
+
+fn write_cache(verbs: &[Verb]) -> Result<()> {
+    let contents = verbs
+        .iter()
+        .map(|verb| {
+            format!(
+                "{}\t{}\t{}\n",
+                verb.category.name(),
+                verb.name,
+                verb.description.replace(['\t', '\n'], " ")
+            )
+        })
+        .collect::<String>();
+    fs::write(CACHE_FILE, contents)
+        .with_context(|| format!("Failed to write {CACHE_FILE}"))
+}
+
+/// Lists every verb, from the cache if there is one, or else by running
+/// winetricks with `environment`, fetching it first if needed. Prints
+/// nothing, so it can run while the TUI owns the terminal.
+pub fn load(
+    runner: &dyn CommandRunner,
+    environment: &BTreeMap<String, OsString>,
+) -> Result<Vec<Verb>> {
+    if let Some(verbs) = read_cache() {
+        return Ok(verbs);
+    }
+    if !Path::new(WINETRICKS_SCRIPT).is_file() {
+        setup::fetch_winetricks_quietly(runner)?;
+    }
+    let mut verbs = vec![];
+    for category in Category::ALL {
+        let list_argument = category.list_argument();
+        let mut command = Command::new("/bin/sh");
+        command.env_clear().envs(environment).args([
+            WINETRICKS_SCRIPT,
+            list_argument,
+            "list",
+        ]);
+        let output = runner.output(&mut command).with_context(|| {
+            format!("Failed to list winetricks {list_argument}")
+        })?;
+        verbs.extend(parse_list(
+            category,
+            &String::from_utf8_lossy(&output.stdout),
+        ));
+    }
+    write_cache(&verbs)?;
+    Ok(verbs)
+}
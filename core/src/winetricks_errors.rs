@@ -0,0 +1,89 @@
+// Copyright (C) 2026 Ethan Uppal.
+//
+// This program is free software: you can redistribute it and/or modify it under
+// the terms of the GNU General Public License as published by the Free Software
+// Foundation, version 3 of the License only.
+//
+// This program is distributed in the hope that it will be useful, but WITHOUT
+// ANY WARRANTY; without even the implied warranty of MERCHANTABILITY or FITNESS
+// FOR A PARTICULAR PURPOSE. See the GNU General Public License for more
+// details.
+//
+// You should have received a copy of the GNU General Public License along with
+// this program.  If not, see <https://www.gnu.org/licenses/>.
+
+//! Boils winetricks' output down to the one line that explains why it
+//! failed, since WSS-winetricks often exits successfully either way.
+
+/// Winetricks' output is only read this far from the end.
+const TAIL_LINES: usize = 200;
+
+/// Lines that only frame a winetricks error message.
+fn is_noise(line: &str) -> bool {
+    line.is_empty()
+        || line.chars().all(|c| c == '-' || c == '=')
+        || line.starts_with("Executing ")
+        || line.starts_with("Using winetricks")
+}
+
+/// The file named by the last `Downloading <url> to <path>` line before
+/// `index`.
+fn last_download(lines: &[&str], index: usize) -> Option<String> {
+    lines[..index].iter().rev().find_map(|line| {
+        let url = line.strip_prefix("Downloading ")?.split(' ').next()?;
+        Some(url.rsplit('/').next().unwrap_or(url).to_owned())
+    })
+}
+
+/// Explains why winetricks failed, or returns `None` if its output shows no
+/// failure. Only errors winetricks is known to print are reported when it
+/// `exited_successfully`; otherwise its last message is the best guess.
+pub fn distill(output: &str, exited_successfully: bool) -> Option<String> {
+    let lines = output.lines().map(str::trim).collect::<Vec<_>>();
+    let lines = &lines[lines.len().saturating_sub(TAIL_LINES)..];
+
+    for (index, line) in lines.iter().enumerate().rev() {
+        if line.contains("sha256sum mismatch") {
+            return Some(match last_download(lines, index) {
+                Some(file) => format!(
+                    "sha256 mismatch downloading {file}; the download changed upstream or is corrupt"
+                ),
+                None => "sha256 mismatch on a download".to_owned(),
+            });
+        }
+        if let Some(rest) = line
+            .split_once("This package (")
+            .map(|(_, rest)| rest)
+            .filter(|_| line.contains("64-bit"))
+        {
+            let verb = rest.split(')').next().unwrap_or(rest);
+            return Some(format!("{verb} requires a 32-bit (win32) prefix"));
+        }
+        if let Some(verb) = line
+            .strip_prefix("winetricks: ")
+            .unwrap_or(line)
+            .strip_prefix("Unknown arg ")
+        {
+            return Some(format!("Unknown verb {verb}"));
+        }
+        if line.starts_with("curl: (") || line.starts_with("wget: ") {
+            return Some(format!("Download failed: {line}"));
+        }
+        if let Some(rest) = line.strip_prefix("Note: command ")
+            && let Some((command, status)) =
+                rest.split_once(" returned status ")
+        {
+            let status = status.trim_end_matches(". Aborting.");
+            return Some(format!("{command} failed with status {status}"));
+        }
+    }
+
+    if exited_successfully {
+        return None;
+    }
+    lines
+        .iter()
+        .rev()
+        .find(|line| !is_noise(line))
+        .map(|line| line.to_string())
+}
@@ -0,0 +1,553 @@
+// Copyright (C) 2026 Ethan Uppal.
+//
+// This program is free software: you can redistribute it and/or modify it under
+// the terms of the GNU General Public License as published by the Free Software
+// Foundation, version 3 of the License only.
+//
+// This program is distributed in the hope that it will be useful, but WITHOUT
+// ANY WARRANTY; without even the implied warranty of MERCHANTABILITY or FITNESS
+// FOR A PARTICULAR PURPOSE. See the GNU General Public License for more
+// details.
+//
+// You should have received a copy of the GNU General Public License along with
+// this program.  If not, see <https://www.gnu.org/licenses/>.
+
+use std::{
+    collections::HashMap,
+    fs,
+    path::{Path, PathBuf},
+    process::Command,
+    sync::{
+        Arc, RwLock,
+        mpsc::{self, RecvTimeoutError},
+    },
+    thread::{self, JoinHandle},
+    time::{Duration, Instant, SystemTime},
+};
+
+use crate::{
+    app::AsyncState,
+    app_config::{AppConfig, KegColumn},
+    checks,
+    disk_usage::directory_size,
+    keg::{Engine, Keg, WineVersion, Wrapper, WrapperFlavor},
+    processes,
+    runner::CommandRunner,
+    translation_layers::inspect_engine,
+    winetricks_cache,
+};
+
+/// How soon to retry a task whose results couldn't be published because the
+/// UI was holding the state.
+const RETRY_DELAY: Duration = Duration::from_millis(200);
+
+/// Run as soon as a scan finds a keg it hadn't seen, e.g., one just created
+/// or imported, instead of waiting out their periods.
+const NEW_KEG_TASKS: [WorkerTask; 3] = [
+    WorkerTask::ReadWineVersions,
+    WorkerTask::MeasureKegs,
+    WorkerTask::InspectEngines,
+];
+
+/// How long an on-demand task waits to be triggered before it runs anyway.
+const ON_DEMAND_IDLE: Duration = Duration::from_secs(60 * 60);
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum WorkerTask {
+    ScanKegs,
+    /// Scans both engines and wrappers.
+    ScanEngines,
+    /// Checks for Homebrew and Kegworks, including Kegworks' version.
+    RunChecks,
+    /// Asks each keg's Wine for its version.
+    ReadWineVersions,
+    /// Adds up each keg's size on disk, if the kegs list shows it.
+    MeasureKegs,
+    /// Finds the translation layers bundled with each keg's engine.
+    InspectEngines,
+    /// Lists the processes running from each keg.
+    ListProcesses,
+    /// Checks whether github.com can be reached, for actions that download.
+    CheckNetwork,
+    /// Adds up what winetricks has downloaded for each verb.
+    MeasureWinetricksCache,
+    /// Finds the crash dumps in each keg's prefix and logs.
+    FindCrashDumps,
+}
+
+impl WorkerTask {
+    pub const ALL: [WorkerTask; 10] = [
+        WorkerTask::ScanKegs,
+        WorkerTask::ScanEngines,
+        WorkerTask::RunChecks,
+        WorkerTask::ReadWineVersions,
+        WorkerTask::MeasureKegs,
+        WorkerTask::InspectEngines,
+        WorkerTask::ListProcesses,
+        WorkerTask::CheckNetwork,
+        WorkerTask::MeasureWinetricksCache,
+        WorkerTask::FindCrashDumps,
+    ];
+
+    fn period(self) -> Duration {
+        match self {
+            WorkerTask::ScanKegs => Duration::from_secs(2),
+            WorkerTask::ScanEngines => Duration::from_secs(30),
+            WorkerTask::RunChecks => Duration::from_secs(5 * 60),
+            WorkerTask::ReadWineVersions => Duration::from_secs(60),
+            WorkerTask::MeasureKegs => Duration::from_secs(10 * 60),
+            WorkerTask::InspectEngines => Duration::from_secs(10 * 60),
+            WorkerTask::ListProcesses => Duration::from_secs(1),
+            WorkerTask::CheckNetwork => Duration::from_secs(60),
+            WorkerTask::MeasureWinetricksCache => Duration::from_secs(5),
+            WorkerTask::FindCrashDumps => Duration::from_secs(10),
+        }
+    }
+
+    /// Whether the task runs only while it's being triggered, and then at
+    /// most once per period, e.g., while a view showing its results is open.
+    fn is_on_demand(self) -> bool {
+        matches!(
+            self,
+            WorkerTask::ListProcesses
+                | WorkerTask::CheckNetwork
+                | WorkerTask::MeasureWinetricksCache
+                | WorkerTask::FindCrashDumps
+        )
+    }
+
+    /// Whether the task runs at startup and then every so often even when
+    /// nothing triggers it. The network check doesn't, so kegtui only asks
+    /// github.com when an action that downloads is highlighted or chosen.
+    fn runs_unprompted(self) -> bool {
+        self != WorkerTask::CheckNetwork
+    }
+}
+
+enum WorkerMessage {
+    Quit,
+    Run(WorkerTask),
+    AddKegSearchPath(PathBuf),
+}
+
+/// Spreads out periodic tasks by up to 10% of their period so they don't all
+/// hit the disk on the same tick.
+struct Jitter(u64);
+
+impl Jitter {
+    fn new() -> Self {
+        let seed = SystemTime::now()
+            .duration_since(SystemTime::UNIX_EPOCH)
+            .unwrap_or_default()
+            .as_nanos() as u64;
+        Self(seed | 1)
+    }
+
+    fn apply(&mut self, period: Duration) -> Duration {
+        // xorshift64
+        self.0 ^= self.0 << 13;
+        self.0 ^= self.0 >> 7;
+        self.0 ^= self.0 << 17;
+        let spread = period / 10;
+        let offset =
+            Duration::from_nanos(self.0 % (2 * spread.as_nanos() as u64 + 1));
+        period - spread + offset
+    }
+}
+
+/// Controls the worker thread. Dropping it stops the worker and waits for it
+/// to finish.
+pub struct WorkerHandle {
+    sender: mpsc::Sender<WorkerMessage>,
+    thread: Option<JoinHandle<()>>,
+}
+
+impl WorkerHandle {
+    /// A handle to no worker, for driving the TUI with a fixed
+    /// [`AsyncState`]. Triggered tasks never run.
+    pub fn detached() -> Self {
+        let (sender, _) = mpsc::channel();
+        Self {
+            sender,
+            thread: None,
+        }
+    }
+
+    /// Runs `task` as soon as possible instead of waiting for its next
+    /// scheduled run.
+    pub fn trigger(&self, task: WorkerTask) {
+        let _ = self.sender.send(WorkerMessage::Run(task));
+    }
+
+    /// Scans `path` for kegs too, starting now.
+    pub fn add_keg_search_path(&self, path: PathBuf) {
+        let _ = self.sender.send(WorkerMessage::AddKegSearchPath(path));
+    }
+
+    /// Stops the worker, waking it if it is waiting for its next task, and
+    /// blocks until any task in progress has finished writing to the state.
+    pub fn shutdown(mut self) {
+        self.stop_and_join();
+    }
+
+    fn stop_and_join(&mut self) {
+        let _ = self.sender.send(WorkerMessage::Quit);
+        if let Some(thread) = self.thread.take() {
+            let _ = thread.join();
+        }
+    }
+}
+
+impl Drop for WorkerHandle {
+    fn drop(&mut self) {
+        self.stop_and_join();
+    }
+}
+
+/// Reads the entries of each search path, which are expected to already be
+/// expanded via [`AppConfig::expand_paths`].
+fn read_search_paths(
+    search_paths: &[PathBuf],
+) -> impl Iterator<Item = fs::DirEntry> {
+    search_paths
+        .iter()
+        .filter_map(|enclosing_location| fs::read_dir(enclosing_location).ok())
+        .flat_map(|read_dir| read_dir.flatten())
+}
+
+pub fn scan_kegs(config: &AppConfig) -> impl Iterator<Item = Keg> {
+    read_search_paths(&config.keg_search_paths).filter_map(|entry| {
+        WrapperFlavor::detect(&entry.path())
+            .is_some()
+            .then(|| Keg::from_path(&entry.path()))
+    })
+}
+
+pub fn scan_engines(config: &AppConfig) -> impl Iterator<Item = Engine> {
+    read_search_paths(&config.engine_search_paths).filter_map(|entry| {
+        entry
+            .path()
+            .file_name()
+            .and_then(|name| name.to_str())
+            .is_some_and(|name| {
+                name.ends_with(".tar.7z") || name.ends_with(".tar.xz")
+            })
+            .then(|| Engine { path: entry.path() })
+    })
+}
+
+pub fn scan_wrappers(config: &AppConfig) -> impl Iterator<Item = Wrapper> {
+    read_search_paths(&config.wrapper_search_paths).filter_map(|entry| {
+        entry
+            .path()
+            .file_name()
+            .and_then(|name| name.to_str())
+            .is_some_and(|name| name.ends_with(".app"))
+            .then(|| Wrapper::from_path(&entry.path()))
+    })
+}
+
+fn read_wine_version(
+    runner: &dyn CommandRunner,
+    wine: &Path,
+) -> Option<WineVersion> {
+    let modified = fs::metadata(wine).and_then(|metadata| metadata.modified());
+    let output = runner.output(Command::new(wine).arg("--version")).ok()?;
+    let version = String::from_utf8_lossy(&output.stdout).trim().to_owned();
+    (output.status.success() && !version.is_empty()).then(|| WineVersion {
+        version,
+        modified: modified.unwrap_or(SystemTime::UNIX_EPOCH),
+    })
+}
+
+/// Runs `task` and publishes its results, returning `false` if the state was
+/// busy and the task should be retried. Tasks that should run right away
+/// because of the results are pushed onto `follow_ups`.
+fn run_task(
+    task: WorkerTask,
+    config: &AppConfig,
+    runner: &dyn CommandRunner,
+    async_state: &RwLock<AsyncState>,
+    follow_ups: &mut Vec<WorkerTask>,
+) -> bool {
+    let start = Instant::now();
+    match task {
+        WorkerTask::ScanKegs => {
+            let kegs = scan_kegs(config).collect::<Vec<_>>();
+            let Ok(mut lock) = async_state.try_write() else {
+                return false;
+            };
+            let has_new_keg = kegs.iter().any(|keg| {
+                !lock
+                    .kegs
+                    .iter()
+                    .any(|known| known.bundle_path() == keg.bundle_path())
+            });
+            if has_new_keg {
+                follow_ups.extend(NEW_KEG_TASKS);
+            }
+            lock.kegs = kegs;
+            lock.last_task_durations.insert(task, start.elapsed());
+        }
+        WorkerTask::ScanEngines => {
+            let engines = scan_engines(config).collect();
+            let wrappers = scan_wrappers(config).collect();
+            let Ok(mut lock) = async_state.try_write() else {
+                return false;
+            };
+            lock.engines = engines;
+            lock.wrappers = wrappers;
+            lock.last_task_durations.insert(task, start.elapsed());
+        }
+        WorkerTask::RunChecks => {
+            let is_brew_installed = checks::is_brew_installed(runner);
+            let is_kegworks_installed = checks::is_kegworks_installed();
+            let kegworks_version = checks::kegworks_version();
+            let Ok(mut lock) = async_state.try_write() else {
+                return false;
+            };
+            lock.is_brew_installed = is_brew_installed;
+            lock.is_kegworks_installed = is_kegworks_installed;
+            lock.kegworks_version = kegworks_version;
+            lock.last_task_durations.insert(task, start.elapsed());
+        }
+        WorkerTask::ReadWineVersions => {
+            let Ok(lock) = async_state.try_read() else {
+                return false;
+            };
+            let wines = lock
+                .kegs
+                .iter()
+                .map(|keg| keg.wine_executable())
+                .collect::<Vec<_>>();
+            let mut wine_versions = lock.wine_versions.clone();
+            drop(lock);
+
+            // Starting Wine is slow, so only ask the ones that changed.
+            let mut updated_wine_versions = HashMap::new();
+            for wine in wines {
+                let modified = fs::metadata(&wine)
+                    .and_then(|metadata| metadata.modified())
+                    .ok();
+                let wine_version = match wine_versions.remove(&wine) {
+                    Some(wine_version)
+                        if Some(wine_version.modified) == modified =>
+                    {
+                        Some(wine_version)
+                    }
+                    _ => read_wine_version(runner, &wine),
+                };
+                if let Some(wine_version) = wine_version {
+                    updated_wine_versions.insert(wine, wine_version);
+                }
+            }
+
+            let Ok(mut lock) = async_state.try_write() else {
+                return false;
+            };
+            lock.wine_versions = updated_wine_versions;
+            lock.last_task_durations.insert(task, start.elapsed());
+        }
+        WorkerTask::MeasureKegs => {
+            if !config.keg_list_columns.contains(&KegColumn::Size) {
+                return true;
+            }
+            let Ok(lock) = async_state.try_read() else {
+                return false;
+            };
+            let bundles = lock
+                .kegs
+                .iter()
+                .map(|keg| keg.bundle_path())
+                .collect::<Vec<_>>();
+            drop(lock);
+
+            let keg_sizes = bundles
+                .into_iter()
+                .map(|bundle| {
+                    let size = directory_size(&bundle);
+                    (bundle, size)
+                })
+                .collect();
+
+            let Ok(mut lock) = async_state.try_write() else {
+                return false;
+            };
+            lock.keg_sizes = keg_sizes;
+            lock.last_task_durations.insert(task, start.elapsed());
+        }
+        WorkerTask::InspectEngines => {
+            let Ok(lock) = async_state.try_read() else {
+                return false;
+            };
+            let engines = lock
+                .kegs
+                .iter()
+                .map(|keg| (keg.bundle_path(), keg.wine_prefix.clone()))
+                .collect::<Vec<_>>();
+            drop(lock);
+
+            let translation_layers = engines
+                .into_iter()
+                .map(|(bundle, wine_prefix)| {
+                    let engine = wine_prefix.parent().unwrap_or(&wine_prefix);
+                    (bundle, inspect_engine(engine))
+                })
+                .collect();
+
+            let Ok(mut lock) = async_state.try_write() else {
+                return false;
+            };
+            lock.translation_layers = translation_layers;
+            lock.last_task_durations.insert(task, start.elapsed());
+        }
+        WorkerTask::ListProcesses => {
+            let Ok(lock) = async_state.try_read() else {
+                return false;
+            };
+            let bundles = lock
+                .kegs
+                .iter()
+                .map(|keg| keg.bundle_path())
+                .collect::<Vec<_>>();
+            drop(lock);
+
+            let keg_processes =
+                processes::by_keg(&processes::list(runner), &bundles);
+
+            let Ok(mut lock) = async_state.try_write() else {
+                return false;
+            };
+            lock.keg_processes = keg_processes;
+            lock.last_task_durations.insert(task, start.elapsed());
+        }
+        WorkerTask::CheckNetwork => {
+            let is_network_reachable = checks::is_network_reachable(runner);
+            let Ok(mut lock) = async_state.try_write() else {
+                return false;
+            };
+            lock.is_network_reachable = Some(is_network_reachable);
+            lock.last_task_durations.insert(task, start.elapsed());
+        }
+        WorkerTask::MeasureWinetricksCache => {
+            let entries = winetricks_cache::entries();
+            let Ok(mut lock) = async_state.try_write() else {
+                return false;
+            };
+            lock.winetricks_cache = Some(entries);
+            lock.last_task_durations.insert(task, start.elapsed());
+        }
+        WorkerTask::FindCrashDumps => {
+            let Ok(lock) = async_state.try_read() else {
+                return false;
+            };
+            let kegs = lock.kegs.clone();
+            drop(lock);
+
+            let crash_dumps = kegs
+                .iter()
+                .map(|keg| (keg.bundle_path(), keg.find_crash_dumps()))
+                .collect();
+
+            let Ok(mut lock) = async_state.try_write() else {
+                return false;
+            };
+            lock.crash_dumps = crash_dumps;
+            lock.last_task_durations.insert(task, start.elapsed());
+        }
+    }
+    true
+}
+
+pub fn spawn_worker(
+    mut config: Arc<AppConfig>,
+    runner: Arc<dyn CommandRunner>,
+) -> (Arc<RwLock<AsyncState>>, WorkerHandle) {
+    let async_state = Arc::new(RwLock::new(AsyncState::default()));
+
+    let (sender, receiver) = mpsc::channel();
+
+    let thread = {
+        let async_state = async_state.clone();
+        thread::spawn(move || {
+            let mut jitter = Jitter::new();
+            let mut schedule = WorkerTask::ALL
+                .map(|task| (task, task.runs_unprompted().then(Instant::now)));
+            // When each on-demand task last ran, so triggering it again waits
+            // out its period.
+            let mut last_runs = HashMap::new();
+            let mut follow_ups = vec![];
+            loop {
+                let now = Instant::now();
+                for (task, due) in &mut schedule {
+                    if due.is_some_and(|due| due <= now) {
+                        *due = if !run_task(
+                            *task,
+                            &config,
+                            &*runner,
+                            &async_state,
+                            &mut follow_ups,
+                        ) {
+                            Some(now + RETRY_DELAY)
+                        } else if task.is_on_demand() {
+                            last_runs.insert(*task, now);
+                            task.runs_unprompted().then(|| now + ON_DEMAND_IDLE)
+                        } else {
+                            Some(now + jitter.apply(task.period()))
+                        };
+                    }
+                }
+
+                for (scheduled_task, due) in &mut schedule {
+                    if follow_ups.contains(scheduled_task) {
+                        *due = Some(Instant::now());
+                    }
+                }
+                follow_ups.clear();
+
+                let next_due = schedule
+                    .iter()
+                    .filter_map(|(_, due)| *due)
+                    .min()
+                    .unwrap_or(now);
+                match receiver.recv_timeout(
+                    next_due.saturating_duration_since(Instant::now()),
+                ) {
+                    Ok(WorkerMessage::Quit)
+                    | Err(RecvTimeoutError::Disconnected) => break,
+                    Ok(WorkerMessage::Run(task)) => {
+                        let mut next_run = Instant::now();
+                        if let Some(last_run) = last_runs.get(&task) {
+                            next_run = next_run.max(*last_run + task.period());
+                        }
+                        for (scheduled_task, due) in &mut schedule {
+                            if *scheduled_task == task {
+                                *due =
+                                    Some(due.map_or(next_run, |due| {
+                                        due.min(next_run)
+                                    }));
+                            }
+                        }
+                    }
+                    Ok(WorkerMessage::AddKegSearchPath(path)) => {
+                        Arc::make_mut(&mut config).keg_search_paths.push(path);
+                        for (scheduled_task, due) in &mut schedule {
+                            if *scheduled_task == WorkerTask::ScanKegs {
+                                *due = Some(Instant::now());
+                            }
+                        }
+                    }
+                    Err(RecvTimeoutError::Timeout) => {}
+                }
+            }
+        })
+    };
+
+    (
+        async_state,
+        WorkerHandle {
+            sender,
+            thread: Some(thread),
+        },
+    )
+}
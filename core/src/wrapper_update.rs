@@ -0,0 +1,214 @@
+// Copyright (C) 2026 Ethan Uppal.
+//
+// This program is free software: you can redistribute it and/or modify it under
+// the terms of the GNU General Public License as published by the Free Software
+// Foundation, version 3 of the License only.
+//
+// This program is distributed in the hope that it will be useful, but WITHOUT
+// ANY WARRANTY; without even the implied warranty of MERCHANTABILITY or FITNESS
+// FOR A PARTICULAR PURPOSE. See the GNU General Public License for more
+// details.
+//
+// You should have received a copy of the GNU General Public License along with
+// this program.  If not, see <https://www.gnu.org/licenses/>.
+
+//! Updating an existing keg's wrapper, i.e., its launcher, frameworks, and
+//! config app, from a newer template while keeping its prefix, engine, and
+//! config.
+
+use std::{
+    fs,
+    os::unix,
+    path::{Path, PathBuf},
+};
+
+use color_eyre::{
+    Result,
+    eyre::{Context, bail},
+};
+use copy_dir::copy_dir;
+use walkdir::WalkDir;
+
+use crate::{snapshots, tasks::KegLock};
+
+/// Where, in the keg's `Contents`, the new wrapper is copied before it's
+/// swapped in.
+const STAGING_DIRECTORY: &str = "kegtui-wrapper-update";
+
+/// What a wrapper update never replaces, relative to `Contents`: the keg's
+/// config, icon and resources, prefix, engine, and logs.
+const PRESERVED: [&str; 6] = [
+    "Info.plist",
+    "Resources",
+    "SharedSupport/prefix",
+    "SharedSupport/wine",
+    "SharedSupport/Logs",
+    "Logs",
+];
+
+/// The entries of `template`'s `Contents` that an update copies over the
+/// keg's, relative to `Contents`. `SharedSupport` is replaced entry by entry,
+/// since it holds the prefix and engine too.
+pub fn replaced_entries(template: &Path) -> Result<Vec<PathBuf>> {
+    let contents = template.join("Contents");
+    let mut entries = vec![];
+    for directory in ["", "SharedSupport"] {
+        let Ok(read_dir) = fs::read_dir(contents.join(directory)) else {
+            continue;
+        };
+        for entry in read_dir {
+            let entry = Path::new(directory).join(entry?.file_name());
+            if entry != Path::new("SharedSupport")
+                && !PRESERVED
+                    .iter()
+                    .any(|preserved| entry == Path::new(preserved))
+            {
+                entries.push(entry);
+            }
+        }
+    }
+    entries.sort();
+    if entries.is_empty() {
+        bail!("{} has no wrapper files to update from", template.display());
+    }
+    Ok(entries)
+}
+
+/// Adds the keys of the template's `Info.plist` that the keg's lacks, so
+/// settings a newer wrapper reads have their defaults, without changing any
+/// the keg already has.
+fn add_missing_plist_keys(bundle: &Path, template: &Path) -> Result<()> {
+    let info_plist_path = bundle.join("Contents/Info.plist");
+    let Some(mut info) = plist::Value::from_file(&info_plist_path)
+        .context("Failed to read the keg's Info.plist")?
+        .into_dictionary()
+    else {
+        bail!("The keg's Info.plist isn't a dictionary");
+    };
+    let Some(template_info) =
+        plist::Value::from_file(template.join("Contents/Info.plist"))
+            .context("Failed to read the template's Info.plist")?
+            .into_dictionary()
+    else {
+        bail!("The template's Info.plist isn't a dictionary");
+    };
+    let mut is_changed = false;
+    for (key, value) in template_info {
+        if !info.contains_key(&key) {
+            info.insert(key, value);
+            is_changed = true;
+        }
+    }
+    if is_changed {
+        plist::Value::Dictionary(info)
+            .to_file_xml(&info_plist_path)
+            .context("Failed to write the keg's Info.plist")?;
+    }
+    Ok(())
+}
+
+/// Snapshots the wrapper files of the keg at `bundle` and replaces them with
+/// `template`'s, returning the snapshot's path.
+pub fn update_wrapper(
+    keg_name: &str,
+    bundle: &Path,
+    template: &Path,
+) -> Result<PathBuf> {
+    let Some(_keg_lock) = KegLock::try_acquire(bundle) else {
+        bail!("Something else is changing this keg; try again once it's done");
+    };
+    let entries = replaced_entries(template)?;
+    let snapshot = snapshots::snapshot_wrapper(keg_name, bundle, &entries)
+        .context("Failed to snapshot the current wrapper")?;
+
+    // Everything is copied beside the keg's wrapper first, so a failed copy
+    // leaves the keg as it was.
+    let staging = bundle.join("Contents").join(STAGING_DIRECTORY);
+    stage_entries(template, &staging, &entries).context(
+        "Failed to copy the new wrapper in; the keg's wrapper is unchanged",
+    )?;
+    let swapped = swap_entries(&staging, bundle, &entries);
+    let _ = fs::remove_dir_all(&staging);
+    if let Err(error) = swapped {
+        return Err(
+            match snapshots::restore_wrapper(bundle, &snapshot, &entries) {
+                Ok(()) => error.wrap_err(
+                    "Failed to swap the new wrapper in; restored the old one",
+                ),
+                Err(restore_error) => error.wrap_err(format!(
+                    "Failed to swap the new wrapper in or to restore the old one ({restore_error}); it's in {}",
+                    snapshot.display()
+                )),
+            },
+        );
+    }
+    add_missing_plist_keys(bundle, template)?;
+    Ok(snapshot)
+}
+
+/// Copies each of `entries` in `template`'s `Contents` to `staging`, clearing
+/// the quarantine a downloaded template leaves on them. Nothing is left in
+/// `staging` if any of them fails to copy.
+fn stage_entries(
+    template: &Path,
+    staging: &Path,
+    entries: &[PathBuf],
+) -> Result<()> {
+    if staging.exists() {
+        fs::remove_dir_all(staging)?;
+    }
+    let result = entries.iter().try_for_each(|entry| -> Result<()> {
+        let source = template.join("Contents").join(entry);
+        let destination = staging.join(entry);
+        if let Some(parent) = destination.parent() {
+            fs::create_dir_all(parent)?;
+        }
+        let metadata = source.symlink_metadata()?;
+        if metadata.is_symlink() {
+            unix::fs::symlink(fs::read_link(&source)?, &destination)?;
+        } else if metadata.is_dir() {
+            let errors = copy_dir(&source, &destination)?;
+            if let Some(error) = errors.into_iter().next() {
+                return Err(error).with_context(|| {
+                    format!("Failed to copy part of {}", source.display())
+                });
+            }
+        } else {
+            fs::copy(&source, &destination)?;
+        }
+        for file in WalkDir::new(&destination).into_iter().flatten() {
+            let _ = xattrs::remove_xattr(file.path(), "com.apple.quarantine");
+        }
+        Ok(())
+    });
+    if result.is_err() {
+        let _ = fs::remove_dir_all(staging);
+    }
+    result
+}
+
+/// Replaces each of `entries` in `bundle`'s `Contents` with the copy in
+/// `staging`, which is on the same volume, so each swap is a rename.
+fn swap_entries(
+    staging: &Path,
+    bundle: &Path,
+    entries: &[PathBuf],
+) -> Result<()> {
+    let contents = bundle.join("Contents");
+    for entry in entries {
+        let destination = contents.join(entry);
+        if let Ok(metadata) = destination.symlink_metadata() {
+            if metadata.is_dir() {
+                fs::remove_dir_all(&destination)?;
+            } else {
+                fs::remove_file(&destination)?;
+            }
+        } else if let Some(parent) = destination.parent() {
+            fs::create_dir_all(parent)?;
+        }
+        fs::rename(staging.join(entry), &destination).with_context(|| {
+            format!("Failed to move {} into place", entry.display())
+        })?;
+    }
+    Ok(())
+}
@@ -0,0 +1,83 @@
+// Copyright (C) 2026 Ethan Uppal.
+//
+// This program is free software: you can redistribute it and/or modify it under
+// the terms of the GNU General Public License as published by the Free Software
+// Foundation, version 3 of the License only.
+//
+// This program is distributed in the hope that it will be useful, but WITHOUT
+// ANY WARRANTY; without even the implied warranty of MERCHANTABILITY or FITNESS
+// FOR A PARTICULAR PURPOSE. See the GNU General Public License for more
+// details.
+//
+// You should have received a copy of the GNU General Public License along with
+// this program.  If not, see <https://www.gnu.org/licenses/>.
+
+use std::{
+    env, fs,
+    path::{Path, PathBuf},
+    process,
+};
+
+use kegtui::{
+    engine_pruning::{self, KeepReason},
+    keg::{Engine, Keg},
+};
+
+fn directory(name: &str) -> PathBuf {
+    let directory = env::temp_dir()
+        .join(format!("kegtui-{}-engine-pruning", process::id()))
+        .join(name);
+    let _ = fs::remove_dir_all(&directory);
+    fs::create_dir_all(&directory).unwrap();
+    directory
+}
+
+fn engine(directory: &Path, name: &str) -> Engine {
+    let path = directory.join(format!("{name}.tar.xz"));
+    fs::write(&path, "engine").unwrap();
+    Engine { path }
+}
+
+fn keg(directory: &Path, name: &str, engine: Option<&str>) -> Keg {
+    let bundle = directory.join(format!("{name}.app"));
+    let wine = bundle.join("Contents/SharedSupport/wine");
+    fs::create_dir_all(&wine).unwrap();
+    if let Some(engine) = engine {
+        fs::write(wine.join("version"), engine).unwrap();
+    }
+    Keg::from_path(&bundle)
+}
+
+#[test]
+fn unused_engines_are_removed() {
+    let directory = directory("unused");
+    let engines = [
+        engine(&directory, "WS12WineA10.0_1"),
+        engine(&directory, "WS12WineB10.0_2"),
+    ];
+    let kegs = [keg(&directory, "Game", Some("WS12WineA10.0_1"))];
+    let plan = engine_pruning::plan(&engines, &kegs, 0);
+    assert_eq!(plan.kept.len(), 1);
+    assert!(matches!(plan.kept[0].1, KeepReason::UsedBy(_)));
+    assert_eq!(plan.removed.len(), 1);
+    assert_eq!(plan.removed[0].0.path, engines[1].path);
+}
+
+#[test]
+fn a_keg_with_an_unknown_engine_keeps_every_engine() {
+    let directory = directory("unknown");
+    let engines = [
+        engine(&directory, "WS12WineA10.0_1"),
+        engine(&directory, "WS12WineB10.0_2"),
+    ];
+    let kegs = [
+        keg(&directory, "Game", Some("WS12WineA10.0_1")),
+        keg(&directory, "Broken", None),
+    ];
+    let plan = engine_pruning::plan(&engines, &kegs, 0);
+    assert!(plan.removed.is_empty());
+    assert!(plan.kept.iter().any(|(engine, reason)| {
+        engine.path == engines[1].path
+            && matches!(reason, KeepReason::MaybeUsedBy(kegs) if kegs == &["Broken.app"])
+    }));
+}
@@ -0,0 +1,50 @@
+// Copyright (C) 2026 Ethan Uppal.
+//
+// This program is free software: you can redistribute it and/or modify it under
+// the terms of the GNU General Public License as published by the Free Software
+// Foundation, version 3 of the License only.
+//
+// This program is distributed in the hope that it will be useful, but WITHOUT
+// ANY WARRANTY; without even the implied warranty of MERCHANTABILITY or FITNESS
+// FOR A PARTICULAR PURPOSE. See the GNU General Public License for more
+// details.
+//
+// You should have received a copy of the GNU General Public License along with
+// this program.  If not, see <https://www.gnu.org/licenses/>.
+
+use std::env;
+
+use kegtui::app_config::expand_path;
+
+fn home() -> String {
+    env::var("HOME").expect("tests need $HOME")
+}
+
+#[test]
+fn a_leading_tilde_is_home() {
+    assert_eq!(expand_path("~"), home());
+    assert_eq!(expand_path("~/Kegs"), format!("{}/Kegs", home()));
+}
+
+#[test]
+fn a_tilde_elsewhere_is_literal() {
+    assert_eq!(expand_path("/Kegs/~"), "/Kegs/~");
+    assert_eq!(expand_path("~user/Kegs"), "~user/Kegs");
+}
+
+#[test]
+fn variables_are_expanded_with_or_without_braces() {
+    assert_eq!(expand_path("$HOME/Kegs"), format!("{}/Kegs", home()));
+    assert_eq!(expand_path("${HOME}Kegs"), format!("{}Kegs", home()));
+}
+
+#[test]
+fn unset_variables_are_left_as_written() {
+    assert_eq!(
+        expand_path("$KEGTUI_UNSET_FOR_TESTS/a/${KEGTUI_UNSET_FOR_TESTS}"),
+        "$KEGTUI_UNSET_FOR_TESTS/a/${KEGTUI_UNSET_FOR_TESTS}"
+    );
+    assert_eq!(expand_path("costs $5"), "costs $5");
+    assert_eq!(expand_path("a$"), "a$");
+    assert_eq!(expand_path("${unclosed"), "${unclosed");
+}
@@ -0,0 +1,100 @@
+// Copyright (C) 2026 Ethan Uppal.
+//
+// This program is free software: you can redistribute it and/or modify it under
+// the terms of the GNU General Public License as published by the Free Software
+// Foundation, version 3 of the License only.
+//
+// This program is distributed in the hope that it will be useful, but WITHOUT
+// ANY WARRANTY; without even the implied warranty of MERCHANTABILITY or FITNESS
+// FOR A PARTICULAR PURPOSE. See the GNU General Public License for more
+// details.
+//
+// You should have received a copy of the GNU General Public License along with
+// this program.  If not, see <https://www.gnu.org/licenses/>.
+
+use std::{env, fs, path::PathBuf, process};
+
+use kegtui::keg_plist::{KegPlist, missing_keys};
+
+const INFO_PLIST: &str = r#"<?xml version="1.0" encoding="UTF-8"?>
+<!DOCTYPE plist PUBLIC "-//Apple//DTD PLIST 1.0//EN" "http://www.apple.com/DTDs/PropertyList-1.0.dtd">
+<plist version="1.0">
+<dict>
+	<key>Program Flags</key>
+	<string>-old</string>
+	<key>kegtui Disable App Nap</key>
+	<true/>
+	<key>Some Future Date</key>
+	<date>2024-05-06T07:08:09Z</date>
+	<key>Some Future Setting</key>
+	<array>
+		<integer>1</integer>
+	</array>
+</dict>
+</plist>
+"#;
+
+fn info_plist(name: &str) -> PathBuf {
+    let path = env::temp_dir()
+        .join(format!("kegtui-{}-{name}-Info.plist", process::id()));
+    fs::write(&path, INFO_PLIST).expect("the plist should be writable");
+    path
+}
+
+#[test]
+fn saving_keeps_unmodeled_keys_as_they_were() {
+    let path = info_plist("unmodeled");
+    let before = plist::Value::from_file(&path).unwrap();
+    let mut keg_plist: KegPlist = plist::from_file(&path).unwrap();
+    keg_plist.program_flags = "-new".to_owned();
+    keg_plist.write_over(&path).unwrap();
+
+    let after = plist::Value::from_file(&path).unwrap();
+    fs::remove_file(&path).unwrap();
+    let (before, after) = (
+        before.as_dictionary().unwrap(),
+        after.as_dictionary().unwrap(),
+    );
+    assert!(after["Some Future Date"].as_date().is_some());
+    assert_eq!(after["Some Future Date"], before["Some Future Date"]);
+    assert_eq!(after["Some Future Setting"], before["Some Future Setting"]);
+    assert_eq!(after["Program Flags"].as_string(), Some("-new"));
+}
+
+#[test]
+fn saving_removes_modeled_keys_that_are_unset() {
+    let path = info_plist("unset");
+    let mut keg_plist: KegPlist = plist::from_file(&path).unwrap();
+    assert!(keg_plist.kegtui_disable_app_nap);
+    keg_plist.kegtui_disable_app_nap = false;
+    keg_plist.write_over(&path).unwrap();
+
+    let after = plist::Value::from_file(&path).unwrap();
+    fs::remove_file(&path).unwrap();
+    let after = after.as_dictionary().unwrap();
+    assert!(!after.contains_key("kegtui Disable App Nap"));
+    assert!(after.contains_key("Some Future Date"));
+}
+
+#[test]
+fn missing_keys_skips_present_and_optional_keys() {
+    let path = info_plist("missing");
+    let info = plist::Value::from_file(&path).unwrap();
+    fs::remove_file(&path).unwrap();
+
+    let missing = missing_keys(info.as_dictionary().unwrap());
+    assert!(!missing.is_empty());
+    assert!(!missing.iter().any(|key| key == "Program Flags"));
+    assert!(!missing.iter().any(|key| key.starts_with("kegtui ")));
+}
+
+#[test]
+fn saving_fills_in_missing_keys() {
+    let path = info_plist("filled");
+    let keg_plist: KegPlist = plist::from_file(&path).unwrap();
+    keg_plist.write_over(&path).unwrap();
+
+    let after = plist::Value::from_file(&path).unwrap();
+    fs::remove_file(&path).unwrap();
+    assert!(missing_keys(after.as_dictionary().unwrap()).is_empty());
+}
@@ -0,0 +1,169 @@
+// Copyright (C) 2026 Ethan Uppal.
+//
+// This program is free software: you can redistribute it and/or modify it under
+// the terms of the GNU General Public License as published by the Free Software
+// Foundation, version 3 of the License only.
+//
+// This program is distributed in the hope that it will be useful, but WITHOUT
+// ANY WARRANTY; without even the implied warranty of MERCHANTABILITY or FITNESS
+// FOR A PARTICULAR PURPOSE. See the GNU General Public License for more
+// details.
+//
+// You should have received a copy of the GNU General Public License along with
+// this program.  If not, see <https://www.gnu.org/licenses/>.
+
+use std::{
+    env,
+    fs::{self, File},
+    path::Path,
+    process,
+    sync::Once,
+    time::{Duration, SystemTime},
+};
+
+use kegtui::{
+    app_config::{MaintenanceConfig, app_state_directory},
+    keg::Keg,
+    maintenance, snapshots,
+};
+
+/// Keeps snapshots and backups out of the real state directory. Every test
+/// shares one, so keg names are unique per test.
+fn use_test_state_directory() {
+    static ONCE: Once = Once::new();
+    ONCE.call_once(|| {
+        let state_home =
+            env::temp_dir().join(format!("kegtui-{}-state", process::id()));
+        // SAFETY: set once, before any test reads the environment.
+        unsafe { env::set_var("XDG_STATE_HOME", state_home) };
+    });
+}
+
+fn keg(name: &str) -> Keg {
+    use_test_state_directory();
+    let bundle = env::temp_dir()
+        .join(format!("kegtui-{}-kegs", process::id()))
+        .join(format!("{name}.app"));
+    fs::create_dir_all(bundle.join("Contents/Logs")).unwrap();
+    Keg::from_path(&bundle)
+}
+
+/// Writes `path`, dated `age` seconds ago.
+fn write_aged(path: &Path, age: u64) {
+    fs::create_dir_all(path.parent().unwrap()).unwrap();
+    fs::write(path, "").unwrap();
+    File::options()
+        .write(true)
+        .open(path)
+        .unwrap()
+        .set_modified(SystemTime::now() - Duration::from_secs(age))
+        .unwrap();
+}
+
+fn config() -> MaintenanceConfig {
+    MaintenanceConfig {
+        max_log_size: 4,
+        keep_rotated_logs: 2,
+        keep_snapshots: 1,
+        keep_runs: 1,
+        keep_backups: 1,
+        clear_temp_files: false,
+        ..MaintenanceConfig::default()
+    }
+}
+
+fn file_names(directory: &Path) -> Vec<String> {
+    let mut names = fs::read_dir(directory)
+        .unwrap()
+        .map(|entry| entry.unwrap().file_name().to_string_lossy().into_owned())
+        .collect::<Vec<_>>();
+    names.sort();
+    names
+}
+
+#[test]
+fn large_logs_are_rotated_and_the_oldest_copy_dropped() {
+    let keg = keg("rotation");
+    let log = keg.log_directory.join("Wine.log");
+    fs::write(&log, "newest").unwrap();
+    fs::write(keg.log_directory.join("Wine.log.1"), "older").unwrap();
+    fs::write(keg.log_directory.join("Wine.log.2"), "oldest").unwrap();
+    fs::write(keg.log_directory.join("Small.log"), "ok").unwrap();
+
+    let report = maintenance::maintain_keg(&keg, &config(), false);
+
+    assert!(report.failures.is_empty(), "{:?}", report.failures);
+    assert_eq!(
+        file_names(&keg.log_directory),
+        ["Small.log", "Wine.log.1", "Wine.log.2"]
+    );
+    let read = |name| fs::read_to_string(keg.log_directory.join(name)).unwrap();
+    assert_eq!(read("Wine.log.1"), "newest");
+    assert_eq!(read("Wine.log.2"), "older");
+    assert_eq!(report.rotated_logs, [log]);
+}
+
+#[test]
+fn snapshots_are_pruned_per_kind() {
+    let keg = keg("snapshots");
+    let directory = snapshots::snapshot_directory(&keg.name);
+    // The engine snapshots are newer, but mustn't crowd out the prefix one.
+    write_aged(&directory.join("100-prefix.tar"), 300);
+    write_aged(&directory.join("200-engine.tar"), 200);
+    write_aged(&directory.join("300-engine.tar"), 100);
+
+    let report = maintenance::maintain_keg(&keg, &config(), false);
+
+    assert_eq!(report.removed_snapshots, [directory.join("200-engine.tar")]);
+    assert_eq!(file_names(&directory), ["100-prefix.tar", "300-engine.tar"]);
+}
+
+#[test]
+fn only_the_newest_runs_are_kept() {
+    let keg = keg("runs");
+    let runs = keg.log_directory.join("kegtui-runs");
+    fs::create_dir_all(&runs).unwrap();
+    fs::write(runs.join("100-launch.log"), "").unwrap();
+    fs::write(runs.join("200-winetricks.log"), "").unwrap();
+
+    let report = maintenance::maintain_keg(&keg, &config(), false);
+
+    assert_eq!(report.removed_runs, [runs.join("100-launch.log")]);
+    assert_eq!(file_names(&runs), ["200-winetricks.log"]);
+}
+
+#[test]
+fn temp_files_are_kept_while_the_keg_runs() {
+    let keg = keg("temp");
+    let temp = keg.c_drive.join("windows/temp");
+    fs::create_dir_all(temp.join("installer")).unwrap();
+    fs::write(temp.join("setup.tmp"), "").unwrap();
+    let config = MaintenanceConfig {
+        clear_temp_files: true,
+        ..config()
+    };
+
+    let report = maintenance::maintain_keg(&keg, &config, true);
+    assert!(report.skipped_temp_files);
+    assert_eq!(file_names(&temp), ["installer", "setup.tmp"]);
+
+    let report = maintenance::maintain_keg(&keg, &config, false);
+    assert_eq!(report.removed_temp_files, 2);
+    assert!(file_names(&temp).is_empty());
+}
+
+#[test]
+fn only_the_newest_backups_are_kept() {
+    use_test_state_directory();
+    let backups = app_state_directory().join("backups");
+    let older = backups.join("100-Old.app");
+    let newer = backups.join("200-New.app");
+    fs::create_dir_all(older.join("Contents")).unwrap();
+    fs::create_dir_all(newer.join("Contents")).unwrap();
+
+    let report = maintenance::prune_backups(&config());
+
+    assert!(!older.exists());
+    assert!(newer.exists());
+    assert_eq!(report.removed_backups, [older]);
+}
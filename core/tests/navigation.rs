@@ -0,0 +1,36 @@
+// Copyright (C) 2026 Ethan Uppal.
+//
+// This program is free software: you can redistribute it and/or modify it under
+// the terms of the GNU General Public License as published by the Free Software
+// Foundation, version 3 of the License only.
+//
+// This program is distributed in the hope that it will be useful, but WITHOUT
+// ANY WARRANTY; without even the implied warranty of MERCHANTABILITY or FITNESS
+// FOR A PARTICULAR PURPOSE. See the GNU General Public License for more
+// details.
+//
+// You should have received a copy of the GNU General Public License along with
+// this program.  If not, see <https://www.gnu.org/licenses/>.
+
+use kegtui::{app::AsyncState, app_config::AppConfig, run_scripted};
+
+fn screen_after(keys: &str) -> String {
+    run_scripted(&AppConfig::default(), AsyncState::default(), 100, 30, keys)
+        .expect("the TUI should run headless")
+}
+
+#[test]
+fn choosing_a_nav_shows_its_menu() {
+    // Engines is the sixth item of the main menu.
+    let screen = screen_after("jjjjj<Enter>");
+    assert!(screen.contains("Install from Homebrew"), "{screen}");
+    assert!(!screen.contains("Create Keg"), "{screen}");
+}
+
+#[test]
+fn back_returns_to_the_main_menu() {
+    // Engines opens with Installed selected, just below Back.
+    let screen = screen_after("jjjjj<Enter>k<Enter>");
+    assert!(screen.contains("Create Keg"), "{screen}");
+    assert!(!screen.contains("Install from Homebrew"), "{screen}");
+}
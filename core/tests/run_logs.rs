@@ -0,0 +1,73 @@
+// Copyright (C) 2026 Ethan Uppal.
+//
+// This program is free software: you can redistribute it and/or modify it under
+// the terms of the GNU General Public License as published by the Free Software
+// Foundation, version 3 of the License only.
+//
+// This program is distributed in the hope that it will be useful, but WITHOUT
+// ANY WARRANTY; without even the implied warranty of MERCHANTABILITY or FITNESS
+// FOR A PARTICULAR PURPOSE. See the GNU General Public License for more
+// details.
+//
+// You should have received a copy of the GNU General Public License along with
+// this program.  If not, see <https://www.gnu.org/licenses/>.
+
+use std::{
+    env, fs, path::PathBuf, process, process::Command, time::SystemTime,
+};
+
+use kegtui::run_logs;
+
+fn log_directory(name: &str) -> PathBuf {
+    let directory = env::temp_dir()
+        .join(format!("kegtui-{}-run-logs", process::id()))
+        .join(name);
+    let _ = fs::remove_dir_all(&directory);
+    directory
+}
+
+#[test]
+fn runs_in_the_same_second_are_all_kept() {
+    let log_directory = log_directory("same-second");
+    let started = SystemTime::now();
+    let command = Command::new("true");
+    let paths = ["first", "second", "third"].map(|output| {
+        run_logs::record(
+            &log_directory,
+            "winetricks",
+            started,
+            &command,
+            "exit status: 0",
+            output.as_bytes(),
+            b"",
+        )
+        .unwrap()
+    });
+    assert_ne!(paths[0], paths[1]);
+    assert_ne!(paths[1], paths[2]);
+    let runs = run_logs::list_runs(&log_directory);
+    assert_eq!(runs.len(), 3);
+    assert!(runs.iter().all(|run| run.label == "winetricks"));
+    let newest = fs::read_to_string(&runs[0].path).unwrap();
+    assert!(newest.contains("third"));
+    let oldest = fs::read_to_string(&runs[2].path).unwrap();
+    assert!(oldest.contains("first"));
+}
+
+#[test]
+fn labels_with_dashes_are_read_back() {
+    let log_directory = log_directory("dashes");
+    run_logs::record(
+        &log_directory,
+        "open-file",
+        SystemTime::now(),
+        &Command::new("true"),
+        "exit status: 0",
+        b"",
+        b"",
+    )
+    .unwrap();
+    let runs = run_logs::list_runs(&log_directory);
+    assert_eq!(runs.len(), 1);
+    assert_eq!(runs[0].label, "open-file");
+}
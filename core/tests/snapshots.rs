@@ -0,0 +1,177 @@
+// Copyright (C) 2026 Ethan Uppal.
+//
+// This program is free software: you can redistribute it and/or modify it under
+// the terms of the GNU General Public License as published by the Free Software
+// Foundation, version 3 of the License only.
+//
+// This program is distributed in the hope that it will be useful, but WITHOUT
+// ANY WARRANTY; without even the implied warranty of MERCHANTABILITY or FITNESS
+// FOR A PARTICULAR PURPOSE. See the GNU General Public License for more
+// details.
+//
+// You should have received a copy of the GNU General Public License along with
+// this program.  If not, see <https://www.gnu.org/licenses/>.
+
+use std::{
+    env, fs, io,
+    os::unix::process::ExitStatusExt,
+    path::{Path, PathBuf},
+    process::{self, Command, ExitStatus, Output},
+    sync::Once,
+};
+
+use kegtui::{
+    keg_creation,
+    runner::{ChildProcess, CommandRunner},
+    snapshots,
+    tasks::KegLock,
+};
+
+/// Keeps snapshots and backups out of the real state directory. Every test
+/// shares one, so keg names are unique per test.
+fn use_test_state_directory() {
+    static ONCE: Once = Once::new();
+    ONCE.call_once(|| {
+        let state_home =
+            env::temp_dir().join(format!("kegtui-{}-state", process::id()));
+        // SAFETY: set once, before any test reads the environment.
+        unsafe { env::set_var("XDG_STATE_HOME", state_home) };
+    });
+}
+
+/// A keg bundle with a prefix holding `drive_c/game.txt`.
+fn bundle(name: &str) -> PathBuf {
+    use_test_state_directory();
+    let bundle = env::temp_dir()
+        .join(format!("kegtui-{}-kegs", process::id()))
+        .join(format!("{name}.app"));
+    let drive_c = bundle.join("Contents/SharedSupport/prefix/drive_c");
+    fs::create_dir_all(&drive_c).unwrap();
+    fs::write(drive_c.join("game.txt"), "saved").unwrap();
+    bundle
+}
+
+/// Answers `ps` with `processes` and fails everything else.
+struct FakeRunner {
+    processes: String,
+}
+
+impl CommandRunner for FakeRunner {
+    fn status(&self, _command: &mut Command) -> io::Result<ExitStatus> {
+        Err(io::ErrorKind::Unsupported.into())
+    }
+
+    fn output(&self, command: &mut Command) -> io::Result<Output> {
+        if command.get_program() != "ps" {
+            return Err(io::ErrorKind::Unsupported.into());
+        }
+        Ok(Output {
+            status: ExitStatus::from_raw(0),
+            stdout: self.processes.clone().into_bytes(),
+            stderr: vec![],
+        })
+    }
+
+    fn spawn(
+        &self,
+        _command: &mut Command,
+    ) -> io::Result<Box<dyn ChildProcess>> {
+        Err(io::ErrorKind::Unsupported.into())
+    }
+}
+
+fn idle() -> FakeRunner {
+    FakeRunner {
+        processes: "1 0 1024 01:00 /sbin/launchd\n".to_owned(),
+    }
+}
+
+fn running(bundle: &Path) -> FakeRunner {
+    FakeRunner {
+        processes: format!(
+            "1 0 1024 01:00 /sbin/launchd\n42 1 2048 00:05 {}/Contents/MacOS/wineskin\n",
+            bundle.display()
+        ),
+    }
+}
+
+fn read_game(bundle: &Path) -> io::Result<String> {
+    fs::read_to_string(
+        bundle.join("Contents/SharedSupport/prefix/drive_c/game.txt"),
+    )
+}
+
+#[test]
+fn restoring_a_prefix_snapshot_undoes_later_changes() {
+    let bundle = bundle("restore");
+    let name = "restore.app";
+    let snapshot = snapshots::snapshot_prefix(name, &bundle).unwrap();
+    let drive_c = bundle.join("Contents/SharedSupport/prefix/drive_c");
+    fs::write(drive_c.join("game.txt"), "corrupted").unwrap();
+    fs::write(drive_c.join("installed.dll"), "").unwrap();
+
+    snapshots::restore_prefix(&bundle, &snapshot).unwrap();
+
+    assert_eq!(read_game(&bundle).unwrap(), "saved");
+    assert!(!drive_c.join("installed.dll").exists());
+    assert!(
+        !bundle
+            .join("Contents/SharedSupport/kegtui-restore")
+            .exists()
+    );
+    assert_eq!(snapshots::list_prefix_snapshots(name), [snapshot]);
+}
+
+#[test]
+fn restoring_a_wrapper_snapshot_removes_entries_added_since() {
+    let bundle = bundle("wrapper");
+    let entries = [PathBuf::from("Frameworks"), PathBuf::from("Launcher")];
+    let contents = bundle.join("Contents");
+    fs::create_dir_all(contents.join("Frameworks")).unwrap();
+    fs::write(contents.join("Frameworks/old.dylib"), "old").unwrap();
+    let snapshot =
+        snapshots::snapshot_wrapper("wrapper.app", &bundle, &entries).unwrap();
+    fs::remove_file(contents.join("Frameworks/old.dylib")).unwrap();
+    fs::write(contents.join("Frameworks/new.dylib"), "new").unwrap();
+    fs::write(contents.join("Launcher"), "new").unwrap();
+
+    snapshots::restore_wrapper(&bundle, &snapshot, &entries).unwrap();
+
+    assert!(contents.join("Frameworks/old.dylib").exists());
+    assert!(!contents.join("Frameworks/new.dylib").exists());
+    assert!(!contents.join("Launcher").exists());
+}
+
+#[test]
+fn backing_up_a_bundle_moves_it_whole() {
+    let bundle = bundle("backup");
+
+    let backup = snapshots::back_up_bundle(&bundle).unwrap();
+
+    assert!(!bundle.exists());
+    assert_eq!(read_game(&backup).unwrap(), "saved");
+    assert!(snapshots::list_backups().contains(&backup));
+}
+
+#[test]
+fn a_replaced_keg_is_backed_up_only_when_idle() {
+    let bundle = bundle("replaced");
+
+    assert!(
+        keg_creation::back_up_replaced_keg(&running(&bundle), 0, &bundle)
+            .is_err()
+    );
+    assert!(keg_creation::back_up_replaced_keg(&idle(), 1, &bundle).is_err());
+    {
+        let _keg_lock = KegLock::try_acquire(&bundle).unwrap();
+        assert!(
+            keg_creation::back_up_replaced_keg(&idle(), 0, &bundle).is_err()
+        );
+    }
+    assert_eq!(read_game(&bundle).unwrap(), "saved");
+
+    let backup =
+        keg_creation::back_up_replaced_keg(&idle(), 0, &bundle).unwrap();
+    assert!(!bundle.exists());
+    assert_eq!(read_game(&backup).unwrap(), "saved");
+}
@@ -0,0 +1,97 @@
+// Copyright (C) 2026 Ethan Uppal.
+//
+// This program is free software: you can redistribute it and/or modify it under
+// the terms of the GNU General Public License as published by the Free Software
+// Foundation, version 3 of the License only.
+//
+// This program is distributed in the hope that it will be useful, but WITHOUT
+// ANY WARRANTY; without even the implied warranty of MERCHANTABILITY or FITNESS
+// FOR A PARTICULAR PURPOSE. See the GNU General Public License for more
+// details.
+//
+// You should have received a copy of the GNU General Public License along with
+// this program.  If not, see <https://www.gnu.org/licenses/>.
+
+use std::{
+    env, io,
+    os::unix::process::ExitStatusExt,
+    process::{self, Command, ExitStatus, Output},
+    sync::{
+        Arc,
+        atomic::{AtomicUsize, Ordering},
+    },
+    thread,
+    time::{Duration, Instant},
+};
+
+use kegtui::{
+    app_config::AppConfig,
+    runner::{ChildProcess, CommandRunner},
+    worker::{WorkerTask, spawn_worker},
+};
+
+/// Counts network checks, which always succeed, and fails everything else.
+#[derive(Default)]
+struct NetworkRunner {
+    checks: AtomicUsize,
+}
+
+impl CommandRunner for NetworkRunner {
+    fn status(&self, _command: &mut Command) -> io::Result<ExitStatus> {
+        Err(io::ErrorKind::Unsupported.into())
+    }
+
+    fn output(&self, command: &mut Command) -> io::Result<Output> {
+        if command.get_program() != "curl" {
+            return Err(io::ErrorKind::Unsupported.into());
+        }
+        self.checks.fetch_add(1, Ordering::SeqCst);
+        Ok(Output {
+            status: ExitStatus::from_raw(0),
+            stdout: vec![],
+            stderr: vec![],
+        })
+    }
+
+    fn spawn(
+        &self,
+        _command: &mut Command,
+    ) -> io::Result<Box<dyn ChildProcess>> {
+        Err(io::ErrorKind::Unsupported.into())
+    }
+}
+
+#[test]
+fn the_network_is_checked_only_when_triggered() {
+    let config = AppConfig {
+        keg_search_paths: vec![
+            env::temp_dir().join(format!("kegtui-{}-none", process::id())),
+        ],
+        engine_search_paths: vec![],
+        wrapper_search_paths: vec![],
+        ..AppConfig::default()
+    };
+    let runner = Arc::new(NetworkRunner::default());
+    let (async_state, worker) = spawn_worker(Arc::new(config), runner.clone());
+
+    thread::sleep(Duration::from_millis(300));
+    assert_eq!(runner.checks.load(Ordering::SeqCst), 0);
+
+    worker.trigger(WorkerTask::CheckNetwork);
+    let deadline = Instant::now() + Duration::from_secs(5);
+    while async_state.read().unwrap().is_network_reachable.is_none() {
+        assert!(Instant::now() < deadline, "the check never ran");
+        thread::sleep(Duration::from_millis(10));
+    }
+    assert_eq!(async_state.read().unwrap().is_network_reachable, Some(true));
+    // More than one if the check's result was retried while the state was
+    // being read.
+    let checks = runner.checks.load(Ordering::SeqCst);
+    assert!(checks >= 1);
+
+    // Triggering it again waits out its period.
+    worker.trigger(WorkerTask::CheckNetwork);
+    thread::sleep(Duration::from_millis(300));
+    assert_eq!(runner.checks.load(Ordering::SeqCst), checks);
+    worker.shutdown();
+}
@@ -1,5 +1,7 @@
 // Code from https://github.com/Harzu/iced_term
 
+mod preferences;
+
 use std::{
     collections::HashMap,
     fs, io,
@@ -17,6 +19,8 @@ use iced::{
     window::{self, Settings},
 };
 use iced_term::{ColorPalette, TerminalView};
+use kegtui::app_config::app_config_file_path_in;
+use preferences::{PreferencesEvent, PreferencesTab};
 
 fn main() -> iced::Result {
     iced::application(App::new, App::update, App::view)
@@ -48,6 +52,15 @@ pub enum Event {
     DebugEditFont,
     DebugEditEnv,
     DebugRefreshConfig,
+    RestartTerminal,
+    ShowTab(Tab),
+    Preferences(PreferencesEvent),
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Tab {
+    Terminal,
+    Preferences,
 }
 
 struct App {
@@ -56,8 +69,17 @@ struct App {
     font_config_file: Option<PathBuf>,
     env_config_file: Option<PathBuf>,
     term: iced_term::Terminal,
+    term_id: u64,
+    /// What to respawn when the user restarts an ended session.
+    backend_program: String,
+    backend_env: HashMap<String, String>,
+    /// Whether the backend exited, in which case the terminal view is
+    /// replaced by a prompt to restart it.
+    session_ended: bool,
     hide_extra_ui: bool,
     exit_on_terminal_shutdown: bool,
+    tab: Tab,
+    preferences: PreferencesTab,
 }
 
 // https://web.archive.org/web/20250718013155/https://github.com/burtonageo/cargo-bundle/issues/167#issuecomment-3032588931
@@ -78,7 +100,46 @@ fn resources_root() -> Option<PathBuf> {
     None
 }
 
-const TUI_EXECUTABLE: &str = "target/x86_64-apple-darwin/release/kegtui";
+/// Backend binaries built for each architecture, relative to the resources
+/// root.
+const ARM64_TUI_EXECUTABLE: &str = "target/aarch64-apple-darwin/release/kegtui";
+const X86_64_TUI_EXECUTABLE: &str = "target/x86_64-apple-darwin/release/kegtui";
+/// A fat binary produced with `lipo`, usable on either architecture.
+const UNIVERSAL_TUI_EXECUTABLE: &str =
+    "target/universal-apple-darwin/release/kegtui";
+
+/// Whether the machine is arm64, even if this process is an x86_64 build
+/// running under Rosetta 2.
+fn host_is_arm64() -> bool {
+    if cfg!(target_arch = "aarch64") {
+        return true;
+    }
+    Command::new("sysctl")
+        .args(["-n", "sysctl.proc_translated"])
+        .output()
+        .map(|output| String::from_utf8_lossy(&output.stdout).trim() == "1")
+        .unwrap_or(false)
+}
+
+/// Picks the backend for the running architecture, falling back to the
+/// universal binary, which is what bundles ship. On Apple Silicon, an x86_64
+/// binary still runs under Rosetta 2, but Intel Macs can't run arm64 ones.
+fn tui_executable(resources_root: &Path) -> PathBuf {
+    let candidates: &[&str] = if host_is_arm64() {
+        &[
+            ARM64_TUI_EXECUTABLE,
+            UNIVERSAL_TUI_EXECUTABLE,
+            X86_64_TUI_EXECUTABLE,
+        ]
+    } else {
+        &[X86_64_TUI_EXECUTABLE, UNIVERSAL_TUI_EXECUTABLE]
+    };
+    candidates
+        .iter()
+        .map(|candidate| resources_root.join(candidate))
+        .find(|candidate| candidate.is_file())
+        .unwrap_or_else(|| resources_root.join(candidates[0]))
+}
 
 fn font_exists(font_name: &str) -> bool {
     let source = SystemSource::new();
@@ -94,10 +155,60 @@ fn read_env_file(env_config_file: Option<&Path>) -> HashMap<String, String> {
         .unwrap_or_default()
 }
 
+fn oxocarbon() -> ColorPalette {
+    ColorPalette {
+        foreground: String::from("#dde1e6"),
+        background: String::from("#161616"),
+        black: String::from("#262626"),
+        red: String::from("#ff7eb6"),
+        green: String::from("#42be65"),
+        yellow: String::from("#82cfff"),
+        blue: String::from("#33b1ff"),
+        magenta: String::from("#ee5396"),
+        cyan: String::from("#3ddbd9"),
+        white: String::from("#dde1e6"),
+        bright_black: String::from("#393939"),
+        bright_red: String::from("#ff7eb6"),
+        bright_green: String::from("#42be65"),
+        bright_yellow: String::from("#82cfff"),
+        bright_blue: String::from("#33b1ff"),
+        bright_magenta: String::from("#ee5396"),
+        bright_cyan: String::from("#3ddbd9"),
+        bright_white: String::from("#ffffff"),
+        bright_foreground: None,
+        dim_foreground: String::from("#525252"),
+        dim_black: String::from("#161616"),
+        dim_red: String::from("#cc6591"),
+        dim_green: String::from("#359851"),
+        dim_yellow: String::from("#69a7cc"),
+        dim_blue: String::from("#2990cc"),
+        dim_magenta: String::from("#be4378"),
+        dim_cyan: String::from("#31b1ae"),
+        dim_white: String::from("#b4b7ba"),
+    }
+}
+
+fn terminal_settings(
+    program: &str,
+    env: &HashMap<String, String>,
+) -> iced_term::settings::Settings {
+    iced_term::settings::Settings {
+        theme: iced_term::settings::ThemeSettings {
+            color_pallete: Box::new(oxocarbon()),
+        },
+        backend: iced_term::settings::BackendSettings {
+            program: program.to_owned(),
+            env: env.clone(),
+            ..Default::default()
+        },
+        ..Default::default()
+    }
+}
+
 impl App {
     fn new() -> (Self, Task<Event>) {
         let executable_path =
-            resources_root().unwrap_or_default().join(TUI_EXECUTABLE);
+            tui_executable(&resources_root().unwrap_or_default());
 
         let fallback_font = if font_exists("Hack Nerd Font Mono") {
             "Hack Nerd Font Mono"
@@ -120,37 +231,6 @@ impl App {
                 Some(config_directory)
             });
 
-        let oxocarbon = ColorPalette {
-            foreground: String::from("#dde1e6"),
-            background: String::from("#161616"),
-            black: String::from("#262626"),
-            red: String::from("#ff7eb6"),
-            green: String::from("#42be65"),
-            yellow: String::from("#82cfff"),
-            blue: String::from("#33b1ff"),
-            magenta: String::from("#ee5396"),
-            cyan: String::from("#3ddbd9"),
-            white: String::from("#dde1e6"),
-            bright_black: String::from("#393939"),
-            bright_red: String::from("#ff7eb6"),
-            bright_green: String::from("#42be65"),
-            bright_yellow: String::from("#82cfff"),
-            bright_blue: String::from("#33b1ff"),
-            bright_magenta: String::from("#ee5396"),
-            bright_cyan: String::from("#3ddbd9"),
-            bright_white: String::from("#ffffff"),
-            bright_foreground: None,
-            dim_foreground: String::from("#525252"),
-            dim_black: String::from("#161616"),
-            dim_red: String::from("#cc6591"),
-            dim_green: String::from("#359851"),
-            dim_yellow: String::from("#69a7cc"),
-            dim_blue: String::from("#2990cc"),
-            dim_magenta: String::from("#be4378"),
-            dim_cyan: String::from("#31b1ae"),
-            dim_white: String::from("#b4b7ba"),
-        };
-
         let shell = read_env_file(env_config_file.as_deref())
             .get("SHELL")
             .map(|inner| inner.to_owned())
@@ -179,18 +259,15 @@ impl App {
             }
         }
 
+        // The backend reads the config from the shell's XDG_CONFIG_HOME, which
+        // apps opened from Finder don't inherit.
+        let preferences = PreferencesTab::new(app_config_file_path_in(
+            env.get("XDG_CONFIG_HOME").map(String::as_str),
+        ));
+
+        let backend_program = executable_path.to_string_lossy().to_string();
         let term_id = 0;
-        let term_settings = iced_term::settings::Settings {
-            theme: iced_term::settings::ThemeSettings {
-                color_pallete: Box::new(oxocarbon),
-            },
-            backend: iced_term::settings::BackendSettings {
-                program: executable_path.to_string_lossy().to_string(),
-                env,
-                ..Default::default()
-            },
-            ..Default::default()
-        };
+        let term_settings = terminal_settings(&backend_program, &env);
 
         (
             Self {
@@ -200,8 +277,14 @@ impl App {
                 env_config_file,
                 term: iced_term::Terminal::new(term_id, term_settings)
                     .expect("Failed to create terminal"),
+                term_id,
+                backend_program,
+                backend_env: env,
+                session_ended: false,
                 hide_extra_ui: Default::default(),
                 exit_on_terminal_shutdown: Default::default(),
+                tab: Tab::Terminal,
+                preferences,
             }
             .refresh_config_owned(),
             Task::none(),
@@ -263,6 +346,7 @@ impl App {
                         if self.exit_on_terminal_shutdown {
                             window::latest().and_then(window::close)
                         } else {
+                            self.session_ended = true;
                             Task::none()
                         }
                     }
@@ -295,11 +379,53 @@ impl App {
                 self.refresh_config();
                 Task::none()
             }
+            Event::RestartTerminal => {
+                self.term_id += 1;
+                match iced_term::Terminal::new(
+                    self.term_id,
+                    terminal_settings(&self.backend_program, &self.backend_env),
+                ) {
+                    Ok(term) => {
+                        self.term = term;
+                        self.session_ended = false;
+                        self.refresh_config();
+                    }
+                    Err(error) => {
+                        self.title =
+                            format!("kegtui (restart failed: {error})");
+                    }
+                }
+                Task::none()
+            }
+            Event::ShowTab(tab) => {
+                self.tab = tab;
+                Task::none()
+            }
+            Event::Preferences(event) => {
+                self.preferences.update(event);
+                Task::none()
+            }
         }
     }
 
     fn view(&self) -> Element<'_, Event, Theme, iced::Renderer> {
-        let terminal_view = TerminalView::show(&self.term).map(Event::Terminal);
+        let terminal_view: Element<'_, Event, Theme, iced::Renderer> =
+            if self.tab == Tab::Preferences && !self.hide_extra_ui {
+                self.preferences.view().map(Event::Preferences)
+            } else if self.session_ended {
+                container(
+                    column![
+                        text("The kegtui session ended."),
+                        button("Restart").on_press(Event::RestartTerminal),
+                    ]
+                    .align_x(Horizontal::Center)
+                    .spacing(8),
+                )
+                .center(Length::Fill)
+                .into()
+            } else {
+                TerminalView::show(&self.term).map(Event::Terminal)
+            };
         container(
             if self.hide_extra_ui {
                 column![terminal_view]
@@ -314,8 +440,16 @@ impl App {
                     terminal_view,
                     container(
                         row![
-                            text("Debug Config"),
+                            button("kegtui").on_press_maybe(
+                                (self.tab != Tab::Terminal)
+                                    .then_some(Event::ShowTab(Tab::Terminal))
+                            ),
+                            button("Preferences").on_press_maybe(
+                                (self.tab != Tab::Preferences)
+                                    .then_some(Event::ShowTab(Tab::Preferences))
+                            ),
                             space::horizontal(),
+                            text("Debug Config"),
                             button("Edit font")
                                 .on_press(Event::DebugEditFont),
                             button("Edit env")
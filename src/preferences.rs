@@ -0,0 +1,243 @@
+// Copyright (C) 2026 Ethan Uppal.
+//
+// This program is free software: you can redistribute it and/or modify it under
+// the terms of the GNU General Public License as published by the Free Software
+// Foundation, version 3 of the License only.
+//
+// This program is distributed in the hope that it will be useful, but WITHOUT
+// ANY WARRANTY; without even the implied warranty of MERCHANTABILITY or FITNESS
+// FOR A PARTICULAR PURPOSE. See the GNU General Public License for more
+// details.
+//
+// You should have received a copy of the GNU General Public License along with
+// this program.  If not, see <https://www.gnu.org/licenses/>.
+
+//! The Preferences tab, which edits the search paths, editor, and explorer in
+//! `kegtui.toml` so nobody has to find and edit the dotfile themselves.
+
+use std::path::PathBuf;
+
+use iced::{
+    Element, Length,
+    widget::{
+        Column, button, column, row, scrollable, space, text, text_input,
+    },
+};
+use kegtui::app_config::Preferences;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SearchPathList {
+    Kegs,
+    Engines,
+    Wrappers,
+}
+
+impl SearchPathList {
+    const ALL: [SearchPathList; 3] = [
+        SearchPathList::Kegs,
+        SearchPathList::Engines,
+        SearchPathList::Wrappers,
+    ];
+
+    fn title(self) -> &'static str {
+        match self {
+            SearchPathList::Kegs => "Keg search paths",
+            SearchPathList::Engines => "Engine search paths",
+            SearchPathList::Wrappers => "Wrapper search paths",
+        }
+    }
+
+    fn explanation(self) -> &'static str {
+        match self {
+            SearchPathList::Kegs => "Folders with your kegs.",
+            SearchPathList::Engines => {
+                "Folders with Kegworks engines. Downloaded engines go in the first."
+            }
+            SearchPathList::Wrappers => {
+                "Folders with template wrappers. Downloaded wrappers go in the first."
+            }
+        }
+    }
+
+    fn paths(self, preferences: &Preferences) -> &Vec<PathBuf> {
+        match self {
+            SearchPathList::Kegs => &preferences.keg_search_paths,
+            SearchPathList::Engines => &preferences.engine_search_paths,
+            SearchPathList::Wrappers => &preferences.wrapper_search_paths,
+        }
+    }
+
+    fn paths_mut(self, preferences: &mut Preferences) -> &mut Vec<PathBuf> {
+        match self {
+            SearchPathList::Kegs => &mut preferences.keg_search_paths,
+            SearchPathList::Engines => &mut preferences.engine_search_paths,
+            SearchPathList::Wrappers => &mut preferences.wrapper_search_paths,
+        }
+    }
+}
+
+#[derive(Debug, Clone)]
+pub enum PreferencesEvent {
+    SearchPathEdited(SearchPathList, usize, String),
+    SearchPathAdded(SearchPathList),
+    SearchPathRemoved(SearchPathList, usize),
+    EditorEdited(String),
+    ExplorerEdited(String),
+    Save,
+    Revert,
+}
+
+pub struct PreferencesTab {
+    config_file: PathBuf,
+    /// What the config file had when last read or written.
+    saved: Preferences,
+    edited: Preferences,
+    status: Option<String>,
+}
+
+impl PreferencesTab {
+    pub fn new(config_file: PathBuf) -> Self {
+        let mut tab = Self {
+            config_file,
+            saved: Preferences::default(),
+            edited: Preferences::default(),
+            status: None,
+        };
+        tab.reload();
+        tab
+    }
+
+    fn reload(&mut self) {
+        match Preferences::read(&self.config_file) {
+            Ok(preferences) => {
+                self.saved = preferences.clone();
+                self.edited = preferences;
+                self.status = None;
+            }
+            Err(error) => {
+                self.status = Some(format!("{error:#}"));
+            }
+        }
+    }
+
+    pub fn update(&mut self, event: PreferencesEvent) {
+        match event {
+            PreferencesEvent::SearchPathEdited(list, index, path) => {
+                if let Some(entry) =
+                    list.paths_mut(&mut self.edited).get_mut(index)
+                {
+                    *entry = PathBuf::from(path);
+                }
+            }
+            PreferencesEvent::SearchPathAdded(list) => {
+                list.paths_mut(&mut self.edited).push(PathBuf::new());
+            }
+            PreferencesEvent::SearchPathRemoved(list, index) => {
+                let paths = list.paths_mut(&mut self.edited);
+                if index < paths.len() {
+                    paths.remove(index);
+                }
+            }
+            PreferencesEvent::EditorEdited(editor) => {
+                self.edited.editor = editor
+            }
+            PreferencesEvent::ExplorerEdited(explorer) => {
+                self.edited.explorer = explorer;
+            }
+            PreferencesEvent::Save => {
+                for list in SearchPathList::ALL {
+                    list.paths_mut(&mut self.edited)
+                        .retain(|path| !path.as_os_str().is_empty());
+                }
+                self.status = Some(
+                    match self.edited.write(&self.config_file) {
+                        Ok(()) => {
+                            self.saved = self.edited.clone();
+                            "Saved. Restart the kegtui session to use the new preferences."
+                            .to_owned()
+                        }
+                        Err(error) => format!("{error:#}"),
+                    },
+                );
+            }
+            PreferencesEvent::Revert => self.reload(),
+        }
+    }
+
+    fn search_paths_view(
+        &self,
+        list: SearchPathList,
+    ) -> Element<'_, PreferencesEvent> {
+        let mut rows = Column::new().spacing(4);
+        for (index, path) in list.paths(&self.edited).iter().enumerate() {
+            rows = rows.push(
+                row![
+                    text_input("~/Applications/", &path.to_string_lossy())
+                        .on_input(move |path| {
+                            PreferencesEvent::SearchPathEdited(
+                                list, index, path,
+                            )
+                        })
+                        .width(Length::Fill),
+                    button("Remove").on_press(
+                        PreferencesEvent::SearchPathRemoved(list, index)
+                    ),
+                ]
+                .spacing(4),
+            );
+        }
+        column![
+            text(list.title()).size(18),
+            text(list.explanation()),
+            rows,
+            button("Add path")
+                .on_press(PreferencesEvent::SearchPathAdded(list)),
+        ]
+        .spacing(6)
+        .into()
+    }
+
+    pub fn view(&self) -> Element<'_, PreferencesEvent> {
+        let is_changed = self.edited != self.saved;
+        let mut content = column![
+            text("Preferences").size(24),
+            text(format!("Saved in {}", self.config_file.display())),
+        ]
+        .spacing(16);
+        for list in SearchPathList::ALL {
+            content = content.push(self.search_paths_view(list));
+        }
+        content = content.push(
+            column![
+                text("Editor").size(18),
+                text("Opens configs and lists to edit. Leave empty to use $EDITOR, or vim."),
+                text_input("vim", &self.edited.editor)
+                    .on_input(PreferencesEvent::EditorEdited),
+            ]
+            .spacing(6),
+        );
+        content = content.push(
+            column![
+                text("Explorer").size(18),
+                text("Opens folders like the C drive. Leave empty to use $EXPLORER, or open."),
+                text_input("open", &self.edited.explorer)
+                    .on_input(PreferencesEvent::ExplorerEdited),
+            ]
+            .spacing(6),
+        );
+        content = content.push(
+            row![
+                text(self.status.as_deref().unwrap_or_default()),
+                space::horizontal(),
+                button("Revert").on_press_maybe(
+                    is_changed.then_some(PreferencesEvent::Revert)
+                ),
+                button("Save").on_press_maybe(
+                    is_changed.then_some(PreferencesEvent::Save)
+                ),
+            ]
+            .spacing(4),
+        );
+        scrollable(content.padding(16)).height(Length::Fill).into()
+    }
+}